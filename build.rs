@@ -1,11 +1,71 @@
+use std::process::Command;
+
 fn main() {
     let hash = std::env::var("COPIUM_BUILD_HASH").unwrap_or_else(|_| "dev".into());
     println!("cargo:rustc-env=COPIUM_BUILD_HASH={hash}");
+    println!("cargo:rustc-env=COPIUM_GIT_HASH={}", git_hash());
+    println!("cargo:rustc-env=COPIUM_RUSTC_VERSION={}", rustc_version());
+    println!(
+        "cargo:rustc-env=COPIUM_PYO3_VERSION={}",
+        locked_version("pyo3")
+    );
     pyo3_build_config::use_pyo3_cfgs();
     emit_python_link_alias_for_custom_ffi_blocks();
     pyo3_build_config::add_extension_module_link_args();
 }
 
+fn git_hash() -> String {
+    if let Ok(hash) = std::env::var("COPIUM_GIT_HASH") {
+        return hash;
+    }
+
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn rustc_version() -> String {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_owned())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn locked_version(package: &str) -> String {
+    let manifest_directory = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let lockfile = std::path::Path::new(&manifest_directory).join("Cargo.lock");
+    let Ok(contents) = std::fs::read_to_string(lockfile) else {
+        return "unknown".into();
+    };
+
+    let name_line = format!("name = \"{package}\"");
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() != name_line {
+            continue;
+        }
+        if let Some(version) = lines
+            .next()
+            .and_then(|line| line.trim().strip_prefix("version = \""))
+            .and_then(|line| line.strip_suffix('"'))
+        {
+            return version.to_owned();
+        }
+    }
+
+    "unknown".into()
+}
+
 fn emit_python_link_alias_for_custom_ffi_blocks() {
     let is_windows_target = std::env::var("CARGO_CFG_TARGET_OS")
         .is_ok_and(|target_operating_system| target_operating_system == "windows");
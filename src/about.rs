@@ -141,3 +141,133 @@ pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
         crate::add_submodule(parent, crate::cstr!("__about__"), module)
     }
 }
+
+#[cfg(Py_3_12)]
+const PATCH_STRATEGY: &str = "vectorcall";
+#[cfg(not(Py_3_12))]
+const PATCH_STRATEGY: &str = "code";
+
+/// Compile-time switches that change observable behavior between wheels.
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = vec![if cfg!(Py_3_12) {
+        "vectorcall_patch"
+    } else {
+        "code_patch"
+    }];
+    if cfg!(Py_3_12) {
+        features.push("immortal_objects");
+    }
+    if cfg!(Py_GIL_DISABLED) {
+        features.push("free_threaded");
+    }
+    if cfg!(Py_LIMITED_API) {
+        features.push("abi3");
+    }
+    if cfg!(all(Py_3_14, not(Py_GIL_DISABLED))) {
+        features.push("dict_watchers");
+    }
+    if cfg!(debug_assertions) {
+        features.push("debug_assertions");
+    }
+    features
+}
+
+unsafe fn set_str_item(dict: *mut PyObject, key: *const core::ffi::c_char, value: &str) -> i32 {
+    unsafe {
+        let item = PyUnicode_FromStringAndSize(value.as_ptr().cast(), value.len() as Py_ssize_t);
+        if item.is_null() {
+            return -1;
+        }
+        let status = PyDict_SetItemString(dict, key, item);
+        item.decref();
+        status
+    }
+}
+
+unsafe fn set_bool_item(dict: *mut PyObject, key: *const core::ffi::c_char, value: bool) -> i32 {
+    unsafe {
+        let item = PyBool_FromLong(value as _);
+        let status = PyDict_SetItemString(dict, key, item);
+        item.decref();
+        status
+    }
+}
+
+unsafe fn build_info() -> *mut PyObject {
+    unsafe {
+        let info = PyDict_New();
+        if info.is_null() {
+            return ptr::null_mut();
+        }
+
+        if set_str_item(info, crate::cstr!("version"), env!("CARGO_PKG_VERSION")) < 0
+            || set_str_item(info, crate::cstr!("git_hash"), env!("COPIUM_GIT_HASH")) < 0
+            || set_str_item(info, crate::cstr!("build_hash"), env!("COPIUM_BUILD_HASH")) < 0
+            || set_str_item(info, crate::cstr!("rustc"), env!("COPIUM_RUSTC_VERSION")) < 0
+            || set_str_item(info, crate::cstr!("pyo3"), env!("COPIUM_PYO3_VERSION")) < 0
+            || set_str_item(info, crate::cstr!("patch_strategy"), PATCH_STRATEGY) < 0
+            || set_bool_item(info, crate::cstr!("abi3"), cfg!(Py_LIMITED_API)) < 0
+            || set_bool_item(info, crate::cstr!("free_threaded"), cfg!(Py_GIL_DISABLED)) < 0
+            || set_bool_item(info, crate::cstr!("debug"), cfg!(debug_assertions)) < 0
+        {
+            info.decref();
+            return ptr::null_mut();
+        }
+
+        info
+    }
+}
+
+unsafe fn features_frozenset() -> *mut PyObject {
+    unsafe {
+        let names = enabled_features();
+        let list = PyList_New(0);
+        if list.is_null() {
+            return ptr::null_mut();
+        }
+        for name in names {
+            let item = PyUnicode_FromStringAndSize(name.as_ptr().cast(), name.len() as Py_ssize_t);
+            if item.is_null() || PyList_Append(list, item) < 0 {
+                item.decref_nullable();
+                list.decref();
+                return ptr::null_mut();
+            }
+            item.decref();
+        }
+        let frozen = PyFrozenSet_New(list);
+        list.decref();
+        frozen
+    }
+}
+
+/// Adds `__version__`, `__build__` and `features` to the top-level module.
+pub unsafe fn add_build_info(module: *mut PyObject) -> i32 {
+    unsafe {
+        let version = concat!(env!("CARGO_PKG_VERSION"), "\0");
+        if PyModule_AddStringConstant(module, crate::cstr!("__version__"), version.as_ptr().cast())
+            < 0
+        {
+            return -1;
+        }
+
+        let info = build_info();
+        if info.is_null() {
+            return -1;
+        }
+        if PyModule_AddObject(module, crate::cstr!("__build__"), info) < 0 {
+            info.decref();
+            return -1;
+        }
+
+        let features = features_frozenset();
+        if features.is_null() {
+            return -1;
+        }
+        if PyModule_AddObject(module, crate::cstr!("features"), features) < 0 {
+            features.decref();
+            return -1;
+        }
+
+        0
+    }
+}
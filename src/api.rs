@@ -0,0 +1,42 @@
+//! Deep copying from other PyO3 extensions, without going through the
+//! `copium` Python module.
+//!
+//! Enabled by the `rust-api` feature. Turn default features off so copium
+//! leaves linking libpython to the host crate:
+//!
+//! ```toml
+//! copium = { git = "https://github.com/Bobronium/copium", default-features = false, features = ["rust-api"] }
+//! ```
+//!
+//! copium needs a nightly toolchain, and so does anything that builds it.
+
+use std::ptr;
+use std::sync::atomic::Ordering;
+
+use pyo3::prelude::*;
+
+pub use crate::deepcopy::{deepcopy, PyResult as RawResult};
+pub use crate::memo::{CallbackMemo, CustomMemo, DictMemo, Memo, MemoTable, PyMemoObject};
+
+/// Readies the caches and types deepcopy relies on, unless the `copium`
+/// module or an earlier call already did. The other functions here call it.
+pub fn init(py: Python<'_>) -> PyResult<()> {
+    if crate::RUNTIME_READY.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    if unsafe { crate::init_runtime() } < 0 {
+        return Err(PyErr::fetch(py));
+    }
+    Ok(())
+}
+
+/// `copium.deepcopy(obj)`, honouring the current `copium.config`.
+pub fn deepcopy_bound(obj: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    let py = obj.py();
+    init(py)?;
+    let args = [obj.as_ptr()];
+    unsafe {
+        let copied = crate::py_deepcopy(ptr::null_mut(), args.as_ptr(), 1, ptr::null_mut());
+        Bound::from_owned_ptr_or_err(py, copied).map(Bound::unbind)
+    }
+}
@@ -0,0 +1,200 @@
+//! `deepcopy(..., max_leaf_bytes=, max_total_bytes=)`: a memory budget for
+//! the byte buffers one call duplicates.
+//!
+//! A bytearray, a bytes subclass or an `array.array` is charged its size right
+//! before its copy is allocated. One larger than `max_leaf_bytes`, or one that
+//! would take the call's running total past `max_total_bytes`, fails the copy
+//! with `copium.CopyError` instead, so a tight host fails fast rather than
+//! running out of memory halfway through. Calls without a budget never get
+//! there.
+
+use pyo3_ffi::*;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::state::STATE;
+use crate::types::*;
+
+#[derive(Clone, Copy)]
+pub struct Budget {
+    leaf: usize,
+    total: usize,
+}
+
+/// The running call's budget, None while no call on this thread has one.
+#[thread_local]
+static mut BUDGET: Option<Budget> = None;
+
+/// Bytes charged so far against `BUDGET.total`.
+#[thread_local]
+static mut SPENT: usize = 0;
+
+#[inline(always)]
+pub fn is_active() -> bool {
+    unsafe { (*ptr::addr_of!(BUDGET)).is_some() }
+}
+
+/// Parses `max_leaf_bytes=` or `max_total_bytes=`: None for the configured
+/// limit, otherwise a non-negative int with 0 for none. Returns Err with an
+/// exception set for anything else.
+#[cold]
+pub unsafe fn parse_limit(
+    value: *mut PyObject,
+    name: *const c_char,
+) -> Result<Option<usize>, ()> {
+    unsafe {
+        if value.is_none() {
+            return Ok(None);
+        }
+        let limit = PyLong_AsSsize_t(value);
+        if limit == -1 && !PyErr_Occurred().is_null() {
+            return Err(());
+        }
+        if limit < 0 {
+            crate::ffi_ext::PyErr_Format(
+                PyExc_ValueError,
+                crate::cstr!("%s must be a non-negative int or None"),
+                name,
+            );
+            return Err(());
+        }
+        Ok(Some(if limit == 0 { usize::MAX } else { limit as usize }))
+    }
+}
+
+/// The budget for one call: its own limits where given, the configured ones
+/// otherwise. None when neither sets any.
+#[inline(always)]
+pub unsafe fn for_call(leaf: Option<usize>, total: Option<usize>) -> Option<Budget> {
+    unsafe {
+        let leaf = leaf.unwrap_or(STATE.max_leaf_bytes);
+        let total = total.unwrap_or(STATE.max_total_bytes);
+        if leaf == usize::MAX && total == usize::MAX {
+            None
+        } else {
+            Some(Budget { leaf, total })
+        }
+    }
+}
+
+/// Runs `copy` under `own`. A call nested in one with a budget is held to
+/// what is left of it too, and what it spends counts toward the outer total.
+#[inline(never)]
+pub unsafe fn with_budget(own: Budget, copy: impl FnOnce() -> *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let previous = BUDGET;
+        let previous_spent = SPENT;
+
+        BUDGET = Some(match previous {
+            Some(outer) => Budget {
+                leaf: own.leaf.min(outer.leaf),
+                total: own.total.min(outer.total.saturating_sub(previous_spent)),
+            },
+            None => own,
+        });
+        SPENT = 0;
+
+        let result = copy();
+
+        SPENT = if previous.is_some() {
+            previous_spent.saturating_add(SPENT)
+        } else {
+            0
+        };
+        BUDGET = previous;
+        result
+    }
+}
+
+/// Charges `size` bytes for a copy of `object` about to be allocated.
+/// Returns -1 with CopyError set when that would go over the budget.
+#[cold]
+pub unsafe fn charge(object: *mut PyObject, size: usize) -> i32 {
+    unsafe {
+        let Some(budget) = BUDGET else {
+            return 0;
+        };
+        let spent = SPENT.saturating_add(size);
+        let message = if size > budget.leaf {
+            format!(
+                "{} of {} exceeds max_leaf_bytes ({})",
+                type_name(object),
+                human_size(size),
+                human_size(budget.leaf),
+            )
+        } else if spent > budget.total {
+            format!(
+                "{} of {} would bring the copy to {}, over max_total_bytes ({})",
+                type_name(object),
+                human_size(size),
+                human_size(spent),
+                human_size(budget.total),
+            )
+        } else {
+            SPENT = spent;
+            return 0;
+        };
+        let message = CString::new(message).unwrap_or_default();
+        PyErr_SetString(crate::reduce::copy_error(), message.as_ptr());
+        -1
+    }
+}
+
+/// `charge` for an object about to be reduced, when it is a buffer whose
+/// reduction duplicates it: a bytearray or bytes subclass.
+#[cold]
+pub unsafe fn charge_reduced(object: *mut PyObject) -> i32 {
+    unsafe {
+        let size = if PyByteArray_Check(object) != 0 {
+            PyByteArray_Size(object)
+        } else if PyBytes_Check(object) != 0 {
+            PyBytes_Size(object)
+        } else {
+            return 0;
+        };
+        if size < 0 {
+            return -1;
+        }
+        charge(object, size as usize)
+    }
+}
+
+/// `charge` for an array.array, which copies itself in its own __deepcopy__
+/// and so is charged before that is looked up.
+#[cold]
+pub unsafe fn charge_array(object: *mut PyObject) -> i32 {
+    unsafe {
+        if !ARRAY_ARRAY.contains(object.class()) {
+            return 0;
+        }
+        let mut view = std::mem::MaybeUninit::<Py_buffer>::uninit();
+        if PyObject_GetBuffer(object, view.as_mut_ptr(), PyBUF_SIMPLE) < 0 {
+            return -1;
+        }
+        let size = (*view.as_ptr()).len;
+        PyBuffer_Release(view.as_mut_ptr());
+        charge(object, size as usize)
+    }
+}
+
+unsafe fn type_name(object: *mut PyObject) -> String {
+    unsafe {
+        CStr::from_ptr((*object.class()).tp_name)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+fn human_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
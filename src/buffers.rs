@@ -0,0 +1,97 @@
+//! `config.apply(copy_buffers="owned")`: deepcopy of objects that only
+//! expose the buffer protocol.
+//!
+//! A memoryview, or a C type with nothing but `tp_as_buffer`, can't be
+//! reduced, so stdlib and copium fail on it by default. With the option on,
+//! such an object is copied as the bytes it exposes instead: `bytes` for a
+//! read-only buffer, `bytearray` for a writable one. A multidimensional or
+//! strided buffer is flattened in C order, like `memoryview.tobytes()`.
+//! Bytes and bytearray subclasses, types with a `__reduce_ex__`,
+//! `__reduce__` or `copyreg` entry of their own (array.array), and types
+//! whose instances have a `__dict__` (a Python class defining `__buffer__`)
+//! are copied as before.
+
+use pyo3_ffi::*;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use crate::memo::Memo;
+use crate::py_obj;
+use crate::py_str;
+use crate::types::*;
+
+/// Whether `object` should be copied as the bytes it exposes: its type fills
+/// `bf_getbuffer` and has no better way to be copied.
+pub unsafe fn is_buffer_only(object: *mut PyObject, tp: *mut PyTypeObject) -> Result<bool, ()> {
+    unsafe {
+        let buffer = (*tp).tp_as_buffer;
+        if buffer.is_null()
+            || (*buffer).bf_getbuffer.is_none()
+            || PyBytes_Check(object) != 0
+            || PyByteArray_Check(object) != 0
+            || (*tp).tp_dictoffset != 0
+        {
+            return Ok(false);
+        }
+        let registered = py_obj!(PyDictObject, "copyreg.dispatch_table").get_item(tp as _);
+        if !registered.is_null() && !registered.is_none() {
+            return Ok(false);
+        }
+        let object_type = ptr::addr_of_mut!(PyBaseObject_Type);
+        for name in [py_str!("__reduce_ex__"), py_str!("__reduce__")] {
+            if !crate::subclasses::resolves_like(tp, object_type, name)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// An owned copy of the bytes `object` exposes, memoized. Null with an
+/// exception set if the buffer can't be had, or the budget doesn't allow it.
+pub unsafe fn copy_owned<M: Memo>(
+    object: *mut PyObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let mut view = MaybeUninit::<Py_buffer>::uninit();
+        if PyObject_GetBuffer(object, view.as_mut_ptr(), PyBUF_FULL_RO) < 0 {
+            return ptr::null_mut();
+        }
+        let copy = copy_view(object, view.as_ptr());
+        PyBuffer_Release(view.as_mut_ptr());
+        if copy.is_null() {
+            return ptr::null_mut();
+        }
+        if memo.memoize(object, copy, probe) < 0 {
+            copy.decref();
+            return ptr::null_mut();
+        }
+        copy
+    }
+}
+
+unsafe fn copy_view(object: *mut PyObject, view: *const Py_buffer) -> *mut PyObject {
+    unsafe {
+        let size = (*view).len;
+        if crate::budget::is_active() && crate::budget::charge(object, size as usize) < 0 {
+            return ptr::null_mut();
+        }
+        let (copy, data) = if (*view).readonly != 0 {
+            let copy = PyBytes_FromStringAndSize(ptr::null(), size);
+            (copy, if copy.is_null() { ptr::null_mut() } else { PyBytes_AsString(copy) })
+        } else {
+            let copy = PyByteArray_FromStringAndSize(ptr::null(), size);
+            (copy, if copy.is_null() { ptr::null_mut() } else { PyByteArray_AsString(copy) })
+        };
+        if copy.is_null() {
+            return ptr::null_mut();
+        }
+        if size > 0 && PyBuffer_ToContiguous(data.cast(), view, size, b'C' as _) < 0 {
+            copy.decref();
+            return ptr::null_mut();
+        }
+        copy
+    }
+}
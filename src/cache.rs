@@ -106,7 +106,6 @@ pub unsafe fn resolve_path(path: &str) -> *mut PyObject {
         if !builtin_hit.is_null() {
             cur = builtin_hit.newref();
         } else {
-            PyErr_Clear();
             cur = PyImport_ImportModule(first.as_ptr());
             if cur.is_null() {
                 return ptr::null_mut();
@@ -132,6 +131,10 @@ pub unsafe fn resolve_path(path: &str) -> *mut PyObject {
                 cur = next;
                 continue;
             }
+            if PyErr_ExceptionMatches(PyExc_AttributeError) == 0 {
+                cur.decref();
+                return ptr::null_mut();
+            }
             PyErr_Clear();
 
             let dotted: String = segments[..=i].join(".");
@@ -142,6 +145,10 @@ pub unsafe fn resolve_path(path: &str) -> *mut PyObject {
                     cur = module;
                     continue;
                 }
+                if PyErr_ExceptionMatches(PyExc_ImportError) == 0 {
+                    cur.decref();
+                    return ptr::null_mut();
+                }
             }
 
             PyErr_Clear();
@@ -160,11 +167,17 @@ pub unsafe fn resolve_path(path: &str) -> *mut PyObject {
     cur
 }
 
+/// Like `resolve_path`, but a missing module or attribute yields null with no
+/// exception set. Anything else raised while importing is left to the caller.
 pub unsafe fn resolve_path_optional(path: &str) -> *mut PyObject {
     let result = unsafe { resolve_path(path) };
     if result.is_null() {
         unsafe {
-            PyErr_Clear();
+            if PyErr_ExceptionMatches(PyExc_ImportError) != 0
+                || PyErr_ExceptionMatches(PyExc_AttributeError) != 0
+            {
+                PyErr_Clear();
+            }
         }
     }
     result
@@ -305,6 +318,9 @@ macro_rules! __py_obj_body {
     (optional, $path:literal, $SLOT:ident) => {
         unsafe {
             let val = $crate::cache::resolve_path_optional($path);
+            if val.is_null() && !::pyo3_ffi::PyErr_Occurred().is_null() {
+                return -1;
+            }
             $SLOT.set(val);
             0
         }
@@ -16,6 +16,12 @@ extern "C" {
         key: *mut *mut PyObject,
         hash: *mut Py_hash_t,
     ) -> c_int;
+    pub fn _PyDict_SetItem_KnownHash(
+        op: *mut PyObject,
+        key: *mut PyObject,
+        value: *mut PyObject,
+        hash: Py_hash_t,
+    ) -> c_int;
 }
 
 #[cfg(Py_3_13)]
@@ -3,7 +3,7 @@ use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyDict};
 use pyo3_ffi::PyObject;
 
-use crate::state::{MemoMode, OnIncompatible, STATE};
+use crate::state::{CopyBuffers, DeepcopyNotImplemented, MemoMode, OnIncompatible, STATE};
 use crate::types::PyObjectPtr;
 
 // ══════════════════════════════════════════════════════════════
@@ -12,6 +12,55 @@ use crate::types::PyObjectPtr;
 //  Called with no kwargs → reset from environment variables.
 //  Called with kwargs    → update only the specified fields.
 //
+//  strict: raise CopyError instead of sharing an original copium
+//  couldn't copy (e.g. `__reduce__` returned a string).
+//
+//  deepcopy_arg_compat: retry `__deepcopy__(memo)` as `__deepcopy__()`
+//  when it rejects the positional memo; remembered per type.
+//
+//  deepcopy_not_implemented: "raise" lets NotImplementedError from
+//  `__deepcopy__` propagate like stdlib; "fallback" copies that object
+//  through reduce instead. Decided per call, never remembered per type;
+//  not read from the environment.
+//
+//  intern_cap: most canonical objects one `deepcopy(..., intern=...)`
+//  keeps; not read from the environment.
+//
+//  negative_cache: fail fast for a type whose `__reduce_ex__` already
+//  raised, until the type changes; opt-in, since a reducer that fails for
+//  one instance may succeed for another; not read from the environment.
+//
+//  max_memo_entries / max_keepalive: fail a copy whose memo grows past
+//  this many entries / kept-alive originals; 0 is no limit (default).
+//  Not read from the environment.
+//
+//  max_leaf_bytes / max_total_bytes: defaults for deepcopy()'s arguments
+//  of the same name; 0 is no limit (default). Not read from the environment.
+//
+//  copy_buffers: "raise" fails on objects that only expose the buffer
+//  protocol (memoryview) like stdlib; "owned" copies them into bytes or a
+//  bytearray. Not read from the environment.
+//
+//  mark_copies: record each top-level deepcopy (id, time, thread, source
+//  type) for `extra.last_copy_info()` and as the result's `__copium__`
+//  attribute where it can be set; not read from the environment.
+//
+//  compat_warnings: warn once per type when an instance's `__dict__`
+//  holds a copy hook (`__reduce__`, ...) the class doesn't define, which
+//  copying ignores; not read from the environment.
+//
+//  memo_key_check: make the memo proxy a `__deepcopy__` receives refuse
+//  keys that aren't the id() of an original the copy has seen, a debugging
+//  aid for memos pre-seeded with hash() or other ints; not read from the
+//  environment.
+//
+//  progress_every: containers between calls to a deepcopy() or replicate()
+//  `progress` callback (default 100000); not read from the environment.
+//
+//  yield_every / on_checkpoint: call `on_checkpoint()` every this many
+//  containers, for cooperative schedulers; yield_every=0 (default) removes
+//  the callback. Not read from the environment.
+//
 //  suppress_warnings: pass a sequence to set, empty sequence to clear,
 //  omit to leave unchanged. (Passing None is treated as omit.)
 // ══════════════════════════════════════════════════════════════
@@ -60,16 +109,157 @@ impl<'py> FromPyObject<'py, 'py> for PyOnIncompatible {
     }
 }
 
-//  copium.config.apply()
+#[derive(Clone, Copy, Debug)]
+enum PyDeepcopyNotImplemented {
+    Raise,
+    Fallback,
+}
+
+impl<'py> FromPyObject<'py, 'py> for PyDeepcopyNotImplemented {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> Result<Self, Self::Error> {
+        let s = obj.extract::<&str>()?;
+        match s {
+            "raise" => Ok(Self::Raise),
+            "fallback" => Ok(Self::Fallback),
+            other => Err(PyValueError::new_err(format!(
+                "deepcopy_not_implemented must be 'raise' or 'fallback', got '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PyCopyBuffers {
+    Raise,
+    Owned,
+}
+
+impl<'py> FromPyObject<'py, 'py> for PyCopyBuffers {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> Result<Self, Self::Error> {
+        let s = obj.extract::<&str>()?;
+        match s {
+            "raise" => Ok(Self::Raise),
+            "owned" => Ok(Self::Owned),
+            other => Err(PyValueError::new_err(format!(
+                "copy_buffers must be 'raise' or 'owned', got '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Configure copium. Only the given arguments change.
+///
+/// memo: "native" (fast, default) or "dict" (what stdlib passes, compatible with
+/// every __deepcopy__).
+/// on_incompatible: when a __deepcopy__ rejects the native memo, "warn" retries
+/// with a dict and warns (default), "silent" retries without warning, "raise"
+/// lets the error propagate.
+/// suppress_warnings: error strings not to warn about; None clears the list.
+/// strict: raise CopyError instead of sharing an original copium couldn't copy.
+/// deepcopy_arg_compat: call a __deepcopy__ that takes no positional memo
+/// (`def __deepcopy__(self)`) without one instead of raising TypeError.
+/// deepcopy_not_implemented: when a __deepcopy__ raises NotImplementedError,
+/// "raise" propagates it (default, like stdlib), "fallback" copies that object
+/// through __reduce_ex__ as if it had no __deepcopy__.
+/// intern_cap: most distinct values one deepcopy(..., intern=...) call keeps
+/// canonical (at least 2, default 65536).
+/// negative_cache: once reducing an instance of a type raised, raise the same
+/// error for later instances without calling __reduce_ex__ again. Only for types
+/// whose reducer fails regardless of the instance (default False).
+/// max_memo_entries, max_keepalive: raise CopyError instead of letting a copy's
+/// memo grow past this many entries or kept-alive originals; 0 (default) is no limit.
+/// max_leaf_bytes, max_total_bytes: defaults for deepcopy()'s arguments of the
+/// same name; 0 (default) is no limit.
+/// copy_buffers: for objects that only expose the buffer protocol, such as
+/// memoryview, "raise" fails like stdlib (default) and "owned" copies the bytes
+/// into bytes (read-only buffer) or bytearray (writable), flattened in C order.
+/// mark_copies: record each top-level deepcopy for extra.last_copy_info() and set
+/// the record as the result's __copium__ attribute where possible (default False).
+/// compat_warnings: warn once per type when an instance's __dict__ holds a
+/// __reduce__, __getnewargs_ex__, __getnewargs__ or __setstate__ that copying
+/// ignores because the class doesn't define it (default False).
+/// memo_key_check: have the memo a __deepcopy__ receives raise KeyError when an
+/// entry is stored under a key that isn't id() of the object being copied or of
+/// one already memoized: keys are id(original), never a hash (default False).
+/// progress_every: containers copied between calls to the progress callback of
+/// deepcopy() and extra.replicate() (at least 1, default 100000).
+/// yield_every, on_checkpoint: call on_checkpoint() every yield_every containers
+/// so a cooperative scheduler (gevent, eventlet) can switch greenlets mid-copy;
+/// yield_every=0 (default) removes the callback.
+///
+/// Raises ValueError for unknown values, TypeError for mistyped or conflicting ones.
 #[pyfunction]
-#[pyo3(signature = (*, memo=None, on_incompatible=None, suppress_warnings=None))]
+#[pyo3(signature = (
+    *,
+    memo=None,
+    on_incompatible=None,
+    suppress_warnings=None,
+    strict=None,
+    deepcopy_arg_compat=None,
+    deepcopy_not_implemented=None,
+    intern_cap=None,
+    negative_cache=None,
+    max_memo_entries=None,
+    max_keepalive=None,
+    max_leaf_bytes=None,
+    max_total_bytes=None,
+    copy_buffers=None,
+    mark_copies=None,
+    compat_warnings=None,
+    memo_key_check=None,
+    progress_every=None,
+    yield_every=None,
+    on_checkpoint=None,
+))]
+// One keyword-only parameter per option mirrors the Python signature, so
+// each is type-checked by PyO3 and listed in config.pyi as is.
+#[allow(clippy::too_many_arguments)]
 fn apply(
     py: Python<'_>,
     memo: Option<PyMemoMode>,
     on_incompatible: Option<PyOnIncompatible>,
     suppress_warnings: Option<Bound<'_, PyAny>>,
+    strict: Option<bool>,
+    deepcopy_arg_compat: Option<bool>,
+    deepcopy_not_implemented: Option<PyDeepcopyNotImplemented>,
+    intern_cap: Option<isize>,
+    negative_cache: Option<bool>,
+    max_memo_entries: Option<usize>,
+    max_keepalive: Option<usize>,
+    max_leaf_bytes: Option<usize>,
+    max_total_bytes: Option<usize>,
+    copy_buffers: Option<PyCopyBuffers>,
+    mark_copies: Option<bool>,
+    compat_warnings: Option<bool>,
+    memo_key_check: Option<bool>,
+    progress_every: Option<u64>,
+    yield_every: Option<u32>,
+    on_checkpoint: Option<Bound<'_, PyAny>>,
 ) -> PyResult<()> {
-    if memo.is_none() && on_incompatible.is_none() && suppress_warnings.is_none() {
+    if memo.is_none()
+        && on_incompatible.is_none()
+        && suppress_warnings.is_none()
+        && strict.is_none()
+        && deepcopy_arg_compat.is_none()
+        && deepcopy_not_implemented.is_none()
+        && intern_cap.is_none()
+        && negative_cache.is_none()
+        && max_memo_entries.is_none()
+        && max_keepalive.is_none()
+        && max_leaf_bytes.is_none()
+        && max_total_bytes.is_none()
+        && copy_buffers.is_none()
+        && mark_copies.is_none()
+        && compat_warnings.is_none()
+        && memo_key_check.is_none()
+        && progress_every.is_none()
+        && yield_every.is_none()
+        && on_checkpoint.is_none()
+    {
         if unsafe { crate::state::load_config_from_env() } < 0 {
             return Err(PyErr::take(py)
                 .unwrap_or_else(|| PyRuntimeError::new_err("load_config_from_env failed")));
@@ -99,6 +289,105 @@ fn apply(
         ));
     }
 
+    if let Some(strict) = strict {
+        unsafe { (*state).strict = strict };
+    }
+
+    if let Some(deepcopy_arg_compat) = deepcopy_arg_compat {
+        unsafe { (*state).deepcopy_arg_compat = deepcopy_arg_compat };
+    }
+
+    if let Some(deepcopy_not_implemented) = deepcopy_not_implemented {
+        unsafe {
+            (*state).deepcopy_not_implemented = match deepcopy_not_implemented {
+                PyDeepcopyNotImplemented::Raise => DeepcopyNotImplemented::Raise,
+                PyDeepcopyNotImplemented::Fallback => DeepcopyNotImplemented::Fallback,
+            };
+        }
+    }
+
+    if let Some(intern_cap) = intern_cap {
+        if intern_cap < 2 {
+            return Err(PyValueError::new_err(format!(
+                "intern_cap must be at least 2, got {intern_cap}"
+            )));
+        }
+        unsafe { (*state).intern_cap = intern_cap };
+    }
+
+    if let Some(negative_cache) = negative_cache {
+        unsafe { (*state).negative_cache = negative_cache };
+    }
+
+    if let Some(max_memo_entries) = max_memo_entries {
+        unsafe { (*state).max_memo_entries = limit_from_py(max_memo_entries) };
+    }
+
+    if let Some(max_keepalive) = max_keepalive {
+        unsafe { (*state).max_keepalive = limit_from_py(max_keepalive) };
+    }
+
+    if let Some(max_leaf_bytes) = max_leaf_bytes {
+        unsafe { (*state).max_leaf_bytes = limit_from_py(max_leaf_bytes) };
+    }
+
+    if let Some(max_total_bytes) = max_total_bytes {
+        unsafe { (*state).max_total_bytes = limit_from_py(max_total_bytes) };
+    }
+
+    if let Some(copy_buffers) = copy_buffers {
+        unsafe {
+            (*state).copy_buffers = match copy_buffers {
+                PyCopyBuffers::Raise => CopyBuffers::Raise,
+                PyCopyBuffers::Owned => CopyBuffers::Owned,
+            };
+        }
+    }
+
+    if let Some(mark_copies) = mark_copies {
+        unsafe { (*state).mark_copies = mark_copies };
+    }
+
+    if let Some(compat_warnings) = compat_warnings {
+        unsafe { (*state).compat_warnings = compat_warnings };
+    }
+
+    if let Some(memo_key_check) = memo_key_check {
+        unsafe { (*state).memo_key_check = memo_key_check };
+    }
+
+    if let Some(progress_every) = progress_every {
+        if progress_every == 0 {
+            return Err(PyValueError::new_err("progress_every must be at least 1"));
+        }
+        unsafe { (*state).progress_every = progress_every };
+    }
+
+    if yield_every.is_some() || on_checkpoint.is_some() {
+        let every = yield_every.unwrap_or(unsafe { (*state).yield_every });
+        if every == 0 {
+            if on_checkpoint.is_some() {
+                return Err(PyTypeError::new_err("on_checkpoint needs yield_every > 0"));
+            }
+            unsafe { crate::state::set_on_checkpoint(0, std::ptr::null_mut()) };
+        } else {
+            let current = unsafe { (*state).on_checkpoint };
+            let callback = match on_checkpoint {
+                Some(callback) if !callback.is_callable() => {
+                    return Err(PyTypeError::new_err("on_checkpoint must be callable"));
+                }
+                Some(callback) => callback.into_ptr(),
+                None if !current.is_null() => unsafe { current.newref() },
+                None => {
+                    return Err(PyTypeError::new_err(
+                        "yield_every needs an on_checkpoint callback",
+                    ));
+                }
+            };
+            unsafe { crate::state::set_on_checkpoint(every, callback) };
+        }
+    }
+
     if let Some(on_incompatible) = on_incompatible {
         unsafe {
             (*state).on_incompatible = match on_incompatible {
@@ -148,12 +437,37 @@ fn apply(
     Ok(())
 }
 
-//  copium.config.get()
+/// 0 is no limit, stored as `usize::MAX` so the check is one comparison.
+fn limit_from_py(limit: usize) -> usize {
+    if limit == 0 { usize::MAX } else { limit }
+}
+
+fn limit_to_py(limit: usize) -> usize {
+    if limit == usize::MAX { 0 } else { limit }
+}
+
+/// The current configuration, as a dict of the arguments apply() takes.
 #[pyfunction]
 fn get(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
     let state_pointer = std::ptr::addr_of!(STATE);
     let memo_mode = unsafe { (*state_pointer).memo_mode };
     let on_incompatible = unsafe { (*state_pointer).on_incompatible };
+    let strict = unsafe { (*state_pointer).strict };
+    let deepcopy_arg_compat = unsafe { (*state_pointer).deepcopy_arg_compat };
+    let deepcopy_not_implemented = unsafe { (*state_pointer).deepcopy_not_implemented };
+    let intern_cap = unsafe { (*state_pointer).intern_cap };
+    let negative_cache = unsafe { (*state_pointer).negative_cache };
+    let max_memo_entries = unsafe { (*state_pointer).max_memo_entries };
+    let max_keepalive = unsafe { (*state_pointer).max_keepalive };
+    let max_leaf_bytes = unsafe { (*state_pointer).max_leaf_bytes };
+    let max_total_bytes = unsafe { (*state_pointer).max_total_bytes };
+    let copy_buffers = unsafe { (*state_pointer).copy_buffers };
+    let mark_copies = unsafe { (*state_pointer).mark_copies };
+    let compat_warnings = unsafe { (*state_pointer).compat_warnings };
+    let memo_key_check = unsafe { (*state_pointer).memo_key_check };
+    let progress_every = unsafe { (*state_pointer).progress_every };
+    let yield_every = unsafe { (*state_pointer).yield_every };
+    let on_checkpoint = unsafe { (*state_pointer).on_checkpoint };
     let ignored_errors = unsafe { (*state_pointer).ignored_errors };
     let dict = PyDict::new(py);
 
@@ -174,6 +488,39 @@ fn get(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
         },
     )?;
 
+    dict.set_item("strict", strict)?;
+    dict.set_item("deepcopy_arg_compat", deepcopy_arg_compat)?;
+    dict.set_item(
+        "deepcopy_not_implemented",
+        match deepcopy_not_implemented {
+            DeepcopyNotImplemented::Raise => "raise",
+            DeepcopyNotImplemented::Fallback => "fallback",
+        },
+    )?;
+    dict.set_item("intern_cap", intern_cap)?;
+    dict.set_item("negative_cache", negative_cache)?;
+    dict.set_item("max_memo_entries", limit_to_py(max_memo_entries))?;
+    dict.set_item("max_keepalive", limit_to_py(max_keepalive))?;
+    dict.set_item("max_leaf_bytes", limit_to_py(max_leaf_bytes))?;
+    dict.set_item("max_total_bytes", limit_to_py(max_total_bytes))?;
+    dict.set_item(
+        "copy_buffers",
+        match copy_buffers {
+            CopyBuffers::Raise => "raise",
+            CopyBuffers::Owned => "owned",
+        },
+    )?;
+    dict.set_item("mark_copies", mark_copies)?;
+    dict.set_item("compat_warnings", compat_warnings)?;
+    dict.set_item("memo_key_check", memo_key_check)?;
+    dict.set_item("progress_every", progress_every)?;
+    dict.set_item("yield_every", yield_every)?;
+    if on_checkpoint.is_null() {
+        dict.set_item("on_checkpoint", py.None())?;
+    } else {
+        dict.set_item("on_checkpoint", unsafe { Bound::from_borrowed_ptr(py, on_checkpoint) })?;
+    }
+
     let sw = unsafe {
         if !ignored_errors.is_null() {
             ignored_errors.newref()
@@ -190,7 +537,7 @@ fn get(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
 pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
     let py = unsafe { Python::assume_attached() };
     let result: PyResult<()> = (|| {
-        let m = pyo3::types::PyModule::new(py, "config")?;
+        let m = pyo3::types::PyModule::new(py, "copium.config")?;
         m.add_function(wrap_pyfunction!(apply, &m)?)?;
         m.add_function(wrap_pyfunction!(get, &m)?)?;
         let ptr = m.into_ptr();
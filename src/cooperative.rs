@@ -0,0 +1,49 @@
+//! `config.apply(yield_every=N, on_checkpoint=callback)`: letting a
+//! cooperative scheduler run during a long copy.
+//!
+//! Under gevent or eventlet, a deepcopy keeps every other greenlet waiting
+//! until it returns. With both options set, the traversal calls the callback
+//! every N containers, at the same place offloaded copies and timeouts check
+//! in, so a callback like `gevent.sleep` can switch to other greenlets. It
+//! runs with the GIL held, in the middle of the copy, and must not mutate
+//! what is being copied; an exception it raises fails the copy. Copies
+//! without a callback never get there.
+
+use pyo3_ffi::*;
+
+use crate::state::STATE;
+use crate::types::PyObjectPtr;
+
+#[thread_local]
+static mut SINCE_CHECKPOINT: u32 = 0;
+
+#[inline(always)]
+pub fn is_active() -> bool {
+    unsafe { STATE.yield_every != 0 }
+}
+
+/// Called on every traversal step while `yield_every` is set.
+#[cold]
+pub unsafe fn checkpoint() -> i32 {
+    unsafe {
+        SINCE_CHECKPOINT += 1;
+        if SINCE_CHECKPOINT < STATE.yield_every {
+            return 0;
+        }
+        SINCE_CHECKPOINT = 0;
+
+        let callback = STATE.on_checkpoint;
+        if callback.is_null() {
+            return 0;
+        }
+        // Held across the call: the callback may reconfigure copium.
+        callback.incref();
+        let result = PyObject_CallNoArgs(callback);
+        callback.decref();
+        if result.is_null() {
+            return -1;
+        }
+        result.decref();
+        0
+    }
+}
@@ -1,30 +1,125 @@
+import builtins
 import sys
+from collections.abc import Callable, Iterable, Mapping
 from copy import Error
-from typing import Any, TypeVar
+from typing import Any, Literal, TypedDict, TypeVar
 
 from copium import patch, config
 
-__all__ = ["copy", "deepcopy", "Error", "patch", "config"]
+__all__ = [
+    "copy",
+    "deepcopy",
+    "deepcopy_fragment",
+    "Error",
+    "CopyError",
+    "TimeoutError",
+    "patch",
+    "config",
+]
 
 T = TypeVar("T")
 
-def copy(x: T) -> T:
+class _BuildInfo(TypedDict):
+    version: str
+    git_hash: str
+    build_hash: str
+    rustc: str
+    pyo3: str
+    patch_strategy: Literal["vectorcall", "code"]
+    abi3: bool
+    free_threaded: bool
+    debug: bool
+
+class CopyError(Error):
+    """
+    Raised by copium for objects it doesn't know how to copy.
+
+    Subclasses copy.Error, so `except copy.Error` keeps catching it.
+    """
+
+    obj_type: str | None
+    """Qualified name of the type that couldn't be copied."""
+    path: list[Any] | None
+    """Access steps leading to the object, or None when paths aren't tracked."""
+    original: BaseException | None
+    """The underlying exception, also chained as __cause__, or None."""
+
+class TimeoutError(Error, builtins.TimeoutError):
+    """
+    Raised when deepcopy() runs past its timeout.
+
+    Subclasses copy.Error and the builtin TimeoutError.
+    """
+
+__version__: str
+__build__: _BuildInfo
+features: frozenset[str]
+
+def copy(
+    x: T,
+    /,
+    *,
+    replace: Mapping[Any, Any] | Iterable[tuple[Any, Any]] | None = None,
+) -> T:
     """
     Natively compiled copy.
 
     :param x: object to copy.
+    :param replace: originals (matched by identity) to substitute with the given
+        replacements among the copy's direct entries: list items, dict values,
+        set members, or otherwise the attributes in its `__dict__`.
+        Nothing deeper is looked at. Pairs can be used for unhashable originals.
     :return: shallow copy of the `x`.
     """
 
-def deepcopy(x: T, memo: dict[int, Any] | None = None) -> T:
+def deepcopy(
+    x: T,
+    memo: dict[int, Any] | None = None,
+    *,
+    replace: Mapping[Any, Any] | Iterable[tuple[Any, Any]] | None = None,
+    assume_tree: bool = False,
+    intern: Literal["none", "str", "str+tuple"] | None = "none",
+    timeout: float | None = None,
+    max_leaf_bytes: int | None = None,
+    max_total_bytes: int | None = None,
+    progress: Callable[[int, None], object] | None = None,
+) -> T:
     """
     Natively compiled deepcopy.
 
     :param x: object to deepcopy
     :param memo: treat as opaque.
+    :param replace: originals (matched by identity) to substitute with the given
+        replacements wherever they appear, instead of copying them.
+        Pairs can be used for unhashable originals.
+    :param assume_tree: promise that no object is reachable twice, so no memo is kept.
+        Shared objects are copied once per reference; cycles raise CopyError.
+        Can't be combined with `memo` or `replace`.
+    :param intern: 'str' makes equal strings in the copy one object; 'str+tuple' also
+        does so for equal tuples of literals. The copy stays equal to `x`, but
+        identities differ from it. Can't be combined with `assume_tree`.
+    :param timeout: seconds the copy may take before it stops with TimeoutError.
+        Checked every few dozen containers, so a single slow `__deepcopy__`
+        overruns it by as long as it takes. A memo passed in is left with only the
+        entries it had before the call.
+    :param max_leaf_bytes: raise CopyError before duplicating a bytearray, bytes subclass
+        or array.array larger than this many bytes. 0 is no limit; None (default) uses
+        `copium.config`'s.
+    :param max_total_bytes: likewise, for all of those together in this call.
+    :param progress: called as `progress(objects_copied, None)` every `progress_every`
+        containers (see `copium.config`, 100000 by default), with the GIL held, at the
+        same checkpoints as `timeout`. An exception it raises stops the copy with it.
     :return: deep copy of the `x`.
     """
 
+def deepcopy_fragment(x: T, memo: Any, /) -> T:
+    """
+    Deep copy x as part of the copy that passed memo to __deepcopy__.
+
+    Positional-only fast path for custom `__deepcopy__` methods; equivalent to
+    `deepcopy(x, memo)`.
+    """
+
 if sys.version_info >= (3, 13):
     def replace(obj: T, /, **changes: Any) -> T:
         """
@@ -0,0 +1,30 @@
+from typing import Any
+
+__all__ = ["assert_idle", "keepalive_contents", "simulate_leak"]
+
+def keepalive_contents() -> list[tuple[str, int, int]]:
+    """
+    (type_name, id, refcount) for each object this thread's memo retains.
+
+    Lists the keepalive, then the copies in the memo table. Empty between
+    deepcopy() calls unless something leaked; objects aren't copied or kept.
+    """
+
+def assert_idle() -> None:
+    """
+    Raise AssertionError if this thread's memo retains any object.
+
+    Meant for an autouse fixture run after each test:
+
+        @pytest.fixture(autouse=True)
+        def _copium_idle():
+            yield
+            copium._debug.assert_idle()
+    """
+
+def simulate_leak(obj: Any, /) -> None:
+    """
+    Leave obj in this thread's keepalive as a memo that wasn't reset would.
+
+    For testing leak checks; the next deepcopy() on this thread releases it.
+    """
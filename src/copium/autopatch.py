@@ -0,0 +1,29 @@
+"""
+Importing this module patches copy.deepcopy() with copium for the whole process.
+
+Meant for sitecustomize.py or a .pth file, so the patch is in place before any
+application code runs: ``import copium.autopatch``. Setting COPIUM_PATCH_DISABLE
+turns the import into a no-op. A failure to patch is reported as a
+RuntimeWarning instead of raised, and stdlib's deepcopy stays in use.
+"""
+
+import os
+import warnings
+
+
+def _enable() -> None:
+    if os.environ.get("COPIUM_PATCH_DISABLE"):
+        return
+    try:
+        import copium.patch
+
+        copium.patch.enable()
+    except Exception as error:  # noqa: BLE001 - must not break interpreter startup
+        warnings.warn(
+            f"copium.autopatch: copy.deepcopy() was left unpatched: {error!r}",
+            RuntimeWarning,
+            stacklevel=2,
+        )
+
+
+_enable()
@@ -1,4 +1,4 @@
-from typing import Literal, Sequence, TypedDict, overload
+from typing import Callable, Literal, Sequence, TypedDict, overload
 
 __all__ = ["apply", "get"]
 
@@ -27,6 +27,22 @@ def apply(
     memo: Literal["native"] = ...,
     on_incompatible: Literal["warn", "raise", "silent"] = ...,
     suppress_warnings: Sequence[str] | None = ...,
+    strict: bool = ...,
+    deepcopy_arg_compat: bool = ...,
+    deepcopy_not_implemented: Literal["raise", "fallback"] = ...,
+    intern_cap: int = ...,
+    negative_cache: bool = ...,
+    max_memo_entries: int = ...,
+    max_keepalive: int = ...,
+    max_leaf_bytes: int = ...,
+    max_total_bytes: int = ...,
+    copy_buffers: Literal["raise", "owned"] = ...,
+    mark_copies: bool = ...,
+    compat_warnings: bool = ...,
+    memo_key_check: bool = ...,
+    progress_every: int = ...,
+    yield_every: int = ...,
+    on_checkpoint: Callable[[], object] = ...,
 ) -> None:
     """
     Configure copium behavior. Only specified arguments are changed.
@@ -39,12 +55,74 @@ def apply(
         Only relevant when memo='native'.
     :param suppress_warnings: Error strings to suppress warnings for.
         None clears the list.
+    :param strict: raise CopyError instead of returning an original copium couldn't copy,
+        e.g. when its __reduce__ returns a string.
+    :param deepcopy_arg_compat: call a __deepcopy__ that rejects the positional memo
+        (`def __deepcopy__(self)`, `def __deepcopy__(self, *, memo=None)`) without it,
+        instead of raising TypeError like stdlib. Remembered per type.
+    :param deepcopy_not_implemented: What to do when __deepcopy__ raises NotImplementedError.
+        'raise' lets it propagate, like stdlib (default).
+        'fallback' copies that object through __reduce_ex__ as if it had no __deepcopy__.
+        Decided for each object, never remembered per type.
+    :param intern_cap: most distinct values one `deepcopy(..., intern=...)` call keeps
+        canonical; the least recently used are dropped first. At least 2.
+    :param negative_cache: once `__reduce_ex__` raised for an instance of a type, raise
+        the same error for later instances without calling it again, until the type
+        changes. Only safe for types whose reducer fails whatever the instance's state,
+        so it is off by default (default False).
+    :param max_memo_entries: raise CopyError when a copy's memo would hold more entries
+        than this; 0 is no limit (default).
+    :param max_keepalive: raise CopyError when a copy's memo would keep more originals
+        alive than this; 0 is no limit (default).
+    :param max_leaf_bytes: default for `deepcopy(..., max_leaf_bytes=)`; 0 is no limit (default).
+    :param max_total_bytes: default for `deepcopy(..., max_total_bytes=)`; 0 is no limit
+        (default).
+    :param copy_buffers: What to do with objects that only expose the buffer protocol,
+        such as memoryview.
+        'raise' fails like stdlib, since they can't be reduced (default).
+        'owned' copies the bytes they expose: into bytes for a read-only buffer, into a
+        bytearray for a writable one. Multidimensional and strided buffers are flattened
+        in C order, like memoryview.tobytes().
+    :param mark_copies: after each top-level deepcopy (one given no memo), keep a record
+        of it for `copium.extra.last_copy_info()` and set it as the result's `__copium__`
+        attribute, unless the result can't take one (default False).
+    :param compat_warnings: warn (once per type) when an instance's __dict__ holds a
+        __reduce__, __getnewargs_ex__, __getnewargs__ or __setstate__ that the class
+        doesn't define. Copying ignores those, like stdlib does (default False).
+    :param memo_key_check: have the memo a __deepcopy__ receives raise KeyError for an
+        entry stored under a key that isn't id() of the object being copied or of one
+        already memoized. A debugging aid: keys are id(original), never hash(original)
+        (default False).
+    :param progress_every: containers copied between calls to the `progress` callback
+        of `deepcopy()` and `copium.extra.replicate()`; at least 1, default 100000.
+    :param yield_every: call `on_checkpoint` every this many containers, so a cooperative
+        scheduler (gevent, eventlet) can switch greenlets during a long copy. 0 (default)
+        removes the callback.
+    :param on_checkpoint: called with no arguments and the GIL held, in the middle of a copy.
+        It must not mutate what is being copied; an exception it raises fails the copy.
+        Pass e.g. `gevent.sleep`.
     """
 
 class _CopiumConfig(TypedDict, total=True):
     memo: Literal["native", "dict"]
     on_incompatible: Literal["warn", "raise", "silent"]
     suppress_warnings: tuple[str, ...]
+    strict: bool
+    deepcopy_arg_compat: bool
+    deepcopy_not_implemented: Literal["raise", "fallback"]
+    intern_cap: int
+    negative_cache: bool
+    max_memo_entries: int
+    max_keepalive: int
+    max_leaf_bytes: int
+    max_total_bytes: int
+    copy_buffers: Literal["raise", "owned"]
+    mark_copies: bool
+    compat_warnings: bool
+    memo_key_check: bool
+    progress_every: int
+    yield_every: int
+    on_checkpoint: Callable[[], object] | None
 
 def get() -> _CopiumConfig:
     """
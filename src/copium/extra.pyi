@@ -1,10 +1,57 @@
+import asyncio
+from typing import Any
 from typing import Callable
+from typing import IO
+from typing import Literal
+from typing import MutableSequence
+from typing import TypedDict
 from typing import TypeVar
 
-__all__ = ["repeatcall", "replicate"]
+__all__ = [
+    "CustomMemo",
+    "adeepcopy",
+    "clear_caches",
+    "deepcopy_json",
+    "deepcopy_with_memo",
+    "diff",
+    "dump_structure",
+    "fingerprint",
+    "last_copy_info",
+    "load_structure",
+    "memo_stats",
+    "repeatcall",
+    "replicate",
+    "replicate_into",
+    "reset_memo_stats",
+]
 
 T = TypeVar("T")
 
+class CustomMemo:
+    """
+    Base class for memos implemented in Python.
+
+    Pass an instance as deepcopy(obj, memo=...) and every memo operation calls
+    its methods: lookup() before copying a mutable object, then insert() and
+    keepalive() once the copy exists. Keys are id() of originals. A failed copy
+    is rolled back with discard(). get(), [] and []= are defined on top of
+    lookup() and insert(), so the memo still works as the mapping custom
+    __deepcopy__ methods and stdlib copy.deepcopy expect.
+    """
+
+    def __init__(self, *args: Any, **kwargs: Any) -> None: ...
+    def lookup(self, key: int, /) -> Any | None:
+        """The copy recorded for key, or None."""
+    def insert(self, key: int, value: Any, /) -> None:
+        """Record value as the copy of the original whose id is key."""
+    def keepalive(self, obj: Any, /) -> None:
+        """Keep obj alive for as long as its id may be looked up."""
+    def discard(self, key: int, /) -> None:
+        """Drop what was recorded for key; does nothing unless overridden."""
+    def get(self, key: int, default: Any = None) -> Any: ...
+    def __getitem__(self, key: int) -> Any: ...
+    def __setitem__(self, key: int, value: Any) -> None: ...
+
 def repeatcall(function: Callable[[], T], size: int, /) -> list[T]:
     """
     Call function repeatedly size times and return the list of results.
@@ -12,9 +59,160 @@ def repeatcall(function: Callable[[], T], size: int, /) -> list[T]:
     Equivalent of [function() for _ in range(size)], but faster.
     """
 
-def replicate(obj: T, /, n: int) -> list[T]:
+def replicate(
+    obj: T,
+    n: int,
+    /,
+    *,
+    verify_first: bool = False,
+    progress: Callable[[int, int], object] | None = None,
+) -> list[T]:
     """
     Returns n copies of the object in a list.
 
     Equivalent of [deepcopy(obj) for _ in range(n)], but faster.
+
+    With verify_first, the first copy is compared against obj the way diff() does,
+    and copium.CopyError is raised before the remaining n - 1 are made if the two
+    differ anywhere. That costs one walk of the graph, however large n is.
+
+    progress is called as progress(copies_done, n) every `progress_every` containers
+    copied (see copium.config); an exception it raises stops replicate() with it.
+    """
+
+def replicate_into(obj: T, target: MutableSequence[T], n: int, /) -> int:
+    """
+    Write n deep copies of obj into target and return n.
+
+    Items that target already has are overwritten by index, the rest are
+    appended, so a pooled list can be refilled without building a new one.
+    target may be any object with __setitem__ and, if it is shorter than n,
+    append. If a copy or a write fails, the items already written stay in
+    place and the exception gets a note naming the failing index.
+    """
+
+def deepcopy_with_memo(obj: T, /) -> tuple[T, dict[int, Any]]:
+    """
+    Deep copy obj and return (copy, memo), where memo maps id(original) to its copy.
+
+    memo is laid out like the dict copy.deepcopy fills: memo[id(memo)] lists every
+    memoized original, which keeps them alive and their ids unambiguous for as long
+    as that entry stays in memo. Passing memo back to deepcopy reuses the copies.
+    """
+
+def deepcopy_json(obj: T, /, *, preserve_sharing: bool = False) -> T:
+    """
+    Deep copy JSON-like data: dicts, lists, str, int, float, bool and None.
+
+    Exact types only; anything else raises TypeError naming its type. Without
+    preserve_sharing no memo is kept, so a container reachable more than once
+    is copied once per reference and a cycle raises RecursionError.
+    """
+
+def adeepcopy(obj: T, /) -> asyncio.Future[T]:
+    """
+    Deep copy obj on the running loop's default executor.
+
+    Equivalent of loop.run_in_executor(None, deepcopy, obj), but the copy
+    periodically lets the event loop run, copies large bytearrays with the GIL
+    released, and stops with CancelledError once the returned future is cancelled.
+    Must be called from a coroutine or callback running in an event loop.
+    """
+
+def memo_stats(*, include_highwater: bool = False) -> dict[str, int]:
+    """
+    Sizes of the calling thread's reusable memo, as kept between deepcopy() calls.
+
+    "slots" is the retained table size, "keepalive" the retained keepalive capacity
+    and "bytes" their combined native footprint. With include_highwater, also
+    "max_used", "max_slots" and "max_keepalive": the largest entry count, table
+    size and keepalive length any deepcopy() on this thread reached since the last
+    reset_memo_stats().
+    """
+
+def reset_memo_stats() -> None:
+    """Forget the calling thread's memo high-water marks."""
+
+def clear_caches() -> None:
+    """
+    Drop the calling thread's per-type caches.
+
+    Each cache entry keeps its type alive until it is evicted; this releases
+    them all, so a class deleted since it was last copied can be collected. It
+    also forgets remembered __reduce_ex__ failures.
+    """
+
+def dump_structure(obj: Any, file: IO[str], /) -> None:
+    """
+    Write the shape of obj's object graph to file as JSON, without its values.
+
+    Records what deepcopy would see: each object's type name and dispatch path,
+    attribute and slot names, container sizes, what __reduce_ex__ returns and
+    which objects are shared or cyclic. Leaf values and dict keys are replaced
+    by references to anonymous nodes. An exception raised while reducing is
+    recorded by its type name. Nothing is copied and __deepcopy__ is not called.
+    """
+
+def load_structure(file: IO[str], /) -> Any:
+    """
+    Build a synthetic object graph shaped like a dump_structure() file.
+
+    Objects are instances of generated classes with the recorded names and
+    copy hooks, and leaves are fresh placeholder values, so deep copying the
+    result takes the same paths as copying the original did. Raises ValueError
+    for anything that is not a dump_structure() file.
+    """
+
+def diff(
+    a: Any, b: Any, /, max_depth: int | None = None
+) -> list[tuple[str, Literal["added", "removed", "changed", "type"], str | None, str | None]]:
+    """
+    List where the object graphs a and b differ.
+
+    Walks both graphs in lockstep: dicts key by key, lists and tuples position by
+    position, sets by membership and other objects by their __dict__ and slots.
+    Each difference is (path, kind, repr of a's value, repr of b's value), where
+    path reads like an expression suffix such as "['key'][0].attr" and the value
+    missing from one side is None. kind is "added", "removed", "changed" or
+    "type" when the two values' types differ. Set members present on one side
+    are reported at the set's own path.
+
+    Identical objects are not looked into, and a pair of objects already being
+    compared is not compared again, so shared subtrees are walked once and
+    cycles end. Other values, and everything max_depth levels down, are compared
+    with ==; an exception raised by __eq__ counts as "changed".
+    """
+
+def fingerprint(obj: Any, /) -> int:
+    """
+    Return a 128-bit content hash of the object graph, as a non-negative int.
+
+    Walks obj like diff() does, without copying anything: lists and tuples hash
+    their items in order, dicts and sets their entries in any order, other
+    objects their __dict__ and slots, and each value its type. Leaves hash with
+    hash(), bytes and bytearray by content, and unhashable leaves by type alone.
+    A mutable container reached again, through a cycle or shared reference,
+    hashes as the position it was first reached at, so sharing counts.
+
+    Use it to group logically equal copies: graphs deepcopy would reproduce get
+    the same fingerprint. It is not cryptographic, collisions are possible, and
+    values are only comparable within one process since str hashes are salted.
+    """
+
+class CopyInfo(TypedDict):
+    copy_id: int
+    timestamp: float
+    thread_id: int
+    source_type: type
+
+def last_copy_info() -> CopyInfo | None:
+    """
+    Return the record of this thread's latest top-level deepcopy, or None.
+
+    Records are kept while copium.config.apply(mark_copies=True) is on: each
+    deepcopy() given no memo gets a process-wide copy_id counting up from 1, a
+    time.time() timestamp, the threading.get_ident() of the copying thread and the
+    type of the object copied. The same dict is set as the result's __copium__
+    attribute when the result is a new object that takes one; this returns it
+    either way.
     """
@@ -1,9 +1,36 @@
-__all__ = ["enable", "disable", "enabled"]
+from collections.abc import Iterable
+from typing import TypedDict
 
-def enable() -> bool:
+__all__ = ["enable", "disable", "enabled", "stats", "check"]
+
+class _PatchStats(TypedDict):
+    calls_forwarded: int
+    calls_since_enable: int
+    callers: dict[str, int]
+
+class _IncompatibleType(TypedDict):
+    type: type
+    reason: str
+
+class _CheckReport(TypedDict):
+    incompatible: list[_IncompatibleType]
+    visited: int
+    truncated: bool
+
+def enable(
+    *,
+    sample_callers: bool = False,
+    check: bool = False,
+    modules: str | Iterable[str] | None = None,
+) -> bool:
     """
     Patch copy.deepcopy to use copium. Idempotent.
 
+    :param sample_callers: record the "filename:lineno" of every 1024th forwarded
+        call in stats()["callers"]. Applies even if already enabled.
+    :param check: run check(modules) first and emit one UserWarning naming
+        the types it registered.
+    :param modules: passed to check().
     :return: True if state changed, False otherwise.
     """
 
@@ -18,3 +45,33 @@ def enabled() -> bool:
     """
     :return: Whether copy.deepcopy is patched.
     """
+
+def stats() -> _PatchStats:
+    """
+    Calls to the patched copy.deepcopy that were forwarded to copium.
+
+    calls_forwarded counts since import, calls_since_enable since the last
+    enable() that changed state. callers holds sampled call sites, reset
+    by that same enable().
+    """
+
+def check(
+    modules: str | Iterable[str] | None = None,
+    *,
+    register: bool = True,
+    limit: int = 100_000,
+) -> _CheckReport:
+    """
+    Find classes whose __deepcopy__ likely rejects copium's memo: native
+    implementations outside the stdlib, ones known to, and Python ones
+    annotating the memo as a dict.
+
+    Only looks at classes that already exist; nothing is imported.
+
+    :param modules: names of already imported modules whose classes to scan.
+        None walks every subclass of object.
+    :param register: give flagged types a dict memo from their first copy,
+        without the failed attempt and warning.
+    :param limit: stop after visiting this many classes; the report is then
+        marked truncated.
+    """
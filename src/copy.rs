@@ -1,4 +1,5 @@
 use crate::deepcopy::PyResult;
+use crate::memo::{seed_replacements, DictMemo, Memo};
 use crate::reduce::{self, ReduceKind};
 use crate::types::*;
 use crate::{ffi_ext, py_str};
@@ -90,13 +91,17 @@ impl PyCopy for *mut PyByteArrayObject {
 impl PyCopy for *mut PyObject {
     unsafe fn copy(self) -> PyResult {
         unsafe {
+            // stdlib resolves __copy__ on the class, not the instance
             let mut custom_copy: *mut PyObject = ptr::null_mut();
-            let has_custom_copy = self.get_optional_attr(py_str!("__copy__"), &mut custom_copy);
+            let class = self.class() as *mut PyObject;
+            let has_custom_copy = class.get_optional_attr(py_str!("__copy__"), &mut custom_copy);
             if has_custom_copy < 0 {
                 return PyResult::error();
             }
-            if has_custom_copy > 0 {
-                let copied = custom_copy.call();
+            if has_custom_copy > 0 && custom_copy.is_none() {
+                custom_copy.decref();
+            } else if has_custom_copy > 0 {
+                let copied = custom_copy.call_one(self);
                 custom_copy.decref();
                 if copied.is_null() {
                     return PyResult::error();
@@ -149,26 +154,7 @@ unsafe fn apply_dict_state(instance: *mut PyObject, dict_state: *mut PyObject) -
         }
 
         if !dict_state.is_dict() {
-            let instance_dict = instance.getattr(py_str!("__dict__"));
-            if instance_dict.is_null() {
-                return -1;
-            }
-
-            let result = PyDict_Merge(instance_dict, dict_state, 1);
-            instance_dict.decref();
-            if result < 0 {
-                let message = ffi_ext::PyUnicode_FromFormat(
-                    crate::cstr!(
-                        "dict state from %s.__reduce__ must be a dict or mapping, got %.200s"
-                    ),
-                    (*instance.class()).tp_name,
-                    (*dict_state.class()).tp_name,
-                );
-                if !message.is_null() {
-                    reduce::chain_type_error(message);
-                }
-            }
-            return result;
+            return reduce::merge_dict_state(instance, dict_state);
         }
 
         let instance_dict = instance.getattr(py_str!("__dict__"));
@@ -181,8 +167,15 @@ unsafe fn apply_dict_state(instance: *mut PyObject, dict_state: *mut PyObject) -
         let mut position: Py_ssize_t = 0;
         let mut result: c_int = 0;
 
+        // The state belongs to the original, and setting an item can run code
+        // that changes it: hold what PyDict_Next lends for the duration.
         while PyDict_Next(dict_state, &mut position, &mut key, &mut value) != 0 {
-            if PyObject_SetItem(instance_dict, key, value) < 0 {
+            key.incref();
+            value.incref();
+            let status = PyObject_SetItem(instance_dict, key, value);
+            key.decref();
+            value.decref();
+            if status < 0 {
                 result = -1;
                 break;
             }
@@ -277,7 +270,12 @@ unsafe fn apply_slot_state(instance: *mut PyObject, slot_state: *mut PyObject) -
         let mut result: c_int = 0;
 
         while PyDict_Next(slot_state, &mut position, &mut key, &mut value) != 0 {
-            if instance.set_attr(key, value) < 0 {
+            key.incref();
+            value.incref();
+            let status = instance.set_attr(key, value);
+            key.decref();
+            value.decref();
+            if status < 0 {
                 result = -1;
                 break;
             }
@@ -460,13 +458,13 @@ unsafe fn copy_via_reduce(object: *mut PyObject) -> PyResult {
                 return PyResult::error();
             }
 
-            reduce_result = reduce::call_reduce_method_preferring_ex(object);
+            reduce_result = reduce::call_reduce_method_preferring_ex(object, false);
             if reduce_result.is_null() {
                 return PyResult::error();
             }
         }
 
-        let (kind, parts) = reduce::validate_reduce_tuple(reduce_result, class);
+        let (kind, parts) = reduce::validate_reduce_tuple(&mut reduce_result, class);
 
         match kind {
             ReduceKind::Error => {
@@ -496,3 +494,169 @@ unsafe fn copy_via_reduce(object: *mut PyObject) -> PyResult {
         }
     }
 }
+
+// ── Substitutions ──────────────────────────────────────────
+
+/// `copy(x, replace=...)`: the shallow copy, with each top-level entry that is
+/// one of the originals in `replace` (by identity) swapped for its
+/// replacement. Entries are the items of a list, the values of a dict and the
+/// members of a set; for anything else, the attributes in the copy's
+/// `__dict__`. Nothing below the top level is looked at. An `x` that is itself
+/// an original is replaced whole.
+pub unsafe fn copy_replacing(object: *mut PyObject, replace: *mut PyObject) -> PyResult {
+    unsafe {
+        let table = check!(PyDict_New());
+        let mut memo = DictMemo::new(table as _);
+        let result = copy_substituted(object, replace, &mut memo);
+        drop(memo);
+        table.decref();
+        result
+    }
+}
+
+unsafe fn copy_substituted(
+    object: *mut PyObject,
+    replace: *mut PyObject,
+    memo: &mut DictMemo,
+) -> PyResult {
+    unsafe {
+        if seed_replacements(memo, replace) < 0 {
+            return PyResult::error();
+        }
+        match replacement_for(memo, object) {
+            Err(()) => return PyResult::error(),
+            Ok(whole) if !whole.is_null() => return PyResult::ok(whole),
+            Ok(_) => {}
+        }
+
+        let copied = copy(object);
+        if copied.is_error() {
+            return copied;
+        }
+        let copied = copied.into_raw();
+        // Shared with the original (immutable, or a __copy__ returning self):
+        // there is nothing of its own to substitute in.
+        if copied == object {
+            return PyResult::ok(copied);
+        }
+
+        let cls = copied.class();
+        let status = if PyListObject::cast_exact(copied, cls).is_some() {
+            substitute_list_items(copied, memo)
+        } else if PyDictObject::cast_exact(copied, cls).is_some() {
+            substitute_dict_values(copied, memo)
+        } else if PySetObject::cast_exact(copied, cls).is_some() {
+            substitute_set_members(copied, memo)
+        } else {
+            substitute_attributes(copied, memo)
+        };
+        if status < 0 {
+            copied.decref();
+            return PyResult::error();
+        }
+        PyResult::ok(copied)
+    }
+}
+
+/// The replacement for `object` as a new reference, or null when there is none.
+#[inline(always)]
+unsafe fn replacement_for(
+    memo: &mut DictMemo,
+    object: *mut PyObject,
+) -> Result<*mut PyObject, ()> {
+    unsafe {
+        let (_, found) = memo.recall(object);
+        if found.is_null() && !PyErr_Occurred().is_null() {
+            return Err(());
+        }
+        Ok(found)
+    }
+}
+
+unsafe fn substitute_list_items(list: *mut PyObject, memo: &mut DictMemo) -> c_int {
+    unsafe {
+        for i in 0..PyList_GET_SIZE(list) {
+            let Ok(found) = replacement_for(memo, PyList_GET_ITEM(list, i)) else {
+                return -1;
+            };
+            if !found.is_null() {
+                PyList_SetItem(list, i, found);
+            }
+        }
+        0
+    }
+}
+
+unsafe fn substitute_dict_values(dict: *mut PyObject, memo: &mut DictMemo) -> c_int {
+    unsafe {
+        let mut key: *mut PyObject = ptr::null_mut();
+        let mut value: *mut PyObject = ptr::null_mut();
+        let mut pos: Py_ssize_t = 0;
+        // Replacing the value of a present key never resizes the dict, and the
+        // old value lives on in the original, so iteration stays valid.
+        while PyDict_Next(dict, &mut pos, &mut key, &mut value) != 0 {
+            let Ok(found) = replacement_for(memo, value) else {
+                return -1;
+            };
+            if !found.is_null() {
+                let status = PyDict_SetItem(dict, key, found);
+                found.decref();
+                if status < 0 {
+                    return -1;
+                }
+            }
+        }
+        0
+    }
+}
+
+unsafe fn substitute_set_members(set: *mut PyObject, memo: &mut DictMemo) -> c_int {
+    unsafe {
+        let members = PySequence_List(set);
+        if members.is_null() {
+            return -1;
+        }
+        let mut ret: c_int = 0;
+        for i in 0..PyList_GET_SIZE(members) {
+            let member = PyList_GET_ITEM(members, i);
+            let Ok(found) = replacement_for(memo, member) else {
+                ret = -1;
+                break;
+            };
+            if found.is_null() {
+                continue;
+            }
+            let status = if PySet_Discard(set, member) < 0 {
+                -1
+            } else {
+                PySet_Add(set, found)
+            };
+            found.decref();
+            if status < 0 {
+                ret = -1;
+                break;
+            }
+        }
+        members.decref();
+        ret
+    }
+}
+
+unsafe fn substitute_attributes(instance: *mut PyObject, memo: &mut DictMemo) -> c_int {
+    unsafe {
+        let mut dict: *mut PyObject = ptr::null_mut();
+        if instance.get_optional_attr(py_str!("__dict__"), &mut dict) < 0 {
+            return -1;
+        }
+        if dict.is_null() {
+            return 0;
+        }
+        let ret = if dict.is_dict() {
+            substitute_dict_values(dict, memo)
+        } else {
+            0
+        };
+        dict.decref();
+        ret
+    }
+}
@@ -0,0 +1,597 @@
+//! Field-level deepcopy policies for dataclasses.
+//!
+//! `field(metadata={"copium": "share"})` keeps a field's value by reference in
+//! the copy, `"skip"` resets it to the field's default. Only types with at least
+//! one such marker take this path; everything else goes through reduce as usual.
+//!
+//! The same per-type lookup also records which fields a dataclass-generated
+//! `__hash__` reads, so dict copies can keep the hash of a key whose copy holds
+//! the very same objects in those fields.
+
+use pyo3_ffi::*;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::deepcopy;
+use crate::memo::Memo;
+use crate::py_str;
+use crate::types::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldMode {
+    Copy,
+    Share,
+    Skip,
+}
+
+struct FieldPolicy {
+    name: *mut PyObject,
+    mode: FieldMode,
+    /// For `Skip` only: the field default, or its default_factory when `factory`
+    /// is set. Null when the field has neither, in which case it is left unset.
+    default: *mut PyObject,
+    factory: bool,
+}
+
+impl Drop for FieldPolicy {
+    fn drop(&mut self) {
+        unsafe {
+            self.name.decref();
+            self.default.decref_nullable();
+        }
+    }
+}
+
+type Policy = Rc<[FieldPolicy]>;
+
+/// An owned field name.
+struct FieldName(*mut PyObject);
+
+impl Drop for FieldName {
+    fn drop(&mut self) {
+        unsafe { self.0.decref() }
+    }
+}
+
+/// The fields a dataclass-generated `__hash__` hashes, in order.
+type HashFields = Rc<[FieldName]>;
+
+#[derive(Clone, Default)]
+struct DataclassInfo {
+    policy: Option<Policy>,
+    hash_fields: Option<HashFields>,
+}
+
+// ── Per-type cache ─────────────────────────────────────────
+
+const POLICY_CACHE_SIZE: usize = 64;
+
+struct PolicyCacheEntry {
+    tp: CachedType,
+    version: u32,
+    info: DataclassInfo,
+}
+
+/// Direct-mapped and per-thread. Entries are keyed by the type's version tag,
+/// which CPython invalidates whenever the type is modified and never reuses, so
+/// a reassigned `__dataclass_fields__` or a recycled type address is a miss.
+#[thread_local]
+static mut POLICY_CACHE: [PolicyCacheEntry; POLICY_CACHE_SIZE] =
+    [EMPTY_POLICY_ENTRY; POLICY_CACHE_SIZE];
+
+const EMPTY_POLICY_ENTRY: PolicyCacheEntry = PolicyCacheEntry {
+    tp: CachedType::EMPTY,
+    version: 0,
+    info: DataclassInfo {
+        policy: None,
+        hash_fields: None,
+    },
+};
+
+/// Empties this thread's cache, releasing the types and policies it held.
+pub unsafe fn clear_cache() {
+    unsafe {
+        let evicted = std::mem::replace(
+            &mut *ptr::addr_of_mut!(POLICY_CACHE),
+            [EMPTY_POLICY_ENTRY; POLICY_CACHE_SIZE],
+        );
+        drop(evicted);
+    }
+}
+
+unsafe fn info_for(tp: *mut PyTypeObject) -> Result<DataclassInfo, ()> {
+    unsafe {
+        let slot = ((tp as usize) >> 4) % POLICY_CACHE_SIZE;
+        let cached = &*ptr::addr_of!(POLICY_CACHE[slot]);
+        if cached.tp.get() == tp && version_of(tp) == Some(cached.version) {
+            return Ok(cached.info.clone());
+        }
+
+        let info = build_info(tp)?;
+        if let Some(version) = version_of(tp) {
+            let entry = PolicyCacheEntry {
+                tp: CachedType::new(tp),
+                version,
+                info: info.clone(),
+            };
+            // Dropping the evicted info may run arbitrary code; do it last.
+            let evicted = std::mem::replace(&mut *ptr::addr_of_mut!(POLICY_CACHE[slot]), entry);
+            drop(evicted);
+        }
+        Ok(info)
+    }
+}
+
+/// The policy for `tp`, or None when no field carries a marker.
+unsafe fn policy_for(tp: *mut PyTypeObject) -> Result<Option<Policy>, ()> {
+    unsafe { info_for(tp).map(|info| info.policy) }
+}
+
+/// Whether instances of `tp` are copied field by field instead of reduced.
+pub(crate) unsafe fn has_fast_path(tp: *mut PyTypeObject) -> Result<bool, ()> {
+    unsafe { policy_for(tp).map(|policy| policy.is_some()) }
+}
+
+/// Whether `copy`, a deep copy of `original`, hashes the same by construction:
+/// their type's `__hash__` is the one dataclasses generates and each field it
+/// reads holds the same object in both. An exception is cleared and counts as no.
+pub(crate) unsafe fn hashes_like(original: *mut PyObject, copy: *mut PyObject) -> bool {
+    unsafe {
+        let tp = original.class();
+        if copy.class() != tp {
+            return false;
+        }
+        let fields = match info_for(tp) {
+            Ok(DataclassInfo {
+                hash_fields: Some(fields),
+                ..
+            }) => fields,
+            Ok(_) => return false,
+            Err(()) => {
+                PyErr_Clear();
+                return false;
+            }
+        };
+        for field in fields.iter() {
+            let before = original.getattr(field.0);
+            let after = if before.is_null() {
+                ptr::null_mut()
+            } else {
+                copy.getattr(field.0)
+            };
+            before.decref_nullable();
+            after.decref_nullable();
+            if after.is_null() {
+                PyErr_Clear();
+                return false;
+            }
+            if before != after {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Whether the `__hash__` `tp` resolves to is one dataclasses generated: it
+/// hashes a tuple of field values and nothing else. Such functions are built
+/// from source text, so their code claims to come from `<string>`.
+unsafe fn has_generated_hash(tp: *mut PyTypeObject) -> Result<bool, ()> {
+    unsafe {
+        let mut hash: *mut PyObject = ptr::null_mut();
+        if type_lookup_optional(tp, py_str!("__hash__"), &mut hash) < 0 {
+            return Err(());
+        }
+        if hash.is_null() {
+            return Ok(false);
+        }
+        if PyFunction_Check(hash) == 0 {
+            hash.decref();
+            return Ok(false);
+        }
+        let code = hash.getattr(py_str!("__code__"));
+        hash.decref();
+        if code.is_null() {
+            return Err(());
+        }
+        let filename = code.getattr(py_str!("co_filename"));
+        let name = code.getattr(py_str!("co_name"));
+        code.decref();
+        let generated = !filename.is_null()
+            && !name.is_null()
+            && filename.is_unicode()
+            && name.is_unicode()
+            && PyUnicode_CompareWithASCIIString(filename, crate::cstr!("<string>")) == 0
+            && PyUnicode_CompareWithASCIIString(name, crate::cstr!("__hash__")) == 0;
+        let failed = filename.is_null() || name.is_null();
+        filename.decref_nullable();
+        name.decref_nullable();
+        if failed {
+            return Err(());
+        }
+        Ok(generated)
+    }
+}
+
+/// `field.hash`, or `field.compare` when that is None, as dataclasses decides.
+unsafe fn field_is_hashed(field: *mut PyObject) -> Result<bool, ()> {
+    unsafe {
+        let mut flag = field.getattr(py_str!("hash"));
+        if flag.is_none() {
+            flag.decref();
+            flag = field.getattr(py_str!("compare"));
+        }
+        if flag.is_null() {
+            return Err(());
+        }
+        let truth = PyObject_IsTrue(flag);
+        flag.decref();
+        if truth < 0 {
+            return Err(());
+        }
+        Ok(truth != 0)
+    }
+}
+
+unsafe fn field_mode(field: *mut PyObject) -> Result<FieldMode, ()> {
+    unsafe {
+        let metadata = field.getattr(py_str!("metadata"));
+        if metadata.is_null() {
+            return Err(());
+        }
+        let marker = PyObject_GetItem(metadata, py_str!("copium"));
+        metadata.decref();
+        if marker.is_null() {
+            if PyErr_ExceptionMatches(PyExc_KeyError) == 0 {
+                return Err(());
+            }
+            PyErr_Clear();
+            return Ok(FieldMode::Copy);
+        }
+
+        let mode = if !marker.is_unicode() {
+            FieldMode::Copy
+        } else if PyUnicode_CompareWithASCIIString(marker, crate::cstr!("share")) == 0 {
+            FieldMode::Share
+        } else if PyUnicode_CompareWithASCIIString(marker, crate::cstr!("skip")) == 0 {
+            FieldMode::Skip
+        } else {
+            FieldMode::Copy
+        };
+        marker.decref();
+        Ok(mode)
+    }
+}
+
+/// Fills `policy.default` for a skipped field from `default`/`default_factory`.
+unsafe fn field_default(
+    field: *mut PyObject,
+    missing: *mut PyObject,
+    policy: &mut FieldPolicy,
+) -> Result<(), ()> {
+    unsafe {
+        let default = field.getattr(py_str!("default"));
+        if default.is_null() {
+            return Err(());
+        }
+        if default != missing {
+            policy.default = default;
+            return Ok(());
+        }
+        default.decref();
+
+        let factory = field.getattr(py_str!("default_factory"));
+        if factory.is_null() {
+            return Err(());
+        }
+        if factory != missing {
+            policy.default = factory;
+            policy.factory = true;
+            return Ok(());
+        }
+        factory.decref();
+        Ok(())
+    }
+}
+
+unsafe fn build_info(tp: *mut PyTypeObject) -> Result<DataclassInfo, ()> {
+    unsafe {
+        let mut fields: *mut PyObject = ptr::null_mut();
+        let has = type_lookup_optional(tp, py_str!("__dataclass_fields__"), &mut fields);
+        if has < 0 {
+            return Err(());
+        }
+        if has == 0 {
+            return Ok(DataclassInfo::default());
+        }
+        let values = if fields.is_dict() {
+            PyDict_Values(fields)
+        } else {
+            ptr::null_mut()
+        };
+        fields.decref();
+        if values.is_null() {
+            return if PyErr_Occurred().is_null() {
+                Ok(DataclassInfo::default())
+            } else {
+                Err(())
+            };
+        }
+
+        let hashed = match has_generated_hash(tp) {
+            Ok(hashed) => hashed,
+            Err(()) => {
+                values.decref();
+                return Err(());
+            }
+        };
+
+        // A type carrying __dataclass_fields__ means dataclasses is imported.
+        let module = PyImport_GetModule(py_str!("dataclasses"));
+        if module.is_null() {
+            values.decref();
+            return if PyErr_Occurred().is_null() {
+                Ok(DataclassInfo::default())
+            } else {
+                Err(())
+            };
+        }
+        let field_kind = module.getattr(py_str!("_FIELD"));
+        let missing = module.getattr(py_str!("MISSING"));
+        module.decref();
+
+        let result = if field_kind.is_null() || missing.is_null() {
+            Err(())
+        } else {
+            collect_info(values, field_kind, missing, hashed)
+        };
+        field_kind.decref_nullable();
+        missing.decref_nullable();
+        values.decref();
+        result
+    }
+}
+
+unsafe fn collect_info(
+    values: *mut PyObject,
+    field_kind: *mut PyObject,
+    missing: *mut PyObject,
+    hashed: bool,
+) -> Result<DataclassInfo, ()> {
+    unsafe {
+        let mut policy = Vec::new();
+        let mut marked = false;
+        let mut hash_fields = Vec::new();
+
+        for i in 0..PyList_GET_SIZE(values) {
+            let field = PyList_GET_ITEM(values, i);
+
+            // ClassVar and InitVar pseudo-fields live in __dataclass_fields__ too.
+            let kind = field.getattr(py_str!("_field_type"));
+            if kind.is_null() {
+                return Err(());
+            }
+            kind.decref();
+            if kind != field_kind {
+                continue;
+            }
+
+            let name = field.getattr(py_str!("name"));
+            if name.is_null() {
+                return Err(());
+            }
+            let mut entry = FieldPolicy {
+                name,
+                mode: FieldMode::Copy,
+                default: ptr::null_mut(),
+                factory: false,
+            };
+            entry.mode = field_mode(field)?;
+            if entry.mode == FieldMode::Skip {
+                field_default(field, missing, &mut entry)?;
+            }
+            marked |= entry.mode != FieldMode::Copy;
+            if hashed && field_is_hashed(field)? {
+                hash_fields.push(FieldName(entry.name.newref()));
+            }
+            policy.push(entry);
+        }
+
+        Ok(DataclassInfo {
+            policy: marked.then(|| Policy::from(policy)),
+            hash_fields: hashed.then(|| HashFields::from(hash_fields)),
+        })
+    }
+}
+
+// ── Reconstruction ─────────────────────────────────────────
+
+unsafe fn field_value<M: Memo>(
+    original: *mut PyObject,
+    field: &FieldPolicy,
+    memo: &mut M,
+) -> Result<*mut PyObject, ()> {
+    unsafe {
+        if field.mode == FieldMode::Skip {
+            if field.default.is_null() {
+                return Ok(ptr::null_mut());
+            }
+            if field.factory {
+                let value = field.default.call();
+                return if value.is_null() { Err(()) } else { Ok(value) };
+            }
+            return Ok(field.default.newref());
+        }
+
+        let mut value: *mut PyObject = ptr::null_mut();
+        let has = original.get_optional_attr(field.name, &mut value);
+        if has < 0 {
+            return Err(());
+        }
+        if has == 0 || field.mode == FieldMode::Share {
+            return Ok(value);
+        }
+
+        let copied = deepcopy::deepcopy(value, memo);
+        value.decref();
+        if copied.is_error() {
+            Err(())
+        } else {
+            Ok(copied.into_raw())
+        }
+    }
+}
+
+/// Instance attributes that are not dataclass fields are deep-copied as usual.
+unsafe fn copy_extra_attributes<M: Memo>(
+    original: *mut PyObject,
+    instance: *mut PyObject,
+    policy: &[FieldPolicy],
+    memo: &mut M,
+) -> Result<(), ()> {
+    unsafe {
+        let mut source: *mut PyObject = ptr::null_mut();
+        if original.get_optional_attr(py_str!("__dict__"), &mut source) < 0 {
+            return Err(());
+        }
+        if source.is_null() {
+            return Ok(());
+        }
+        let snapshot = if source.is_dict() {
+            PyDict_Items(source)
+        } else {
+            ptr::null_mut()
+        };
+        source.decref();
+        if snapshot.is_null() {
+            return if PyErr_Occurred().is_null() {
+                Ok(())
+            } else {
+                Err(())
+            };
+        }
+
+        let target = instance.getattr(py_str!("__dict__"));
+        if target.is_null() {
+            snapshot.decref();
+            return Err(());
+        }
+
+        let mut result = Ok(());
+        for i in 0..PyList_GET_SIZE(snapshot) {
+            let pair = PyList_GET_ITEM(snapshot, i);
+            let key = PyTuple_GET_ITEM(pair, 0);
+            let mut is_field = false;
+            for field in policy {
+                let equal = if key == field.name {
+                    1
+                } else {
+                    PyObject_RichCompareBool(key, field.name, Py_EQ)
+                };
+                if equal < 0 {
+                    result = Err(());
+                    break;
+                }
+                if equal > 0 {
+                    is_field = true;
+                    break;
+                }
+            }
+            if result.is_err() {
+                break;
+            }
+            if is_field {
+                continue;
+            }
+
+            let copied = deepcopy::deepcopy(PyTuple_GET_ITEM(pair, 1), memo);
+            if copied.is_error() {
+                result = Err(());
+                break;
+            }
+            let copied = copied.into_raw();
+            let status = PyObject_SetItem(target, key, copied);
+            copied.decref();
+            if status < 0 {
+                result = Err(());
+                break;
+            }
+        }
+
+        target.decref();
+        snapshot.decref();
+        result
+    }
+}
+
+unsafe fn populate<M: Memo>(
+    original: *mut PyObject,
+    instance: *mut PyObject,
+    policy: &[FieldPolicy],
+    memo: &mut M,
+) -> Result<(), ()> {
+    unsafe {
+        for field in policy {
+            let value = field_value(original, field, memo)?;
+            if value.is_null() {
+                continue;
+            }
+            // object.__setattr__: frozen dataclasses refuse the regular route.
+            let status = PyObject_GenericSetAttr(instance, field.name, value);
+            value.decref();
+            if status < 0 {
+                return Err(());
+            }
+        }
+        copy_extra_attributes(original, instance, policy, memo)
+    }
+}
+
+/// Field-wise deepcopy for dataclasses with share/skip markers. Returns None when
+/// `tp` has no markers, otherwise the copy or null with an exception set.
+pub unsafe fn reconstruct<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> Option<*mut PyObject> {
+    unsafe {
+        let policy = match policy_for(tp) {
+            Ok(Some(policy)) => policy,
+            Ok(None) => return None,
+            Err(()) => return Some(ptr::null_mut()),
+        };
+
+        if crate::recursion::enter() < 0 {
+            return Some(ptr::null_mut());
+        }
+
+        let no_args = PyTuple_New(0);
+        let instance = if no_args.is_null() {
+            ptr::null_mut()
+        } else {
+            let instance = crate::reduce::call_tp_new(tp, no_args, ptr::null_mut());
+            no_args.decref();
+            instance
+        };
+        if instance.is_null() {
+            crate::recursion::leave();
+            return Some(ptr::null_mut());
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            crate::recursion::leave();
+            return Some(ptr::null_mut());
+        }
+
+        let populated = populate(original, instance, &policy, memo);
+        crate::recursion::leave();
+        if populated.is_err() {
+            memo.forget(original, probe);
+            instance.decref();
+            return Some(ptr::null_mut());
+        }
+        Some(instance)
+    }
+}
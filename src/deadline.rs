@@ -0,0 +1,174 @@
+//! `deepcopy(..., timeout=)`: a time budget for one call.
+//!
+//! While a budget is set, the traversal reads the monotonic clock every few
+//! dozen containers, at the same place offloaded copies check for
+//! cancellation. Once the deadline has passed, that check and every one after
+//! it raises `copium.TimeoutError`, so a `__deepcopy__` swallowing the first
+//! one doesn't keep the copy going. Calls without a timeout never get there.
+
+use pyo3_ffi::*;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::py_obj;
+use crate::types::PyObjectPtr;
+
+const CHECKPOINT_STRIDE: u32 = 32;
+
+/// When the running call's budget runs out, and the budget itself for the
+/// message. None while no call on this thread has a timeout.
+#[thread_local]
+static mut DEADLINE: Option<(Instant, f64)> = None;
+
+#[thread_local]
+static mut EXPIRED: bool = false;
+
+#[thread_local]
+static mut SINCE_CHECKPOINT: u32 = 0;
+
+#[inline(always)]
+pub fn is_active() -> bool {
+    unsafe { (*ptr::addr_of!(DEADLINE)).is_some() }
+}
+
+/// Borrowed `copium.TimeoutError`.
+#[inline(always)]
+pub fn timeout_error() -> *mut PyObject {
+    TIMEOUT_ERROR.load(Ordering::Acquire)
+}
+
+/// Whether the pending exception is a `copium.TimeoutError`.
+pub unsafe fn timed_out() -> bool {
+    unsafe { PyErr_ExceptionMatches(timeout_error()) != 0 }
+}
+
+/// Called on every traversal step while a budget is set.
+#[cold]
+pub unsafe fn checkpoint() -> i32 {
+    unsafe {
+        let Some((deadline, seconds)) = DEADLINE else {
+            return 0;
+        };
+        if !EXPIRED {
+            SINCE_CHECKPOINT += 1;
+            if SINCE_CHECKPOINT < CHECKPOINT_STRIDE {
+                return 0;
+            }
+            SINCE_CHECKPOINT = 0;
+            if Instant::now() < deadline {
+                return 0;
+            }
+            EXPIRED = true;
+        }
+        raise_timeout(seconds);
+        -1
+    }
+}
+
+/// Parses `timeout=`: None for no budget, otherwise non-negative seconds.
+/// Returns Err with an exception set for anything else.
+#[cold]
+pub unsafe fn parse_timeout(value: *mut PyObject) -> Result<Option<f64>, ()> {
+    unsafe {
+        if value.is_none() {
+            return Ok(None);
+        }
+        let seconds = PyFloat_AsDouble(value);
+        if seconds == -1.0 && !PyErr_Occurred().is_null() {
+            return Err(());
+        }
+        if seconds.is_nan() || seconds < 0.0 {
+            PyErr_SetString(
+                PyExc_ValueError,
+                crate::cstr!("timeout must be a non-negative number or None"),
+            );
+            return Err(());
+        }
+        Ok(Some(seconds))
+    }
+}
+
+/// Runs `copy` with `seconds` as the budget. A budget set by an enclosing call
+/// still applies when it ends sooner; the enclosing one is restored afterwards.
+#[inline(never)]
+pub unsafe fn with_timeout(seconds: f64, copy: impl FnOnce() -> *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let previous = DEADLINE;
+        let previous_expired = EXPIRED;
+        let previous_count = SINCE_CHECKPOINT;
+
+        // A budget too large to represent is no budget.
+        let own = Duration::try_from_secs_f64(seconds)
+            .ok()
+            .and_then(|budget| Instant::now().checked_add(budget))
+            .map(|deadline| (deadline, seconds));
+        DEADLINE = match (previous, own) {
+            (Some(outer), Some(inner)) if outer.0 <= inner.0 => Some(outer),
+            (outer, None) => outer,
+            (_, inner) => inner,
+        };
+        SINCE_CHECKPOINT = 0;
+
+        let result = copy();
+
+        DEADLINE = previous;
+        EXPIRED = previous_expired;
+        SINCE_CHECKPOINT = previous_count;
+        result
+    }
+}
+
+// ── TimeoutError ───────────────────────────────────────────
+
+/// `copium.TimeoutError`. Created on each runtime init, never freed.
+static TIMEOUT_ERROR: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+const TIMEOUT_ERROR_DOC: &str = "\
+Raised when deepcopy() runs past its timeout.
+
+Subclasses copy.Error and the builtin TimeoutError.\0";
+
+pub(crate) unsafe fn init_timeout_error() -> c_int {
+    unsafe {
+        let bases = PyTuple_Pack(2, py_obj!("copy.Error"), PyExc_TimeoutError);
+        if bases.is_null() {
+            return -1;
+        }
+        let timeout_error = PyErr_NewExceptionWithDoc(
+            crate::cstr!("copium.TimeoutError"),
+            TIMEOUT_ERROR_DOC.as_ptr().cast(),
+            bases,
+            ptr::null_mut(),
+        );
+        bases.decref();
+        if timeout_error.is_null() {
+            return -1;
+        }
+
+        let previous = TIMEOUT_ERROR.swap(timeout_error, Ordering::AcqRel);
+        previous.decref_nullable();
+        0
+    }
+}
+
+pub(crate) unsafe fn add_timeout_error(module: *mut PyObject) -> c_int {
+    unsafe {
+        let timeout_error = TIMEOUT_ERROR.load(Ordering::Acquire).newref();
+        if PyModule_AddObject(module, crate::cstr!("TimeoutError"), timeout_error) < 0 {
+            timeout_error.decref();
+            return -1;
+        }
+        0
+    }
+}
+
+unsafe fn raise_timeout(seconds: f64) {
+    unsafe {
+        let message = CString::new(format!("deepcopy() exceeded its timeout of {seconds}s"))
+            .unwrap_or_default();
+        PyErr_SetString(TIMEOUT_ERROR.load(Ordering::Acquire), message.as_ptr());
+    }
+}
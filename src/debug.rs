@@ -0,0 +1,207 @@
+use pyo3_ffi::*;
+use std::ptr;
+
+use crate::memo::PyMemoObject;
+use crate::types::PyObjectPtr;
+
+/// Objects this thread's memo holds on to: its keepalive, then its table's
+/// copies, then the proxies and dicts handed to a `__deepcopy__`. Borrowed.
+unsafe fn retained(memo: *mut PyMemoObject) -> Vec<*mut PyObject> {
+    unsafe {
+        if memo.is_null() {
+            return Vec::new();
+        }
+        let memo = &*memo;
+        let mut objects = memo.keepalive.items.clone();
+        objects.extend(memo.table.entries().map(|entry| entry.value));
+        if !memo.dict_proxy.is_null() {
+            objects.push(memo.dict_proxy);
+        }
+        if !memo.dict_view.is_null() {
+            objects.push(memo.dict_view);
+        }
+        objects
+    }
+}
+
+/// `(type_name, id, refcount)`; the refcount is read before the tuple takes
+/// a reference of its own.
+unsafe fn describe(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let refcount = Py_REFCNT(object);
+        let name = PyUnicode_FromString((*object.class()).tp_name);
+        if name.is_null() {
+            return ptr::null_mut();
+        }
+        let id = PyLong_FromVoidPtr(object.cast());
+        if id.is_null() {
+            name.decref();
+            return ptr::null_mut();
+        }
+        let count = PyLong_FromSsize_t(refcount);
+        if count.is_null() {
+            name.decref();
+            id.decref();
+            return ptr::null_mut();
+        }
+        let entry = PyTuple_New(3);
+        if entry.is_null() {
+            name.decref();
+            id.decref();
+            count.decref();
+            return ptr::null_mut();
+        }
+        PyTuple_SET_ITEM(entry, 0, name);
+        PyTuple_SET_ITEM(entry, 1, id);
+        PyTuple_SET_ITEM(entry, 2, count);
+        entry
+    }
+}
+
+unsafe fn contents() -> *mut PyObject {
+    unsafe {
+        let objects = retained(crate::memo::thread_memo());
+        let list = PyList_New(objects.len() as Py_ssize_t);
+        if list.is_null() {
+            return ptr::null_mut();
+        }
+        for (i, &object) in objects.iter().enumerate() {
+            let entry = describe(object);
+            if entry.is_null() {
+                list.decref();
+                return ptr::null_mut();
+            }
+            PyList_SET_ITEM(list, i as Py_ssize_t, entry);
+        }
+        list
+    }
+}
+
+unsafe extern "C" fn py_keepalive_contents(
+    _self: *mut PyObject,
+    _: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe { contents() })
+}
+
+unsafe extern "C" fn py_assert_idle(_self: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let memo = crate::memo::thread_memo();
+        if retained(memo).is_empty() {
+            return Py_None().newref();
+        }
+        let found = contents();
+        if found.is_null() {
+            return ptr::null_mut();
+        }
+        let attached = if (*memo).attached {
+            crate::cstr!(" (a deepcopy() is running)")
+        } else {
+            crate::cstr!("")
+        };
+        PyErr_Format(
+            PyExc_AssertionError,
+            crate::cstr!("copium retains %zd objects on this thread%s: %R"),
+            PyList_GET_SIZE(found),
+            attached,
+            found,
+        );
+        found.decref();
+        ptr::null_mut()
+    })
+}
+
+unsafe extern "C" fn py_simulate_leak(
+    _self: *mut PyObject,
+    object: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let (memo, is_tss) = crate::memo::get_memo();
+        if memo.is_null() {
+            return ptr::null_mut();
+        }
+        if !is_tss {
+            crate::memo::cleanup_memo(memo, false);
+            PyErr_SetString(
+                PyExc_RuntimeError,
+                crate::cstr!("simulate_leak() can't run while this thread's memo is in use"),
+            );
+            return ptr::null_mut();
+        }
+        // Detach without the reset a finished deepcopy() would do.
+        let appended = (*memo).keepalive.append(object);
+        (*memo).attached = false;
+        if appended < 0 {
+            return ptr::null_mut();
+        }
+        Py_None().newref()
+    })
+}
+
+static mut DEBUG_METHODS: [PyMethodDef; 4] = [PyMethodDef::zeroed(); 4];
+
+static mut DEBUG_MODULE_DEF: PyModuleDef = PyModuleDef {
+    m_base: PyModuleDef_HEAD_INIT,
+    m_name: b"copium._debug\0".as_ptr().cast(),
+    m_doc: b"Introspection of copium's per-thread state, for hunting leaks.\0"
+        .as_ptr()
+        .cast(),
+    m_size: -1,
+    m_methods: ptr::null_mut(),
+    m_slots: ptr::null_mut(),
+    m_traverse: None,
+    m_clear: None,
+    m_free: None,
+};
+
+pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
+    unsafe {
+        DEBUG_METHODS[0] = PyMethodDef {
+            ml_name: crate::cstr!("keepalive_contents"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_keepalive_contents,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: crate::cstr!(
+                "keepalive_contents()\n--\n\n\
+                 (type_name, id, refcount) for each object this thread's memo retains.\n\n\
+                 Lists the keepalive, then the copies in the memo table. Empty between\n\
+                 deepcopy() calls unless something leaked; objects aren't copied or kept."
+            ),
+        };
+        DEBUG_METHODS[1] = PyMethodDef {
+            ml_name: crate::cstr!("assert_idle"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_assert_idle,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: crate::cstr!(
+                "assert_idle()\n--\n\n\
+                 Raise AssertionError if this thread's memo retains any object.\n\n\
+                 Meant for an autouse fixture run after each test."
+            ),
+        };
+        DEBUG_METHODS[2] = PyMethodDef {
+            ml_name: crate::cstr!("simulate_leak"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_simulate_leak,
+            },
+            ml_flags: METH_O,
+            ml_doc: crate::cstr!(
+                "simulate_leak(obj, /)\n--\n\n\
+                 Leave obj in this thread's keepalive as a memo that wasn't reset would.\n\n\
+                 For testing leak checks; the next deepcopy() on this thread releases it."
+            ),
+        };
+        DEBUG_METHODS[3] = PyMethodDef::zeroed();
+
+        DEBUG_MODULE_DEF.m_methods = ptr::addr_of_mut!(DEBUG_METHODS).cast::<PyMethodDef>();
+
+        let module = PyModule_Create(std::ptr::addr_of_mut!(DEBUG_MODULE_DEF));
+        if module.is_null() {
+            return -1;
+        }
+
+        crate::add_submodule(parent, crate::cstr!("_debug"), module)
+    }
+}
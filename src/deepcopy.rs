@@ -1,3 +1,4 @@
+use libc::c_ulong;
 use pyo3_ffi::*;
 use std::hint::{likely, unlikely};
 use std::ptr;
@@ -98,13 +99,28 @@ unsafe fn is_postmemo_atomic<M: Memo>(cls: *mut PyTypeObject) -> bool {
     }
 }
 
+/// An atomic object's copy: itself, unless the memo canonicalizes leaves.
+#[inline(always)]
+unsafe fn share_atomic<M: Memo>(object: *mut PyObject, memo: &mut M) -> PyResult {
+    unsafe {
+        if M::CANONICALIZES {
+            return PyResult::ok(memo.canonicalize(object));
+        }
+        PyResult::ok(object.newref())
+    }
+}
+
 #[inline(always)]
 pub unsafe fn deepcopy<M: Memo>(object: *mut PyObject, memo: &mut M) -> PyResult {
     unsafe {
         let cls = object.class();
 
         if likely(is_prememo_atomic::<M>(cls)) {
-            return PyResult::ok(object.newref());
+            return share_atomic(object, memo);
+        }
+        #[cfg(not(Py_3_14))]
+        if M::RECALL_CAN_ERROR && !memo.may_recall_atomics() && cls.is_atomic_immutable() {
+            return share_atomic(object, memo);
         }
 
         let (probe, found) = memo.recall(object);
@@ -115,21 +131,27 @@ pub unsafe fn deepcopy<M: Memo>(object: *mut PyObject, memo: &mut M) -> PyResult
             return PyResult::error();
         }
 
-        if let Some(object) = PyTupleObject::cast_exact(object, cls) {
-            return protect_stack!(object.deepcopy(memo, probe));
-        }
-        if let Some(object) = PyDictObject::cast_exact(object, cls) {
-            return protect_stack!(object.deepcopy(memo, probe));
-        }
-        if let Some(object) = PyListObject::cast_exact(object, cls) {
-            return protect_stack!(object.deepcopy(memo, probe));
-        }
-        if let Some(object) = PySetObject::cast_exact(object, cls) {
+        // A type carries at most one of these flags; the exact type confirms it,
+        // subclasses continue below.
+        let flags = tp_flags_of(cls);
+        if flags & (Py_TPFLAGS_TUPLE_SUBCLASS as c_ulong) != 0 {
+            if let Some(object) = PyTupleObject::cast_exact(object, cls) {
+                return protect_stack!(object.deepcopy(memo, probe));
+            }
+        } else if flags & (Py_TPFLAGS_DICT_SUBCLASS as c_ulong) != 0 {
+            if let Some(object) = PyDictObject::cast_exact(object, cls) {
+                return protect_stack!(object.deepcopy(memo, probe));
+            }
+        } else if flags & (Py_TPFLAGS_LIST_SUBCLASS as c_ulong) != 0 {
+            if let Some(object) = PyListObject::cast_exact(object, cls) {
+                return protect_stack!(object.deepcopy(memo, probe));
+            }
+        } else if let Some(object) = PySetObject::cast_exact(object, cls) {
             return protect_stack!(object.deepcopy(memo, probe));
         }
 
         if unlikely(is_postmemo_atomic::<M>(cls)) {
-            return PyResult::ok(object.newref());
+            return share_atomic(object, memo);
         }
 
         if let Some(object) = PyFrozensetObject::cast_exact(object, cls) {
@@ -142,10 +164,173 @@ pub unsafe fn deepcopy<M: Memo>(object: *mut PyObject, memo: &mut M) -> PyResult
             return object.deepcopy(memo, probe);
         }
 
+        // Reconstruction can recurse without passing through Python (e.g. a
+        // Counter holding itself), so it counts toward the stack check too.
+        protect_stack!(object.deepcopy(memo, probe))
+    }
+}
+
+/// How many leading items (or values) decide whether a list (or dict) takes
+/// a fused path for its inner containers.
+const SHAPE_SCAN: Py_ssize_t = 8;
+
+/// `deepcopy` for an object already known to be of the exact type `T`
+/// handles, skipping the atomic checks and the type dispatch. The caller
+/// accounts for the stack.
+#[inline(always)]
+unsafe fn deepcopy_known<M: Memo, T>(object: *mut T, memo: &mut M) -> PyResult
+where
+    *mut T: PyDeepCopy,
+{
+    unsafe {
+        let (probe, found) = memo.recall(object as _);
+        if !found.is_null() {
+            return PyResult::ok(found);
+        }
+        if M::RECALL_CAN_ERROR && unlikely(!PyErr_Occurred().is_null()) {
+            return PyResult::error();
+        }
         object.deepcopy(memo, probe)
     }
 }
 
+/// Stores `item_copy` at `i` of `copied`, unless something reached the copy
+/// through the memo and resized it. Steals `item_copy` either way.
+#[inline(always)]
+unsafe fn store_list_item(
+    copied: *mut PyListObject,
+    size: Py_ssize_t,
+    i: Py_ssize_t,
+    item_copy: *mut PyObject,
+) -> bool {
+    unsafe {
+        let mut size_changed = false;
+        with_critical_section_raw(copied as _, || {
+            if unlikely(copied.length() != size) {
+                size_changed = true;
+            } else {
+                #[cfg(not(any(Py_3_12, Py_3_13, Py_3_14)))]
+                let old_item = copied.get_borrowed_unchecked(i);
+                copied.set_slot_steal_unchecked(i, item_copy);
+                #[cfg(not(any(Py_3_12, Py_3_13, Py_3_14)))]
+                old_item.decref();
+            }
+        });
+        if unlikely(size_changed) {
+            item_copy.decref();
+            PyErr_SetString(
+                PyExc_RuntimeError,
+                crate::cstr!("list changed size during iteration"),
+            );
+            return false;
+        }
+        true
+    }
+}
+
+#[cold]
+unsafe fn list_changed_size() -> Py_ssize_t {
+    unsafe {
+        PyErr_SetString(
+            PyExc_RuntimeError,
+            crate::cstr!("list changed size during iteration"),
+        );
+    }
+    -1
+}
+
+/// Copies the items of `list` from `start` on into `copied`. Returns -1 on
+/// error, or the index it stopped at: the end, or with `ROWS`, the first item
+/// that isn't an exact dict, from where the generic loop takes over.
+#[inline(always)]
+unsafe fn deepcopy_list_items<M: Memo, const ROWS: bool>(
+    list: *mut PyListObject,
+    copied: *mut PyListObject,
+    size: Py_ssize_t,
+    start: Py_ssize_t,
+    memo: &mut M,
+) -> Py_ssize_t {
+    unsafe {
+        // Lists of containers: pull the next item's memo slot into cache while
+        // this one is copied. Judged by the first item so that lists of
+        // literals keep a branch-free loop.
+        let prefetching = size > start + 1
+            && !list
+                .get_borrowed_unchecked(start)
+                .class()
+                .is_literal_immutable();
+        for i in start..size {
+            if prefetching && i + 1 < list.length() {
+                memo.prefetch(list.get_borrowed_unchecked(i + 1));
+            }
+
+            // Literals run no code, so nothing can have resized either list
+            // since the last item was stored.
+            #[cfg(not(Py_GIL_DISABLED))]
+            if !ROWS && !M::CANONICALIZES && likely(i < list.length()) {
+                let item = list.get_borrowed_unchecked(i);
+                if is_prememo_atomic::<M>(item.class()) {
+                    #[cfg(not(any(Py_3_12, Py_3_13, Py_3_14)))]
+                    copied.get_borrowed_unchecked(i).decref();
+                    copied.set_slot_steal_unchecked(i, item.newref());
+                    continue;
+                }
+            }
+
+            let item = list.get_owned_check_bounds(i);
+            if unlikely(item.is_null()) {
+                return list_changed_size();
+            }
+
+            let item_copy = if ROWS {
+                let Some(row) = PyDictObject::cast_exact(item, item.class()) else {
+                    item.decref();
+                    return i;
+                };
+                deepcopy_known(row, memo)
+            } else {
+                deepcopy(item, memo)
+            };
+            item.decref();
+
+            if unlikely(item_copy.is_error()) {
+                return -1;
+            }
+            if unlikely(!store_list_item(copied, size, i, item_copy.into_raw())) {
+                return -1;
+            }
+        }
+        size
+    }
+}
+
+/// Rows: a list of dicts. The dicts are copied one level down without going
+/// through the dispatch (and its stack check) for each of them.
+#[inline(never)]
+unsafe fn deepcopy_list_of_dicts<M: Memo>(
+    list: *mut PyListObject,
+    copied: *mut PyListObject,
+    size: Py_ssize_t,
+    memo: &mut M,
+) -> Py_ssize_t {
+    unsafe {
+        if unlikely(crate::recursion::enter() < 0) {
+            return -1;
+        }
+        memo.reserve(size as usize);
+        let stopped = deepcopy_list_items::<M, true>(list, copied, size, 0, memo);
+        crate::recursion::leave();
+        stopped
+    }
+}
+
+#[inline(always)]
+unsafe fn leads_with(list: *mut PyListObject, size: Py_ssize_t, tp: *mut PyTypeObject) -> bool {
+    unsafe {
+        size > 1 && (0..size.min(SHAPE_SCAN)).all(|i| list.get_borrowed_unchecked(i).class() == tp)
+    }
+}
+
 impl PyDeepCopy for *mut PyListObject {
     unsafe fn deepcopy<M: Memo>(self, memo: &mut M, probe: M::Probe) -> PyResult {
         unsafe {
@@ -164,50 +349,17 @@ impl PyDeepCopy for *mut PyListObject {
                 return PyResult::error();
             }
 
-            for i in 0..sz {
-                let item = self.get_owned_check_bounds(i);
-                if unlikely(item.is_null()) {
-                    PyErr_SetString(
-                        PyExc_RuntimeError,
-                        crate::cstr!("list changed size during iteration"),
-                    );
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
-                }
-
-                let item_copy = deepcopy(item, memo);
-                item.decref();
-
-                if unlikely(item_copy.is_error()) {
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
-                }
-
-                let raw = item_copy.into_raw();
-                let mut size_changed = false;
-                with_critical_section_raw(copied as _, || {
-                    if unlikely(copied.length() != sz) {
-                        size_changed = true;
-                    } else {
-                        #[cfg(not(any(Py_3_12, Py_3_13, Py_3_14)))]
-                        let old_item = copied.get_borrowed_unchecked(i);
-                        copied.set_slot_steal_unchecked(i, raw);
-                        #[cfg(not(any(Py_3_12, Py_3_13, Py_3_14)))]
-                        old_item.decref();
-                    }
-                });
-                if unlikely(size_changed) {
-                    raw.decref();
-                    PyErr_SetString(
-                        PyExc_RuntimeError,
-                        crate::cstr!("list changed size during iteration"),
-                    );
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
-                }
+            let mut stopped = 0;
+            if leads_with(self, sz, ptr::addr_of_mut!(PyDict_Type)) {
+                stopped = deepcopy_list_of_dicts(self, copied, sz, memo);
+            }
+            if stopped >= 0 && stopped < sz {
+                stopped = deepcopy_list_items::<M, false>(self, copied, sz, stopped, memo);
+            }
+            if unlikely(stopped < 0) {
+                memo.forget(self as _, &probe);
+                copied.decref();
+                return PyResult::error();
             }
 
             PyResult::ok(copied as _)
@@ -222,7 +374,15 @@ impl PyDeepCopy for *mut PyTupleObject {
             let copied = check!(py_tuple_new(sz));
 
             let mut all_same = true;
+            let prefetching = sz > 1
+                && !self
+                    .get_borrowed_unchecked(0)
+                    .class()
+                    .is_literal_immutable();
             for i in 0..sz {
+                if prefetching && i + 1 < sz {
+                    memo.prefetch(self.get_borrowed_unchecked(i + 1));
+                }
                 let item = self.get_borrowed_unchecked(i);
                 let item_copy = deepcopy(item, memo);
                 if unlikely(item_copy.is_error()) {
@@ -238,7 +398,7 @@ impl PyDeepCopy for *mut PyTupleObject {
 
             if all_same {
                 copied.decref();
-                return PyResult::ok(self.newref());
+                return share_atomic(self as _, memo);
             }
 
             let existing = memo.recall_probed(self as _, &probe);
@@ -247,6 +407,21 @@ impl PyDeepCopy for *mut PyTupleObject {
                 return PyResult::ok(existing);
             }
 
+            let copied = if M::CANONICALIZES {
+                let canonical = memo.canonicalize(copied as _);
+                copied.decref();
+                check!(canonical) as *mut PyTupleObject
+            } else {
+                copied
+            };
+
+            // A tuple of untracked items cannot be part of a cycle. CPython
+            // drops such tuples from the GC lists only when a collection
+            // reaches them; the copy is final here, so drop it right away.
+            if (0..sz).all(|i| !copied.get_borrowed_unchecked(i).may_be_gc_tracked()) {
+                PyObject_GC_UnTrack(copied.cast());
+            }
+
             if memo.memoize(self as _, copied as _, &probe) < 0 {
                 copied.decref();
                 return PyResult::error();
@@ -257,60 +432,290 @@ impl PyDeepCopy for *mut PyTupleObject {
     }
 }
 
-impl PyDeepCopy for *mut PyDictObject {
-    unsafe fn deepcopy<M: Memo>(self, memo: &mut M, probe: M::Probe) -> PyResult {
-        unsafe {
-            let copied = check!(py_dict_new(self.len()));
+/// Head of CPython's `struct _dictkeysobject`; `dk_indices` follows it.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[repr(C)]
+struct DictKeysHead {
+    dk_refcnt: Py_ssize_t,
+    dk_log2_size: u8,
+    dk_log2_index_bytes: u8,
+    dk_kind: u8,
+    dk_version: u32,
+    dk_usable: Py_ssize_t,
+    dk_nentries: Py_ssize_t,
+}
 
-            if memo.memoize(self as _, copied as _, &probe) < 0 {
-                copied.decref();
-                return PyResult::error();
-            }
+/// `PyDictKeyEntry`, the entry layout of `DICT_KEYS_GENERAL` tables.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[repr(C)]
+struct DictKeyEntry {
+    me_hash: Py_hash_t,
+    me_key: *mut PyObject,
+    me_value: *mut PyObject,
+}
 
-            let mut guard = DictIterGuard::new(self as _);
-            guard.activate();
-            let mut key: *mut PyObject = ptr::null_mut();
-            let mut value: *mut PyObject = ptr::null_mut();
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+const DICT_KEYS_GENERAL: u8 = 0;
 
-            loop {
-                let flag = guard.next(&mut key, &mut value);
-                if flag == 0 {
-                    break;
-                }
-                if flag < 0 {
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
-                }
+/// `dk_kind` of the dict's key table is `DICT_KEYS_UNICODE` or
+/// `DICT_KEYS_SPLIT`, which CPython keeps only while every key is an exact `str`.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[inline(always)]
+unsafe fn has_only_str_keys(dict: *mut PyDictObject) -> bool {
+    unsafe { (*((*dict).ma_keys as *const DictKeysHead)).dk_kind != DICT_KEYS_GENERAL }
+}
+
+/// The hash `dict` stores for `key`, which `PyDict_Next` just returned from
+/// position `pos`. None unless the table is `DICT_KEYS_GENERAL`.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[inline(always)]
+unsafe fn stored_hash(
+    dict: *mut PyDictObject,
+    pos: Py_ssize_t,
+    key: *mut PyObject,
+) -> Option<Py_hash_t> {
+    unsafe {
+        let keys = (*dict).ma_keys as *const DictKeysHead;
+        if (*keys).dk_kind != DICT_KEYS_GENERAL || pos < 1 || pos > (*keys).dk_nentries {
+            return None;
+        }
+        let indices = keys.add(1) as *const u8;
+        let entries = indices.add(1 << (*keys).dk_log2_index_bytes) as *const DictKeyEntry;
+        let entry = &*entries.add(pos as usize - 1);
+        (entry.me_key == key).then_some(entry.me_hash)
+    }
+}
+
+/// Keys that copy to themselves stay where they are: cloning the table keeps
+/// them and their hashes, and only values whose copy is a new object are
+/// written back. Any mutation of `dict` meanwhile fails the iteration guard,
+/// so the cloned values still match the ones being visited.
+///
+/// With `COLUMNS`, list values are copied one level down without going
+/// through the dispatch (and its stack check) for each of them.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+unsafe fn deepcopy_str_keyed_dict<M: Memo, const COLUMNS: bool>(
+    dict: *mut PyDictObject,
+    memo: &mut M,
+    probe: M::Probe,
+) -> PyResult {
+    unsafe {
+        let copied = check!(PyDict_Copy(dict as _)) as *mut PyDictObject;
+
+        if memo.memoize(dict as _, copied as _, &probe) < 0 {
+            copied.decref();
+            return PyResult::error();
+        }
+
+        if COLUMNS && unlikely(crate::recursion::enter() < 0) {
+            memo.forget(dict as _, &probe);
+            copied.decref();
+            return PyResult::error();
+        }
+        let result = copy_str_keyed_values::<M, COLUMNS>(dict, copied, memo);
+        if COLUMNS {
+            crate::recursion::leave();
+        }
+        if unlikely(result < 0) {
+            memo.forget(dict as _, &probe);
+            copied.decref();
+            return PyResult::error();
+        }
+        PyResult::ok(copied as _)
+    }
+}
+
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[inline(always)]
+unsafe fn copy_str_keyed_values<M: Memo, const COLUMNS: bool>(
+    dict: *mut PyDictObject,
+    copied: *mut PyDictObject,
+    memo: &mut M,
+) -> i32 {
+    unsafe {
+        let mut guard = DictIterGuard::new(dict as _);
+        guard.activate();
+        let mut key: *mut PyObject = ptr::null_mut();
+        let mut value: *mut PyObject = ptr::null_mut();
+        let mut may_track = false;
+
+        loop {
+            let flag = guard.next(&mut key, &mut value);
+            if flag == 0 {
+                break;
+            }
+            if flag < 0 {
+                return -1;
+            }
 
-                let key_copy = deepcopy(key, memo);
+            let column = if COLUMNS {
+                PyListObject::cast_exact(value, value.class())
+            } else {
+                None
+            };
+            let val_copy = match column {
+                Some(column) => deepcopy_known(column, memo),
+                None => deepcopy(value, memo),
+            };
+            if unlikely(val_copy.is_error()) {
                 key.decref();
-                if unlikely(key_copy.is_error()) {
-                    value.decref();
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
-                }
+                value.decref();
+                return -1;
+            }
+
+            let val_copy = val_copy.into_raw();
+            may_track |= val_copy.may_be_gc_tracked();
+            let unchanged = val_copy == value;
+            value.decref();
+            if unchanged {
+                val_copy.decref();
+                key.decref();
+                continue;
+            }
+            if unlikely(copied.set_item_steal_two(key, val_copy) < 0) {
+                return -1;
+            }
+        }
+
+        // The clone inherits tracking from the original, while a dict built
+        // item by item is tracked only once it holds something trackable.
+        if !may_track && PyObject_GC_IsTracked(copied as _) != 0 {
+            PyObject_GC_UnTrack(copied.cast());
+        }
+
+        0
+    }
+}
+
+/// Columns: the dict's first values are all exact `tp`.
+#[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+#[inline(always)]
+unsafe fn leading_values_are(dict: *mut PyDictObject, tp: *mut PyTypeObject) -> bool {
+    unsafe {
+        if dict.len() < 2 {
+            return false;
+        }
+        let mut pos: Py_ssize_t = 0;
+        let mut key: *mut PyObject = ptr::null_mut();
+        let mut value: *mut PyObject = ptr::null_mut();
+        for _ in 0..SHAPE_SCAN {
+            if PyDict_Next(dict as _, &mut pos, &mut key, &mut value) == 0 {
+                break;
+            }
+            if value.class() != tp {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+unsafe fn deepcopy_dict_items<M: Memo>(
+    dict: *mut PyDictObject,
+    memo: &mut M,
+    probe: M::Probe,
+) -> PyResult {
+    unsafe {
+        let copied = check!(py_dict_new(dict.len()));
+
+        if memo.memoize(dict as _, copied as _, &probe) < 0 {
+            copied.decref();
+            return PyResult::error();
+        }
 
-                let val_copy = deepcopy(value, memo);
+        let mut guard = DictIterGuard::new(dict as _);
+        guard.activate();
+        let mut key: *mut PyObject = ptr::null_mut();
+        let mut value: *mut PyObject = ptr::null_mut();
+
+        loop {
+            let flag = guard.next(&mut key, &mut value);
+            if flag == 0 {
+                break;
+            }
+            if flag < 0 {
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return PyResult::error();
+            }
+
+            let key_copy = deepcopy(key, memo);
+            if unlikely(key_copy.is_error()) {
+                key.decref();
                 value.decref();
-                if unlikely(val_copy.is_error()) {
-                    key_copy.into_raw().decref();
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return PyResult::error();
+            }
+            let key_copy = key_copy.into_raw();
+
+            // A copied dataclass key whose hashed fields are the original's
+            // objects hashes to what the original did; skip the Python-level
+            // __hash__ call on insert.
+            #[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+            let hash = if key_copy != key
+                && (*key.class()).tp_flags & Py_TPFLAGS_HEAPTYPE != 0
+                && crate::dataclasses::hashes_like(key, key_copy)
+            {
+                stored_hash(dict, guard.position(), key)
+            } else {
+                None
+            };
+            key.decref();
+
+            let val_copy = deepcopy(value, memo);
+            value.decref();
+            if unlikely(val_copy.is_error()) {
+                key_copy.decref();
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return PyResult::error();
+            }
+            let val_copy = val_copy.into_raw();
+
+            #[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+            let rc = match hash {
+                Some(hash) => {
+                    let rc = crate::compat::_PyDict_SetItem_KnownHash(
+                        copied as _,
+                        key_copy,
+                        val_copy,
+                        hash,
+                    );
+                    key_copy.decref();
+                    val_copy.decref();
+                    rc
                 }
+                None => copied.set_item_steal_two(key_copy, val_copy),
+            };
+            #[cfg(not(all(Py_3_11, not(Py_GIL_DISABLED))))]
+            let rc = copied.set_item_steal_two(key_copy, val_copy);
 
-                let rc = copied.set_item_steal_two(key_copy.into_raw(), val_copy.into_raw());
+            if unlikely(rc < 0) {
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return PyResult::error();
+            }
+        }
 
-                if unlikely(rc < 0) {
-                    memo.forget(self as _, &probe);
-                    copied.decref();
-                    return PyResult::error();
+        PyResult::ok(copied as _)
+    }
+}
+
+impl PyDeepCopy for *mut PyDictObject {
+    unsafe fn deepcopy<M: Memo>(self, memo: &mut M, probe: M::Probe) -> PyResult {
+        unsafe {
+            #[cfg(all(Py_3_11, not(Py_GIL_DISABLED)))]
+            if !M::CANONICALIZES
+                && is_prememo_atomic::<M>(ptr::addr_of_mut!(PyUnicode_Type))
+                && has_only_str_keys(self)
+            {
+                if leading_values_are(self, ptr::addr_of_mut!(PyList_Type)) {
+                    return deepcopy_str_keyed_dict::<M, true>(self, memo, probe);
                 }
+                return deepcopy_str_keyed_dict::<M, false>(self, memo, probe);
             }
-
-            PyResult::ok(copied as _)
+            deepcopy_dict_items(self, memo, probe)
         }
     }
 }
@@ -407,6 +812,7 @@ impl PyDeepCopy for *mut PyFrozensetObject {
                 return PyResult::error();
             }
 
+            let mut all_same = true;
             for j in 0..i {
                 let orig = snapshot.get_borrowed_unchecked(j);
                 let item_copy = deepcopy(orig, memo);
@@ -415,10 +821,27 @@ impl PyDeepCopy for *mut PyFrozensetObject {
                     items.decref();
                     return PyResult::error();
                 }
-                items.set_slot_steal_unchecked(j, item_copy.into_raw());
+                let raw = item_copy.into_raw();
+                if raw != orig {
+                    all_same = false;
+                }
+                items.set_slot_steal_unchecked(j, raw);
             }
             snapshot.decref();
 
+            // Same reasoning as tuples: a frozenset whose members all copy to
+            // themselves is deeply immutable, so the original is a valid copy.
+            if all_same {
+                items.decref();
+                return PyResult::ok(self.newref() as _);
+            }
+
+            let existing = memo.recall_probed(self as _, &probe);
+            if unlikely(!existing.is_null()) {
+                items.decref();
+                return PyResult::ok(existing);
+            }
+
             let copied = frozenset_from(items as _);
             items.decref();
             if copied.is_null() {
@@ -439,9 +862,21 @@ impl PyDeepCopy for *mut PyByteArrayObject {
     unsafe fn deepcopy<M: Memo>(self, memo: &mut M, probe: M::Probe) -> PyResult {
         unsafe {
             let sz = self.len();
+            if unlikely(crate::budget::is_active())
+                && crate::budget::charge(self as _, sz as usize) < 0
+            {
+                return PyResult::error();
+            }
             let copied = check!(py_bytearray_new(sz));
 
-            if sz > 0 {
+            if unlikely(crate::offload::is_active())
+                && sz as usize >= crate::offload::RELEASE_GIL_MIN_BYTES
+            {
+                if crate::offload::copy_buffer(self as _, copied.as_ptr().cast(), sz as usize) < 0 {
+                    copied.decref();
+                    return PyResult::error();
+                }
+            } else if sz > 0 {
                 ptr::copy_nonoverlapping(self.as_ptr(), copied.as_ptr(), sz as usize);
             }
 
@@ -488,16 +923,31 @@ impl PyDeepCopy for *mut PyMethodObject {
 impl PyDeepCopy for *mut PyObject {
     unsafe fn deepcopy<M: Memo>(self, memo: &mut M, probe: M::Probe) -> PyResult {
         unsafe {
+            if unlikely(crate::budget::is_active()) && crate::budget::charge_array(self) < 0 {
+                return PyResult::error();
+            }
+
             let mut custom_deepcopy_method: *mut PyObject = ptr::null_mut();
             let has = self.get_optional_attr(py_str!("__deepcopy__"), &mut custom_deepcopy_method);
             if has < 0 {
                 return PyResult::error();
             }
-            if has > 0 {
+            // like stdlib, `__deepcopy__ = None` opts out rather than failing the call
+            if has > 0 && custom_deepcopy_method.is_none() {
+                custom_deepcopy_method.decref();
+            } else if has > 0 {
                 return deepcopy_custom(self, custom_deepcopy_method, memo, probe);
             }
 
-            let result = crate::reduce::reconstruct(self, self.class(), memo, probe);
+            if unlikely(crate::budget::is_active()) && crate::budget::charge_reduced(self) < 0 {
+                return PyResult::error();
+            }
+
+            let result = match copy_buffers_owned(self) {
+                Ok(true) => crate::buffers::copy_owned(self, memo, &probe),
+                Ok(false) => crate::reduce::reconstruct(self, self.class(), memo, probe),
+                Err(()) => ptr::null_mut(),
+            };
             if result.is_null() {
                 PyResult::error()
             } else {
@@ -507,6 +957,17 @@ impl PyDeepCopy for *mut PyObject {
     }
 }
 
+/// Whether `copy_buffers="owned"` applies to `object`.
+#[inline(always)]
+unsafe fn copy_buffers_owned(object: *mut PyObject) -> Result<bool, ()> {
+    unsafe {
+        if likely(crate::state::STATE.copy_buffers == crate::state::CopyBuffers::Raise) {
+            return Ok(false);
+        }
+        crate::buffers::is_buffer_only(object, object.class())
+    }
+}
+
 unsafe fn deepcopy_custom<M: Memo>(
     object: *mut PyObject,
     custom_deepcopy_method: *mut PyObject,
@@ -514,8 +975,42 @@ unsafe fn deepcopy_custom<M: Memo>(
     probe: M::Probe,
 ) -> PyResult {
     unsafe {
+        if crate::fallback::needs_dict_memo(object.class()) {
+            let native_memo = memo.as_native_memo();
+            if !native_memo.is_null() {
+                let copied =
+                    crate::fallback::call_with_dict_memo(custom_deepcopy_method, &mut *native_memo);
+                custom_deepcopy_method.decref();
+                if copied.is_null() {
+                    return not_implemented_fallback(object, memo, probe, None);
+                }
+                if copied != object && memo.memoize(object, copied, &probe) < 0 {
+                    copied.decref();
+                    return PyResult::error();
+                }
+                return PyResult::ok(copied);
+            }
+        }
+
         let checkpoint = memo.checkpoint();
-        let mut copied = custom_deepcopy_method.call_one(memo.as_call_arg());
+        let mut copied = if unlikely(crate::fallback::takes_no_memo_arg(object.class())) {
+            PyObject_CallNoArgs(custom_deepcopy_method)
+        } else {
+            let memo_arg = memo.as_call_arg();
+            if memo_arg.is_null() {
+                custom_deepcopy_method.decref();
+                return PyResult::error();
+            }
+            if unlikely(crate::state::STATE.memo_key_check) {
+                crate::memo::with_pending_key(object, || custom_deepcopy_method.call_one(memo_arg))
+            } else {
+                custom_deepcopy_method.call_one(memo_arg)
+            }
+        };
+
+        if copied.is_null() && crate::state::STATE.deepcopy_arg_compat {
+            copied = crate::fallback::maybe_retry_without_memo_arg(object, custom_deepcopy_method);
+        }
 
         if copied.is_null() {
             if let Some(saved_checkpoint) = checkpoint {
@@ -534,7 +1029,7 @@ unsafe fn deepcopy_custom<M: Memo>(
         custom_deepcopy_method.decref();
 
         if copied.is_null() {
-            return PyResult::error();
+            return not_implemented_fallback(object, memo, probe, checkpoint);
         }
 
         if copied != object {
@@ -542,8 +1037,49 @@ unsafe fn deepcopy_custom<M: Memo>(
                 copied.decref();
                 return PyResult::error();
             }
+        } else {
+            let native_memo = memo.as_native_memo();
+            if !native_memo.is_null() && (*native_memo).keep_alive_if_keyed(object) < 0 {
+                copied.decref();
+                return PyResult::error();
+            }
         }
 
         PyResult::ok(copied)
     }
 }
+
+/// Called with `__deepcopy__`'s error pending. With
+/// `deepcopy_not_implemented="fallback"`, a NotImplementedError is dropped
+/// along with whatever the method memoized, and `object` is reduced as if it
+/// had no `__deepcopy__`. Anything else, or the default, is returned as is.
+#[cold]
+unsafe fn not_implemented_fallback<M: Memo>(
+    object: *mut PyObject,
+    memo: &mut M,
+    probe: M::Probe,
+    checkpoint: Option<crate::memo::MemoCheckpoint>,
+) -> PyResult {
+    unsafe {
+        if crate::state::STATE.deepcopy_not_implemented
+            != crate::state::DeepcopyNotImplemented::Fallback
+            || PyErr_ExceptionMatches(PyExc_NotImplementedError) == 0
+        {
+            return PyResult::error();
+        }
+        PyErr_Clear();
+        if let Some(checkpoint) = checkpoint {
+            let native_memo = memo.as_native_memo();
+            if !native_memo.is_null() {
+                (*native_memo).rollback(checkpoint);
+            }
+        }
+
+        let result = crate::reduce::reconstruct(object, object.class(), memo, probe);
+        if result.is_null() {
+            PyResult::error()
+        } else {
+            PyResult::ok(result)
+        }
+    }
+}
@@ -1,9 +1,9 @@
+use crate::types::PyObjectPtr;
+#[cfg(Py_GIL_DISABLED)]
+use crate::{py_cache_typed, py_obj};
 use pyo3_ffi::*;
 use std::hint::{likely, unlikely};
 use std::ptr;
-#[cfg(Py_GIL_DISABLED)]
-use crate::{py_cache_typed, py_obj};
-use crate::types::PyObjectPtr;
 
 #[cfg(all(Py_3_14, not(Py_GIL_DISABLED)))]
 extern "C" {
@@ -164,6 +164,13 @@ impl DictIterGuard {
         }
     }
 
+    /// `PyDict_Next`'s position: one past the entry it returned last.
+    #[cfg(not(Py_GIL_DISABLED))]
+    #[inline(always)]
+    pub fn position(&self) -> Py_ssize_t {
+        self.pos
+    }
+
     #[inline(always)]
     pub unsafe fn new(dict: *mut PyObject) -> Self {
         #[cfg(not(Py_3_14))]
@@ -325,6 +332,17 @@ impl DictIterGuard {
                 return 1;
             }
 
+            // Python that ran for the last value may have resized the dict:
+            // PyDict_Next then stops early instead of failing, so the size
+            // is checked once more here, as dict iterators do on exhaustion.
+            if unlikely(dict_used(self.dict) != self.used0) {
+                PyErr_SetString(
+                    PyExc_RuntimeError,
+                    crate::cstr!("dictionary changed size during iteration"),
+                );
+                return -1;
+            }
+
             0
         }
 
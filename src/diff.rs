@@ -0,0 +1,495 @@
+//! `copium.extra.diff`: where two object graphs differ, walked in lockstep
+//! with the classification deepcopy uses.
+//!
+//! Dicts are compared by key, lists and tuples by position, sets by
+//! membership and other objects by their instance dict and slots. Atomic
+//! values, and anything past `max_depth`, are leaves compared with `==`; an
+//! exception raised there counts as a difference. Identical objects are not
+//! looked into, and a pair already being compared is not entered again, so
+//! shared subtrees are visited once and cycles end.
+
+use pyo3_ffi::*;
+use std::collections::HashSet;
+use std::ptr;
+
+use crate::ffi_ext::{PyUnicode_FromFormat, Py_None};
+use crate::py_str;
+use crate::types::{PyObjectPtr, PyTypeObjectPtr};
+
+struct Differ {
+    /// `(id(a), id(b))` for every pair of containers entered so far.
+    seen: HashSet<(usize, usize)>,
+    /// Entered pairs, kept alive so their ids stay unambiguous even if a
+    /// comparison drops the last other reference.
+    keepalive: Vec<*mut PyObject>,
+    max_depth: Option<usize>,
+    entries: *mut PyObject,
+}
+
+impl Drop for Differ {
+    fn drop(&mut self) {
+        unsafe {
+            self.entries.decref_nullable();
+            for object in self.keepalive.drain(..) {
+                object.decref();
+            }
+        }
+    }
+}
+
+/// `repr(object)`, or the default `<T object at 0x...>` if that raised.
+unsafe fn value_repr(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        if object.is_null() {
+            return Py_None().newref();
+        }
+        let repr = PyObject_Repr(object);
+        if !repr.is_null() || PyErr_ExceptionMatches(PyExc_Exception) == 0 {
+            return repr;
+        }
+        PyErr_Clear();
+        PyUnicode_FromFormat(
+            crate::cstr!("<%s object at %p>"),
+            (*object.class()).tp_name,
+            object,
+        )
+    }
+}
+
+/// `a == b`, where an `Exception` raised by `__eq__` means "not equal".
+unsafe fn leaves_equal(a: *mut PyObject, b: *mut PyObject) -> Option<bool> {
+    unsafe {
+        match PyObject_RichCompareBool(a, b, Py_EQ) {
+            1 => Some(true),
+            0 => Some(false),
+            _ if PyErr_ExceptionMatches(PyExc_Exception) != 0 => {
+                PyErr_Clear();
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Names in the instance dict, then the slot names pickle would use.
+pub(crate) unsafe fn attribute_names(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let names = PyList_New(0);
+        if names.is_null() {
+            return ptr::null_mut();
+        }
+        let mut dict: *mut PyObject = ptr::null_mut();
+        let has = object.get_optional_attr(py_str!("__dict__"), &mut dict);
+        if has < 0 {
+            names.decref();
+            return ptr::null_mut();
+        }
+        if has > 0 {
+            let mut pos: Py_ssize_t = 0;
+            let mut key: *mut PyObject = ptr::null_mut();
+            let mut value: *mut PyObject = ptr::null_mut();
+            let mut status = 0;
+            if PyDict_Check(dict) != 0 {
+                while PyDict_Next(dict, &mut pos, &mut key, &mut value) != 0 {
+                    if PyUnicode_Check(key) != 0 && PyList_Append(names, key) < 0 {
+                        status = -1;
+                        break;
+                    }
+                }
+            }
+            dict.decref();
+            if status < 0 {
+                names.decref();
+                return ptr::null_mut();
+            }
+        }
+
+        let slots = crate::py_obj!("copyreg._slotnames").call_one(object.class() as _);
+        if slots.is_null() {
+            names.decref();
+            return ptr::null_mut();
+        }
+        let status = PyList_SetSlice(names, PY_SSIZE_T_MAX, PY_SSIZE_T_MAX, slots);
+        slots.decref();
+        if status < 0 {
+            names.decref();
+            return ptr::null_mut();
+        }
+        names
+    }
+}
+
+impl Differ {
+    /// Appends `(path, kind, repr(a), repr(b))`; a null side is `None`.
+    unsafe fn record(
+        &mut self,
+        path: *mut PyObject,
+        kind: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+    ) -> i32 {
+        unsafe {
+            let a_repr = value_repr(a);
+            let b_repr = if a_repr.is_null() {
+                ptr::null_mut()
+            } else {
+                value_repr(b)
+            };
+            if b_repr.is_null() {
+                a_repr.decref_nullable();
+                return -1;
+            }
+            let entry = PyTuple_New(4);
+            if entry.is_null() {
+                a_repr.decref();
+                b_repr.decref();
+                return -1;
+            }
+            PyTuple_SET_ITEM(entry, 0, path.newref());
+            PyTuple_SET_ITEM(entry, 1, kind.newref());
+            PyTuple_SET_ITEM(entry, 2, a_repr);
+            PyTuple_SET_ITEM(entry, 3, b_repr);
+            let status = PyList_Append(self.entries, entry);
+            entry.decref();
+            status
+        }
+    }
+
+    unsafe fn compare_leaves(
+        &mut self,
+        path: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+    ) -> i32 {
+        unsafe {
+            match leaves_equal(a, b) {
+                Some(true) => 0,
+                Some(false) => self.record(path, py_str!("changed"), a, b),
+                None => -1,
+            }
+        }
+    }
+
+    unsafe fn compare(
+        &mut self,
+        path: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+        depth: usize,
+    ) -> i32 {
+        unsafe {
+            if a == b {
+                return 0;
+            }
+            let tp = a.class();
+            if tp != b.class() {
+                return self.record(path, py_str!("type"), a, b);
+            }
+            let is_container = PyDict_Check(a) != 0
+                || PyList_Check(a) != 0
+                || PyTuple_Check(a) != 0
+                || PyAnySet_Check(a) != 0;
+            let has_attributes = !is_container
+                && !tp.is_atomic_immutable()
+                && PyByteArray_Check(a) == 0
+                && ((*tp).tp_dictoffset != 0 || !(*tp).tp_members.is_null());
+            if (!is_container && !has_attributes) || self.max_depth.is_some_and(|max| depth >= max)
+            {
+                return self.compare_leaves(path, a, b);
+            }
+
+            if !self.seen.insert((a as usize, b as usize)) {
+                return 0;
+            }
+            self.keepalive.push(a.newref());
+            self.keepalive.push(b.newref());
+
+            if crate::recursion::enter() < 0 {
+                return -1;
+            }
+            let status = if PyDict_Check(a) != 0 {
+                self.compare_dicts(path, a, b, depth + 1)
+            } else if PyAnySet_Check(a) != 0 {
+                self.compare_sets(path, a, b)
+            } else if is_container {
+                self.compare_sequences(path, a, b, depth + 1)
+            } else {
+                self.compare_attributes(path, a, b, depth + 1)
+            };
+            crate::recursion::leave();
+            status
+        }
+    }
+
+    unsafe fn compare_dicts(
+        &mut self,
+        path: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+        depth: usize,
+    ) -> i32 {
+        unsafe {
+            // Comparing values runs __eq__, which may resize either dict.
+            let a_items = PyDict_Items(a);
+            if a_items.is_null() {
+                return -1;
+            }
+            let mut status = 0;
+            for i in 0..PyList_GET_SIZE(a_items) {
+                let pair = PyList_GET_ITEM(a_items, i);
+                let key = PyTuple_GET_ITEM(pair, 0);
+                let value = PyTuple_GET_ITEM(pair, 1);
+                let child = PyUnicode_FromFormat(crate::cstr!("%U[%R]"), path, key);
+                if child.is_null() {
+                    status = -1;
+                    break;
+                }
+                let other = PyDict_GetItemWithError(b, key);
+                status = if !other.is_null() {
+                    other.incref();
+                    let status = self.compare(child, value, other, depth);
+                    other.decref();
+                    status
+                } else if PyErr_Occurred().is_null() {
+                    self.record(child, py_str!("removed"), value, ptr::null_mut())
+                } else {
+                    -1
+                };
+                child.decref();
+                if status < 0 {
+                    break;
+                }
+            }
+            a_items.decref();
+            if status < 0 {
+                return -1;
+            }
+
+            let b_items = PyDict_Items(b);
+            if b_items.is_null() {
+                return -1;
+            }
+            for i in 0..PyList_GET_SIZE(b_items) {
+                let pair = PyList_GET_ITEM(b_items, i);
+                let key = PyTuple_GET_ITEM(pair, 0);
+                let found = PyDict_Contains(a, key);
+                if found != 0 {
+                    status = found.min(0);
+                } else {
+                    let child = PyUnicode_FromFormat(crate::cstr!("%U[%R]"), path, key);
+                    status = if child.is_null() {
+                        -1
+                    } else {
+                        let status = self.record(
+                            child,
+                            py_str!("added"),
+                            ptr::null_mut(),
+                            PyTuple_GET_ITEM(pair, 1),
+                        );
+                        child.decref();
+                        status
+                    };
+                }
+                if status < 0 {
+                    break;
+                }
+            }
+            b_items.decref();
+            status
+        }
+    }
+
+    unsafe fn compare_sequences(
+        &mut self,
+        path: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+        depth: usize,
+    ) -> i32 {
+        unsafe {
+            let a_items = PySequence_Tuple(a);
+            let b_items = if a_items.is_null() {
+                ptr::null_mut()
+            } else {
+                PySequence_Tuple(b)
+            };
+            if b_items.is_null() {
+                a_items.decref_nullable();
+                return -1;
+            }
+            let a_len = PyTuple_GET_SIZE(a_items);
+            let b_len = PyTuple_GET_SIZE(b_items);
+            let mut status = 0;
+            for i in 0..a_len.max(b_len) {
+                let child = PyUnicode_FromFormat(crate::cstr!("%U[%zd]"), path, i);
+                if child.is_null() {
+                    status = -1;
+                    break;
+                }
+                status = if i >= b_len {
+                    self.record(
+                        child,
+                        py_str!("removed"),
+                        PyTuple_GET_ITEM(a_items, i),
+                        ptr::null_mut(),
+                    )
+                } else if i >= a_len {
+                    self.record(
+                        child,
+                        py_str!("added"),
+                        ptr::null_mut(),
+                        PyTuple_GET_ITEM(b_items, i),
+                    )
+                } else {
+                    self.compare(
+                        child,
+                        PyTuple_GET_ITEM(a_items, i),
+                        PyTuple_GET_ITEM(b_items, i),
+                        depth,
+                    )
+                };
+                child.decref();
+                if status < 0 {
+                    break;
+                }
+            }
+            a_items.decref();
+            b_items.decref();
+            status
+        }
+    }
+
+    /// Members of only one side, recorded at the set's own path.
+    unsafe fn compare_sets(&mut self, path: *mut PyObject, a: *mut PyObject, b: *mut PyObject) -> i32 {
+        unsafe {
+            for (from, to, kind) in [(a, b, py_str!("removed")), (b, a, py_str!("added"))] {
+                let members = PySequence_List(from);
+                if members.is_null() {
+                    return -1;
+                }
+                let mut status = 0;
+                for i in 0..PyList_GET_SIZE(members) {
+                    let member = PyList_GET_ITEM(members, i);
+                    status = match PySet_Contains(to, member) {
+                        0 if from == a => self.record(path, kind, member, ptr::null_mut()),
+                        0 => self.record(path, kind, ptr::null_mut(), member),
+                        found => found.min(0),
+                    };
+                    if status < 0 {
+                        break;
+                    }
+                }
+                members.decref();
+                if status < 0 {
+                    return -1;
+                }
+            }
+            0
+        }
+    }
+
+    unsafe fn compare_attributes(
+        &mut self,
+        path: *mut PyObject,
+        a: *mut PyObject,
+        b: *mut PyObject,
+        depth: usize,
+    ) -> i32 {
+        unsafe {
+            let a_names = attribute_names(a);
+            let b_names = if a_names.is_null() {
+                ptr::null_mut()
+            } else {
+                attribute_names(b)
+            };
+            if b_names.is_null() {
+                a_names.decref_nullable();
+                return -1;
+            }
+            let names = PyList_New(0);
+            let mut status = if names.is_null() { -1 } else { 0 };
+            for list in [a_names, b_names] {
+                for i in 0..PyList_GET_SIZE(list) {
+                    if status < 0 {
+                        break;
+                    }
+                    let name = PyList_GET_ITEM(list, i);
+                    status = match PySequence_Contains(names, name) {
+                        0 => PyList_Append(names, name),
+                        found => found.min(0),
+                    };
+                }
+            }
+            a_names.decref();
+            b_names.decref();
+            if status < 0 {
+                names.decref_nullable();
+                return -1;
+            }
+            if PyList_GET_SIZE(names) == 0 {
+                names.decref();
+                return self.compare_leaves(path, a, b);
+            }
+
+            for i in 0..PyList_GET_SIZE(names) {
+                let name = PyList_GET_ITEM(names, i);
+                let mut a_value: *mut PyObject = ptr::null_mut();
+                let mut b_value: *mut PyObject = ptr::null_mut();
+                if a.get_optional_attr(name, &mut a_value) < 0
+                    || b.get_optional_attr(name, &mut b_value) < 0
+                {
+                    a_value.decref_nullable();
+                    status = -1;
+                    break;
+                }
+                let child = PyUnicode_FromFormat(crate::cstr!("%U.%U"), path, name);
+                status = if child.is_null() {
+                    -1
+                } else if a_value.is_null() && b_value.is_null() {
+                    0
+                } else if b_value.is_null() {
+                    self.record(child, py_str!("removed"), a_value, ptr::null_mut())
+                } else if a_value.is_null() {
+                    self.record(child, py_str!("added"), ptr::null_mut(), b_value)
+                } else {
+                    self.compare(child, a_value, b_value, depth)
+                };
+                child.decref_nullable();
+                a_value.decref_nullable();
+                b_value.decref_nullable();
+                if status < 0 {
+                    break;
+                }
+            }
+            names.decref();
+            status
+        }
+    }
+}
+
+/// `[(path, kind, repr(a_value), repr(b_value)), ...]` for every place `a`
+/// and `b` differ, in traversal order.
+pub unsafe fn diff(a: *mut PyObject, b: *mut PyObject, max_depth: Option<usize>) -> *mut PyObject {
+    unsafe {
+        let entries = PyList_New(0);
+        if entries.is_null() {
+            return ptr::null_mut();
+        }
+        let mut differ = Differ {
+            seen: HashSet::new(),
+            keepalive: Vec::new(),
+            max_depth,
+            entries,
+        };
+        let root = PyUnicode_FromString(crate::cstr!(""));
+        if root.is_null() {
+            return ptr::null_mut();
+        }
+        let status = differ.compare(root, a, b, 0);
+        root.decref();
+        if status < 0 {
+            return ptr::null_mut();
+        }
+        differ.entries.newref()
+    }
+}
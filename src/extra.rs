@@ -1,8 +1,11 @@
 use pyo3_ffi::*;
+use std::ffi::c_void;
 use std::ptr;
 
 use crate::deepcopy;
-use crate::types::{PyObjectPtr, PyTypeObjectPtr};
+use crate::memo::{DictMemo, PyMemoObject};
+use crate::state::{MemoMode, STATE};
+use crate::types::{py_dict_new, PyObjectPtr, PyTypeObjectPtr};
 
 unsafe extern "C" fn py_replicate(
     _self: *mut PyObject,
@@ -10,18 +13,45 @@ unsafe extern "C" fn py_replicate(
     nargs: Py_ssize_t,
     kwnames: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         if nargs != 2 {
-            PyErr_SetString(PyExc_TypeError, crate::cstr!("replicate(obj, n, /)"));
-            return ptr::null_mut();
-        }
-        if !kwnames.is_null() && PyTuple_Size(kwnames) > 0 {
             PyErr_SetString(
                 PyExc_TypeError,
-                crate::cstr!("replicate() does not accept keyword arguments"),
+                crate::cstr!("replicate(obj, n, /, *, verify_first=False, progress=None)"),
             );
             return ptr::null_mut();
         }
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_Size(kwnames)
+        };
+        let mut verify_first = false;
+        let mut progress_callback: Option<*mut PyObject> = None;
+        for i in 0..kwcount {
+            let name = PyTuple_GetItem(kwnames, i);
+            let value = *args.offset(nargs + i);
+            if PyUnicode_CompareWithASCIIString(name, crate::cstr!("progress")) == 0 {
+                match crate::progress::parse_callback(value) {
+                    Ok(callback) => progress_callback = callback,
+                    Err(()) => return ptr::null_mut(),
+                }
+                continue;
+            }
+            if PyUnicode_CompareWithASCIIString(name, crate::cstr!("verify_first")) != 0 {
+                PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("replicate() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+            let truth = PyObject_IsTrue(value);
+            if truth < 0 {
+                return ptr::null_mut();
+            }
+            verify_first = truth != 0;
+        }
 
         let obj = *args;
         let n = PyLong_AsLong(*args.add(1));
@@ -49,32 +79,219 @@ unsafe extern "C" fn py_replicate(
             return out;
         }
 
-        let out = PyList_New(n as Py_ssize_t);
+        match progress_callback {
+            Some(callback) => crate::progress::with_progress(callback, Some(n as Py_ssize_t), || {
+                replicate_n(obj, n as Py_ssize_t, verify_first)
+            }),
+            None => replicate_n(obj, n as Py_ssize_t, verify_first),
+        }
+    })
+}
+
+unsafe fn replicate_n(obj: *mut PyObject, n: Py_ssize_t, verify_first: bool) -> *mut PyObject {
+    unsafe {
+        let out = PyList_New(n);
         if out.is_null() {
             return ptr::null_mut();
         }
 
-        for i in 0..n as Py_ssize_t {
-            let memo = crate::memo::pymemo_alloc();
-            let copy = deepcopy::deepcopy(obj, &mut *memo);
-            (*memo).reset();
-            if copy.is_error() {
+        for i in 0..n {
+            let copy = replicate_one(obj);
+            if copy.is_null() {
                 out.decref();
                 return ptr::null_mut();
             }
-            PyList_SetItem(out, i, copy.into_raw());
+            if i == 0 && verify_first && verify_copy(obj, copy) < 0 {
+                copy.decref();
+                out.decref();
+                return ptr::null_mut();
+            }
+            PyList_SET_ITEM(out, i, copy);
+            crate::progress::set_copies_done(i + 1);
         }
         out
     }
 }
 
+/// `replicate(..., verify_first=True)`: one `diff()` walk of the first copy
+/// against the original, raising `CopyError` if they differ anywhere, before
+/// the rest are made from the same recipe.
+#[cold]
+unsafe fn verify_copy(original: *mut PyObject, copy: *mut PyObject) -> i32 {
+    unsafe {
+        let differences = crate::diff::diff(original, copy, None);
+        if differences.is_null() {
+            return -1;
+        }
+        let count = PyList_GET_SIZE(differences);
+        if count > 0 {
+            PyErr_Format(
+                crate::reduce::copy_error(),
+                crate::cstr!(
+                    "replicate(): the first copy differs from the original in %zd place(s), first %R"
+                ),
+                count,
+                PyList_GET_ITEM(differences, 0),
+            );
+        }
+        differences.decref();
+        if count > 0 {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+/// One deep copy of `obj`, made on the thread's reusable memo.
+unsafe fn replicate_one(obj: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let (memo, is_tss) = crate::memo::get_memo();
+        if memo.is_null() {
+            return ptr::null_mut();
+        }
+        let copy = deepcopy::deepcopy(obj, &mut *memo);
+        crate::memo::cleanup_memo(memo, is_tss);
+        copy.into_raw()
+    }
+}
+
+/// Adds "replicate_into() failed at index N" to the pending exception's
+/// `__notes__`, the list `add_note()` appends to.
+#[cold]
+unsafe fn note_failed_index(index: Py_ssize_t) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        #[allow(deprecated)]
+        PyErr_NormalizeException(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+        let note = crate::ffi_ext::PyUnicode_FromFormat(
+            crate::cstr!("replicate_into() failed at index %zd"),
+            index,
+        );
+        if !note.is_null() && !exc_value.is_null() {
+            let mut notes = exc_value.getattr(crate::py_str!("__notes__"));
+            if notes.is_null() {
+                PyErr_Clear();
+                notes = PyList_New(0);
+                if !notes.is_null() && exc_value.set_attr(crate::py_str!("__notes__"), notes) < 0 {
+                    notes.decref();
+                    notes = ptr::null_mut();
+                }
+            }
+            if notes.is_null() || PyList_Check(notes) == 0 || PyList_Append(notes, note) < 0 {
+                PyErr_Clear();
+            }
+            notes.decref_nullable();
+        }
+        note.decref_nullable();
+        PyErr_Clear();
+
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
+    }
+}
+
+unsafe extern "C" fn py_replicate_into(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        if nargs != 3 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("replicate_into(obj, target, n, /)"),
+            );
+            return ptr::null_mut();
+        }
+        if !kwnames.is_null() && PyTuple_Size(kwnames) > 0 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("replicate_into() does not accept keyword arguments"),
+            );
+            return ptr::null_mut();
+        }
+
+        let obj = *args;
+        let target = *args.add(1);
+        let n = PyLong_AsSsize_t(*args.add(2));
+        if n == -1 && !PyErr_Occurred().is_null() {
+            return ptr::null_mut();
+        }
+        if n < 0 {
+            PyErr_SetString(PyExc_ValueError, crate::cstr!("n must be >= 0"));
+            return ptr::null_mut();
+        }
+
+        // Slots that already exist are overwritten, the rest appended.
+        let mut length = PyObject_Size(target);
+        if length < 0 {
+            PyErr_Clear();
+            length = 0;
+        }
+        let is_list = PyList_CheckExact(target) != 0;
+        let atomic = obj.class().is_atomic_immutable();
+        let mut append: *mut PyObject = ptr::null_mut();
+
+        for i in 0..n {
+            let item = if atomic {
+                obj.newref()
+            } else {
+                replicate_one(obj)
+            };
+            if item.is_null() {
+                append.decref_nullable();
+                note_failed_index(i);
+                return ptr::null_mut();
+            }
+
+            let status = if i < length {
+                PySequence_SetItem(target, i, item)
+            } else if is_list {
+                PyList_Append(target, item)
+            } else {
+                if append.is_null() {
+                    append = target.getattr(crate::py_str!("append"));
+                }
+                let result = if append.is_null() {
+                    ptr::null_mut()
+                } else {
+                    append.call_one(item)
+                };
+                if result.is_null() {
+                    -1
+                } else {
+                    result.decref();
+                    0
+                }
+            };
+            item.decref();
+            if status < 0 {
+                append.decref_nullable();
+                note_failed_index(i);
+                return ptr::null_mut();
+            }
+        }
+
+        append.decref_nullable();
+        PyLong_FromSsize_t(n)
+    })
+}
+
 unsafe extern "C" fn py_repeatcall(
     _self: *mut PyObject,
     args: *const *mut PyObject,
     nargs: Py_ssize_t,
     kwnames: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         if nargs != 2 {
             PyErr_SetString(
                 PyExc_TypeError,
@@ -119,15 +336,379 @@ unsafe extern "C" fn py_repeatcall(
             PyList_SetItem(out, i, item);
         }
         out
+    })
+}
+
+/// The native memo as a plain dict laid out like the one copy.deepcopy fills:
+/// `{id(original): copy}`, with every original listed under the dict's own id.
+/// That entry keeps the originals alive, so the id keys stay meaningful for as
+/// long as it stays in the dict.
+unsafe fn memo_as_dict(memo: *mut PyMemoObject) -> *mut PyObject {
+    unsafe {
+        let dict = (*memo).to_dict();
+        if dict.is_null() || (*memo).keepalive.items.is_empty() {
+            return dict;
+        }
+
+        let items = &(*memo).keepalive.items;
+        let keepalive = PyList_New(items.len() as Py_ssize_t);
+        if keepalive.is_null() {
+            dict.decref();
+            return ptr::null_mut();
+        }
+        for (i, &item) in items.iter().enumerate() {
+            PyList_SET_ITEM(keepalive, i as Py_ssize_t, item.newref());
+        }
+
+        let key = PyLong_FromVoidPtr(dict as *mut c_void);
+        let status = if key.is_null() {
+            -1
+        } else {
+            PyDict_SetItem(dict, key, keepalive)
+        };
+        key.decref_nullable();
+        keepalive.decref();
+        if status < 0 {
+            dict.decref();
+            return ptr::null_mut();
+        }
+        dict
     }
 }
 
-static mut EXTRA_METHODS: [PyMethodDef; 3] = [PyMethodDef::zeroed(); 3];
+unsafe extern "C" fn py_deepcopy_with_memo(
+    _self: *mut PyObject,
+    obj: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let (copy, memo) = if obj.class().is_atomic_immutable() {
+            let memo = PyDict_New();
+            if memo.is_null() {
+                return ptr::null_mut();
+            }
+            (obj.newref(), memo)
+        } else if STATE.memo_mode == MemoMode::Native {
+            let (pm, is_tss) = crate::memo::get_memo();
+            if pm.is_null() {
+                return ptr::null_mut();
+            }
+            let copy = deepcopy::deepcopy(obj, &mut *pm);
+            // Snapshot before cleanup resets the thread-local memo.
+            let memo = if copy.is_error() {
+                ptr::null_mut()
+            } else {
+                memo_as_dict(pm)
+            };
+            crate::memo::cleanup_memo(pm, is_tss);
+            let copy = copy.into_raw();
+            if memo.is_null() {
+                copy.decref_nullable();
+                return ptr::null_mut();
+            }
+            (copy, memo)
+        } else {
+            // memo="dict" config: the memo already is the plain dict.
+            let memo = py_dict_new(0) as *mut PyObject;
+            if memo.is_null() {
+                return ptr::null_mut();
+            }
+            let mut m = DictMemo::new(memo as _);
+            let copy = deepcopy::deepcopy(obj, &mut m);
+            drop(m);
+            if copy.is_error() {
+                memo.decref();
+                return ptr::null_mut();
+            }
+            (copy.into_raw(), memo)
+        };
+
+        let result = PyTuple_New(2);
+        if result.is_null() {
+            copy.decref();
+            memo.decref();
+            return ptr::null_mut();
+        }
+        PyTuple_SET_ITEM(result, 0, copy);
+        PyTuple_SET_ITEM(result, 1, memo);
+        result
+    })
+}
+
+unsafe extern "C" fn py_deepcopy_json(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        if nargs != 1 {
+            PyErr_Format(
+                PyExc_TypeError,
+                crate::cstr!("deepcopy_json() takes exactly 1 positional argument (%zd given)"),
+                nargs,
+            );
+            return ptr::null_mut();
+        }
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_Size(kwnames)
+        };
+        let mut preserve_sharing = false;
+        for i in 0..kwcount {
+            let name = PyTuple_GetItem(kwnames, i);
+            if PyUnicode_CompareWithASCIIString(name, crate::cstr!("preserve_sharing")) != 0 {
+                PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("deepcopy_json() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+            let truth = PyObject_IsTrue(*args.offset(nargs + i));
+            if truth < 0 {
+                return ptr::null_mut();
+            }
+            preserve_sharing = truth != 0;
+        }
+
+        crate::json::deepcopy_json(*args, preserve_sharing)
+    })
+}
+
+unsafe fn set_count(dict: *mut PyObject, key: *const std::ffi::c_char, value: usize) -> i32 {
+    unsafe {
+        let count = PyLong_FromSize_t(value);
+        if count.is_null() {
+            return -1;
+        }
+        let status = PyDict_SetItemString(dict, key, count);
+        count.decref();
+        status
+    }
+}
+
+unsafe extern "C" fn py_memo_stats(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        if nargs != 0 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("memo_stats() takes no positional arguments"),
+            );
+            return ptr::null_mut();
+        }
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_Size(kwnames)
+        };
+        let mut include_highwater = false;
+        for i in 0..kwcount {
+            let name = PyTuple_GetItem(kwnames, i);
+            if PyUnicode_CompareWithASCIIString(name, crate::cstr!("include_highwater")) != 0 {
+                PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("memo_stats() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+            let truth = PyObject_IsTrue(*args.offset(i));
+            if truth < 0 {
+                return ptr::null_mut();
+            }
+            include_highwater = truth != 0;
+        }
+
+        let memo = crate::memo::thread_memo();
+        let (slots, keepalive, bytes) = if memo.is_null() {
+            (0, 0, 0)
+        } else {
+            (
+                (*memo).table.size,
+                (*memo).keepalive.items.capacity(),
+                (*memo).heap_bytes(),
+            )
+        };
+
+        let stats = PyDict_New();
+        if stats.is_null() {
+            return ptr::null_mut();
+        }
+        let mut counts = vec![
+            (crate::cstr!("slots"), slots),
+            (crate::cstr!("keepalive"), keepalive),
+            (crate::cstr!("bytes"), bytes),
+        ];
+        if include_highwater {
+            let mark = crate::memo::highwater();
+            counts.extend([
+                (crate::cstr!("max_used"), mark.used),
+                (crate::cstr!("max_slots"), mark.slots),
+                (crate::cstr!("max_keepalive"), mark.keepalive),
+            ]);
+        }
+        for (key, value) in counts {
+            if set_count(stats, key, value) < 0 {
+                stats.decref();
+                return ptr::null_mut();
+            }
+        }
+        stats
+    })
+}
+
+unsafe extern "C" fn py_reset_memo_stats(_self: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    crate::memo::reset_highwater();
+    unsafe { Py_None().newref() }
+}
+
+unsafe extern "C" fn py_clear_caches(_self: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        crate::dataclasses::clear_cache();
+        crate::subclasses::clear_cache();
+        crate::negative_cache::clear();
+        crate::types::clear_lazy_type_negative_cache();
+        crate::types::clear_stdlib_atomic_cache();
+        Py_None().newref()
+    }
+}
+
+unsafe extern "C" fn py_dump_structure(
+    _self: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        if nargs != 2 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("dump_structure(obj, file, /)"),
+            );
+            return ptr::null_mut();
+        }
+        let description = crate::structure::describe(*args);
+        if description.is_null() {
+            return ptr::null_mut();
+        }
+        let json = PyImport_ImportModule(crate::cstr!("json"));
+        if json.is_null() {
+            description.decref();
+            return ptr::null_mut();
+        }
+        let written = PyObject_CallMethodObjArgs(
+            json,
+            crate::py_str!("dump"),
+            description,
+            *args.add(1),
+            ptr::null_mut::<PyObject>(),
+        );
+        json.decref();
+        description.decref();
+        if written.is_null() {
+            return ptr::null_mut();
+        }
+        written.decref();
+        Py_None().newref()
+    })
+}
+
+unsafe extern "C" fn py_load_structure(_self: *mut PyObject, file: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let json = PyImport_ImportModule(crate::cstr!("json"));
+        if json.is_null() {
+            return ptr::null_mut();
+        }
+        let description = PyObject_CallMethodObjArgs(
+            json,
+            crate::py_str!("load"),
+            file,
+            ptr::null_mut::<PyObject>(),
+        );
+        json.decref();
+        if description.is_null() {
+            return ptr::null_mut();
+        }
+        let rebuilt = crate::structure::rebuild(description);
+        description.decref();
+        rebuilt
+    })
+}
+
+unsafe extern "C" fn py_diff(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_Size(kwnames)
+        };
+        if !(2..=3).contains(&nargs) || nargs + kwcount > 3 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("diff(a, b, /, max_depth=None)"),
+            );
+            return ptr::null_mut();
+        }
+        let mut limit = if nargs == 3 {
+            *args.add(2)
+        } else {
+            ptr::null_mut()
+        };
+        for i in 0..kwcount {
+            let name = PyTuple_GetItem(kwnames, i);
+            if PyUnicode_CompareWithASCIIString(name, crate::cstr!("max_depth")) != 0 {
+                PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("diff() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+            limit = *args.offset(nargs + i);
+        }
+
+        let max_depth = if limit.is_null() || limit == Py_None() {
+            None
+        } else {
+            let depth = PyLong_AsSsize_t(limit);
+            if depth == -1 && !PyErr_Occurred().is_null() {
+                return ptr::null_mut();
+            }
+            if depth < 0 {
+                PyErr_SetString(PyExc_ValueError, crate::cstr!("max_depth must be >= 0"));
+                return ptr::null_mut();
+            }
+            Some(depth as usize)
+        };
+
+        crate::diff::diff(*args, *args.add(1), max_depth)
+    })
+}
+
+unsafe extern "C" fn py_fingerprint(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe { crate::fingerprint::fingerprint(obj) })
+}
+
+unsafe extern "C" fn py_last_copy_info(_self: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe { crate::provenance::last_copy_info() })
+}
+
+static mut EXTRA_METHODS: [PyMethodDef; 15] = [PyMethodDef::zeroed(); 15];
 
 static mut EXTRA_MODULE_DEF: PyModuleDef = PyModuleDef {
     m_base: PyModuleDef_HEAD_INIT,
-    m_name: b"orcopium.extra\0".as_ptr().cast(),
-    m_doc: b"Batch copying utilities for orcopium.\0".as_ptr().cast(),
+    m_name: b"copium.extra\0".as_ptr().cast(),
+    m_doc: b"Batch copying utilities for copium.\0".as_ptr().cast(),
     m_size: -1,
     m_methods: ptr::null_mut(),
     m_slots: ptr::null_mut(),
@@ -145,7 +726,11 @@ pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
             },
             ml_flags: METH_FASTCALL | METH_KEYWORDS,
             ml_doc: crate::cstr!(
-                "replicate(obj, n, /)\n--\n\nReturns n deep copies of the object in a list."
+                "replicate(obj, n, /, *, verify_first=False, progress=None)\n--\n\nReturns n deep copies of the object in a list.\n\n\
+                 Equivalent of [deepcopy(obj) for _ in range(n)], but faster. With verify_first,\n\
+                 the first copy is compared against obj with diff() and CopyError is raised\n\
+                 before the rest are made if they differ. progress is called as\n\
+                 progress(copies_done, n) every config progress_every containers copied."
             ),
         };
         EXTRA_METHODS[1] = PyMethodDef {
@@ -155,10 +740,158 @@ pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
             },
             ml_flags: METH_FASTCALL | METH_KEYWORDS,
             ml_doc: crate::cstr!(
-                "repeatcall(function, size, /)\n--\n\nCall function repeatedly size times."
+                "repeatcall(function, size, /)\n--\n\nCall function repeatedly size times.\n\n\
+                 Equivalent of [function() for _ in range(size)], but faster."
+            ),
+        };
+        EXTRA_METHODS[2] = PyMethodDef {
+            ml_name: crate::cstr!("deepcopy_with_memo"),
+            ml_meth: PyMethodDefPointer { PyCFunction: py_deepcopy_with_memo },
+            ml_flags: METH_O,
+            ml_doc: crate::cstr!(
+                "deepcopy_with_memo(obj, /)\n--\n\nDeep copy obj and return (copy, memo), where memo maps\n\
+                 id(original) to its copy.\n\n\
+                 memo is laid out like the dict copy.deepcopy fills, keepalive list under\n\
+                 id(memo) included. Passing it back to deepcopy reuses the copies."
+            ),
+        };
+        EXTRA_METHODS[3] = PyMethodDef {
+            ml_name: crate::cstr!("adeepcopy"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: crate::offload::py_adeepcopy,
+            },
+            ml_flags: METH_O,
+            ml_doc: crate::cstr!(
+                "adeepcopy(obj, /)\n--\n\nDeep copy obj on the running loop's default executor; returns a future.\n\n\
+                 Like loop.run_in_executor(None, deepcopy, obj), but the copy lets the loop\n\
+                 run now and then and stops with CancelledError once the future is\n\
+                 cancelled. Must be called from within a running event loop."
+            ),
+        };
+        EXTRA_METHODS[4] = PyMethodDef {
+            ml_name: crate::cstr!("memo_stats"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFastWithKeywords: py_memo_stats,
+            },
+            ml_flags: METH_FASTCALL | METH_KEYWORDS,
+            ml_doc: crate::cstr!(
+                "memo_stats(*, include_highwater=False)\n--\n\nSizes of this thread's reusable memo.\n\n\
+                 \"slots\", \"keepalive\" and their footprint in \"bytes\". With include_highwater,\n\
+                 also the largest \"max_used\", \"max_slots\" and \"max_keepalive\" reached since\n\
+                 reset_memo_stats()."
+            ),
+        };
+        EXTRA_METHODS[5] = PyMethodDef {
+            ml_name: crate::cstr!("reset_memo_stats"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_reset_memo_stats,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: crate::cstr!(
+                "reset_memo_stats()\n--\n\nForget this thread's memo high-water marks."
+            ),
+        };
+        EXTRA_METHODS[6] = PyMethodDef {
+            ml_name: crate::cstr!("deepcopy_json"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFastWithKeywords: py_deepcopy_json,
+            },
+            ml_flags: METH_FASTCALL | METH_KEYWORDS,
+            ml_doc: crate::cstr!(
+                "deepcopy_json(obj, /, *, preserve_sharing=False)\n--\n\nDeep copy JSON-like data: dicts, lists, str, int, float, bool and None.\n\n\
+                 Exact types only; anything else raises TypeError. Without preserve_sharing\n\
+                 no memo is kept: shared containers are copied once per reference and a\n\
+                 cycle raises RecursionError."
+            ),
+        };
+        EXTRA_METHODS[7] = PyMethodDef {
+            ml_name: crate::cstr!("dump_structure"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFast: py_dump_structure,
+            },
+            ml_flags: METH_FASTCALL,
+            ml_doc: crate::cstr!(
+                "dump_structure(obj, file, /)\n--\n\nWrite the anonymized shape of obj's graph to file as JSON.\n\n\
+                 Records types, dispatch paths, attribute names, sizes, reduce results and\n\
+                 sharing, but no values. Nothing is copied and __deepcopy__ is not called."
+            ),
+        };
+        EXTRA_METHODS[8] = PyMethodDef {
+            ml_name: crate::cstr!("load_structure"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_load_structure,
+            },
+            ml_flags: METH_O,
+            ml_doc: crate::cstr!(
+                "load_structure(file, /)\n--\n\nBuild a synthetic graph shaped like a dump_structure() file.\n\n\
+                 Copying the result takes the same paths copying the original did. Raises\n\
+                 ValueError for anything that is not a dump_structure() file."
+            ),
+        };
+        EXTRA_METHODS[9] = PyMethodDef {
+            ml_name: crate::cstr!("replicate_into"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFastWithKeywords: py_replicate_into,
+            },
+            ml_flags: METH_FASTCALL | METH_KEYWORDS,
+            ml_doc: crate::cstr!(
+                "replicate_into(obj, target, n, /)\n--\n\nWrite n deep copies of obj into target and return n.\n\n\
+                 Overwrites target[0:len(target)] item by item and appends the rest, so\n\
+                 a pooled list is reused instead of building a new one. On failure the\n\
+                 items already written stay and the error notes the failing index."
+            ),
+        };
+        EXTRA_METHODS[10] = PyMethodDef {
+            ml_name: crate::cstr!("diff"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFastWithKeywords: py_diff,
+            },
+            ml_flags: METH_FASTCALL | METH_KEYWORDS,
+            ml_doc: crate::cstr!(
+                "diff(a, b, /, max_depth=None)\n--\n\nList where the object graphs a and b differ.\n\n\
+                 Returns (path, kind, repr of a's value, repr of b's value) tuples, kind\n\
+                 being \"added\", \"removed\", \"changed\" or \"type\". Identical objects are\n\
+                 not looked into; below max_depth values are compared with ==."
             ),
         };
-        EXTRA_METHODS[2] = PyMethodDef::zeroed();
+        EXTRA_METHODS[11] = PyMethodDef {
+            ml_name: crate::cstr!("clear_caches"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_clear_caches,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: crate::cstr!(
+                "clear_caches()\n--\n\nDrop this thread's per-type caches.\n\n\
+                 Releases the types they hold, so a class deleted since it was last copied\n\
+                 can be collected, and forgets remembered __reduce_ex__ failures."
+            ),
+        };
+        EXTRA_METHODS[12] = PyMethodDef {
+            ml_name: crate::cstr!("fingerprint"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_fingerprint,
+            },
+            ml_flags: METH_O,
+            ml_doc: crate::cstr!(
+                "fingerprint(obj, /)\n--\n\n128-bit content hash of the object graph, as an int.\n\n\
+                 Walks obj like diff() without copying it. Graphs that deepcopy would\n\
+                 reproduce hash equal within this process; others almost always differ.\n\
+                 Not cryptographic: collisions are possible."
+            ),
+        };
+        EXTRA_METHODS[13] = PyMethodDef {
+            ml_name: crate::cstr!("last_copy_info"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: py_last_copy_info,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: crate::cstr!(
+                "last_copy_info()\n--\n\nThe record of this thread's latest top-level deepcopy.\n\n\
+                 A dict of copy_id, timestamp, thread_id and source_type, kept while\n\
+                 config.apply(mark_copies=True) is on; None before any."
+            ),
+        };
+        EXTRA_METHODS[14] = PyMethodDef::zeroed();
 
         EXTRA_MODULE_DEF.m_methods = ptr::addr_of_mut!(EXTRA_METHODS).cast::<PyMethodDef>();
 
@@ -167,6 +900,13 @@ pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
             return -1;
         }
 
+        let custom_memo = crate::memo::custom_memo_type();
+        if PyModule_AddObject(module, crate::cstr!("CustomMemo"), custom_memo) < 0 {
+            custom_memo.decref();
+            module.decref();
+            return -1;
+        }
+
         crate::add_submodule(parent, crate::cstr!("extra"), module)
     }
 }
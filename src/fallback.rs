@@ -1,6 +1,7 @@
 use pyo3_ffi::*;
 use std::ffi::{c_char, CStr};
 use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
 
 use crate::ffi_ext::PyUnicode_FromFormat;
 use crate::memo::{MemoCheckpoint, PyMemoObject};
@@ -34,8 +35,7 @@ macro_rules! finish_warning_emit {
 }
 
 macro_rules! finish_fallback_retry {
-    ($result:expr, $dict_memo:expr, $exception_type:expr, $exception_value:expr, $exception_traceback:expr, $error_identifier:expr) => {{
-        $dict_memo.decref_nullable();
+    ($result:expr, $exception_type:expr, $exception_value:expr, $exception_traceback:expr, $error_identifier:expr) => {{
         $exception_type.decref_nullable();
         $exception_value.decref_nullable();
         $exception_traceback.decref_nullable();
@@ -541,18 +541,31 @@ unsafe fn emit_fallback_warning(
 ) -> i32 {
     unsafe {
         let mut status = 0;
+        // Each lookup below is best effort, and the next one must not run with
+        // its exception still set, so failures are cleared on the spot.
         let caller_info = get_caller_frame_info();
+        if caller_info.is_null() {
+            PyErr_Clear();
+        }
         let mut traceback_string = format_combined_traceback(caller_info, exception_value);
+        if traceback_string.is_null() {
+            PyErr_Clear();
+        }
         let mut full_message: *mut PyObject = ptr::null_mut();
         let type_object = object.class() as *mut PyObject;
         let mut module_name = PyObject_GetAttrString(type_object, crate::cstr!("__module__"));
+        if module_name.is_null() {
+            PyErr_Clear();
+        }
         let mut type_name = PyObject_GetAttrString(type_object, crate::cstr!("__name__"));
+        if type_name.is_null() {
+            PyErr_Clear();
+        }
         let mut deepcopy_qualified_name: *mut PyObject = ptr::null_mut();
         let mut deepcopy_expression: *mut PyObject = ptr::null_mut();
         let mut deepcopy_expression_with_memo: *mut PyObject = ptr::null_mut();
 
         if traceback_string.is_null() {
-            PyErr_Clear();
             traceback_string = PyUnicode_FromString(crate::cstr!("[traceback unavailable]\n"));
             if traceback_string.is_null() {
                 status = -1;
@@ -571,7 +584,6 @@ unsafe fn emit_fallback_warning(
         }
 
         if module_name.is_null() {
-            PyErr_Clear();
             module_name = PyUnicode_FromString(crate::cstr!("__main__"));
             if module_name.is_null() {
                 status = -1;
@@ -590,7 +602,6 @@ unsafe fn emit_fallback_warning(
         }
 
         if type_name.is_null() {
-            PyErr_Clear();
             type_name = PyUnicode_FromString(crate::cstr!("?"));
             if type_name.is_null() {
                 status = -1;
@@ -712,8 +723,6 @@ unsafe fn emit_fallback_warning(
 
         if PyErr_WarnEx(PyExc_UserWarning, PyUnicode_AsUTF8(full_message), 1) < 0 {
             status = -1;
-        } else {
-            PyErr_Clear();
         }
 
         finish_warning_emit!(
@@ -730,6 +739,140 @@ unsafe fn emit_fallback_warning(
     }
 }
 
+// ── Pre-registered types ───────────────────────────────────
+
+/// Types whose `__deepcopy__` is handed a dict memo from the first call, as
+/// registered by `copium.patch.check()`. Holds strong references.
+static DICT_MEMO_TYPES: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+/// Types whose `__deepcopy__` took no memo once `deepcopy_arg_compat` retried
+/// it, so later calls skip the failing one. Holds strong references.
+static NO_MEMO_ARG_TYPES: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+unsafe fn register_type(registry: &AtomicPtr<PyObject>, tp: *mut PyObject) -> i32 {
+    unsafe {
+        let mut types = registry.load(Ordering::Acquire);
+        if types.is_null() {
+            types = PySet_New(ptr::null_mut());
+            if types.is_null() {
+                return -1;
+            }
+            registry.store(types, Ordering::Release);
+        }
+        PySet_Add(types, tp)
+    }
+}
+
+#[inline(always)]
+unsafe fn is_registered(registry: &AtomicPtr<PyObject>, tp: *mut PyTypeObject) -> bool {
+    unsafe {
+        let types = registry.load(Ordering::Acquire);
+        if types.is_null() {
+            return false;
+        }
+        match PySet_Contains(types, tp as *mut PyObject) {
+            1 => true,
+            0 => false,
+            _ => {
+                PyErr_Clear();
+                false
+            }
+        }
+    }
+}
+
+pub unsafe fn register_dict_memo_type(tp: *mut PyObject) -> i32 {
+    unsafe { register_type(&DICT_MEMO_TYPES, tp) }
+}
+
+#[inline(always)]
+pub unsafe fn needs_dict_memo(tp: *mut PyTypeObject) -> bool {
+    unsafe { is_registered(&DICT_MEMO_TYPES, tp) }
+}
+
+#[inline(always)]
+pub unsafe fn takes_no_memo_arg(tp: *mut PyTypeObject) -> bool {
+    unsafe { STATE.deepcopy_arg_compat && is_registered(&NO_MEMO_ARG_TYPES, tp) }
+}
+
+/// For `deepcopy_arg_compat`: retries a `__deepcopy__(memo)` that just failed
+/// as `__deepcopy__()` when the pending error is a TypeError raised while
+/// binding the argument (no traceback: the method body never ran). On success
+/// the type is remembered. Otherwise returns null with the error untouched.
+#[cold]
+pub unsafe fn maybe_retry_without_memo_arg(
+    object: *mut PyObject,
+    dunder_deepcopy: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        if PyErr_ExceptionMatches(PyExc_TypeError) == 0 {
+            return ptr::null_mut();
+        }
+
+        let mut exception_type: *mut PyObject = ptr::null_mut();
+        let mut exception_value: *mut PyObject = ptr::null_mut();
+        let mut exception_traceback: *mut PyObject = ptr::null_mut();
+        #[allow(deprecated)]
+        PyErr_Fetch(
+            &mut exception_type,
+            &mut exception_value,
+            &mut exception_traceback,
+        );
+
+        let rejected_memo = exception_traceback.is_null() && !exception_value.is_null() && {
+            let message = PyObject_Str(exception_value);
+            let found = !message.is_null()
+                && unicode_to_string(message)
+                    .is_some_and(|text| text.contains("positional argument"));
+            message.decref_nullable();
+            PyErr_Clear();
+            found
+        };
+
+        if !rejected_memo {
+            #[allow(deprecated)]
+            PyErr_Restore(exception_type, exception_value, exception_traceback);
+            return ptr::null_mut();
+        }
+        exception_type.decref_nullable();
+        exception_value.decref_nullable();
+
+        let result = PyObject_CallNoArgs(dunder_deepcopy);
+        if result.is_null() {
+            return ptr::null_mut();
+        }
+        if register_type(&NO_MEMO_ARG_TYPES, object.class() as *mut PyObject) < 0 {
+            result.decref();
+            return ptr::null_mut();
+        }
+        result
+    }
+}
+
+/// Calls `dunder_deepcopy` with a dict copy of `memo` and folds whatever it
+/// memoized back into `memo`. Every such call in one deepcopy() gets the same
+/// dict, and `memo` keeps it up to date afterwards, so a method that kept it
+/// can still look up what was copied after it returned.
+#[cold]
+pub unsafe fn call_with_dict_memo(
+    dunder_deepcopy: *mut PyObject,
+    memo: &mut PyMemoObject,
+) -> *mut PyObject {
+    unsafe {
+        let dict_memo = memo.refresh_dict_view();
+        if dict_memo.is_null() {
+            return ptr::null_mut();
+        }
+        let mut result = PyObject_CallOneArg(dunder_deepcopy, dict_memo);
+        if !result.is_null() && memo.sync_from_dict(dict_memo) < 0 {
+            result.decref();
+            result = ptr::null_mut();
+        }
+        dict_memo.decref();
+        result
+    }
+}
+
 pub unsafe fn maybe_retry_with_dict_memo(
     object: *mut PyObject,
     dunder_deepcopy: *mut PyObject,
@@ -737,7 +880,6 @@ pub unsafe fn maybe_retry_with_dict_memo(
     checkpoint: MemoCheckpoint,
 ) -> *mut PyObject {
     unsafe {
-        let mut result: *mut PyObject = ptr::null_mut();
         let mut exception_type: *mut PyObject = ptr::null_mut();
         let mut exception_value: *mut PyObject = ptr::null_mut();
         let mut exception_traceback: *mut PyObject = ptr::null_mut();
@@ -768,37 +910,10 @@ pub unsafe fn maybe_retry_with_dict_memo(
 
         memo.rollback(checkpoint);
 
-        let dict_memo = memo.to_dict();
-        if dict_memo.is_null() {
-            finish_fallback_retry!(
-                result,
-                dict_memo,
-                exception_type,
-                exception_value,
-                exception_traceback,
-                error_identifier
-            );
-        }
-
-        let dict_size_before = PyDict_Size(dict_memo);
-        result = PyObject_CallOneArg(dunder_deepcopy, dict_memo);
+        let mut result = call_with_dict_memo(dunder_deepcopy, memo);
         if result.is_null() {
             finish_fallback_retry!(
                 result,
-                dict_memo,
-                exception_type,
-                exception_value,
-                exception_traceback,
-                error_identifier
-            );
-        }
-
-        if memo.sync_from_dict(dict_memo, dict_size_before) < 0 {
-            result.decref_nullable();
-            result = ptr::null_mut();
-            finish_fallback_retry!(
-                result,
-                dict_memo,
                 exception_type,
                 exception_value,
                 exception_traceback,
@@ -823,7 +938,6 @@ pub unsafe fn maybe_retry_with_dict_memo(
 
         finish_fallback_retry!(
             result,
-            dict_memo,
             exception_type,
             exception_value,
             exception_traceback,
@@ -19,6 +19,25 @@ extern "C" {
     pub static mut _PyNotImplemented_Type: PyTypeObject;
 }
 
+#[cfg(not(Py_3_13))]
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    fn _Py_IsFinalizing() -> core::ffi::c_int;
+}
+
+/// `Py_IsFinalizing`, public only since 3.13.
+#[inline]
+pub unsafe fn is_finalizing() -> bool {
+    #[cfg(Py_3_13)]
+    unsafe {
+        Py_IsFinalizing() != 0
+    }
+    #[cfg(not(Py_3_13))]
+    unsafe {
+        _Py_IsFinalizing() != 0
+    }
+}
+
 /// PyMethod_Function is a macro in CPython; access via struct layout.
 #[repr(C)]
 pub struct PyMethodObject {
@@ -102,3 +121,45 @@ macro_rules! cstr {
         concat!($s, "\0").as_ptr() as *const core::ffi::c_char
     };
 }
+
+// ── Panic boundary ──────────────────────────────────────────
+
+/// Runs the body of an `extern "C"` entry point. A Rust panic is reported as
+/// SystemError and yields `None` instead of unwinding into the interpreter,
+/// where it would abort the process.
+#[inline(always)]
+pub fn catch_panic<R>(body: impl FnOnce() -> R) -> Option<R> {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(body)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.as_str()
+            } else {
+                "unknown panic"
+            };
+            let message = std::ffi::CString::new(message.replace('\0', "\\0")).unwrap_or_default();
+            unsafe {
+                PyErr_Format(
+                    PyExc_SystemError,
+                    crate::cstr!("copium panicked: %s"),
+                    message.as_ptr(),
+                );
+            }
+            None
+        }
+    }
+}
+
+/// `ffi_guard!(error_value, unsafe { ... })`: `return` inside the body returns
+/// from the guarded body, not from the enclosing function.
+#[macro_export]
+macro_rules! ffi_guard {
+    ($error:expr, $body:expr) => {
+        match $crate::ffi_ext::catch_panic(|| $body) {
+            Some(result) => result,
+            None => $error,
+        }
+    };
+}
@@ -0,0 +1,306 @@
+//! `copium.extra.fingerprint`: a 128-bit content hash of an object graph,
+//! computed without copying it.
+//!
+//! The graph is walked with the classification `diff` uses. Every value
+//! contributes its type; lists and tuples their length and items in order,
+//! dicts and sets their length and entries in any order, other objects their
+//! instance dict and slots. Leaves contribute `hash()`, bytes and bytearrays
+//! their contents, and unhashable leaves only their type. A mutable
+//! container or object met again, through a cycle or a shared subtree,
+//! contributes the position it was first met at instead of its contents, so
+//! sharing is part of the fingerprint just as deepcopy preserves it.
+//!
+//! Not cryptographic, and only comparable within one process: `hash()` of
+//! strings is salted per process, and types are identified by address.
+
+use pyo3_ffi::*;
+use std::collections::HashMap;
+use std::ptr;
+
+use crate::types::{PyObjectPtr, PyTypeObjectPtr};
+
+const UNHASHABLE: u64 = 0x756e_6861_7368_6162;
+const BACK_REFERENCE: u64 = 0x6261_636b_7265_6621;
+
+/// Two 64-bit lanes, each fed every word through a multiply-fold.
+#[derive(Clone, Copy)]
+struct Digest(u64, u64);
+
+#[inline(always)]
+fn fold(a: u64, b: u64) -> u64 {
+    let product = (a as u128).wrapping_mul(b as u128);
+    (product as u64) ^ ((product >> 64) as u64)
+}
+
+impl Digest {
+    const SEED: Digest = Digest(0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344);
+
+    #[inline(always)]
+    fn feed(self, word: u64) -> Digest {
+        let low = fold(self.0 ^ word, 0x9e37_79b9_7f4a_7c15);
+        let high = fold(self.1.rotate_left(29) ^ word, 0xc2b2_ae3d_27d4_eb4f) ^ self.0;
+        Digest(low, high)
+    }
+
+    fn feed_digest(self, other: Digest) -> Digest {
+        self.feed(other.0).feed(other.1)
+    }
+
+    fn feed_bytes(self, bytes: &[u8]) -> Digest {
+        let mut digest = self.feed(bytes.len() as u64);
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            digest = digest.feed(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let mut tail = [0u8; 8];
+        tail[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+        digest.feed(u64::from_le_bytes(tail))
+    }
+}
+
+/// Sum of the digests of unordered entries, so their order doesn't matter.
+#[derive(Clone, Copy)]
+struct Unordered(u64, u64);
+
+impl Unordered {
+    fn add(self, entry: Digest) -> Unordered {
+        Unordered(self.0.wrapping_add(entry.0), self.1.wrapping_add(entry.1))
+    }
+}
+
+struct Fingerprinter {
+    /// Position each container was first met at, by id.
+    positions: HashMap<usize, u64>,
+    /// Containers met so far, kept alive so their ids stay unambiguous.
+    keepalive: Vec<*mut PyObject>,
+}
+
+impl Drop for Fingerprinter {
+    fn drop(&mut self) {
+        unsafe {
+            for object in self.keepalive.drain(..) {
+                object.decref();
+            }
+        }
+    }
+}
+
+impl Fingerprinter {
+    unsafe fn fingerprint(&mut self, object: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let tp = object.class();
+            let digest = Digest::SEED.feed(tp as u64);
+            let is_container = PyDict_Check(object) != 0
+                || PyList_Check(object) != 0
+                || PyTuple_Check(object) != 0
+                || PyAnySet_Check(object) != 0;
+            let has_attributes = !is_container
+                && !tp.is_atomic_immutable()
+                && PyByteArray_Check(object) == 0
+                && ((*tp).tp_dictoffset != 0 || !(*tp).tp_members.is_null());
+            if !is_container && !has_attributes {
+                return leaf(digest, object);
+            }
+
+            // Tuples and frozensets are hashed in full wherever they appear:
+            // whether equal ones are shared is up to the compiler. A cycle
+            // through one still passes through something mutable.
+            if PyTuple_Check(object) == 0 && PyFrozenSet_Check(object) == 0 {
+                let position = self.positions.len() as u64;
+                if let Some(&first) = self.positions.get(&(object as usize)) {
+                    return Ok(digest.feed(BACK_REFERENCE).feed(first));
+                }
+                self.positions.insert(object as usize, position);
+                self.keepalive.push(object.newref());
+            }
+
+            if crate::recursion::enter() < 0 {
+                return Err(());
+            }
+            let result = if PyDict_Check(object) != 0 {
+                self.dict(digest, object)
+            } else if PyAnySet_Check(object) != 0 {
+                self.set(digest, object)
+            } else if is_container {
+                self.sequence(digest, object)
+            } else {
+                self.attributes(digest, object)
+            };
+            crate::recursion::leave();
+            result
+        }
+    }
+
+    unsafe fn sequence(&mut self, digest: Digest, object: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let items = PySequence_Tuple(object);
+            if items.is_null() {
+                return Err(());
+            }
+            let result = self.items(digest, items);
+            items.decref();
+            result
+        }
+    }
+
+    unsafe fn items(&mut self, digest: Digest, items: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let size = PyTuple_GET_SIZE(items);
+            let mut digest = digest.feed(size as u64);
+            for i in 0..size {
+                digest = digest.feed_digest(self.fingerprint(PyTuple_GET_ITEM(items, i))?);
+            }
+            Ok(digest)
+        }
+    }
+
+    unsafe fn dict(&mut self, digest: Digest, object: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            // Hashing values may run Python code that resizes the dict.
+            let items = PyDict_Items(object);
+            if items.is_null() {
+                return Err(());
+            }
+            let result = self.pairs(digest, items);
+            items.decref();
+            result
+        }
+    }
+
+    unsafe fn pairs(&mut self, digest: Digest, items: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let size = PyList_GET_SIZE(items);
+            let mut entries = Unordered(0, 0);
+            for i in 0..size {
+                let pair = PyList_GET_ITEM(items, i);
+                let key = self.fingerprint(PyTuple_GET_ITEM(pair, 0))?;
+                let value = self.fingerprint(PyTuple_GET_ITEM(pair, 1))?;
+                entries = entries.add(Digest::SEED.feed_digest(key).feed_digest(value));
+            }
+            Ok(digest.feed(size as u64).feed(entries.0).feed(entries.1))
+        }
+    }
+
+    unsafe fn set(&mut self, digest: Digest, object: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let members = PySequence_List(object);
+            if members.is_null() {
+                return Err(());
+            }
+            let result = self.members(digest, members);
+            members.decref();
+            result
+        }
+    }
+
+    unsafe fn members(&mut self, digest: Digest, members: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let size = PyList_GET_SIZE(members);
+            let mut entries = Unordered(0, 0);
+            for i in 0..size {
+                entries = entries.add(self.fingerprint(PyList_GET_ITEM(members, i))?);
+            }
+            Ok(digest.feed(size as u64).feed(entries.0).feed(entries.1))
+        }
+    }
+
+    /// Instance dict and slot values by name, in any order, like a dict.
+    unsafe fn attributes(&mut self, digest: Digest, object: *mut PyObject) -> Result<Digest, ()> {
+        unsafe {
+            let names = crate::diff::attribute_names(object);
+            if names.is_null() {
+                return Err(());
+            }
+            let result = self.named(digest, object, names);
+            names.decref();
+            result
+        }
+    }
+
+    unsafe fn named(
+        &mut self,
+        digest: Digest,
+        object: *mut PyObject,
+        names: *mut PyObject,
+    ) -> Result<Digest, ()> {
+        unsafe {
+            let mut count: u64 = 0;
+            let mut entries = Unordered(0, 0);
+            for i in 0..PyList_GET_SIZE(names) {
+                let name = PyList_GET_ITEM(names, i);
+                let mut value: *mut PyObject = ptr::null_mut();
+                if object.get_optional_attr(name, &mut value) < 0 {
+                    return Err(());
+                }
+                if value.is_null() {
+                    continue;
+                }
+                let key = self.fingerprint(name);
+                let result = key.and_then(|key| Ok((key, self.fingerprint(value)?)));
+                value.decref();
+                let (key, value) = result?;
+                entries = entries.add(Digest::SEED.feed_digest(key).feed_digest(value));
+                count += 1;
+            }
+            Ok(digest.feed(count).feed(entries.0).feed(entries.1))
+        }
+    }
+}
+
+/// `hash()`, the contents of a bytes or bytearray, or nothing past the type
+/// for an unhashable value.
+unsafe fn leaf(digest: Digest, object: *mut PyObject) -> Result<Digest, ()> {
+    unsafe {
+        if PyBytes_Check(object) != 0 || PyByteArray_Check(object) != 0 {
+            let (data, size) = if PyBytes_Check(object) != 0 {
+                (PyBytes_AsString(object), PyBytes_Size(object))
+            } else {
+                (PyByteArray_AsString(object), PyByteArray_Size(object))
+            };
+            let bytes = std::slice::from_raw_parts(data.cast::<u8>(), size as usize);
+            return Ok(digest.feed_bytes(bytes));
+        }
+        let hash = PyObject_Hash(object);
+        if hash != -1 {
+            return Ok(digest.feed(hash as u64));
+        }
+        if PyErr_ExceptionMatches(PyExc_TypeError) == 0 {
+            return Err(());
+        }
+        PyErr_Clear();
+        Ok(digest.feed(UNHASHABLE))
+    }
+}
+
+/// The fingerprint of `object` as a non-negative int below 2**128.
+pub unsafe fn fingerprint(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let mut fingerprinter = Fingerprinter {
+            positions: HashMap::new(),
+            keepalive: Vec::new(),
+        };
+        let Ok(digest) = fingerprinter.fingerprint(object) else {
+            return ptr::null_mut();
+        };
+        drop(fingerprinter);
+
+        let high = PyLong_FromUnsignedLongLong(digest.1);
+        let shift = PyLong_FromLong(64);
+        let low = PyLong_FromUnsignedLongLong(digest.0);
+        let shifted = if high.is_null() || shift.is_null() {
+            ptr::null_mut()
+        } else {
+            PyNumber_Lshift(high, shift)
+        };
+        let result = if shifted.is_null() || low.is_null() {
+            ptr::null_mut()
+        } else {
+            PyNumber_Or(shifted, low)
+        };
+        high.decref_nullable();
+        shift.decref_nullable();
+        low.decref_nullable();
+        shifted.decref_nullable();
+        result
+    }
+}
@@ -0,0 +1,124 @@
+//! `config.apply(compat_warnings=True)`: a warning for copy hooks stored on
+//! an instance, which copying ignores.
+//!
+//! Like stdlib, copium reduces an instance through `__reduce_ex__`, and
+//! `object.__reduce_ex__` only calls `__reduce__` when the class overrides
+//! it; `__getnewargs_ex__`, `__getnewargs__` and `__setstate__` are looked up
+//! on the type too. A callable under one of those names in an instance's
+//! `__dict__` therefore does nothing unless the class defines the same name.
+//! With the option on, the first such instance of each type that is reduced
+//! warns about it. Copies with the option off never get there.
+
+use pyo3_ffi::*;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::ffi_ext::PyUnicode_FromFormat;
+use crate::py_str;
+use crate::types::PyObjectPtr;
+
+/// Types already warned about, held so each is warned about once.
+static WARNED_TYPES: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+unsafe fn warned_types() -> *mut PyObject {
+    unsafe {
+        let warned = WARNED_TYPES.load(Ordering::Acquire);
+        if !warned.is_null() {
+            return warned;
+        }
+        let warned = PySet_New(ptr::null_mut());
+        if warned.is_null() {
+            return ptr::null_mut();
+        }
+        match WARNED_TYPES.compare_exchange(
+            ptr::null_mut(),
+            warned,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => warned,
+            Err(existing) => {
+                warned.decref();
+                existing
+            }
+        }
+    }
+}
+
+/// Warns, once per type, when `object` carries a hook in its `__dict__` that
+/// reducing it ignores. Returns -1 with an exception set if the warning was
+/// turned into one.
+#[cold]
+pub unsafe fn warn_ignored(object: *mut PyObject, tp: *mut PyTypeObject) -> i32 {
+    unsafe {
+        if (*tp).tp_dictoffset == 0 {
+            return 0;
+        }
+        let dict = PyObject_GenericGetDict(object, ptr::null_mut());
+        if dict.is_null() {
+            return -1;
+        }
+        let status = warn_for_dict(dict, tp);
+        dict.decref();
+        status
+    }
+}
+
+unsafe fn warn_for_dict(dict: *mut PyObject, tp: *mut PyTypeObject) -> i32 {
+    unsafe {
+        let hooks = [
+            py_str!("__reduce__"),
+            py_str!("__getnewargs_ex__"),
+            py_str!("__getnewargs__"),
+            py_str!("__setstate__"),
+        ];
+        for name in hooks {
+            let value = PyDict_GetItemWithError(dict, name);
+            if value.is_null() {
+                if !PyErr_Occurred().is_null() {
+                    return -1;
+                }
+                continue;
+            }
+            if PyCallable_Check(value) == 0 {
+                continue;
+            }
+            // Finding what object finds, or nothing, means the class doesn't define it.
+            let object_type = ptr::addr_of_mut!(PyBaseObject_Type);
+            match crate::subclasses::resolves_like(tp, object_type, name) {
+                Ok(false) => continue,
+                Ok(true) => {}
+                Err(()) => return -1,
+            }
+            return warn_once(name, tp);
+        }
+        0
+    }
+}
+
+unsafe fn warn_once(name: *mut PyObject, tp: *mut PyTypeObject) -> i32 {
+    unsafe {
+        let warned = warned_types();
+        if warned.is_null() {
+            return -1;
+        }
+        match PySet_Contains(warned, tp as *mut PyObject) {
+            0 => {}
+            found => return found.min(0),
+        }
+        if PySet_Add(warned, tp as *mut PyObject) < 0 {
+            return -1;
+        }
+        let message = PyUnicode_FromFormat(
+            crate::cstr!("copium: ignoring instance-level %U on %s; define it on the class"),
+            name,
+            (*tp).tp_name,
+        );
+        if message.is_null() {
+            return -1;
+        }
+        let status = PyErr_WarnEx(PyExc_UserWarning, PyUnicode_AsUTF8(message), 1);
+        message.decref();
+        status
+    }
+}
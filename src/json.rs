@@ -0,0 +1,171 @@
+//! `copium.extra.deepcopy_json`: deepcopy restricted to what `json.loads`
+//! produces — exact dict, list, str, int, float, bool and None.
+//!
+//! Inside that universe no user code runs and nothing needs reducing, so the
+//! traversal is a pointer switch on the type over containers built by shallow
+//! copies. Anything else is a TypeError naming its type.
+
+use pyo3_ffi::*;
+use std::hint::{likely, unlikely};
+use std::ptr;
+
+use crate::ffi_ext::_PyNone_Type;
+use crate::memo::{Memo, TreeMemo};
+use crate::types::{PyObjectPtr, PySeqPtr};
+
+#[inline(always)]
+fn is_json_scalar(tp: *mut PyTypeObject) -> bool {
+    (tp == ptr::addr_of_mut!(PyUnicode_Type))
+        | (tp == ptr::addr_of_mut!(PyLong_Type))
+        | (tp == ptr::addr_of_mut!(PyFloat_Type))
+        | (tp == ptr::addr_of_mut!(PyBool_Type))
+        | (tp == ptr::addr_of_mut!(_PyNone_Type))
+}
+
+#[cold]
+unsafe fn raise_not_json(object: *mut PyObject, what: *const std::ffi::c_char) {
+    unsafe {
+        PyErr_Format(
+            PyExc_TypeError,
+            crate::cstr!("deepcopy_json() can't copy %s of type %.200s"),
+            what,
+            (*object.class()).tp_name,
+        );
+    }
+}
+
+unsafe fn copy_value<M: Memo>(object: *mut PyObject, memo: &mut M) -> *mut PyObject {
+    unsafe {
+        let tp = object.class();
+        if likely(is_json_scalar(tp)) {
+            return object.newref();
+        }
+
+        let is_list = tp == ptr::addr_of_mut!(PyList_Type);
+        if unlikely(!is_list && tp != ptr::addr_of_mut!(PyDict_Type)) {
+            raise_not_json(object, crate::cstr!("object"));
+            return ptr::null_mut();
+        }
+
+        let (probe, found) = memo.recall(object);
+        if !found.is_null() {
+            return found;
+        }
+        if M::RECALL_CAN_ERROR && !PyErr_Occurred().is_null() {
+            return ptr::null_mut();
+        }
+
+        if unlikely(crate::recursion::enter() < 0) {
+            return ptr::null_mut();
+        }
+        let copied = if is_list {
+            copy_list(object as *mut PyListObject, memo, probe)
+        } else {
+            copy_dict(object as *mut PyDictObject, memo, probe)
+        };
+        crate::recursion::leave();
+        copied
+    }
+}
+
+/// Scalars are shared between original and copy, so a shallow copy does
+/// most of the work; only nested containers are then replaced in place.
+unsafe fn copy_list<M: Memo>(
+    list: *mut PyListObject,
+    memo: &mut M,
+    probe: M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let copied = PyList_GetSlice(list as _, 0, PY_SSIZE_T_MAX) as *mut PyListObject;
+        if copied.is_null() {
+            return ptr::null_mut();
+        }
+        if memo.memoize(list as _, copied as _, &probe) < 0 {
+            copied.decref();
+            return ptr::null_mut();
+        }
+
+        for i in 0..copied.length() {
+            let item = copied.get_borrowed_unchecked(i);
+            if likely(is_json_scalar(item.class())) {
+                continue;
+            }
+            let item_copy = copy_value(item, memo);
+            if unlikely(item_copy.is_null()) {
+                memo.forget(list as _, &probe);
+                copied.decref();
+                return ptr::null_mut();
+            }
+            copied.set_slot_steal_unchecked(i, item_copy);
+            item.decref();
+        }
+        copied as _
+    }
+}
+
+unsafe fn copy_dict<M: Memo>(
+    dict: *mut PyDictObject,
+    memo: &mut M,
+    probe: M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let copied = PyDict_Copy(dict as _);
+        if copied.is_null() {
+            return ptr::null_mut();
+        }
+        if memo.memoize(dict as _, copied, &probe) < 0 {
+            copied.decref();
+            return ptr::null_mut();
+        }
+
+        // Only values are replaced, so the copy keeps its size and the
+        // iteration stays valid.
+        let mut pos: Py_ssize_t = 0;
+        let mut key: *mut PyObject = ptr::null_mut();
+        let mut value: *mut PyObject = ptr::null_mut();
+        while PyDict_Next(copied, &mut pos, &mut key, &mut value) != 0 {
+            if unlikely(!is_json_scalar(key.class())) {
+                raise_not_json(key, crate::cstr!("dict key"));
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return ptr::null_mut();
+            }
+            if likely(is_json_scalar(value.class())) {
+                continue;
+            }
+
+            let value_copy = copy_value(value, memo);
+            if unlikely(value_copy.is_null()) {
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return ptr::null_mut();
+            }
+            let status = PyDict_SetItem(copied, key, value_copy);
+            value_copy.decref();
+            if unlikely(status < 0) {
+                memo.forget(dict as _, &probe);
+                copied.decref();
+                return ptr::null_mut();
+            }
+        }
+        copied
+    }
+}
+
+/// Copies `object`, sharing copies of containers reachable more than once
+/// only when `preserve_sharing` is set.
+pub unsafe fn deepcopy_json(object: *mut PyObject, preserve_sharing: bool) -> *mut PyObject {
+    unsafe {
+        if !preserve_sharing {
+            return copy_value(object, &mut TreeMemo::new());
+        }
+
+        let (memo, is_tss) = crate::memo::get_memo();
+        if memo.is_null() {
+            return ptr::null_mut();
+        }
+        let copied = copy_value(object, &mut *memo);
+        crate::memo::cleanup_memo(memo, is_tss);
+        copied
+    }
+}
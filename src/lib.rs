@@ -5,41 +5,125 @@ use core::ffi::{c_char, c_void};
 use pyo3_ffi::*;
 use std::hint::{likely, unlikely};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[macro_use]
 mod ffi_ext;
 mod about;
+#[cfg(feature = "rust-api")]
+pub mod api;
+mod buffers;
+mod budget;
 #[allow(dead_code)]
 mod cache;
 mod compat;
 mod config;
+mod cooperative;
 mod copy;
 mod critical_section;
+mod dataclasses;
+mod deadline;
+mod debug;
 mod deepcopy;
 mod dict_iter;
+mod diff;
 mod extra;
 mod fallback;
+mod fingerprint;
+mod instance_hooks;
+mod json;
 mod memo;
+mod negative_cache;
+mod offload;
 mod patch;
+mod progress;
+mod provenance;
 mod recursion;
 mod reduce;
 mod state;
+mod structure;
+mod subclasses;
 mod types;
 
 use crate::memo::PyMemoObject;
 use crate::types::{py_dict_new, PyObjectPtr, PyTypeInfo, PyTypeObjectPtr};
-use memo::{AnyMemo, DictMemo};
+use memo::{
+    AnyMemo, CallbackMemo, DictMemo, InnerMemo, InternMemo, InternMode, Memo, TreeMemo,
+};
 use state::{MemoMode, STATE};
 // ══════════════════════════════════════════════════════════════
-//  copy(obj, /) — METH_O
+//  copy(x, /, *, replace=None) — METH_FASTCALL | METH_KEYWORDS
 // ══════════════════════════════════════════════════════════════
 
-unsafe extern "C" fn py_copy(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
-    unsafe { copy::copy(obj).into_raw() }
+unsafe extern "C" fn py_copy(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_Size(kwnames)
+        };
+        if unlikely(nargs != 1) {
+            PyErr_Format(
+                PyExc_TypeError,
+                cstr!("copy() takes exactly 1 positional argument (%zd given)"),
+                nargs,
+            );
+            return ptr::null_mut();
+        }
+        if likely(kwcount == 0) {
+            return copy::copy(*args).into_raw();
+        }
+
+        let mut replace_arg: *mut PyObject = ptr::null_mut();
+        for i in 0..kwcount {
+            let name = PyTuple_GetItem(kwnames, i);
+            let val = *args.offset(nargs + i);
+            if PyUnicode_CompareWithASCIIString(name, cstr!("replace")) == 0 {
+                if val != Py_None() {
+                    replace_arg = val;
+                }
+            } else {
+                PyErr_Format(
+                    PyExc_TypeError,
+                    cstr!("copy() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+        }
+
+        if replace_arg.is_null() {
+            return copy::copy(*args).into_raw();
+        }
+        copy::copy_replacing(*args, replace_arg).into_raw()
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
-//  deepcopy(x, memo=None, /) — METH_FASTCALL | METH_KEYWORDS
+//  _panic(message, /) — only registered when COPIUM_TEST_HOOKS is set
+// ══════════════════════════════════════════════════════════════
+
+unsafe extern "C" fn py_test_panic(_self: *mut PyObject, message: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let mut size: Py_ssize_t = 0;
+        let data = PyUnicode_AsUTF8AndSize(message, &mut size);
+        if data.is_null() {
+            return ptr::null_mut();
+        }
+        let bytes = std::slice::from_raw_parts(data.cast::<u8>(), size as usize);
+        panic!("{}", String::from_utf8_lossy(bytes))
+    })
+}
+
+// ══════════════════════════════════════════════════════════════
+//  deepcopy(x, memo=None, *, replace=None, assume_tree=False, intern="none", timeout=None,
+//           max_leaf_bytes=None, max_total_bytes=None, progress=None)
+//  — METH_FASTCALL | METH_KEYWORDS
 // ══════════════════════════════════════════════════════════════
 
 pub(crate) unsafe extern "C" fn py_deepcopy(
@@ -48,9 +132,16 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
     nargs: Py_ssize_t,
     kwnames: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let mut obj: *mut PyObject = ptr::null_mut();
         let mut memo_arg: *mut PyObject = Py_None();
+        let mut replace_arg: *mut PyObject = ptr::null_mut();
+        let mut assume_tree = false;
+        let mut intern = InternMode::None;
+        let mut timeout: Option<f64> = None;
+        let mut max_leaf_bytes: Option<usize> = None;
+        let mut max_total_bytes: Option<usize> = None;
+        let mut progress_callback: Option<*mut PyObject> = None;
 
         // ── Fast path: no keyword arguments ─────────────────
         let kwcount = if kwnames.is_null() {
@@ -121,6 +212,41 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
                     }
                     memo_arg = val;
                     seen_memo_kw = true;
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("replace")) == 0 {
+                    if val != Py_None() {
+                        replace_arg = val;
+                    }
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("assume_tree")) == 0 {
+                    let truth = PyObject_IsTrue(val);
+                    if truth < 0 {
+                        return ptr::null_mut();
+                    }
+                    assume_tree = truth != 0;
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("intern")) == 0 {
+                    match parse_intern_mode(val) {
+                        Some(mode) => intern = mode,
+                        None => return ptr::null_mut(),
+                    }
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("timeout")) == 0 {
+                    match deadline::parse_timeout(val) {
+                        Ok(seconds) => timeout = seconds,
+                        Err(()) => return ptr::null_mut(),
+                    }
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("max_leaf_bytes")) == 0 {
+                    match budget::parse_limit(val, cstr!("max_leaf_bytes")) {
+                        Ok(limit) => max_leaf_bytes = limit,
+                        Err(()) => return ptr::null_mut(),
+                    }
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("max_total_bytes")) == 0 {
+                    match budget::parse_limit(val, cstr!("max_total_bytes")) {
+                        Ok(limit) => max_total_bytes = limit,
+                        Err(()) => return ptr::null_mut(),
+                    }
+                } else if PyUnicode_CompareWithASCIIString(name, cstr!("progress")) == 0 {
+                    match progress::parse_callback(val) {
+                        Ok(callback) => progress_callback = callback,
+                        Err(()) => return ptr::null_mut(),
+                    }
                 } else {
                     PyErr_Format(
                         PyExc_TypeError,
@@ -140,6 +266,130 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
             }
         }
 
+        let copy = || {
+            if let Some(budget) = budget::for_call(max_leaf_bytes, max_total_bytes) {
+                budget::with_budget(budget, || match timeout {
+                    Some(seconds) => deepcopy_with_timeout(
+                        obj,
+                        memo_arg,
+                        replace_arg,
+                        assume_tree,
+                        intern,
+                        seconds,
+                    ),
+                    None => dispatch(obj, memo_arg, replace_arg, assume_tree, intern),
+                })
+            } else if let Some(seconds) = timeout {
+                deepcopy_with_timeout(obj, memo_arg, replace_arg, assume_tree, intern, seconds)
+            } else {
+                dispatch(obj, memo_arg, replace_arg, assume_tree, intern)
+            }
+        };
+        let result = match progress_callback {
+            Some(callback) => progress::with_progress(callback, None, copy),
+            None => copy(),
+        };
+        if unlikely(STATE.mark_copies) && memo_arg == Py_None() && !result.is_null() {
+            return provenance::mark(obj, result);
+        }
+        result
+    })
+}
+
+/// `timeout=`: the regular call under a time budget. If it runs out, what the
+/// call memoized in a caller's memo or dict memo, hooks' writes included, is
+/// undone; the memo copium keeps for itself is reset as after any other
+/// failure.
+#[cold]
+#[inline(never)]
+unsafe fn deepcopy_with_timeout(
+    obj: *mut PyObject,
+    memo_arg: *mut PyObject,
+    replace_arg: *mut PyObject,
+    assume_tree: bool,
+    intern: InternMode,
+    seconds: f64,
+) -> *mut PyObject {
+    unsafe {
+        let checkpoint = PyMemoObject::cast_exact(memo_arg, memo_arg.class()).map(|memo| {
+            let logging = std::mem::replace(&mut (*memo).log_all_inserts, true);
+            (memo, (*memo).checkpoint(), logging)
+        });
+        // A dict memo has no undo log: it is put back from a copy instead.
+        let dict_before = if PyDict_CheckExact(memo_arg) != 0 {
+            let before = PyDict_Copy(memo_arg);
+            if before.is_null() {
+                return ptr::null_mut();
+            }
+            before
+        } else {
+            ptr::null_mut()
+        };
+        let result = deadline::with_timeout(seconds, || {
+            dispatch(obj, memo_arg, replace_arg, assume_tree, intern)
+        });
+        let timed_out = result.is_null() && deadline::timed_out();
+        if let Some((memo, checkpoint, logging)) = checkpoint {
+            (*memo).log_all_inserts = logging;
+            if timed_out {
+                (*memo).rollback(checkpoint);
+            }
+        }
+        if !dict_before.is_null() {
+            if timed_out {
+                restore_dict(memo_arg, dict_before);
+            }
+            dict_before.decref();
+        }
+        result
+    }
+}
+
+/// Puts `dict` back to the entries of `before`, keeping the pending error.
+#[cold]
+unsafe fn restore_dict(dict: *mut PyObject, before: *mut PyObject) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+        PyDict_Clear(dict);
+        if PyDict_Update(dict, before) < 0 {
+            PyErr_Clear();
+        }
+
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
+    }
+}
+
+#[inline(always)]
+unsafe fn dispatch(
+    obj: *mut PyObject,
+    memo_arg: *mut PyObject,
+    replace_arg: *mut PyObject,
+    assume_tree: bool,
+    intern: InternMode,
+) -> *mut PyObject {
+    unsafe {
+        if unlikely(intern != InternMode::None) {
+            if assume_tree {
+                PyErr_SetString(
+                    PyExc_TypeError,
+                    cstr!("deepcopy() can't combine intern with assume_tree=True"),
+                );
+                return ptr::null_mut();
+            }
+            return deepcopy_interned(obj, memo_arg, replace_arg, intern);
+        }
+
+        if unlikely(assume_tree) {
+            return deepcopy_tree(obj, memo_arg, replace_arg);
+        }
+
         // ── Dispatch based on memo type ─────────────────────
         if likely(memo_arg == Py_None()) {
             let tp = obj.class();
@@ -152,7 +402,7 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
                 if unlikely(pm.is_null()) {
                     return ptr::null_mut();
                 }
-                let result = deepcopy::deepcopy(obj, &mut *pm);
+                let result = deepcopy_seeded(obj, &mut *pm, replace_arg);
                 memo::cleanup_memo(pm, is_tss);
                 return result.into_raw();
             }
@@ -163,7 +413,7 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
                 return ptr::null_mut();
             }
             let mut m = DictMemo::new(dict as _);
-            let result = deepcopy::deepcopy(obj, &mut m);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
             drop(m);
             dict.decref();
             return result.into_raw();
@@ -171,24 +421,211 @@ pub(crate) unsafe extern "C" fn py_deepcopy(
 
         let memo_type = memo_arg.class();
 
+        // A `__deepcopy__` passing on the memo it was given, through
+        // copium.deepcopy or a patched copy.deepcopy, lands here and keeps
+        // filling the same native memo rather than wrapping it as a user's.
         if let Some(memo) = PyMemoObject::cast_exact(memo_arg, memo_type) {
-            let result = deepcopy::deepcopy(obj, &mut *memo);
+            let was_attached = (*memo).attach();
+            let result = deepcopy_seeded(obj, &mut *memo, replace_arg);
+            (*memo).attached = was_attached;
             return result.into_raw();
         }
 
         if let Some(memo) = PyDictObject::cast_exact(memo_arg, memo_type) {
             let mut m = DictMemo::new(memo);
-            let result = deepcopy::deepcopy(obj, &mut m);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
+            return result.into_raw();
+        }
+
+        if unlikely(memo::is_custom_memo(memo_arg)) {
+            let mut m = CallbackMemo::new(memo_arg);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
             return result.into_raw();
         }
 
         // Any other mapping-like object
         let mut m = AnyMemo::new(memo_arg);
-        let result = deepcopy::deepcopy(obj, &mut m);
+        let result = deepcopy_seeded(obj, &mut m, replace_arg);
         result.into_raw()
     }
 }
 
+#[inline(always)]
+unsafe fn deepcopy_seeded<M: Memo>(
+    obj: *mut PyObject,
+    memo: &mut M,
+    replace: *mut PyObject,
+) -> deepcopy::PyResult {
+    unsafe {
+        if unlikely(!replace.is_null()) && memo::seed_replacements(memo, replace) < 0 {
+            return deepcopy::PyResult::error();
+        }
+        deepcopy::deepcopy(obj, memo)
+    }
+}
+
+// ══════════════════════════════════════════════════════════════
+//  deepcopy_fragment(x, memo, /) — METH_FASTCALL
+// ══════════════════════════════════════════════════════════════
+
+/// For use inside `__deepcopy__`: the memo copium passed in continues the
+/// running traversal directly; any other memo behaves like `deepcopy(x, memo)`.
+unsafe extern "C" fn py_deepcopy_fragment(
+    self_: *mut PyObject,
+    args: *mut *mut PyObject,
+    nargs: Py_ssize_t,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        if unlikely(nargs != 2) {
+            PyErr_Format(
+                PyExc_TypeError,
+                cstr!("deepcopy_fragment() takes exactly 2 positional arguments (%zd given)"),
+                nargs,
+            );
+            return ptr::null_mut();
+        }
+        let obj = *args;
+        let memo_arg = *args.add(1);
+        if let Some(memo) = PyMemoObject::cast_exact(memo_arg, memo_arg.class()) {
+            return deepcopy::deepcopy(obj, &mut *memo).into_raw();
+        }
+        py_deepcopy(self_, args, nargs, ptr::null_mut())
+    })
+}
+
+/// `assume_tree=True`: no memo at all. A cycle (or a graph too deep to walk)
+/// ends in RecursionError, reported as `CopyError` since the input broke the
+/// promise rather than the copy.
+#[inline(never)]
+unsafe fn deepcopy_tree(
+    obj: *mut PyObject,
+    memo_arg: *mut PyObject,
+    replace_arg: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        if unlikely(memo_arg != Py_None()) {
+            PyErr_SetString(
+                PyExc_TypeError,
+                cstr!("deepcopy() can't combine memo with assume_tree=True"),
+            );
+            return ptr::null_mut();
+        }
+        if unlikely(!replace_arg.is_null()) {
+            PyErr_SetString(
+                PyExc_TypeError,
+                cstr!("deepcopy() can't combine replace with assume_tree=True"),
+            );
+            return ptr::null_mut();
+        }
+        if obj.class().is_atomic_immutable() {
+            return obj.newref();
+        }
+
+        let mut m = TreeMemo::new();
+        let result = deepcopy::deepcopy(obj, &mut m).into_raw();
+        drop(m);
+        if result.is_null() && PyErr_ExceptionMatches(PyExc_RecursionError) != 0 {
+            reduce::reraise_as_copy_error(cstr!("cycle or size limit exceeded under assume_tree"));
+        }
+        result
+    }
+}
+
+/// `intern=`: "none" (or None), "str" or "str+tuple".
+#[cold]
+unsafe fn parse_intern_mode(value: *mut PyObject) -> Option<InternMode> {
+    unsafe {
+        if value == Py_None() {
+            return Some(InternMode::None);
+        }
+        if PyUnicode_Check(value) != 0 {
+            for (name, mode) in [
+                (cstr!("none"), InternMode::None),
+                (cstr!("str"), InternMode::Str),
+                (cstr!("str+tuple"), InternMode::StrTuple),
+            ] {
+                if PyUnicode_CompareWithASCIIString(value, name) == 0 {
+                    return Some(mode);
+                }
+            }
+        }
+        PyErr_Format(
+            PyExc_ValueError,
+            cstr!("intern must be 'none', 'str' or 'str+tuple', got %R"),
+            value,
+        );
+        None
+    }
+}
+
+/// `deepcopy(..., intern=...)`: the same dispatch on `memo` as a plain call,
+/// with the chosen memo wrapped so that leaves are canonicalized.
+#[inline(never)]
+unsafe fn deepcopy_interned(
+    obj: *mut PyObject,
+    memo_arg: *mut PyObject,
+    replace_arg: *mut PyObject,
+    mode: InternMode,
+) -> *mut PyObject {
+    unsafe {
+        let cap = STATE.intern_cap;
+
+        if memo_arg == Py_None() && STATE.memo_mode == MemoMode::Native {
+            let (pm, is_tss) = memo::get_memo();
+            if unlikely(pm.is_null()) {
+                return ptr::null_mut();
+            }
+            let mut m = InternMemo::new(InnerMemo::Native(&mut *pm), mode, cap);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
+            drop(m);
+            memo::cleanup_memo(pm, is_tss);
+            return result.into_raw();
+        }
+
+        if memo_arg == Py_None() {
+            // memo="dict" config
+            let dict = py_dict_new(0);
+            if dict.is_null() {
+                return ptr::null_mut();
+            }
+            let mut inner = DictMemo::new(dict as _);
+            let mut m = InternMemo::new(InnerMemo::Dict(&mut inner), mode, cap);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
+            drop(m);
+            drop(inner);
+            dict.decref();
+            return result.into_raw();
+        }
+
+        let memo_type = memo_arg.class();
+
+        if let Some(memo) = PyMemoObject::cast_exact(memo_arg, memo_type) {
+            let was_attached = (*memo).attach();
+            let mut m = InternMemo::new(InnerMemo::Native(&mut *memo), mode, cap);
+            let result = deepcopy_seeded(obj, &mut m, replace_arg);
+            drop(m);
+            (*memo).attached = was_attached;
+            return result.into_raw();
+        }
+
+        if let Some(memo) = PyDictObject::cast_exact(memo_arg, memo_type) {
+            let mut inner = DictMemo::new(memo);
+            let mut m = InternMemo::new(InnerMemo::Dict(&mut inner), mode, cap);
+            return deepcopy_seeded(obj, &mut m, replace_arg).into_raw();
+        }
+
+        if memo::is_custom_memo(memo_arg) {
+            let mut inner = CallbackMemo::new(memo_arg);
+            let mut m = InternMemo::new(InnerMemo::Callback(&mut inner), mode, cap);
+            return deepcopy_seeded(obj, &mut m, replace_arg).into_raw();
+        }
+
+        let mut inner = AnyMemo::new(memo_arg);
+        let mut m = InternMemo::new(InnerMemo::Any(&mut inner), mode, cap);
+        deepcopy_seeded(obj, &mut m, replace_arg).into_raw()
+    }
+}
+
 // ══════════════════════════════════════════════════════════════
 //  replace(obj, /, **changes) — 3.13+ only
 // ══════════════════════════════════════════════════════════════
@@ -200,7 +637,7 @@ unsafe extern "C" fn py_replace(
     nargs: Py_ssize_t,
     kwnames: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         if nargs == 0 {
             PyErr_SetString(
                 PyExc_TypeError,
@@ -222,6 +659,9 @@ unsafe extern "C" fn py_replace(
         let class_object = type_pointer as *mut PyObject;
         let func = PyObject_GetAttrString(class_object, cstr!("__replace__"));
         if func.is_null() {
+            if PyErr_ExceptionMatches(PyExc_AttributeError) == 0 {
+                return ptr::null_mut();
+            }
             PyErr_Clear();
             PyErr_Format(
                 PyExc_TypeError,
@@ -263,14 +703,14 @@ unsafe extern "C" fn py_replace(
         posargs.decref();
         kwargs.decref_nullable();
         out
-    }
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
 //  Module definition
 // ══════════════════════════════════════════════════════════════
 
-static mut MAIN_METHODS: [PyMethodDef; 4] = [PyMethodDef::zeroed(); 4];
+static mut MAIN_METHODS: [PyMethodDef; 6] = [PyMethodDef::zeroed(); 6];
 
 unsafe fn init_methods() {
     unsafe {
@@ -279,10 +719,16 @@ unsafe fn init_methods() {
         MAIN_METHODS[i] = PyMethodDef {
             ml_name: cstr!("copy"),
             ml_meth: PyMethodDefPointer {
-                PyCFunction: py_copy,
+                PyCFunctionFastWithKeywords: py_copy,
             },
-            ml_flags: METH_O,
-            ml_doc: cstr!("copy(obj, /)\n--\n\nReturn a shallow copy of obj."),
+            ml_flags: METH_FASTCALL | METH_KEYWORDS,
+            ml_doc: cstr!(
+                "copy(x, /, *, replace=None)\n--\n\nReturn a shallow copy of x.\n\n\
+                 Same as copy.copy(): honours __copy__, copyreg and the reduce protocol.\n\n\
+                 replace: originals (matched by identity) to substitute among the copy's\n\
+                 direct entries, as a mapping or as (original, replacement) pairs: list\n\
+                 items, dict values, set members, or the attributes in __dict__."
+            ),
         };
         i += 1;
 
@@ -292,7 +738,44 @@ unsafe fn init_methods() {
                 PyCFunctionFastWithKeywords: py_deepcopy,
             },
             ml_flags: METH_FASTCALL | METH_KEYWORDS,
-            ml_doc: cstr!("deepcopy(x, memo=None, /)\n--\n\nReturn a deep copy of obj."),
+            ml_doc: cstr!(
+                "deepcopy(x, memo=None, *, replace=None, assume_tree=False, intern='none', timeout=None,\n\
+                 max_leaf_bytes=None, max_total_bytes=None, progress=None)\n\
+                 --\n\n\
+                 Return a deep copy of x.\n\n\
+                 Same as copy.deepcopy(), including __deepcopy__, copyreg and the reduce\n\
+                 protocol. __deepcopy__ receives a copium.memo unless memo is a dict.\n\n\
+                 memo: treat as opaque, like stdlib's.\n\
+                 replace: originals (matched by identity) to substitute wherever they\n\
+                 appear, as a mapping or as (original, replacement) pairs.\n\
+                 assume_tree: promise that no object is reachable twice, so no memo is\n\
+                 kept. Can't be combined with memo or replace.\n\
+                 intern: 'str' makes equal strings in the copy one object, 'str+tuple'\n\
+                 also equal tuples of literals. Can't be combined with assume_tree.\n\
+                 timeout: seconds the copy may take, checked every few dozen containers.\n\
+                 max_leaf_bytes, max_total_bytes: most bytes one bytearray, bytes subclass\n\
+                 or array.array, and all of them together, may take in the copy; 0 for no\n\
+                 limit, None for copium.config's.\n\
+                 progress: called as progress(objects_copied, None) every\n\
+                 config progress_every containers; an exception from it stops the copy.\n\n\
+                 Raises copium.CopyError (a copy.Error) for objects that can't be copied,\n\
+                 for cycles under assume_tree and past a byte limit; copium.TimeoutError\n\
+                 (also a copy.Error) when timeout runs out."
+            ),
+        };
+        i += 1;
+
+        MAIN_METHODS[i] = PyMethodDef {
+            ml_name: cstr!("deepcopy_fragment"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionFast: py_deepcopy_fragment,
+            },
+            ml_flags: METH_FASTCALL,
+            ml_doc: cstr!(
+                "deepcopy_fragment(x, memo, /)\n--\n\n\
+                 Deep copy x as part of the copy that passed memo to __deepcopy__.\n\n\
+                 Equivalent to deepcopy(x, memo), without the argument parsing."
+            ),
         };
         i += 1;
 
@@ -304,7 +787,23 @@ unsafe fn init_methods() {
                     PyCFunctionFastWithKeywords: py_replace,
                 },
                 ml_flags: METH_FASTCALL | METH_KEYWORDS,
-                ml_doc: cstr!("replace(obj, /, **changes)\n--\n\nReplace fields on a copy."),
+                ml_doc: cstr!(
+                    "replace(obj, /, **changes)\n--\n\n\
+                     Return a copy of obj with fields replaced from changes.\n\n\
+                     Same as copy.replace(): calls obj.__replace__(**changes)."
+                ),
+            };
+            i += 1;
+        }
+
+        if std::env::var_os("COPIUM_TEST_HOOKS").is_some_and(|value| !value.is_empty()) {
+            MAIN_METHODS[i] = PyMethodDef {
+                ml_name: cstr!("_panic"),
+                ml_meth: PyMethodDefPointer {
+                    PyCFunction: py_test_panic,
+                },
+                ml_flags: METH_O,
+                ml_doc: cstr!("_panic(message, /)\n--\n\nPanic inside Rust (test hook)."),
             };
             i += 1;
         }
@@ -313,25 +812,41 @@ unsafe fn init_methods() {
     }
 }
 
-unsafe extern "C" fn orcopium_exec(module: *mut PyObject) -> i32 {
+/// Set once the caches, config state and types deepcopy relies on are ready.
+static RUNTIME_READY: AtomicBool = AtomicBool::new(false);
+
+/// Everything deepcopy needs that doesn't belong to a module object. Module
+/// exec runs it on every import, which also reloads config from the
+/// environment; `api::init` runs it only when nothing has yet.
+unsafe fn init_runtime() -> i32 {
     unsafe {
-        if cache::init() < 0 {
+        if cache::init() < 0
+            || state::init() < 0
+            || dict_iter::dict_iter_module_init() < 0
+            || memo::memo_ready_type() < 0
+            || reduce::init_copy_error() < 0
+            || deadline::init_timeout_error() < 0
+        {
             return -1;
         }
+        RUNTIME_READY.store(true, Ordering::Release);
+        0
+    }
+}
 
-        if state::init() < 0 {
+unsafe extern "C" fn orcopium_exec(module: *mut PyObject) -> i32 {
+    unsafe {
+        if init_runtime() < 0 {
             return -1;
         }
 
-        if dict_iter::dict_iter_module_init() < 0 {
+        if PyModule_AddObject(module, cstr!("Error"), py_obj!("copy.Error").newref()) < 0 {
             return -1;
         }
-
-        if memo::memo_ready_type() < 0 {
+        if reduce::add_copy_error(module) < 0 {
             return -1;
         }
-
-        if PyModule_AddObject(module, cstr!("Error"), py_obj!("copy.Error").newref()) < 0 {
+        if deadline::add_timeout_error(module) < 0 {
             return -1;
         }
 
@@ -349,6 +864,9 @@ unsafe extern "C" fn orcopium_exec(module: *mut PyObject) -> i32 {
         if config::create_module(module) < 0 {
             return -1;
         }
+        if debug::create_module(module) < 0 {
+            return -1;
+        }
 
         let config_module = PyObject_GetAttrString(module, cstr!("config"));
         if config_module.is_null() {
@@ -379,6 +897,9 @@ unsafe extern "C" fn orcopium_exec(module: *mut PyObject) -> i32 {
         if about::create_module(module) < 0 {
             return -1;
         }
+        if about::add_build_info(module) < 0 {
+            return -1;
+        }
 
         0
     }
@@ -388,6 +909,7 @@ unsafe extern "C" fn orcopium_free(_: *mut c_void) {
     unsafe {
         dict_iter::dict_iter_module_cleanup();
         state::cleanup();
+        RUNTIME_READY.store(false, Ordering::Release);
     }
 }
 
@@ -9,6 +9,8 @@ use crate::{py_cache, py_eval, py_str};
 pub struct AnyMemo {
     pub object: *mut PyObject,
     keepalive: *mut PyObject,
+    last_original: *mut PyObject,
+    last_key: *mut PyObject,
 }
 
 impl AnyMemo {
@@ -16,6 +18,24 @@ impl AnyMemo {
         Self {
             object,
             keepalive: ptr::null_mut(),
+            last_original: ptr::null_mut(),
+            last_key: ptr::null_mut(),
+        }
+    }
+
+    /// Borrowed `int` key for `object`, cached for the last pointer seen (see `DictMemo`).
+    unsafe fn key_for(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            if object != self.last_original {
+                let pykey = PyLong_FromVoidPtr(object as *mut c_void);
+                if pykey.is_null() {
+                    return ptr::null_mut();
+                }
+                self.last_key.decref_nullable();
+                self.last_key = pykey;
+                self.last_original = object;
+            }
+            self.last_key
         }
     }
 
@@ -77,7 +97,7 @@ impl Memo for AnyMemo {
     unsafe fn recall(&mut self, object: *mut PyObject) -> ((), *mut PyObject) {
         unsafe {
             let sentinel = py_cache!(py_eval!("object()"));
-            let pykey = PyLong_FromVoidPtr(object as *mut c_void);
+            let pykey = self.key_for(object);
             if pykey.is_null() {
                 return ((), ptr::null_mut());
             }
@@ -89,7 +109,6 @@ impl Memo for AnyMemo {
                 sentinel,
                 ptr::null_mut::<PyObject>(),
             );
-            pykey.decref();
 
             if found.is_null() {
                 return ((), ptr::null_mut());
@@ -105,14 +124,12 @@ impl Memo for AnyMemo {
 
     unsafe fn memoize(&mut self, original: *mut PyObject, copy: *mut PyObject, _probe: &()) -> i32 {
         unsafe {
-            let pykey = PyLong_FromVoidPtr(original as *mut c_void);
+            let pykey = self.key_for(original);
             if pykey.is_null() {
                 return -1;
             }
 
-            let rc = PyObject_SetItem(self.object, pykey, copy);
-            pykey.decref();
-            if rc < 0 {
+            if PyObject_SetItem(self.object, pykey, copy) < 0 {
                 return -1;
             }
 
@@ -120,11 +137,13 @@ impl Memo for AnyMemo {
                 return -1;
             }
 
-            PyList_Append(self.keepalive, original)
+            super::keepalive_append(self.keepalive, original)
         }
     }
 
-    unsafe fn forget(&mut self, _original: *mut PyObject, _probe: &()) {}
+    unsafe fn forget(&mut self, original: *mut PyObject, _probe: &()) {
+        unsafe { super::forget_key(self.object, original) }
+    }
 
     #[inline(always)]
     unsafe fn as_call_arg(&mut self) -> *mut PyObject {
@@ -138,8 +157,9 @@ impl Memo for AnyMemo {
 
 impl Drop for AnyMemo {
     fn drop(&mut self) {
-        if !self.keepalive.is_null() {
-            unsafe { self.keepalive.decref() };
+        unsafe {
+            self.keepalive.decref_nullable();
+            self.last_key.decref_nullable();
         }
     }
 }
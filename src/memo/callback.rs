@@ -0,0 +1,240 @@
+use pyo3::exceptions::{PyKeyError, PyNotImplementedError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyNone, PyTuple};
+use pyo3::{intern, PyTypeInfo};
+use pyo3_ffi::*;
+use std::ffi::c_void;
+use std::ptr;
+
+use super::Memo;
+use crate::py_str;
+use crate::types::PyObjectPtr;
+
+/// `copium.extra.CustomMemo`: base class for memos written in Python.
+///
+/// Subclasses implement `lookup`, `insert` and `keepalive`; passing one as
+/// `deepcopy(..., memo=)` routes every memo operation through those methods.
+#[pyclass(subclass, module = "copium.extra", name = "CustomMemo")]
+pub struct CustomMemo;
+
+#[pymethods]
+impl CustomMemo {
+    #[new]
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn new(_args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>) -> Self {
+        CustomMemo
+    }
+
+    /// The copy recorded for `key` (an id), or None.
+    fn lookup(&self, _key: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        Err(PyNotImplementedError::new_err(
+            "CustomMemo subclasses must implement lookup()",
+        ))
+    }
+
+    /// Records `value` as the copy of the original whose id is `key`.
+    fn insert(&self, _key: &Bound<'_, PyAny>, _value: &Bound<'_, PyAny>) -> PyResult<()> {
+        Err(PyNotImplementedError::new_err(
+            "CustomMemo subclasses must implement insert()",
+        ))
+    }
+
+    /// Keeps `obj` alive for as long as its id may be looked up.
+    fn keepalive(&self, _obj: &Bound<'_, PyAny>) -> PyResult<()> {
+        Err(PyNotImplementedError::new_err(
+            "CustomMemo subclasses must implement keepalive()",
+        ))
+    }
+
+    /// Drops what was recorded for `key` when copying its original failed.
+    fn discard(&self, _key: &Bound<'_, PyAny>) {}
+
+    // The mapping face stdlib's deepcopy and custom __deepcopy__ expect.
+
+    /// `lookup(key)`, or `default` when it returns None.
+    #[pyo3(signature = (key, default=None))]
+    fn get<'py>(
+        slf: &Bound<'py, Self>,
+        key: &Bound<'py, PyAny>,
+        default: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = slf.py();
+        let found = slf.call_method1(intern!(py, "lookup"), (key,))?;
+        if found.is_none() {
+            return Ok(default.unwrap_or_else(|| PyNone::get(py).to_owned().into_any()));
+        }
+        Ok(found)
+    }
+
+    fn __getitem__<'py>(
+        slf: &Bound<'py, Self>,
+        key: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let found = slf.call_method1(intern!(slf.py(), "lookup"), (key,))?;
+        if found.is_none() {
+            return Err(PyKeyError::new_err(key.clone().unbind()));
+        }
+        Ok(found)
+    }
+
+    fn __setitem__(
+        slf: &Bound<'_, Self>,
+        key: &Bound<'_, PyAny>,
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        slf.call_method1(intern!(slf.py(), "insert"), (key, value))?;
+        Ok(())
+    }
+}
+
+/// New reference to the `CustomMemo` type.
+pub unsafe fn custom_memo_type() -> *mut PyObject {
+    let py = unsafe { Python::assume_attached() };
+    CustomMemo::type_object(py).into_any().into_ptr()
+}
+
+/// Whether `object` is a `CustomMemo` (or subclass) instance.
+#[inline]
+pub unsafe fn is_custom_memo(object: *mut PyObject) -> bool {
+    unsafe {
+        let py = Python::assume_attached();
+        PyObject_TypeCheck(object, CustomMemo::type_object_raw(py)) != 0
+    }
+}
+
+/// Adapter driving a `CustomMemo` through its methods. A call per operation
+/// makes it the slowest memo, but whatever the subclass keeps is its own.
+pub struct CallbackMemo {
+    pub object: *mut PyObject,
+    /// Last pointer turned into a key, and the owned `int` made for it (see `DictMemo`).
+    last_original: *mut PyObject,
+    last_key: *mut PyObject,
+}
+
+impl CallbackMemo {
+    pub fn new(object: *mut PyObject) -> Self {
+        Self {
+            object,
+            last_original: ptr::null_mut(),
+            last_key: ptr::null_mut(),
+        }
+    }
+
+    /// Borrowed `int` key for `object`, or null with an exception set.
+    unsafe fn key_for(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            if object != self.last_original {
+                let pykey = PyLong_FromVoidPtr(object as *mut c_void);
+                if pykey.is_null() {
+                    return ptr::null_mut();
+                }
+                self.last_key.decref_nullable();
+                self.last_key = pykey;
+                self.last_original = object;
+            }
+            self.last_key
+        }
+    }
+
+    /// `self.object.<name>(args...)`, discarding the result.
+    #[inline(always)]
+    unsafe fn call_method(
+        &self,
+        name: *mut PyObject,
+        first: *mut PyObject,
+        second: *mut PyObject,
+    ) -> i32 {
+        unsafe {
+            let result = PyObject_CallMethodObjArgs(
+                self.object,
+                name,
+                first,
+                second,
+                ptr::null_mut::<PyObject>(),
+            );
+            if result.is_null() {
+                return -1;
+            }
+            result.decref();
+            0
+        }
+    }
+}
+
+impl Memo for CallbackMemo {
+    type Probe = ();
+    const RECALL_CAN_ERROR: bool = true;
+
+    unsafe fn recall(&mut self, object: *mut PyObject) -> ((), *mut PyObject) {
+        unsafe {
+            let pykey = self.key_for(object);
+            if pykey.is_null() {
+                return ((), ptr::null_mut());
+            }
+
+            let found = PyObject_CallMethodObjArgs(
+                self.object,
+                py_str!("lookup"),
+                pykey,
+                ptr::null_mut::<PyObject>(),
+            );
+            if found.is_null() {
+                return ((), ptr::null_mut());
+            }
+            if found.is_none() {
+                found.decref();
+                return ((), ptr::null_mut());
+            }
+
+            ((), found)
+        }
+    }
+
+    unsafe fn memoize(&mut self, original: *mut PyObject, copy: *mut PyObject, _probe: &()) -> i32 {
+        unsafe {
+            let pykey = self.key_for(original);
+            if pykey.is_null() {
+                return -1;
+            }
+            if self.call_method(py_str!("insert"), pykey, copy) < 0 {
+                return -1;
+            }
+            self.call_method(py_str!("keepalive"), original, ptr::null_mut())
+        }
+    }
+
+    /// Runs while the failure that caused it is pending, so that error is
+    /// kept and a failing `discard` is ignored.
+    #[cold]
+    unsafe fn forget(&mut self, original: *mut PyObject, _probe: &()) {
+        unsafe {
+            let mut exc_type: *mut PyObject = ptr::null_mut();
+            let mut exc_value: *mut PyObject = ptr::null_mut();
+            let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+            #[allow(deprecated)]
+            PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+            let pykey = self.key_for(original);
+            if pykey.is_null()
+                || self.call_method(py_str!("discard"), pykey, ptr::null_mut()) < 0
+            {
+                PyErr_Clear();
+            }
+
+            #[allow(deprecated)]
+            PyErr_Restore(exc_type, exc_value, exc_tb);
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn as_call_arg(&mut self) -> *mut PyObject {
+        self.object
+    }
+}
+
+impl Drop for CallbackMemo {
+    fn drop(&mut self) {
+        unsafe { self.last_key.decref_nullable() };
+    }
+}
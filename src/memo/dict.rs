@@ -3,11 +3,18 @@ use std::ffi::c_void;
 use std::ptr;
 
 use super::Memo;
-use crate::types::{py_list_new, PyMapPtr, PyObjectPtr};
+use crate::types::{py_list_new, PyMapPtr, PyObjectPtr, PyTypeObjectPtr};
 
 pub struct DictMemo {
     pub dict: *mut PyDictObject,
-    keepalive: *mut PyListObject,
+    keepalive: *mut PyObject,
+    /// Last pointer turned into a key, and the owned `int` made for it. Wide fan-outs
+    /// recall the same shared object over and over; this spares one allocation each.
+    last_original: *mut PyObject,
+    last_key: *mut PyObject,
+    /// Started empty and only copium has written to it since: every key is the
+    /// id of a live non-atomic original, so atomics need no lookup.
+    pristine: bool,
 }
 
 impl DictMemo {
@@ -15,6 +22,26 @@ impl DictMemo {
         Self {
             dict,
             keepalive: ptr::null_mut(),
+            last_original: ptr::null_mut(),
+            last_key: ptr::null_mut(),
+            pristine: unsafe { dict.len() == 0 },
+        }
+    }
+
+    /// Borrowed `int` key for `object`, or null with an exception set.
+    #[inline(always)]
+    unsafe fn key_for(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            if object != self.last_original {
+                let pykey = PyLong_FromVoidPtr(object as *mut c_void);
+                if pykey.is_null() {
+                    return ptr::null_mut();
+                }
+                self.last_key.decref_nullable();
+                self.last_key = pykey;
+                self.last_original = object;
+            }
+            self.last_key
         }
     }
 
@@ -33,7 +60,7 @@ impl DictMemo {
             let existing = self.dict.get_item(pykey);
             if !existing.is_null() {
                 existing.incref();
-                self.keepalive = existing as *mut PyListObject;
+                self.keepalive = existing;
                 pykey.decref();
                 return 0;
             }
@@ -54,7 +81,7 @@ impl DictMemo {
                 return -1;
             }
 
-            self.keepalive = list as *mut PyListObject;
+            self.keepalive = list as *mut PyObject;
             pykey.decref();
             0
         }
@@ -68,7 +95,7 @@ impl Memo for DictMemo {
     #[inline(always)]
     unsafe fn recall(&mut self, object: *mut PyObject) -> ((), *mut PyObject) {
         unsafe {
-            let pykey = PyLong_FromVoidPtr(object as *mut c_void);
+            let pykey = self.key_for(object);
             if pykey.is_null() {
                 return ((), ptr::null_mut());
             }
@@ -77,7 +104,6 @@ impl Memo for DictMemo {
             if !found.is_null() {
                 found.incref();
             }
-            pykey.decref();
             ((), found)
         }
     }
@@ -85,14 +111,15 @@ impl Memo for DictMemo {
     #[inline(always)]
     unsafe fn memoize(&mut self, original: *mut PyObject, copy: *mut PyObject, _probe: &()) -> i32 {
         unsafe {
-            let pykey = PyLong_FromVoidPtr(original as *mut c_void);
+            let pykey = self.key_for(original);
             if pykey.is_null() {
                 return -1;
             }
 
-            let rc = self.dict.set_item(pykey, copy);
-            pykey.decref();
-            if rc < 0 {
+            if self.pristine && original.class().is_atomic_immutable() {
+                self.pristine = false;
+            }
+            if self.dict.set_item(pykey, copy) < 0 {
                 return -1;
             }
 
@@ -100,18 +127,26 @@ impl Memo for DictMemo {
                 return -1;
             }
 
-            PyList_Append(self.keepalive as *mut PyObject, original)
+            super::keepalive_append(self.keepalive, original)
         }
     }
 
     #[inline(always)]
-    unsafe fn forget(&mut self, _original: *mut PyObject, _probe: &()) {}
+    unsafe fn forget(&mut self, original: *mut PyObject, _probe: &()) {
+        unsafe { super::forget_key(self.dict as *mut PyObject, original) }
+    }
 
     #[inline(always)]
     unsafe fn as_call_arg(&mut self) -> *mut PyObject {
+        self.pristine = false;
         self.dict as *mut PyObject
     }
 
+    #[inline(always)]
+    fn may_recall_atomics(&self) -> bool {
+        !self.pristine
+    }
+
     unsafe fn ensure_memo_is_valid(&mut self) -> i32 {
         unsafe { self.ensure_keepalive() }
     }
@@ -119,8 +154,9 @@ impl Memo for DictMemo {
 
 impl Drop for DictMemo {
     fn drop(&mut self) {
-        if !self.keepalive.is_null() {
-            unsafe { self.keepalive.decref() };
+        unsafe {
+            self.keepalive.decref_nullable();
+            self.last_key.decref_nullable();
         }
     }
 }
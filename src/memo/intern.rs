@@ -0,0 +1,258 @@
+use pyo3_ffi::*;
+use std::ptr;
+
+use super::{AnyMemo, CallbackMemo, DictMemo, Memo, MemoCheckpoint, PyMemoObject};
+use crate::types::{PyObjectPtr, PySeqPtr, PyTypeObjectPtr};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InternMode {
+    None,
+    Str,
+    StrTuple,
+}
+
+/// The memo an interning copy would have used without `intern`. One enum
+/// rather than a type parameter keeps this opt-in path to a single
+/// instantiation of the copier.
+pub enum InnerMemo<'m> {
+    Native(&'m mut PyMemoObject),
+    Dict(&'m mut DictMemo),
+    Any(&'m mut AnyMemo),
+    Callback(&'m mut CallbackMemo),
+}
+
+/// Memo for `deepcopy(..., intern="str" | "str+tuple")`: wraps the memo the
+/// copy would have used and hands out one object per distinct value for
+/// exact `str` leaves (and, with tuples, for tuples made only of literals).
+///
+/// Canonical objects live in two generations of at most `cap / 2` entries
+/// each. A hit in the older one moves the entry to the recent one; once the
+/// recent one is full the older one is dropped, so whatever wasn't used for a
+/// whole generation is evicted first and the table never exceeds `cap`.
+pub struct InternMemo<'m> {
+    inner: InnerMemo<'m>,
+    tuples: bool,
+    generation: Py_ssize_t,
+    recent: *mut PyObject,
+    older: *mut PyObject,
+}
+
+impl<'m> InternMemo<'m> {
+    pub fn new(inner: InnerMemo<'m>, mode: InternMode, cap: Py_ssize_t) -> Self {
+        Self {
+            inner,
+            tuples: mode == InternMode::StrTuple,
+            generation: cap / 2,
+            recent: ptr::null_mut(),
+            older: ptr::null_mut(),
+        }
+    }
+
+    /// The table's entry for `value`, or null with no error set when it has
+    /// none. Borrowed.
+    unsafe fn lookup(table: *mut PyObject, value: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            if table.is_null() {
+                return ptr::null_mut();
+            }
+            PyDict_GetItemWithError(table, value)
+        }
+    }
+
+    /// Records `canonical` as the entry for its value in the recent
+    /// generation, starting a new generation once it is full.
+    unsafe fn remember(&mut self, canonical: *mut PyObject) -> i32 {
+        unsafe {
+            if self.recent.is_null() {
+                self.recent = PyDict_New();
+                if self.recent.is_null() {
+                    return -1;
+                }
+            }
+            if PyDict_SetItem(self.recent, canonical, canonical) < 0 {
+                return -1;
+            }
+            if PyDict_Size(self.recent) >= self.generation {
+                self.older.decref_nullable();
+                self.older = self.recent;
+                self.recent = ptr::null_mut();
+            }
+            0
+        }
+    }
+
+    /// Tuples are canonical only when made of literals (and such tuples), so
+    /// looking them up runs no user `__hash__` or `__eq__`.
+    unsafe fn is_plain_tuple(value: *mut PyObject) -> bool {
+        unsafe {
+            let tuple = value as *mut PyTupleObject;
+            (0..tuple.length()).all(|i| {
+                let tp = tuple.get_borrowed_unchecked(i).class();
+                tp.is_literal_immutable() || tp == ptr::addr_of_mut!(PyTuple_Type)
+            })
+        }
+    }
+
+    /// Equal tuples may still differ in their items' types (`(1,)` and
+    /// `(True,)`), so only one holding the very same items stands in.
+    unsafe fn same_items(found: *mut PyObject, value: *mut PyObject) -> bool {
+        unsafe {
+            let found = found as *mut PyTupleObject;
+            let value = value as *mut PyTupleObject;
+            found.length() == value.length()
+                && (0..value.length())
+                    .all(|i| found.get_borrowed_unchecked(i) == value.get_borrowed_unchecked(i))
+        }
+    }
+
+    unsafe fn intern(&mut self, value: *mut PyObject, is_tuple: bool) -> *mut PyObject {
+        unsafe {
+            let mut found = Self::lookup(self.recent, value);
+            let mut promote = false;
+            if found.is_null() && PyErr_Occurred().is_null() {
+                found = Self::lookup(self.older, value);
+                promote = true;
+            }
+            if !PyErr_Occurred().is_null() {
+                return ptr::null_mut();
+            }
+            if !found.is_null() && is_tuple && !Self::same_items(found, value) {
+                return value.newref();
+            }
+
+            let canonical = if found.is_null() { value } else { found };
+            canonical.incref();
+            if (found.is_null() || promote) && self.remember(canonical) < 0 {
+                canonical.decref();
+                return ptr::null_mut();
+            }
+            canonical
+        }
+    }
+}
+
+/// Evaluates `$call` with `$memo` bound to whichever memo `$inner` holds.
+macro_rules! on_inner {
+    ($inner:expr, $memo:ident => $call:expr) => {
+        match $inner {
+            InnerMemo::Native($memo) => $call,
+            InnerMemo::Dict($memo) => $call,
+            InnerMemo::Any($memo) => $call,
+            InnerMemo::Callback($memo) => $call,
+        }
+    };
+}
+
+impl Memo for InternMemo<'_> {
+    /// The native memo's probe; unused for the others.
+    type Probe = usize;
+    const RECALL_CAN_ERROR: bool = true;
+    const CANONICALIZES: bool = true;
+
+    unsafe fn recall(&mut self, object: *mut PyObject) -> (usize, *mut PyObject) {
+        unsafe {
+            match &mut self.inner {
+                InnerMemo::Native(memo) => memo.recall(object),
+                InnerMemo::Dict(memo) => (0, memo.recall(object).1),
+                InnerMemo::Any(memo) => (0, memo.recall(object).1),
+                InnerMemo::Callback(memo) => (0, memo.recall(object).1),
+            }
+        }
+    }
+
+    unsafe fn recall_probed(&mut self, object: *mut PyObject, probe: &usize) -> *mut PyObject {
+        unsafe {
+            match &mut self.inner {
+                InnerMemo::Native(memo) => memo.recall_probed(object, probe),
+                InnerMemo::Dict(memo) => memo.recall_probed(object, &()),
+                InnerMemo::Any(memo) => memo.recall_probed(object, &()),
+                InnerMemo::Callback(memo) => memo.recall_probed(object, &()),
+            }
+        }
+    }
+
+    unsafe fn memoize(
+        &mut self,
+        original: *mut PyObject,
+        copy: *mut PyObject,
+        probe: &usize,
+    ) -> i32 {
+        unsafe {
+            match &mut self.inner {
+                InnerMemo::Native(memo) => memo.memoize(original, copy, probe),
+                InnerMemo::Dict(memo) => memo.memoize(original, copy, &()),
+                InnerMemo::Any(memo) => memo.memoize(original, copy, &()),
+                InnerMemo::Callback(memo) => memo.memoize(original, copy, &()),
+            }
+        }
+    }
+
+    unsafe fn forget(&mut self, original: *mut PyObject, probe: &usize) {
+        unsafe {
+            match &mut self.inner {
+                InnerMemo::Native(memo) => memo.forget(original, probe),
+                InnerMemo::Dict(memo) => memo.forget(original, &()),
+                InnerMemo::Any(memo) => memo.forget(original, &()),
+                InnerMemo::Callback(memo) => memo.forget(original, &()),
+            }
+        }
+    }
+
+    unsafe fn as_call_arg(&mut self) -> *mut PyObject {
+        unsafe { on_inner!(&mut self.inner, memo => memo.as_call_arg()) }
+    }
+
+    fn may_recall_atomics(&self) -> bool {
+        match &self.inner {
+            // Native memos are consulted after atomics, as if recall can't error.
+            InnerMemo::Native(_) => false,
+            InnerMemo::Dict(memo) => memo.may_recall_atomics(),
+            InnerMemo::Any(memo) => memo.may_recall_atomics(),
+            InnerMemo::Callback(memo) => memo.may_recall_atomics(),
+        }
+    }
+
+    unsafe fn ensure_memo_is_valid(&mut self) -> i32 {
+        unsafe { on_inner!(&mut self.inner, memo => memo.ensure_memo_is_valid()) }
+    }
+
+    unsafe fn checkpoint(&mut self) -> Option<MemoCheckpoint> {
+        unsafe { on_inner!(&mut self.inner, memo => memo.checkpoint()) }
+    }
+
+    unsafe fn prefetch(&self, object: *mut PyObject) {
+        unsafe { on_inner!(&self.inner, memo => memo.prefetch(object)) }
+    }
+
+    unsafe fn reserve(&mut self, additional: usize) {
+        unsafe { on_inner!(&mut self.inner, memo => memo.reserve(additional)) }
+    }
+
+    unsafe fn as_native_memo(&mut self) -> *mut PyMemoObject {
+        unsafe { on_inner!(&mut self.inner, memo => memo.as_native_memo()) }
+    }
+
+    #[inline(always)]
+    unsafe fn canonicalize(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let tp = object.class();
+            if tp == ptr::addr_of_mut!(PyUnicode_Type) {
+                return self.intern(object, false);
+            }
+            if self.tuples && tp == ptr::addr_of_mut!(PyTuple_Type) && Self::is_plain_tuple(object)
+            {
+                return self.intern(object, true);
+            }
+            object.newref()
+        }
+    }
+}
+
+impl Drop for InternMemo<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.recent.decref_nullable();
+            self.older.decref_nullable();
+        }
+    }
+}
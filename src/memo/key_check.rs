@@ -0,0 +1,69 @@
+//! `config.apply(memo_key_check=True)`: the memo proxy a `__deepcopy__`
+//! receives refuses keys that can't be an original's id().
+//!
+//! Memo keys are `id(original)`. A memo pre-seeded under `hash(original)` or
+//! any other int is accepted by a dict and by the proxy alike, and only shows
+//! up as copies that don't share what they should. With the option on, storing
+//! an entry (`memo[key] = ...`, `setdefault`) raises KeyError unless the key is
+//! the id of an object whose `__deepcopy__` is running or of an original
+//! already memoized. Copies with the option off never get here.
+
+use pyo3_ffi::*;
+use std::ptr;
+
+use super::native::PyMemoObject;
+use super::table::hash_pointer;
+use crate::ffi_ext::PyErr_Format;
+
+/// Deepest nesting of `__deepcopy__` calls tracked; past it, keys are accepted.
+const MAX_PENDING: usize = 64;
+
+/// Addresses of the objects whose `__deepcopy__` is running on this thread.
+#[thread_local]
+static mut PENDING: [usize; MAX_PENDING] = [0; MAX_PENDING];
+
+#[thread_local]
+static mut DEPTH: usize = 0;
+
+/// Calls `object`'s `__deepcopy__` through `call`, with its id accepted as a key.
+#[cold]
+pub unsafe fn with_pending_key(
+    object: *mut PyObject,
+    call: impl FnOnce() -> *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let depth = DEPTH;
+        if depth < MAX_PENDING {
+            PENDING[depth] = object as usize;
+        }
+        DEPTH = depth + 1;
+        let result = call();
+        DEPTH = depth;
+        result
+    }
+}
+
+/// Returns -1 with KeyError set if `key` is none of the ids `memo` may be
+/// keyed by.
+#[cold]
+pub unsafe fn check(memo: *mut PyMemoObject, key: usize) -> i32 {
+    unsafe {
+        let depth = DEPTH;
+        let pending = &*ptr::addr_of!(PENDING);
+        if depth > MAX_PENDING
+            || pending[..depth].contains(&key)
+            || !(*memo).table.lookup_h(key, hash_pointer(key)).is_null()
+        {
+            return 0;
+        }
+        PyErr_Format(
+            PyExc_KeyError,
+            crate::cstr!(
+                "memo key %zu is not id() of an object this copy has seen; \
+                 memo keys are id(original), not hash(original)"
+            ),
+            key,
+        );
+        -1
+    }
+}
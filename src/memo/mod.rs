@@ -1,27 +1,90 @@
 mod any;
+mod callback;
 mod dict;
+mod intern;
+mod key_check;
 mod native;
 mod pytype;
 mod table;
+mod tree;
 mod tss;
 
 use pyo3_ffi::*;
 use std::ptr;
 
+use crate::types::PyObjectPtr;
+
 pub use any::AnyMemo;
+pub use callback::{custom_memo_type, is_custom_memo, CallbackMemo};
+#[cfg(feature = "rust-api")]
+pub use callback::CustomMemo;
 pub use dict::DictMemo;
+pub use intern::{InnerMemo, InternMemo, InternMode};
+pub use key_check::with_pending_key;
 pub use native::PyMemoObject;
 pub use pytype::{memo_ready_type, Memo_Type};
 pub use table::{KeepaliveVec, MemoTable, UndoLog};
-pub use tss::{cleanup_memo, get_memo, pymemo_alloc};
+pub use tree::TreeMemo;
+pub use tss::{cleanup_memo, get_memo, highwater, reset_highwater, thread_memo};
 
 pub type MemoCheckpoint = usize;
 
+/// memo[id(memo)] may hold whatever the caller put there. Like stdlib's
+/// _keep_alive, anything but an exact list is asked to `.append` itself.
+#[inline(always)]
+unsafe fn keepalive_append(keepalive: *mut PyObject, original: *mut PyObject) -> i32 {
+    unsafe {
+        if PyList_CheckExact(keepalive) != 0 {
+            return PyList_Append(keepalive, original);
+        }
+
+        let append = keepalive.getattr(crate::py_str!("append"));
+        if append.is_null() {
+            return -1;
+        }
+        let result = append.call_one(original);
+        append.decref();
+        if result.is_null() {
+            return -1;
+        }
+        result.decref();
+        0
+    }
+}
+
+/// Drops memo[id(original)] from a Python-level memo. Runs while the failure
+/// that caused it is pending, so that error is kept and a failing delete is
+/// ignored.
+#[cold]
+unsafe fn forget_key(memo: *mut PyObject, original: *mut PyObject) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+        let pykey = PyLong_FromVoidPtr(original as *mut std::ffi::c_void);
+        if pykey.is_null() || PyObject_DelItem(memo, pykey) < 0 {
+            PyErr_Clear();
+        }
+        pykey.decref_nullable();
+
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
+    }
+}
+
 pub trait Memo: Sized {
     type Probe;
 
     const RECALL_CAN_ERROR: bool;
 
+    /// Whether atomic leaves go through `canonicalize` instead of being
+    /// shared as they are.
+    const CANONICALIZES: bool = false;
+
     unsafe fn recall(&mut self, object: *mut PyObject) -> (Self::Probe, *mut PyObject);
 
     unsafe fn recall_probed(
@@ -44,6 +107,14 @@ pub trait Memo: Sized {
 
     unsafe fn as_call_arg(&mut self) -> *mut PyObject;
 
+    /// Whether a lookup for an atomic object could find anything. Before 3.14
+    /// stdlib consults the memo even for atomics, so Python-level memos answer
+    /// `true` unless they can prove no such key was ever added.
+    #[inline(always)]
+    fn may_recall_atomics(&self) -> bool {
+        true
+    }
+
     #[inline(always)]
     unsafe fn ensure_memo_is_valid(&mut self) -> i32 {
         0
@@ -54,8 +125,92 @@ pub trait Memo: Sized {
         None
     }
 
+    /// Hints that `object` is about to be recalled.
+    #[inline(always)]
+    unsafe fn prefetch(&self, object: *mut PyObject) {
+        let _ = object;
+    }
+
+    /// Hints that about `additional` more originals are about to be memoized.
+    #[inline(always)]
+    unsafe fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
     #[inline(always)]
     unsafe fn as_native_memo(&mut self) -> *mut PyMemoObject {
         ptr::null_mut()
     }
+
+    /// What the copy holds in place of the atomic `object`: a new reference,
+    /// or null with an exception set. Only called when `CANONICALIZES`.
+    #[inline(always)]
+    unsafe fn canonicalize(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe { object.newref() }
+    }
+}
+
+/// Pre-inserts `replace` into the memo so each original is substituted by its
+/// replacement (shared, not copied) wherever it appears in the graph. Accepts a
+/// mapping or an iterable of (original, replacement) pairs; keys are compared
+/// by identity, so pairs allow unhashable originals.
+#[cold]
+pub unsafe fn seed_replacements<M: Memo>(memo: &mut M, replace: *mut PyObject) -> i32 {
+    unsafe {
+        let mut has_items = PyDict_Check(replace) != 0;
+        if !has_items {
+            has_items = PyObject_HasAttr(replace, crate::py_str!("items")) != 0;
+        }
+        let pairs = if has_items {
+            PyMapping_Items(replace)
+        } else {
+            PySequence_List(replace)
+        };
+        if pairs.is_null() {
+            return -1;
+        }
+
+        let count = PyList_GET_SIZE(pairs);
+        for i in 0..count {
+            let pair = PySequence_Fast(
+                PyList_GET_ITEM(pairs, i),
+                crate::cstr!("replace items must be (original, replacement) pairs"),
+            );
+            if pair.is_null() {
+                pairs.decref();
+                return -1;
+            }
+            if crate::ffi_ext::PySequence_Fast_GET_SIZE(pair) != 2 {
+                PyErr_SetString(
+                    PyExc_ValueError,
+                    crate::cstr!("replace items must be (original, replacement) pairs"),
+                );
+                pair.decref();
+                pairs.decref();
+                return -1;
+            }
+            let original = crate::ffi_ext::PySequence_Fast_GET_ITEM(pair, 0);
+            let replacement = crate::ffi_ext::PySequence_Fast_GET_ITEM(pair, 1);
+
+            let (probe, existing) = memo.recall(original);
+            if !existing.is_null() {
+                existing.decref();
+                memo.forget(original, &probe);
+            } else if M::RECALL_CAN_ERROR && !PyErr_Occurred().is_null() {
+                pair.decref();
+                pairs.decref();
+                return -1;
+            }
+
+            let status = memo.memoize(original, replacement, &probe);
+            pair.decref();
+            if status < 0 {
+                pairs.decref();
+                return -1;
+            }
+        }
+
+        pairs.decref();
+        0
+    }
 }
@@ -1,5 +1,5 @@
 use super::{KeepaliveVec, Memo, MemoCheckpoint, MemoTable, UndoLog};
-use crate::memo::table::{hash_pointer, TOMBSTONE};
+use crate::memo::table::hash_pointer;
 use crate::types::PyObjectPtr;
 use pyo3_ffi::*;
 use std::ffi::c_void;
@@ -13,6 +13,18 @@ pub struct PyMemoObject {
     pub keepalive: KeepaliveVec,
     pub undo_log: UndoLog,
     pub dict_proxy: *mut PyObject,
+    /// Dict handed to `__deepcopy__` methods that need a real one. Kept for the
+    /// whole deepcopy() call, so each of them is handed the same mapping.
+    pub dict_view: *mut PyObject,
+    /// True while a deepcopy() call is driving this memo.
+    pub attached: bool,
+    /// True during a `timeout=` call on this memo: copium's own entries go to
+    /// the undo log as well, so running out of time can drop all of them.
+    pub log_all_inserts: bool,
+    /// Debug builds: the type each original copium memoized had, checked on
+    /// every hit so a key whose address was recycled fails loudly.
+    #[cfg(debug_assertions)]
+    pub key_types: std::collections::HashMap<usize, *mut PyTypeObject>,
 }
 
 impl PyMemoObject {
@@ -22,19 +34,90 @@ impl PyMemoObject {
             ptr::write(ptr::addr_of_mut!(self.keepalive), KeepaliveVec::new());
             ptr::write(ptr::addr_of_mut!(self.undo_log), UndoLog::new());
             ptr::write(ptr::addr_of_mut!(self.dict_proxy), ptr::null_mut());
+            ptr::write(ptr::addr_of_mut!(self.dict_view), ptr::null_mut());
+            ptr::write(ptr::addr_of_mut!(self.attached), false);
+            ptr::write(ptr::addr_of_mut!(self.log_all_inserts), false);
+            #[cfg(debug_assertions)]
+            ptr::write(
+                ptr::addr_of_mut!(self.key_types),
+                std::collections::HashMap::new(),
+            );
         }
     }
 
-    pub fn reset(&mut self) {
-        self.keepalive.clear();
-        self.keepalive.shrink_if_large();
-        self.undo_log.clear();
-        self.undo_log.shrink_if_large();
-        self.table.reset();
-        if !self.dict_proxy.is_null() {
-            unsafe { self.dict_proxy.decref() };
-            self.dict_proxy = ptr::null_mut();
+    /// Releases everything the memo holds, keeping modest allocations. A raw
+    /// pointer like [`MemoTable::clear`], as releasing runs arbitrary code.
+    pub unsafe fn reset(this: *mut Self) {
+        unsafe {
+            KeepaliveVec::clear(ptr::addr_of_mut!((*this).keepalive));
+            (*this).keepalive.shrink_if_large();
+            (*this).undo_log.clear();
+            (*this).undo_log.shrink_if_large();
+            MemoTable::reset(ptr::addr_of_mut!((*this).table));
+            #[cfg(debug_assertions)]
+            (*this).key_types.clear();
+            let dict_proxy = ptr::replace(ptr::addr_of_mut!((*this).dict_proxy), ptr::null_mut());
+            dict_proxy.decref_nullable();
+            let dict_view = ptr::replace(ptr::addr_of_mut!((*this).dict_view), ptr::null_mut());
+            dict_view.decref_nullable();
+        }
+    }
+
+    /// Frees the retained table and vectors of a memo that holds no
+    /// references, which needs no GIL. Returns false, leaving everything in
+    /// place, if the memo isn't idle.
+    pub fn release_buffers(&mut self) -> bool {
+        if self.attached
+            || self.table.used != 0
+            || !self.keepalive.items.is_empty()
+            || !self.dict_proxy.is_null()
+            || !self.dict_view.is_null()
+        {
+            return false;
+        }
+        self.table = MemoTable::new();
+        self.keepalive.items = Vec::new();
+        self.undo_log = UndoLog::new();
+        true
+    }
+
+    /// Native allocations owned by this memo, excluding the object itself.
+    pub fn heap_bytes(&self) -> usize {
+        self.table.heap_bytes() + self.keepalive.heap_bytes() + self.undo_log.heap_bytes()
+    }
+
+    /// Marks the memo as driven by a deepcopy() call and returns the previous
+    /// mark, which the call restores when it returns: nested calls on one
+    /// memo leave it attached until the outermost one is done.
+    #[inline(always)]
+    pub fn attach(&mut self) -> bool {
+        std::mem::replace(&mut self.attached, true)
+    }
+
+    /// Drops every entry, leaving the keepalive alone. A raw pointer like
+    /// [`MemoTable::clear`].
+    pub unsafe fn clear_entries(this: *mut Self) {
+        unsafe {
+            MemoTable::clear(ptr::addr_of_mut!((*this).table));
+            #[cfg(debug_assertions)]
+            (*this).key_types.clear();
+        }
+    }
+
+    /// Raises RuntimeError and returns true if a deepcopy() is using the memo.
+    /// Its handlers rely on the entries they made staying put until they return.
+    #[cold]
+    pub unsafe fn refuse_clear_while_attached(&self) -> bool {
+        if !self.attached {
+            return false;
+        }
+        unsafe {
+            PyErr_SetString(
+                PyExc_RuntimeError,
+                crate::cstr!("cannot clear memo while a deepcopy() is using it"),
+            );
         }
+        true
     }
 
     #[inline(always)]
@@ -44,7 +127,7 @@ impl PyMemoObject {
 
     #[cold]
     pub fn rollback(&mut self, cp: MemoCheckpoint) {
-        for i in cp..self.undo_log.keys.len() {
+        for i in (cp..self.undo_log.keys.len()).rev() {
             let key = self.undo_log.keys[i];
             let hash = hash_pointer(key);
             let _ = self.table.remove_h(key, hash);
@@ -56,10 +139,25 @@ impl PyMemoObject {
         if self.table.insert_h(key, value, hash) < 0 {
             return -1;
         }
-        self.undo_log.append(key);
+        if self.undo_log.append(key) < 0 {
+            let _ = self.table.remove_h(key, hash);
+            return -1;
+        }
         0
     }
 
+    /// Keeps `original` alive when a `__deepcopy__` that returned it stored an
+    /// entry under its address, which copium then doesn't memoize itself: the
+    /// key must not be recycled by another object within the call.
+    #[cold]
+    pub fn keep_alive_if_keyed(&mut self, original: *mut PyObject) -> i32 {
+        let key = original as usize;
+        if self.table.lookup_h(key, hash_pointer(key)).is_null() {
+            return 0;
+        }
+        self.keepalive.append(original)
+    }
+
     #[cold]
     pub unsafe fn to_dict(&self) -> *mut PyObject {
         unsafe {
@@ -67,50 +165,77 @@ impl PyMemoObject {
             if dict.is_null() {
                 return ptr::null_mut();
             }
-
-            if self.table.slots.is_null() {
-                return dict;
+            if self.fill_dict(dict) < 0 {
+                dict.decref();
+                return ptr::null_mut();
             }
+            dict
+        }
+    }
 
-            for i in 0..self.table.size {
-                let entry = &*self.table.slots.add(i);
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    let pykey = PyLong_FromVoidPtr(entry.key as *mut c_void);
-                    if pykey.is_null() {
-                        dict.decref();
-                        return ptr::null_mut();
-                    }
-                    if PyDict_SetItem(dict, pykey, entry.value) < 0 {
-                        pykey.decref();
-                        dict.decref();
-                        return ptr::null_mut();
-                    }
-                    pykey.decref();
+    /// `dict_view` refilled with the table's current entries. New reference.
+    #[cold]
+    pub unsafe fn refresh_dict_view(&mut self) -> *mut PyObject {
+        unsafe {
+            if self.dict_view.is_null() {
+                let dict = self.to_dict();
+                if dict.is_null() {
+                    return ptr::null_mut();
                 }
+                self.dict_view = dict;
+                return dict.newref();
             }
-
-            dict
+            let dict = self.dict_view;
+            PyDict_Clear(dict);
+            if self.fill_dict(dict) < 0 {
+                return ptr::null_mut();
+            }
+            dict.newref()
         }
     }
 
+    /// Keeps `dict_view` live once one was handed out: a method that kept it
+    /// can look up copies made after it returned.
     #[cold]
-    pub unsafe fn sync_from_dict(&mut self, dict: *mut PyObject, orig_size: Py_ssize_t) -> i32 {
+    unsafe fn mirror_to_dict_view(&mut self, original: *mut PyObject, copy: *mut PyObject) -> i32 {
         unsafe {
-            let cur_size = PyDict_Size(dict);
-            if cur_size <= orig_size {
-                return 0;
+            let pykey = PyLong_FromVoidPtr(original as *mut c_void);
+            if pykey.is_null() {
+                return -1;
+            }
+            let status = PyDict_SetItem(self.dict_view, pykey, copy);
+            pykey.decref();
+            status
+        }
+    }
+
+    unsafe fn fill_dict(&self, dict: *mut PyObject) -> i32 {
+        unsafe {
+            for entry in self.table.entries() {
+                let pykey = PyLong_FromVoidPtr(entry.key as *mut c_void);
+                if pykey.is_null() {
+                    return -1;
+                }
+                if PyDict_SetItem(dict, pykey, entry.value) < 0 {
+                    pykey.decref();
+                    return -1;
+                }
+                pykey.decref();
             }
+            0
+        }
+    }
 
+    /// Copies entries written through a `to_dict` snapshot back into the table,
+    /// including overwrites of keys the table already held.
+    #[cold]
+    pub unsafe fn sync_from_dict(&mut self, dict: *mut PyObject) -> i32 {
+        unsafe {
             let mut pos: Py_ssize_t = 0;
             let mut py_key: *mut PyObject = ptr::null_mut();
             let mut value: *mut PyObject = ptr::null_mut();
-            let mut idx: Py_ssize_t = 0;
 
             while PyDict_Next(dict, &mut pos, &mut py_key, &mut value) != 0 {
-                idx += 1;
-                if idx <= orig_size {
-                    continue;
-                }
                 if PyLong_Check(py_key) == 0 {
                     continue;
                 }
@@ -119,6 +244,9 @@ impl PyMemoObject {
                     return -1;
                 }
                 let hash = hash_pointer(key);
+                if self.table.lookup_h(key, hash) == value {
+                    continue;
+                }
                 if self.table.insert_h(key, value, hash) < 0 {
                     return -1;
                 }
@@ -146,6 +274,23 @@ impl Memo for PyMemoObject {
         let key = object as usize;
         let found = self.table.lookup_h(key, *probe);
         if !found.is_null() {
+            // The keepalive holds every original copium memoized, so a key can
+            // only change hands by address reuse if something let go of it.
+            // __class__ assignment mid-copy is legal and looks the same, so a
+            // mismatch is reported rather than asserted on.
+            #[cfg(debug_assertions)]
+            if let Some(tp) = self.key_types.get_mut(&key) {
+                let current = unsafe { object.class() };
+                if *tp != current {
+                    eprintln!(
+                        "copium: memo key {key:#x} was memoized for a {} and is now a {}; \
+                         __class__ was reassigned or the address was reused",
+                        unsafe { std::ffi::CStr::from_ptr((**tp).tp_name) }.to_string_lossy(),
+                        unsafe { std::ffi::CStr::from_ptr((*current).tp_name) }.to_string_lossy(),
+                    );
+                    *tp = current;
+                }
+            }
             unsafe { found.incref() };
         }
         found
@@ -162,13 +307,39 @@ impl Memo for PyMemoObject {
         if unlikely(self.table.insert_h(key, copy, *probe) < 0) {
             return -1;
         }
-        self.keepalive.append(original);
+        if unlikely(self.keepalive.append(original) < 0) {
+            // Without the keepalive the key could be reused by another object.
+            let _ = self.table.remove_h(key, *probe);
+            return -1;
+        }
+        if unlikely(self.log_all_inserts) && self.undo_log.append(key) < 0 {
+            let _ = self.table.remove_h(key, *probe);
+            return -1;
+        }
+        #[cfg(debug_assertions)]
+        self.key_types.insert(key, unsafe { original.class() });
+        if unlikely(!self.dict_view.is_null()) {
+            return unsafe { self.mirror_to_dict_view(original, copy) };
+        }
         0
     }
 
+    #[inline(always)]
+    unsafe fn prefetch(&self, object: *mut PyObject) {
+        self.table.prefetch(object as usize);
+    }
+
+    #[inline(always)]
+    unsafe fn reserve(&mut self, additional: usize) {
+        self.table.reserve(additional);
+    }
+
     #[cold]
     unsafe fn forget(&mut self, original: *mut PyObject, probe: &usize) {
         let _ = self.table.remove_h(original as usize, *probe);
+        if unlikely(!self.dict_view.is_null()) {
+            unsafe { super::forget_key(self.dict_view, original) };
+        }
     }
 
     unsafe fn as_call_arg(&mut self) -> *mut PyObject {
@@ -186,6 +357,6 @@ impl Memo for PyMemoObject {
 
 impl Drop for PyMemoObject {
     fn drop(&mut self) {
-        self.reset();
+        unsafe { Self::reset(self) };
     }
 }
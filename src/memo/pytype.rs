@@ -2,16 +2,19 @@ use pyo3_ffi::*;
 use std::ffi::c_void;
 use std::ptr;
 
+use super::key_check;
 use super::native::PyMemoObject;
+use super::KeepaliveVec;
 use crate::ffi_ext::PyUnicode_FromFormat;
 use crate::memo::table::{hash_pointer, TOMBSTONE};
+use crate::state::STATE;
 use crate::types::{PyObjectPtr, PyObjectSlotPtr};
 
 #[allow(non_upper_case_globals)]
 pub static mut Memo_Type: PyTypeObject = unsafe { std::mem::zeroed() };
 static mut MEMO_MAPPING: PyMappingMethods = unsafe { std::mem::zeroed() };
 static mut MEMO_SEQUENCE: PySequenceMethods = unsafe { std::mem::zeroed() };
-static mut MEMO_METHODS_TABLE: [PyMethodDef; 9] = unsafe { std::mem::zeroed() };
+static mut MEMO_METHODS_TABLE: [PyMethodDef; 14] = unsafe { std::mem::zeroed() };
 
 // ══════════════════════════════════════════════════════════════
 //  KeepaliveList — proxy type exposing keepalive vec to Python
@@ -25,7 +28,7 @@ struct PyKeepaliveListObject {
 
 static mut KEEPALIVE_LIST_TYPE: PyTypeObject = unsafe { std::mem::zeroed() };
 static mut KEEPALIVE_LIST_SEQUENCE: PySequenceMethods = unsafe { std::mem::zeroed() };
-static mut KEEPALIVE_LIST_METHODS_TABLE: [PyMethodDef; 3] = unsafe { std::mem::zeroed() };
+static mut KEEPALIVE_LIST_METHODS_TABLE: [PyMethodDef; 7] = unsafe { std::mem::zeroed() };
 
 unsafe fn keepalive_list_new(owner: *mut PyMemoObject) -> *mut PyObject {
     unsafe {
@@ -88,20 +91,20 @@ unsafe extern "C" fn keepalive_list_clear(obj: *mut PyObject) -> std::ffi::c_int
 }
 
 unsafe extern "C" fn keepalive_list_len(obj: *mut PyObject) -> Py_ssize_t {
-    unsafe {
+    ffi_guard!(-1, unsafe {
         let self_ = obj as *mut PyKeepaliveListObject;
         if (*self_).owner.is_null() {
             return 0;
         }
         (*(*self_).owner).keepalive.items.len() as Py_ssize_t
-    }
+    })
 }
 
 unsafe extern "C" fn keepalive_list_getitem(
     obj: *mut PyObject,
     index: Py_ssize_t,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyKeepaliveListObject;
         if (*self_).owner.is_null() {
             PyErr_SetString(PyExc_SystemError, cstr!("keepalive has no owner"));
@@ -120,11 +123,11 @@ unsafe extern "C" fn keepalive_list_getitem(
         }
 
         items[i as usize].newref()
-    }
+    })
 }
 
 unsafe extern "C" fn keepalive_list_iter(obj: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyKeepaliveListObject;
         if (*self_).owner.is_null() {
             PyErr_SetString(PyExc_SystemError, cstr!("keepalive has no owner"));
@@ -146,11 +149,11 @@ unsafe extern "C" fn keepalive_list_iter(obj: *mut PyObject) -> *mut PyObject {
         let it = PyObject_GetIter(list);
         list.decref();
         it
-    }
+    })
 }
 
 unsafe extern "C" fn keepalive_list_repr(obj: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let list = PySequence_List(obj);
         if list.is_null() {
             return ptr::null_mut();
@@ -160,39 +163,105 @@ unsafe extern "C" fn keepalive_list_repr(obj: *mut PyObject) -> *mut PyObject {
         if inner.is_null() {
             return ptr::null_mut();
         }
-        let wrapped = PyUnicode_FromFormat(cstr!("keepalive(%U)"), inner);
+        let self_ = obj as *mut PyKeepaliveListObject;
+        let attached = !(*self_).owner.is_null() && (*(*self_).owner).attached;
+        let wrapped = PyUnicode_FromFormat(
+            cstr!("keepalive(%U, attached=%s)"),
+            inner,
+            if attached {
+                cstr!("True")
+            } else {
+                cstr!("False")
+            },
+        );
         inner.decref();
         wrapped
+    })
+}
+
+unsafe extern "C" fn proxy_reduce(_obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        PyErr_SetString(PyExc_TypeError, cstr!("cannot pickle copium memo proxy"));
+        ptr::null_mut()
+    }
+}
+
+/// `__init_subclass__`: the proxies are only made by copium and their layout
+/// is private, so subclassing them is refused with a pointer to `CustomMemo`.
+unsafe extern "C" fn proxy_init_subclass(
+    cls: *mut PyObject,
+    _args: *mut PyObject,
+    _kwargs: *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let base = if PyType_IsSubtype(cls as _, ptr::addr_of_mut!(Memo_Type)) != 0 {
+            ptr::addr_of_mut!(Memo_Type)
+        } else {
+            ptr::addr_of_mut!(KEEPALIVE_LIST_TYPE)
+        };
+        crate::ffi_ext::PyErr_Format(
+            PyExc_TypeError,
+            cstr!(
+                "%s cannot be subclassed; to customize memoization, subclass \
+                 copium.extra.CustomMemo and pass an instance as deepcopy(..., memo=)"
+            ),
+            (*base).tp_name,
+        );
+        ptr::null_mut()
     }
 }
 
+/// `__copy__` / `__deepcopy__`: a plain list of what is currently kept alive.
+unsafe extern "C" fn keepalive_list_snapshot(
+    obj: *mut PyObject,
+    _: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe { PySequence_List(obj) })
+}
+
 unsafe extern "C" fn keepalive_list_append(
     obj: *mut PyObject,
     arg: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyKeepaliveListObject;
         if (*self_).owner.is_null() {
             PyErr_SetString(PyExc_SystemError, cstr!("keepalive has no owner"));
             return ptr::null_mut();
         }
-        (*(*self_).owner).keepalive.append(arg);
+        if (*(*self_).owner).keepalive.append(arg) < 0 {
+            return ptr::null_mut();
+        }
         Py_None().newref()
-    }
+    })
 }
 
 unsafe extern "C" fn keepalive_list_clear_py(
     obj: *mut PyObject,
     _: *mut PyObject,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyKeepaliveListObject;
         if (*self_).owner.is_null() {
             PyErr_SetString(PyExc_SystemError, cstr!("keepalive has no owner"));
             return ptr::null_mut();
         }
-        (*(*self_).owner).keepalive.clear();
+        if (*(*self_).owner).refuse_clear_while_attached() {
+            return ptr::null_mut();
+        }
+        KeepaliveVec::clear(ptr::addr_of_mut!((*(*self_).owner).keepalive));
         Py_None().newref()
+    })
+}
+
+fn init_subclass_method() -> PyMethodDef {
+    PyMethodDef {
+        ml_name: cstr!("__init_subclass__"),
+        ml_meth: PyMethodDefPointer {
+            PyCFunctionWithKeywords: proxy_init_subclass,
+        },
+        ml_flags: METH_CLASS | METH_VARARGS | METH_KEYWORDS,
+        ml_doc: cstr!("__init_subclass__(cls, /, **kwargs)\n--\n\nRaises TypeError: use copium.extra.CustomMemo to customize memoization."),
     }
 }
 
@@ -204,7 +273,7 @@ unsafe fn init_keepalive_methods() {
                 PyCFunction: keepalive_list_append,
             },
             ml_flags: METH_O,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("append($self, obj, /)\n--\n\nKeep obj alive until the copy finishes."),
         };
         KEEPALIVE_LIST_METHODS_TABLE[1] = PyMethodDef {
             ml_name: cstr!("clear"),
@@ -212,9 +281,34 @@ unsafe fn init_keepalive_methods() {
                 PyCFunction: keepalive_list_clear_py,
             },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("clear($self, /)\n--\n\nRelease everything kept alive so far."),
+        };
+        KEEPALIVE_LIST_METHODS_TABLE[2] = PyMethodDef {
+            ml_name: cstr!("__reduce__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: proxy_reduce,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: cstr!("__reduce__($self, /)\n--\n\nRaises TypeError: the keepalive list cannot be pickled."),
         };
-        KEEPALIVE_LIST_METHODS_TABLE[2] = PyMethodDef::zeroed();
+        KEEPALIVE_LIST_METHODS_TABLE[3] = PyMethodDef {
+            ml_name: cstr!("__copy__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: keepalive_list_snapshot,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: cstr!("__copy__($self, /)\n--\n\nA list of what is currently kept alive."),
+        };
+        KEEPALIVE_LIST_METHODS_TABLE[4] = PyMethodDef {
+            ml_name: cstr!("__deepcopy__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: keepalive_list_snapshot,
+            },
+            ml_flags: METH_O,
+            ml_doc: cstr!("__deepcopy__($self, memo, /)\n--\n\nA list of what is currently kept alive; its items are not copied."),
+        };
+        KEEPALIVE_LIST_METHODS_TABLE[5] = init_subclass_method();
+        KEEPALIVE_LIST_METHODS_TABLE[6] = PyMethodDef::zeroed();
 
         KEEPALIVE_LIST_SEQUENCE = PySequenceMethods {
             sq_length: Some(keepalive_list_len),
@@ -231,6 +325,10 @@ unsafe fn init_keepalive_methods() {
 
         let tp = ptr::addr_of_mut!(KEEPALIVE_LIST_TYPE);
         (*tp).tp_name = cstr!("copium.keepalive");
+        (*tp).tp_doc = cstr!(
+            "The originals a copy keeps alive while it runs, found at memo[id(memo)].\n\n\
+             Behaves like the list stdlib's deepcopy keeps there."
+        );
         (*tp).tp_basicsize = size_of::<PyKeepaliveListObject>() as Py_ssize_t;
         (*tp).tp_dealloc = Some(keepalive_list_dealloc);
         (*tp).tp_repr = Some(keepalive_list_repr);
@@ -238,13 +336,13 @@ unsafe fn init_keepalive_methods() {
         #[cfg(Py_GIL_DISABLED)]
         {
             (*tp).tp_flags.store(
-                Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC,
+                Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | Py_TPFLAGS_BASETYPE,
                 core::sync::atomic::Ordering::Relaxed,
             );
         }
         #[cfg(not(Py_GIL_DISABLED))]
         {
-            (*tp).tp_flags = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC;
+            (*tp).tp_flags = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | Py_TPFLAGS_BASETYPE;
         }
         (*tp).tp_traverse = Some(keepalive_list_traverse);
         (*tp).tp_clear = Some(keepalive_list_clear);
@@ -272,12 +370,18 @@ unsafe extern "C" fn memo_dealloc(obj: *mut PyObject) {
 unsafe extern "C" fn memo_finalize(obj: *mut PyObject) {
     unsafe {
         let self_ = obj as *mut PyMemoObject;
-        (*self_).table.clear();
-        (*self_).keepalive.clear();
+        // A finalizer can't raise; a memo a deepcopy() is using is left alone,
+        // and released by the call once it is done with it.
+        if (*self_).attached {
+            return;
+        }
+        PyMemoObject::clear_entries(self_);
+        KeepaliveVec::clear(ptr::addr_of_mut!((*self_).keepalive));
         if !(*self_).dict_proxy.is_null() {
             (*self_).dict_proxy.decref();
             (*self_).dict_proxy = ptr::null_mut();
         }
+        (&mut (*self_).dict_view as *mut *mut PyObject).clear();
     }
 }
 
@@ -318,6 +422,13 @@ unsafe extern "C" fn memo_traverse(
             }
         }
 
+        if !inner.dict_view.is_null() {
+            let rc = visit(inner.dict_view, arg);
+            if rc != 0 {
+                return rc;
+            }
+        }
+
         0
     }
 }
@@ -325,12 +436,13 @@ unsafe extern "C" fn memo_traverse(
 unsafe extern "C" fn memo_clear_gc(obj: *mut PyObject) -> std::ffi::c_int {
     unsafe {
         let self_ = obj as *mut PyMemoObject;
-        (*self_).table.clear();
-        (*self_).keepalive.clear();
+        PyMemoObject::clear_entries(self_);
+        KeepaliveVec::clear(ptr::addr_of_mut!((*self_).keepalive));
         if !(*self_).dict_proxy.is_null() {
             (*self_).dict_proxy.decref();
             (*self_).dict_proxy = ptr::null_mut();
         }
+        (&mut (*self_).dict_view as *mut *mut PyObject).clear();
         0
     }
 }
@@ -339,8 +451,44 @@ unsafe extern "C" fn memo_clear_gc(obj: *mut PyObject) -> std::ffi::c_int {
 //  tp_repr / tp_iter
 // ══════════════════════════════════════════════════════════════
 
-unsafe extern "C" fn memo_repr(obj: *mut PyObject) -> *mut PyObject {
+/// Plain dict of the current contents; the keepalive entry becomes a list.
+unsafe fn memo_snapshot(self_: *mut PyMemoObject) -> *mut PyObject {
     unsafe {
+        let dict = (*self_).to_dict();
+        if dict.is_null() || (*self_).keepalive.items.is_empty() {
+            return dict;
+        }
+
+        let py_key = PyLong_FromVoidPtr(self_ as *mut c_void);
+        if py_key.is_null() {
+            dict.decref();
+            return ptr::null_mut();
+        }
+
+        let items = &(*self_).keepalive.items;
+        let keepalive = PyList_New(items.len() as Py_ssize_t);
+        if keepalive.is_null() {
+            py_key.decref();
+            dict.decref();
+            return ptr::null_mut();
+        }
+        for (i, &item) in items.iter().enumerate() {
+            PyList_SET_ITEM(keepalive, i as Py_ssize_t, item.newref());
+        }
+
+        let status = PyDict_SetItem(dict, py_key, keepalive);
+        keepalive.decref();
+        py_key.decref();
+        if status < 0 {
+            dict.decref();
+            return ptr::null_mut();
+        }
+        dict
+    }
+}
+
+unsafe extern "C" fn memo_repr(obj: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
         let dict = (*self_).to_dict();
         if dict.is_null() {
@@ -378,14 +526,32 @@ unsafe extern "C" fn memo_repr(obj: *mut PyObject) -> *mut PyObject {
             return ptr::null_mut();
         }
 
-        let wrapped = PyUnicode_FromFormat(cstr!("memo(%U)"), inner);
+        let wrapped = PyUnicode_FromFormat(
+            cstr!("memo(%U, attached=%s, entries=%zd, keepalive=%zd)"),
+            inner,
+            if (*self_).attached {
+                cstr!("True")
+            } else {
+                cstr!("False")
+            },
+            (*self_).table.used as Py_ssize_t,
+            (*self_).keepalive.items.len() as Py_ssize_t,
+        );
         inner.decref();
         wrapped
-    }
+    })
+}
+
+/// `__copy__` / `__deepcopy__`: the proxy itself must not outlive its call,
+/// but a snapshot of what it holds is safe to keep.
+unsafe extern "C" fn memo_py_snapshot(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        memo_snapshot(obj as *mut PyMemoObject)
+    })
 }
 
 unsafe extern "C" fn memo_iter(obj: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
         let table = &(*self_).table;
 
@@ -394,19 +560,14 @@ unsafe extern "C" fn memo_iter(obj: *mut PyObject) -> *mut PyObject {
             return ptr::null_mut();
         }
 
-        if !table.slots.is_null() {
-            for i in 0..table.size {
-                let entry = &*table.slots.add(i);
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
-                    if py_key.is_null() || PyList_Append(list, py_key) < 0 {
-                        py_key.decref_nullable();
-                        list.decref();
-                        return ptr::null_mut();
-                    }
-                    py_key.decref();
-                }
+        for entry in table.entries() {
+            let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
+            if py_key.is_null() || PyList_Append(list, py_key) < 0 {
+                py_key.decref_nullable();
+                list.decref();
+                return ptr::null_mut();
             }
+            py_key.decref();
         }
 
         if !(*self_).keepalive.items.is_empty() {
@@ -422,7 +583,7 @@ unsafe extern "C" fn memo_iter(obj: *mut PyObject) -> *mut PyObject {
         let it = PyObject_GetIter(list);
         list.decref();
         it
-    }
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -430,18 +591,18 @@ unsafe extern "C" fn memo_iter(obj: *mut PyObject) -> *mut PyObject {
 // ══════════════════════════════════════════════════════════════
 
 unsafe extern "C" fn memo_mp_length(obj: *mut PyObject) -> Py_ssize_t {
-    unsafe {
+    ffi_guard!(-1, unsafe {
         let self_ = obj as *mut PyMemoObject;
         let mut count = (*self_).table.used as Py_ssize_t;
         if !(*self_).keepalive.items.is_empty() {
             count += 1;
         }
         count
-    }
+    })
 }
 
 unsafe extern "C" fn memo_mp_subscript(obj: *mut PyObject, pykey: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
 
         if PyLong_Check(pykey) == 0 {
@@ -469,7 +630,7 @@ unsafe extern "C" fn memo_mp_subscript(obj: *mut PyObject, pykey: *mut PyObject)
         }
 
         found.newref()
-    }
+    })
 }
 
 unsafe extern "C" fn memo_mp_ass_subscript(
@@ -477,7 +638,7 @@ unsafe extern "C" fn memo_mp_ass_subscript(
     pykey: *mut PyObject,
     value: *mut PyObject,
 ) -> std::ffi::c_int {
-    unsafe {
+    ffi_guard!(-1, unsafe {
         let self_ = obj as *mut PyMemoObject;
 
         if PyLong_Check(pykey) == 0 {
@@ -496,32 +657,35 @@ unsafe extern "C" fn memo_mp_ass_subscript(
                     PyErr_SetObject(PyExc_KeyError, pykey);
                     return -1;
                 }
-                (*self_).keepalive.clear();
+                if (*self_).refuse_clear_while_attached() {
+                    return -1;
+                }
+                KeepaliveVec::clear(ptr::addr_of_mut!((*self_).keepalive));
                 return 0;
             }
 
-            let it = PyObject_GetIter(value);
-            if it.is_null() {
+            // Replacing the keepalive releases what it held, which may be the
+            // originals whose addresses the running copy's entries are keyed by.
+            if !(*self_).keepalive.items.is_empty() && (*self_).refuse_clear_while_attached() {
                 return -1;
             }
 
-            (*self_).keepalive.clear();
-
-            loop {
-                let item = PyIter_Next(it);
-                if item.is_null() {
-                    break;
-                }
-                (*self_).keepalive.append(item);
-                item.decref();
+            // Materialize first: a failing iterable must leave the keepalive intact.
+            let items = PySequence_List(value);
+            if items.is_null() {
+                return -1;
             }
 
-            it.decref();
+            KeepaliveVec::clear(ptr::addr_of_mut!((*self_).keepalive));
 
-            if !PyErr_Occurred().is_null() {
-                return -1;
+            for i in 0..PyList_GET_SIZE(items) {
+                if (*self_).keepalive.append(PyList_GET_ITEM(items, i)) < 0 {
+                    items.decref();
+                    return -1;
+                }
             }
 
+            items.decref();
             return 0;
         }
 
@@ -534,8 +698,11 @@ unsafe extern "C" fn memo_mp_ass_subscript(
             return 0;
         }
 
+        if STATE.memo_key_check && key_check::check(self_, key) < 0 {
+            return -1;
+        }
         (*self_).insert_logged(key, value, hash_pointer(key))
-    }
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -543,7 +710,7 @@ unsafe extern "C" fn memo_mp_ass_subscript(
 // ══════════════════════════════════════════════════════════════
 
 unsafe extern "C" fn memo_sq_contains(obj: *mut PyObject, pykey: *mut PyObject) -> std::ffi::c_int {
-    unsafe {
+    ffi_guard!(-1, unsafe {
         let self_ = obj as *mut PyMemoObject;
 
         if PyLong_Check(pykey) == 0 {
@@ -566,7 +733,7 @@ unsafe extern "C" fn memo_sq_contains(obj: *mut PyObject, pykey: *mut PyObject)
         } else {
             1
         }
-    }
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -574,16 +741,28 @@ unsafe extern "C" fn memo_sq_contains(obj: *mut PyObject, pykey: *mut PyObject)
 // ══════════════════════════════════════════════════════════════
 
 unsafe extern "C" fn memo_py_clear(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
-        (*self_).table.clear();
-        (*self_).keepalive.clear();
+        if (*self_).refuse_clear_while_attached() {
+            return ptr::null_mut();
+        }
+        PyMemoObject::clear_entries(self_);
+        KeepaliveVec::clear(ptr::addr_of_mut!((*self_).keepalive));
         if !(*self_).dict_proxy.is_null() {
             (*self_).dict_proxy.decref();
             (*self_).dict_proxy = ptr::null_mut();
         }
+        (&mut (*self_).dict_view as *mut *mut PyObject).clear();
         Py_None().newref()
-    }
+    })
+}
+
+unsafe extern "C" fn memo_py_sizeof(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let self_ = obj as *mut PyMemoObject;
+        let basic = (*obj.class()).tp_basicsize as usize;
+        PyLong_FromSize_t(basic + (*self_).heap_bytes())
+    })
 }
 
 unsafe extern "C" fn memo_py_get(
@@ -591,7 +770,7 @@ unsafe extern "C" fn memo_py_get(
     args: *mut *mut PyObject,
     nargs: Py_ssize_t,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         if nargs < 1 || nargs > 2 {
             PyErr_SetString(PyExc_TypeError, cstr!("get expected 1 or 2 arguments"));
             return ptr::null_mut();
@@ -610,6 +789,10 @@ unsafe extern "C" fn memo_py_get(
             return ptr::null_mut();
         }
 
+        if key == self_ as usize && !(*self_).keepalive.items.is_empty() {
+            return memo_keepalive_proxy(self_);
+        }
+
         let found = (*self_).table.lookup_h(key, hash_pointer(key));
         if !found.is_null() {
             return found.newref();
@@ -620,21 +803,21 @@ unsafe extern "C" fn memo_py_get(
         }
 
         Py_None().newref()
-    }
+    })
 }
 
 unsafe extern "C" fn memo_py_contains(obj: *mut PyObject, pykey: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let result = memo_sq_contains(obj, pykey);
         if result < 0 {
             return ptr::null_mut();
         }
         PyBool_FromLong(result as _)
-    }
+    })
 }
 
 unsafe extern "C" fn memo_py_values(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
         let mut n = (*self_).table.used as Py_ssize_t;
         if !(*self_).keepalive.items.is_empty() {
@@ -647,15 +830,10 @@ unsafe extern "C" fn memo_py_values(obj: *mut PyObject, _: *mut PyObject) -> *mu
         }
 
         let mut idx: Py_ssize_t = 0;
-        if !(*self_).table.slots.is_null() {
-            for i in 0..(*self_).table.size {
-                let entry = &*(*self_).table.slots.add(i);
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    entry.value.incref();
-                    PyList_SetItem(list, idx, entry.value);
-                    idx += 1;
-                }
-            }
+        for entry in (*self_).table.entries() {
+            entry.value.incref();
+            PyList_SetItem(list, idx, entry.value);
+            idx += 1;
         }
 
         if !(*self_).keepalive.items.is_empty() {
@@ -668,11 +846,11 @@ unsafe extern "C" fn memo_py_values(obj: *mut PyObject, _: *mut PyObject) -> *mu
         }
 
         list
-    }
+    })
 }
 
 unsafe extern "C" fn memo_py_keys(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
         let mut n = (*self_).table.used as Py_ssize_t;
         if !(*self_).keepalive.items.is_empty() {
@@ -685,19 +863,14 @@ unsafe extern "C" fn memo_py_keys(obj: *mut PyObject, _: *mut PyObject) -> *mut
         }
 
         let mut idx: Py_ssize_t = 0;
-        if !(*self_).table.slots.is_null() {
-            for i in 0..(*self_).table.size {
-                let entry = &*(*self_).table.slots.add(i);
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
-                    if py_key.is_null() {
-                        list.decref();
-                        return ptr::null_mut();
-                    }
-                    PyList_SetItem(list, idx, py_key);
-                    idx += 1;
-                }
+        for entry in (*self_).table.entries() {
+            let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
+            if py_key.is_null() {
+                list.decref();
+                return ptr::null_mut();
             }
+            PyList_SetItem(list, idx, py_key);
+            idx += 1;
         }
 
         if !(*self_).keepalive.items.is_empty() {
@@ -710,11 +883,11 @@ unsafe extern "C" fn memo_py_keys(obj: *mut PyObject, _: *mut PyObject) -> *mut
         }
 
         list
-    }
+    })
 }
 
 unsafe extern "C" fn memo_py_items(obj: *mut PyObject, _: *mut PyObject) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         let self_ = obj as *mut PyMemoObject;
         let mut n = (*self_).table.used as Py_ssize_t;
         if !(*self_).keepalive.items.is_empty() {
@@ -727,28 +900,23 @@ unsafe extern "C" fn memo_py_items(obj: *mut PyObject, _: *mut PyObject) -> *mut
         }
 
         let mut idx: Py_ssize_t = 0;
-        if !(*self_).table.slots.is_null() {
-            for i in 0..(*self_).table.size {
-                let entry = &*(*self_).table.slots.add(i);
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
-                    if py_key.is_null() {
-                        list.decref();
-                        return ptr::null_mut();
-                    }
-                    let pair = PyTuple_New(2);
-                    if pair.is_null() {
-                        py_key.decref();
-                        list.decref();
-                        return ptr::null_mut();
-                    }
-                    PyTuple_SetItem(pair, 0, py_key);
-                    entry.value.incref();
-                    PyTuple_SetItem(pair, 1, entry.value);
-                    PyList_SetItem(list, idx, pair);
-                    idx += 1;
-                }
+        for entry in (*self_).table.entries() {
+            let py_key = PyLong_FromVoidPtr(entry.key as *mut c_void);
+            if py_key.is_null() {
+                list.decref();
+                return ptr::null_mut();
+            }
+            let pair = PyTuple_New(2);
+            if pair.is_null() {
+                py_key.decref();
+                list.decref();
+                return ptr::null_mut();
             }
+            PyTuple_SetItem(pair, 0, py_key);
+            entry.value.incref();
+            PyTuple_SetItem(pair, 1, entry.value);
+            PyList_SetItem(list, idx, pair);
+            idx += 1;
         }
 
         if !(*self_).keepalive.items.is_empty() {
@@ -776,7 +944,7 @@ unsafe extern "C" fn memo_py_items(obj: *mut PyObject, _: *mut PyObject) -> *mut
         }
 
         list
-    }
+    })
 }
 
 unsafe extern "C" fn memo_py_setdefault(
@@ -784,7 +952,7 @@ unsafe extern "C" fn memo_py_setdefault(
     args: *mut *mut PyObject,
     nargs: Py_ssize_t,
 ) -> *mut PyObject {
-    unsafe {
+    ffi_guard!(ptr::null_mut(), unsafe {
         if nargs < 1 || nargs > 2 {
             PyErr_SetString(
                 PyExc_TypeError,
@@ -810,18 +978,22 @@ unsafe extern "C" fn memo_py_setdefault(
             return memo_keepalive_proxy(self_);
         }
 
-        let found = (*self_).table.lookup_h(key, hash_pointer(key));
+        let hash = hash_pointer(key);
+        let found = (*self_).table.lookup_h(key, hash);
         if !found.is_null() {
             return found.newref();
         }
 
+        if STATE.memo_key_check && key_check::check(self_, key) < 0 {
+            return ptr::null_mut();
+        }
         let default_value = if nargs == 2 { *args.add(1) } else { Py_None() };
-        if (*self_).insert_logged(key, default_value, hash_pointer(key)) < 0 {
+        if (*self_).insert_logged(key, default_value, hash) < 0 {
             return ptr::null_mut();
         }
 
         default_value.newref()
-    }
+    })
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -836,7 +1008,7 @@ unsafe fn init_memo_methods() {
                 PyCFunction: memo_py_clear,
             },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("clear($self, /)\n--\n\nRemove all entries, like dict.clear()."),
         };
         MEMO_METHODS_TABLE[1] = PyMethodDef {
             ml_name: cstr!("get"),
@@ -844,7 +1016,7 @@ unsafe fn init_memo_methods() {
                 PyCFunctionFast: memo_py_get,
             },
             ml_flags: METH_FASTCALL,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("get($self, key, default=None, /)\n--\n\nThe copy memoized under id key, else default. Keys must be ints."),
         };
         MEMO_METHODS_TABLE[2] = PyMethodDef {
             ml_name: cstr!("values"),
@@ -852,7 +1024,7 @@ unsafe fn init_memo_methods() {
                 PyCFunction: memo_py_values,
             },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("values($self, /)\n--\n\nA list of the memoized copies, in the order they were memoized."),
         };
         MEMO_METHODS_TABLE[3] = PyMethodDef {
             ml_name: cstr!("keys"),
@@ -860,7 +1032,7 @@ unsafe fn init_memo_methods() {
                 PyCFunction: memo_py_keys,
             },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("keys($self, /)\n--\n\nA list of the ids of memoized originals, in the order they were memoized."),
         };
         MEMO_METHODS_TABLE[4] = PyMethodDef {
             ml_name: cstr!("items"),
@@ -868,7 +1040,7 @@ unsafe fn init_memo_methods() {
                 PyCFunction: memo_py_items,
             },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("items($self, /)\n--\n\nA list of (id, copy) pairs, in the order they were memoized."),
         };
         MEMO_METHODS_TABLE[5] = PyMethodDef {
             ml_name: cstr!("setdefault"),
@@ -876,7 +1048,7 @@ unsafe fn init_memo_methods() {
                 PyCFunctionFast: memo_py_setdefault,
             },
             ml_flags: METH_FASTCALL,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("setdefault($self, key, default=None, /)\n--\n\nLike dict.setdefault(). Keys must be ints."),
         };
         MEMO_METHODS_TABLE[6] = PyMethodDef {
             ml_name: cstr!("__contains__"),
@@ -884,17 +1056,54 @@ unsafe fn init_memo_methods() {
                 PyCFunction: memo_py_contains,
             },
             ml_flags: METH_O,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("__contains__($self, key, /)\n--\n\nWhether id key has a memoized copy."),
         };
         MEMO_METHODS_TABLE[7] = PyMethodDef {
             ml_name: cstr!("__del__"),
             ml_meth: PyMethodDefPointer {
                 PyCFunction: memo_py_clear,
             },
+            // Takes the name from the tp_finalize wrapper, which can't raise
+            // when the memo is in use.
+            ml_flags: METH_NOARGS | METH_COEXIST,
+            ml_doc: cstr!("__del__($self, /)\n--\n\nRemove all entries, like clear()."),
+        };
+        MEMO_METHODS_TABLE[8] = PyMethodDef {
+            ml_name: cstr!("__reduce__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: proxy_reduce,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: cstr!(
+                "__reduce__($self, /)\n--\n\nRaises TypeError: the memo cannot be pickled."
+            ),
+        };
+        MEMO_METHODS_TABLE[9] = PyMethodDef {
+            ml_name: cstr!("__copy__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: memo_py_snapshot,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: cstr!("__copy__($self, /)\n--\n\nA dict snapshot of the entries, with the keepalive list as a plain list."),
+        };
+        MEMO_METHODS_TABLE[10] = PyMethodDef {
+            ml_name: cstr!("__deepcopy__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: memo_py_snapshot,
+            },
+            ml_flags: METH_O,
+            ml_doc: cstr!("__deepcopy__($self, memo, /)\n--\n\nA dict snapshot of the entries, like __copy__(); the entries are not copied."),
+        };
+        MEMO_METHODS_TABLE[11] = PyMethodDef {
+            ml_name: cstr!("__sizeof__"),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: memo_py_sizeof,
+            },
             ml_flags: METH_NOARGS,
-            ml_doc: ptr::null(),
+            ml_doc: cstr!("__sizeof__($self, /)\n--\n\nSize in bytes, including the native table and vectors."),
         };
-        MEMO_METHODS_TABLE[8] = PyMethodDef::zeroed();
+        MEMO_METHODS_TABLE[12] = init_subclass_method();
+        MEMO_METHODS_TABLE[13] = PyMethodDef::zeroed();
     }
 }
 
@@ -917,6 +1126,12 @@ pub unsafe fn memo_ready_type() -> i32 {
 
         let tp = ptr::addr_of_mut!(Memo_Type);
         (*tp).tp_name = cstr!("copium.memo");
+        (*tp).tp_doc = cstr!(
+            "The memo copium passes to __deepcopy__: a mapping from id(original) to its \
+             copy, with the keepalive list under id(memo), like the dict stdlib's deepcopy \
+             passes.\n\n\
+             Treat it as opaque and hand it to any deepcopy() calls made from there."
+        );
         (*tp).tp_basicsize = std::mem::size_of::<PyMemoObject>() as Py_ssize_t;
         (*tp).tp_dealloc = Some(memo_dealloc);
         (*tp).tp_repr = Some(memo_repr);
@@ -925,13 +1140,13 @@ pub unsafe fn memo_ready_type() -> i32 {
         #[cfg(Py_GIL_DISABLED)]
         {
             (*tp).tp_flags.store(
-                Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC,
+                Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | Py_TPFLAGS_BASETYPE,
                 core::sync::atomic::Ordering::Relaxed,
             );
         }
         #[cfg(not(Py_GIL_DISABLED))]
         {
-            (*tp).tp_flags = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC;
+            (*tp).tp_flags = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_HAVE_GC | Py_TPFLAGS_BASETYPE;
         }
         (*tp).tp_traverse = Some(memo_traverse);
         (*tp).tp_clear = Some(memo_clear_gc);
@@ -2,6 +2,7 @@ use pyo3_ffi::*;
 use std::hint::likely;
 use std::ptr;
 
+use crate::state::STATE;
 use crate::types::PyObjectPtr;
 
 pub(crate) const TOMBSTONE: usize = usize::MAX;
@@ -16,11 +17,15 @@ pub(crate) struct MemoEntry {
     pub(crate) value: *mut PyObject,
 }
 
+#[repr(C)]
 pub struct MemoTable {
     pub(crate) slots: *mut MemoEntry,
     pub(crate) size: usize,
     pub(crate) used: usize,
     pub(crate) filled: usize,
+    /// Slot indices of live entries in insertion order, so iteration follows
+    /// traversal order instead of address order.
+    order: Vec<u32>,
 }
 
 #[inline(always)]
@@ -41,6 +46,30 @@ impl MemoTable {
             size: 0,
             used: 0,
             filled: 0,
+            order: Vec::new(),
+        }
+    }
+
+    /// Bytes held outside the owning object: slot array plus insertion order.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.size * std::mem::size_of::<MemoEntry>()
+            + self.order.capacity() * std::mem::size_of::<u32>()
+    }
+
+    /// Live entries in the order their keys were first inserted.
+    #[inline]
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &MemoEntry> + '_ {
+        self.order
+            .iter()
+            .map(move |&i| unsafe { &*self.slots.add(i as usize) })
+    }
+
+    /// Grows the table once ahead of `additional` inserts instead of doubling
+    /// its way there. Only a hint: on allocation failure the table stays as is.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.used.saturating_add(additional);
+        if needed * 10 >= self.size * 7 {
+            let _ = self.resize(needed);
         }
     }
 
@@ -51,13 +80,17 @@ impl MemoTable {
         self.resize(1)
     }
 
+    /// Returns -1 without an exception set if the new slots can't be allocated;
+    /// the table is left as it was.
     fn resize(&mut self, min_needed: usize) -> i32 {
         let mut new_size = 8usize;
         while new_size < min_needed.saturating_mul(2) {
             new_size = new_size.saturating_mul(2);
         }
 
-        let layout = std::alloc::Layout::array::<MemoEntry>(new_size).unwrap();
+        let Ok(layout) = std::alloc::Layout::array::<MemoEntry>(new_size) else {
+            return -1;
+        };
         let new_slots = unsafe { std::alloc::alloc_zeroed(layout) as *mut MemoEntry };
         if new_slots.is_null() {
             return -1;
@@ -72,20 +105,46 @@ impl MemoTable {
         self.filled = 0;
 
         if !old_slots.is_null() {
-            for i in 0..old_size {
-                let entry = unsafe { &*old_slots.add(i) };
-                if entry.key != 0 && entry.key != TOMBSTONE {
-                    self.insert_no_grow(entry.key, entry.value);
-                }
+            // Rehome entries in insertion order, rewriting their indices in place.
+            for n in 0..self.order.len() {
+                let entry = unsafe { &*old_slots.add(self.order[n] as usize) };
+                self.order[n] = self.insert_no_grow(entry.key, entry.value) as u32;
             }
             let old_layout = std::alloc::Layout::array::<MemoEntry>(old_size).unwrap();
             unsafe { std::alloc::dealloc(old_slots as *mut u8, old_layout) };
         }
 
+        #[cfg(debug_assertions)]
+        self.check_counts();
         0
     }
 
-    fn insert_no_grow(&mut self, key: usize, value: *mut PyObject) {
+    /// Debug builds: `used` counts live entries, `filled` those plus
+    /// tombstones, and `order` lists each live slot exactly once.
+    #[cfg(debug_assertions)]
+    fn check_counts(&self) {
+        let (mut live, mut tombstones) = (0usize, 0usize);
+        for i in 0..self.size {
+            match unsafe { (*self.slots.add(i)).key } {
+                0 => {}
+                TOMBSTONE => tombstones += 1,
+                _ => live += 1,
+            }
+        }
+        debug_assert_eq!(live, self.used, "memo table lost track of its entries");
+        debug_assert_eq!(live + tombstones, self.filled, "memo table miscounted its tombstones");
+        debug_assert_eq!(self.order.len(), self.used, "memo table order out of step");
+        debug_assert!(self.order.iter().all(|&i| {
+            let key = unsafe { (*self.slots.add(i as usize)).key };
+            key != 0 && key != TOMBSTONE
+        }));
+        debug_assert!(self.filled * 10 < self.size * 7 || self.size == 0);
+    }
+
+    /// Re-home an entry during resize and return its new slot. Keys are unique
+    /// and the table already owns `value`, so the reference moves without
+    /// touching its count.
+    fn insert_no_grow(&mut self, key: usize, value: *mut PyObject) -> usize {
         let mask = self.size - 1;
         let mut idx = hash_pointer(key) & mask;
 
@@ -94,24 +153,29 @@ impl MemoTable {
             if entry.key == 0 {
                 entry.key = key;
                 entry.value = value;
-                unsafe { value.incref() };
                 self.used += 1;
                 self.filled += 1;
-                return;
-            }
-            if entry.key == key {
-                let old = entry.value;
-                entry.value = value;
-                unsafe {
-                    value.incref();
-                    old.decref_nullable();
-                }
-                return;
+                return idx;
             }
             idx = (idx + 1) & mask;
         }
     }
 
+    /// Pulls the first slot `key` probes into cache.
+    #[inline(always)]
+    pub fn prefetch(&self, key: usize) {
+        if std::hint::unlikely(self.slots.is_null()) {
+            return;
+        }
+        let slot = unsafe { self.slots.add(hash_pointer(key) & (self.size - 1)) };
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            std::arch::x86_64::_mm_prefetch(slot as *const i8, std::arch::x86_64::_MM_HINT_T0)
+        };
+        #[cfg(not(target_arch = "x86_64"))]
+        let _ = slot;
+    }
+
     #[inline(always)]
     pub fn lookup_h(&self, key: usize, hash: usize) -> *mut PyObject {
         if std::hint::unlikely(self.slots.is_null()) {
@@ -133,14 +197,16 @@ impl MemoTable {
         }
     }
 
+    /// Returns -1 with `MemoryError` set if the table can't grow, or
+    /// `CopyError` set once `config.apply(max_memo_entries=...)` is reached.
     #[inline(always)]
     pub fn insert_h(&mut self, key: usize, value: *mut PyObject, hash: usize) -> i32 {
         if std::hint::unlikely(self.ensure() < 0) {
-            return -1;
+            return no_memory();
         }
         if std::hint::unlikely(self.filled * 10 >= self.size * 7) {
             if self.resize(self.used + 1) < 0 {
-                return -1;
+                return no_memory();
             }
         }
 
@@ -151,6 +217,15 @@ impl MemoTable {
         loop {
             let entry = unsafe { &mut *self.slots.add(idx) };
             if likely(entry.key == 0) {
+                let limit = unsafe { STATE.max_memo_entries };
+                if std::hint::unlikely(self.used >= limit) {
+                    super::tss::record_limit_reached(self.used, self.size, 0);
+                    unsafe { raise_limit_exceeded(crate::cstr!("memo"), limit) };
+                    return -1;
+                }
+                if std::hint::unlikely(self.order.try_reserve(1).is_err()) {
+                    return no_memory();
+                }
                 let at = first_tomb.unwrap_or(idx);
                 let slot = unsafe { &mut *self.slots.add(at) };
                 slot.key = key;
@@ -158,6 +233,7 @@ impl MemoTable {
                 slot.value = value;
                 self.used += 1;
                 self.filled += 1;
+                self.order.push(at as u32);
                 return 0;
             }
             if std::hint::unlikely(entry.key == TOMBSTONE) {
@@ -191,58 +267,131 @@ impl MemoTable {
                 return -1;
             }
             if entry.key != TOMBSTONE && entry.key == key {
+                let value = entry.value;
                 entry.key = TOMBSTONE;
-                unsafe { entry.value.decref_nullable() };
                 entry.value = ptr::null_mut();
                 self.used -= 1;
+                // Removals are rare and mostly undo the latest inserts.
+                if let Some(pos) = self.order.iter().rposition(|&i| i as usize == idx) {
+                    self.order.remove(pos);
+                }
+                unsafe { value.decref_nullable() };
                 return 0;
             }
             idx = (idx + 1) & mask;
         }
     }
 
-    pub fn clear(&mut self) {
-        if self.slots.is_null() {
-            return;
-        }
+    /// Drops every entry, keeping the allocations for the next copy.
+    ///
+    /// Takes a raw pointer: releasing a value can run a `__del__` that reaches
+    /// this table through a retained proxy, so no `&mut` may be live across it.
+    pub unsafe fn clear(this: *mut Self) {
+        unsafe {
+            if (*this).slots.is_null() {
+                return;
+            }
 
-        for i in 0..self.size {
-            let entry = unsafe { &*self.slots.add(i) };
-            if likely(entry.key != 0 && entry.key != TOMBSTONE) {
-                unsafe { entry.value.decref_nullable() };
+            // Detach everything before releasing any of it, so such a __del__
+            // finds an empty table rather than one half-way through this loop.
+            let slots = ptr::replace(ptr::addr_of_mut!((*this).slots), ptr::null_mut());
+            let size = ptr::replace(ptr::addr_of_mut!((*this).size), 0);
+            let mut order = ptr::replace(ptr::addr_of_mut!((*this).order), Vec::new());
+            (*this).used = 0;
+            (*this).filled = 0;
+
+            while let Some(i) = order.pop() {
+                (*slots.add(i as usize)).value.decref_nullable();
+            }
+
+            // Keep the slots unless a __del__ stored entries meanwhile, which
+            // gave the table new ones.
+            if (*this).slots.is_null() {
+                ptr::write_bytes(slots, 0, size);
+                (*this).slots = slots;
+                (*this).size = size;
+                (*this).order = order;
+            } else {
+                let layout = std::alloc::Layout::array::<MemoEntry>(size).unwrap();
+                std::alloc::dealloc(slots as *mut u8, layout);
             }
+            #[cfg(debug_assertions)]
+            (*this).check_counts();
         }
-        unsafe { ptr::write_bytes(self.slots, 0, self.size) };
-        self.used = 0;
-        self.filled = 0;
     }
 
-    pub fn reset(&mut self) {
-        self.clear();
-        if self.size > MEMO_RETAIN_MAX_SLOTS {
-            let _ = self.resize(MEMO_RETAIN_SHRINK_TO / 2);
+    /// [`clear`](Self::clear), but an oversized table is replaced by a small
+    /// one. Raw pointer for the same reason.
+    pub unsafe fn reset(this: *mut Self) {
+        unsafe {
+            if (*this).size <= MEMO_RETAIN_MAX_SLOTS {
+                Self::clear(this);
+                return;
+            }
+
+            // An oversized table is about to be replaced anyway: swap in a small
+            // one first and release the old slots from the side, skipping the
+            // zeroing pass clear() would spend on memory that is freed next.
+            let slots = ptr::replace(ptr::addr_of_mut!((*this).slots), ptr::null_mut());
+            let size = ptr::replace(ptr::addr_of_mut!((*this).size), 0);
+            (*this).used = 0;
+            (*this).filled = 0;
+            (*this).order.clear();
+            (*this).order.shrink_to(MEMO_RETAIN_SHRINK_TO);
+            let _ = (*this).resize(MEMO_RETAIN_SHRINK_TO / 2);
+            release_slots(slots, size);
         }
     }
 }
 
-impl Drop for MemoTable {
-    fn drop(&mut self) {
-        if self.slots.is_null() {
-            return;
-        }
+/// Raises `CopyError` for a memo or keepalive that reached its configured size.
+#[cold]
+#[inline(never)]
+unsafe fn raise_limit_exceeded(what: *const std::os::raw::c_char, limit: usize) {
+    unsafe {
+        PyErr_Format(
+            crate::reduce::copy_error(),
+            crate::cstr!("%s limit exceeded (%zu entries); object graph too large"),
+            what,
+            limit,
+        );
+    }
+}
 
-        for i in 0..self.size {
-            let entry = unsafe { &*self.slots.add(i) };
-            if entry.key != 0 && entry.key != TOMBSTONE {
-                unsafe { entry.value.decref_nullable() };
-            }
+/// Releases every live value of a slot array no table refers to anymore, then
+/// frees it.
+unsafe fn release_slots(slots: *mut MemoEntry, size: usize) {
+    if slots.is_null() {
+        return;
+    }
+
+    for i in 0..size {
+        let entry = unsafe { &*slots.add(i) };
+        if entry.key != 0 && entry.key != TOMBSTONE {
+            unsafe { entry.value.decref_nullable() };
         }
+    }
+
+    let layout = std::alloc::Layout::array::<MemoEntry>(size).unwrap();
+    unsafe { std::alloc::dealloc(slots as *mut u8, layout) };
+}
 
-        let layout = std::alloc::Layout::array::<MemoEntry>(self.size).unwrap();
-        unsafe { std::alloc::dealloc(self.slots as *mut u8, layout) };
+impl Drop for MemoTable {
+    fn drop(&mut self) {
+        let slots = std::mem::replace(&mut self.slots, ptr::null_mut());
+        let size = std::mem::replace(&mut self.size, 0);
+        unsafe { release_slots(slots, size) };
     }
 }
 
+/// Sets `MemoryError` for a failed native allocation and returns -1. The copy
+/// stops there: going on without the entry would break sharing in the result.
+#[cold]
+fn no_memory() -> i32 {
+    unsafe { PyErr_NoMemory() };
+    -1
+}
+
 // ── KeepaliveVec ───────────────────────────────────────────
 
 pub struct KeepaliveVec {
@@ -254,16 +403,51 @@ impl KeepaliveVec {
         Self { items: Vec::new() }
     }
 
-    pub fn append(&mut self, obj: *mut PyObject) {
+    /// Returns -1 with `CopyError` set once `config.apply(max_keepalive=...)`
+    /// is reached, or with `MemoryError` set if the vector can't grow.
+    #[inline(always)]
+    pub fn append(&mut self, obj: *mut PyObject) -> i32 {
+        let limit = unsafe { STATE.max_keepalive };
+        if std::hint::unlikely(self.items.len() >= limit) {
+            super::tss::record_limit_reached(0, 0, self.items.len());
+            unsafe { raise_limit_exceeded(crate::cstr!("keepalive"), limit) };
+            return -1;
+        }
+        if std::hint::unlikely(self.items.try_reserve(1).is_err()) {
+            return no_memory();
+        }
         unsafe { obj.incref() };
         self.items.push(obj);
+        0
     }
 
-    pub fn clear(&mut self) {
-        for &item in &self.items {
-            unsafe { item.decref() };
+    /// Releases every item, and whatever a `__del__` appends through the
+    /// keepalive proxy meanwhile. Raw pointer like [`MemoTable::clear`].
+    pub unsafe fn clear(this: *mut Self) {
+        unsafe {
+            loop {
+                // Detach the buffer first so such an append can't reallocate
+                // it mid-iteration; it goes into a fresh one instead.
+                let mut items = ptr::replace(ptr::addr_of_mut!((*this).items), Vec::new());
+                if items.is_empty() {
+                    (*this).items = items;
+                    return;
+                }
+                for &item in &items {
+                    item.decref();
+                }
+                items.clear();
+                // Keep the buffer unless something was appended; release that first.
+                if (*this).items.is_empty() {
+                    (*this).items = items;
+                    return;
+                }
+            }
         }
-        self.items.clear();
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.items.capacity() * std::mem::size_of::<*mut PyObject>()
     }
 
     pub fn shrink_if_large(&mut self) {
@@ -275,7 +459,7 @@ impl KeepaliveVec {
 
 impl Drop for KeepaliveVec {
     fn drop(&mut self) {
-        self.clear();
+        unsafe { Self::clear(self) };
     }
 }
 
@@ -290,14 +474,23 @@ impl UndoLog {
         Self { keys: Vec::new() }
     }
 
-    pub(super) fn append(&mut self, key: usize) {
+    /// Returns -1 with `MemoryError` set if the log can't grow.
+    pub(super) fn append(&mut self, key: usize) -> i32 {
+        if std::hint::unlikely(self.keys.try_reserve(1).is_err()) {
+            return no_memory();
+        }
         self.keys.push(key);
+        0
     }
 
     pub fn clear(&mut self) {
         self.keys.clear();
     }
 
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.keys.capacity() * std::mem::size_of::<usize>()
+    }
+
     pub fn shrink_if_large(&mut self) {
         if self.keys.capacity() > KEEP_RETAIN_MAX {
             self.keys.shrink_to(KEEP_RETAIN_TARGET);
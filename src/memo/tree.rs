@@ -0,0 +1,63 @@
+use pyo3_ffi::*;
+use std::ptr;
+
+use super::Memo;
+use crate::types::PyObjectPtr;
+
+/// Memo for `deepcopy(..., assume_tree=True)`: remembers nothing, so memory
+/// stays O(depth). Shared references are copied once per occurrence, and a
+/// cycle recurses until the stack guard stops it.
+pub struct TreeMemo {
+    /// Handed to custom `__deepcopy__`; replaced on every call so that nothing
+    /// recorded there outlives the call that recorded it.
+    call_arg: *mut PyObject,
+}
+
+impl TreeMemo {
+    pub fn new() -> Self {
+        Self {
+            call_arg: ptr::null_mut(),
+        }
+    }
+}
+
+impl Memo for TreeMemo {
+    type Probe = ();
+    const RECALL_CAN_ERROR: bool = false;
+
+    #[inline(always)]
+    unsafe fn recall(&mut self, _object: *mut PyObject) -> ((), *mut PyObject) {
+        ((), ptr::null_mut())
+    }
+
+    #[inline(always)]
+    unsafe fn memoize(
+        &mut self,
+        _original: *mut PyObject,
+        _copy: *mut PyObject,
+        _probe: &(),
+    ) -> i32 {
+        0
+    }
+
+    #[inline(always)]
+    unsafe fn forget(&mut self, _original: *mut PyObject, _probe: &()) {}
+
+    unsafe fn as_call_arg(&mut self) -> *mut PyObject {
+        unsafe {
+            let dict = PyDict_New();
+            if dict.is_null() {
+                return ptr::null_mut();
+            }
+            self.call_arg.decref_nullable();
+            self.call_arg = dict;
+            dict
+        }
+    }
+}
+
+impl Drop for TreeMemo {
+    fn drop(&mut self) {
+        unsafe { self.call_arg.decref_nullable() };
+    }
+}
@@ -10,6 +10,102 @@ use crate::types::PyObjectPtr;
 #[thread_local]
 static mut TSS_MEMO: *mut PyMemoObject = ptr::null_mut();
 
+/// Frees this thread's memo when the thread exits. Registered on the first
+/// allocation, so threads that never deepcopy pay nothing.
+struct ThreadMemoOwner;
+
+impl Drop for ThreadMemoOwner {
+    fn drop(&mut self) {
+        unsafe { release_thread_memo() };
+    }
+}
+
+thread_local! {
+    static THREAD_MEMO_OWNER: ThreadMemoOwner = const { ThreadMemoOwner };
+}
+
+/// Runs from a TLS destructor, without the GIL and possibly after the
+/// thread's Python state is gone. The table and vectors are plain allocations
+/// and are freed regardless; the object itself is released under a fresh
+/// GIL state unless the interpreter is finalizing, where it is left to leak.
+#[cold]
+unsafe fn release_thread_memo() {
+    unsafe {
+        let memo = TSS_MEMO;
+        if memo.is_null() {
+            return;
+        }
+        TSS_MEMO = ptr::null_mut();
+
+        if memo.refcount() != 1 || !(*memo).release_buffers() {
+            return;
+        }
+        if Py_IsInitialized() == 0 || crate::ffi_ext::is_finalizing() {
+            return;
+        }
+        let gil = PyGILState_Ensure();
+        memo.decref();
+        PyGILState_Release(gil);
+    }
+}
+
+/// Largest memo this thread has finished a deepcopy() with since the last
+/// `reset_highwater`, measured right before the memo is reset.
+#[derive(Clone, Copy)]
+pub struct MemoHighwater {
+    pub used: usize,
+    pub slots: usize,
+    pub keepalive: usize,
+}
+
+#[thread_local]
+static mut HIGHWATER: MemoHighwater = MemoHighwater {
+    used: 0,
+    slots: 0,
+    keepalive: 0,
+};
+
+#[inline(always)]
+unsafe fn record_highwater(memo: &PyMemoObject) {
+    unsafe {
+        let mark = &mut *ptr::addr_of_mut!(HIGHWATER);
+        mark.used = mark.used.max(memo.table.used);
+        mark.slots = mark.slots.max(memo.table.size);
+        mark.keepalive = mark.keepalive.max(memo.keepalive.items.len());
+    }
+}
+
+/// Raises the marks to a table or keepalive that has just hit its limit,
+/// before the failed copy's cleanup takes entries back out.
+#[cold]
+pub(super) fn record_limit_reached(used: usize, slots: usize, keepalive: usize) {
+    unsafe {
+        let mark = &mut *ptr::addr_of_mut!(HIGHWATER);
+        mark.used = mark.used.max(used);
+        mark.slots = mark.slots.max(slots);
+        mark.keepalive = mark.keepalive.max(keepalive);
+    }
+}
+
+pub fn highwater() -> MemoHighwater {
+    unsafe { HIGHWATER }
+}
+
+pub fn reset_highwater() {
+    unsafe {
+        HIGHWATER = MemoHighwater {
+            used: 0,
+            slots: 0,
+            keepalive: 0,
+        };
+    }
+}
+
+/// This thread's reusable memo, null until its first deepcopy().
+pub fn thread_memo() -> *mut PyMemoObject {
+    unsafe { TSS_MEMO }
+}
+
 pub unsafe fn pymemo_alloc() -> *mut PyMemoObject {
     unsafe {
         let memo = PyObject_GC_New::<PyMemoObject>(ptr::addr_of_mut!(Memo_Type));
@@ -32,24 +128,36 @@ pub unsafe fn get_memo() -> (*mut PyMemoObject, bool) {
                 return (ptr::null_mut(), false);
             }
             TSS_MEMO = fresh;
+            THREAD_MEMO_OWNER.with(|_| {});
+            (*fresh).attached = true;
             return (fresh, true);
         }
 
-        if likely(tss.refcount() == 1) {
+        if likely(tss.refcount() == 1 && !(*tss).attached) {
+            (*tss).attached = true;
             return (tss, true);
         }
 
-        (pymemo_alloc(), false)
+        let fresh = pymemo_alloc();
+        if !fresh.is_null() {
+            (*fresh).attached = true;
+        }
+        (fresh, false)
     }
 }
 
 #[inline(always)]
 pub unsafe fn cleanup_memo(memo: *mut PyMemoObject, is_tss: bool) {
     unsafe {
+        record_highwater(&*memo);
         if likely(is_tss && memo.refcount() == 1) {
-            (*memo).reset();
+            // Stays attached while releasing originals: a weakref callback or
+            // __del__ calling deepcopy() from there must get a memo of its own.
+            PyMemoObject::reset(memo);
+            (*memo).attached = false;
             return;
         }
+        (*memo).attached = false;
 
         if is_tss {
             let fresh = pymemo_alloc();
@@ -0,0 +1,184 @@
+//! Per-type memory of failed `__reduce_ex__` calls.
+//!
+//! When reducing an instance raises, the exception's type and args are kept
+//! for the instance's type. Later instances of that type fail with a fresh
+//! exception built from them, noted as a cached failure, without calling the
+//! reducer again: a copy retried after catching the error doesn't pay for it
+//! once per instance. Entries are keyed by version tag like the dataclass
+//! cache, so fixing a method on the type (or a base) forgets the failure.
+//! On with `config.apply(negative_cache=True)`: a reducer whose failure depends
+//! on the instance would otherwise fail copies of instances it can reduce.
+
+use pyo3_ffi::*;
+use std::ptr;
+
+use crate::py_str;
+use crate::state::STATE;
+use crate::types::*;
+
+struct Failure {
+    exc_type: *mut PyObject,
+    args: *mut PyObject,
+}
+
+impl Drop for Failure {
+    fn drop(&mut self) {
+        unsafe {
+            self.exc_type.decref();
+            self.args.decref();
+        }
+    }
+}
+
+const FAILURE_CACHE_SIZE: usize = 64;
+
+struct FailureCacheEntry {
+    tp: CachedType,
+    version: u32,
+    failure: Option<Failure>,
+}
+
+const EMPTY_ENTRY: FailureCacheEntry = FailureCacheEntry {
+    tp: CachedType::EMPTY,
+    version: 0,
+    failure: None,
+};
+
+#[thread_local]
+static mut FAILURE_CACHE: [FailureCacheEntry; FAILURE_CACHE_SIZE] =
+    [EMPTY_ENTRY; FAILURE_CACHE_SIZE];
+
+#[inline(always)]
+fn slot_of(tp: *mut PyTypeObject) -> usize {
+    ((tp as usize) >> 4) % FAILURE_CACHE_SIZE
+}
+
+/// Raises the failure remembered for `tp` and returns true, or returns false
+/// when there is none and the reducer should be called.
+#[inline(always)]
+pub unsafe fn raise_cached(tp: *mut PyTypeObject) -> bool {
+    unsafe {
+        if !STATE.negative_cache {
+            return false;
+        }
+        let cached = &*ptr::addr_of!(FAILURE_CACHE[slot_of(tp)]);
+        match &cached.failure {
+            Some(failure) if cached.tp.get() == tp && version_of(tp) == Some(cached.version) => {
+                raise_fresh(failure)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cold]
+unsafe fn raise_fresh(failure: &Failure) -> bool {
+    unsafe {
+        let exc = PyObject_Call(failure.exc_type, failure.args, ptr::null_mut());
+        if exc.is_null() {
+            // Not rebuildable after all: let the reducer raise it again.
+            PyErr_Clear();
+            return false;
+        }
+        #[cfg(Py_3_11)]
+        {
+            let note = PyUnicode_FromString(crate::cstr!(
+                "copium: cached failure, __reduce_ex__ was not called again for this type"
+            ));
+            let add_note = exc.getattr(py_str!("add_note"));
+            if !note.is_null() && !add_note.is_null() {
+                add_note.call_one(note).decref_nullable();
+            }
+            note.decref_nullable();
+            add_note.decref_nullable();
+            PyErr_Clear();
+        }
+        PyErr_SetObject(failure.exc_type, exc);
+        exc.decref();
+        true
+    }
+}
+
+/// Remembers the pending exception, raised by reducing an instance of `tp`,
+/// and leaves it pending. Errors that say nothing about the type, or that
+/// carry more than their args, aren't kept.
+#[cold]
+pub unsafe fn remember(tp: *mut PyTypeObject) {
+    unsafe {
+        if !STATE.negative_cache {
+            return;
+        }
+        let Some(version) = version_of(tp) else {
+            return;
+        };
+
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        #[allow(deprecated)]
+        PyErr_NormalizeException(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+        let keep = !exc_value.is_null()
+            && PyErr_GivenExceptionMatches(exc_value, PyExc_Exception) != 0
+            && PyErr_GivenExceptionMatches(exc_value, PyExc_MemoryError) == 0
+            && PyErr_GivenExceptionMatches(exc_value, PyExc_RecursionError) == 0
+            && PyErr_GivenExceptionMatches(exc_value, crate::reduce::copy_error()) == 0
+            && PyErr_GivenExceptionMatches(exc_value, crate::deadline::timeout_error()) == 0;
+        let args = if keep {
+            let args = exc_value.getattr(py_str!("args"));
+            if args.is_null() || !args.is_tuple() {
+                args.decref_nullable();
+                PyErr_Clear();
+                ptr::null_mut()
+            } else {
+                args
+            }
+        } else {
+            ptr::null_mut()
+        };
+
+        let evicted = if args.is_null() {
+            None
+        } else {
+            let entry = FailureCacheEntry {
+                tp: CachedType::new(tp),
+                version,
+                failure: Some(Failure {
+                    exc_type: exc_type.newref(),
+                    args,
+                }),
+            };
+            Some(std::mem::replace(
+                &mut *ptr::addr_of_mut!(FAILURE_CACHE[slot_of(tp)]),
+                entry,
+            ))
+        };
+
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
+        // Dropping the evicted failure may run arbitrary code; do it last.
+        if let Some(evicted) = evicted {
+            let mut exc_type: *mut PyObject = ptr::null_mut();
+            let mut exc_value: *mut PyObject = ptr::null_mut();
+            let mut exc_tb: *mut PyObject = ptr::null_mut();
+            #[allow(deprecated)]
+            PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+            drop(evicted);
+            #[allow(deprecated)]
+            PyErr_Restore(exc_type, exc_value, exc_tb);
+        }
+    }
+}
+
+/// Forgets every failure this thread remembered.
+pub unsafe fn clear() {
+    unsafe {
+        let evicted = std::mem::replace(
+            &mut *ptr::addr_of_mut!(FAILURE_CACHE),
+            [EMPTY_ENTRY; FAILURE_CACHE_SIZE],
+        );
+        drop(evicted);
+    }
+}
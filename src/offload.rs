@@ -0,0 +1,262 @@
+//! `copium.extra.adeepcopy`: deepcopy on the running loop's default executor.
+//!
+//! The worker thread holds the GIL while it copies, so moving the copy off the
+//! event loop thread alone would not unblock the loop. While a copy is offloaded
+//! the traversal reaches a checkpoint every few hundred containers, where it
+//! briefly drops the GIL (letting a waiting loop thread in) and stops with
+//! CancelledError once the awaiting future has been cancelled. Large bytearrays
+//! are copied with the GIL released; the source is pinned by a buffer export
+//! meanwhile, so other threads that try to resize it get BufferError.
+
+use pyo3_ffi::*;
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::py_str;
+use crate::types::{PyObjectPtr, PyTypeObjectPtr};
+
+const CHECKPOINT_STRIDE: u32 = 256;
+
+/// Buffers at least this large are copied with the GIL released.
+pub const RELEASE_GIL_MIN_BYTES: usize = 64 * 1024;
+
+const TOKEN_NAME: *const core::ffi::c_char = b"copium.extra._cancel_token\0".as_ptr().cast();
+
+/// Cancellation flag of the copy this thread is running, null when none is.
+#[thread_local]
+static mut ACTIVE: *const AtomicBool = ptr::null();
+
+#[thread_local]
+static mut SINCE_CHECKPOINT: u32 = 0;
+
+#[inline(always)]
+pub fn is_active() -> bool {
+    unsafe { !ACTIVE.is_null() }
+}
+
+unsafe fn raise_cancelled() {
+    unsafe {
+        let asyncio = PyImport_ImportModule(crate::cstr!("asyncio"));
+        if asyncio.is_null() {
+            return;
+        }
+        let cancelled_error = asyncio.getattr(py_str!("CancelledError"));
+        asyncio.decref();
+        if cancelled_error.is_null() {
+            return;
+        }
+        PyErr_SetNone(cancelled_error);
+        cancelled_error.decref();
+    }
+}
+
+#[inline(always)]
+unsafe fn check_cancelled() -> i32 {
+    unsafe {
+        if (*ACTIVE).load(Ordering::Relaxed) {
+            raise_cancelled();
+            return -1;
+        }
+        0
+    }
+}
+
+/// Called on every traversal step while a copy is offloaded.
+#[cold]
+pub unsafe fn checkpoint() -> i32 {
+    unsafe {
+        SINCE_CHECKPOINT += 1;
+        if SINCE_CHECKPOINT < CHECKPOINT_STRIDE {
+            return 0;
+        }
+        SINCE_CHECKPOINT = 0;
+
+        let thread_state = PyEval_SaveThread();
+        PyEval_RestoreThread(thread_state);
+        check_cancelled()
+    }
+}
+
+/// Copies `len` bytes out of `source`'s buffer with the GIL released.
+#[cold]
+pub unsafe fn copy_buffer(source: *mut PyObject, destination: *mut u8, len: usize) -> i32 {
+    unsafe {
+        let mut view: Py_buffer = std::mem::zeroed();
+        if PyObject_GetBuffer(source, &mut view, PyBUF_SIMPLE) < 0 {
+            return -1;
+        }
+        let len = len.min(view.len as usize);
+
+        let thread_state = PyEval_SaveThread();
+        ptr::copy_nonoverlapping(view.buf as *const u8, destination, len);
+        PyEval_RestoreThread(thread_state);
+
+        PyBuffer_Release(&mut view);
+        check_cancelled()
+    }
+}
+
+// ── Worker and cancellation callback ───────────────────────
+
+unsafe extern "C" fn token_destructor(capsule: *mut PyObject) {
+    unsafe {
+        let flag = PyCapsule_GetPointer(capsule, TOKEN_NAME) as *mut AtomicBool;
+        if !flag.is_null() {
+            drop(Box::from_raw(flag));
+        }
+    }
+}
+
+/// Runs on an executor thread: the regular deepcopy, with checkpoints enabled.
+unsafe extern "C" fn offloaded_deepcopy(token: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let flag = PyCapsule_GetPointer(token, TOKEN_NAME) as *const AtomicBool;
+        if flag.is_null() {
+            return ptr::null_mut();
+        }
+        if (*flag).load(Ordering::Relaxed) {
+            raise_cancelled();
+            return ptr::null_mut();
+        }
+
+        let previous = ACTIVE;
+        ACTIVE = flag;
+        SINCE_CHECKPOINT = 0;
+        let args = [obj];
+        let result = crate::py_deepcopy(ptr::null_mut(), args.as_ptr(), 1, ptr::null_mut());
+        ACTIVE = previous;
+        result
+    })
+}
+
+/// Done-callback of the returned future. By the time the future completes on
+/// its own the worker has already returned, so only a cancellation is observed.
+unsafe extern "C" fn request_cancel(token: *mut PyObject, _future: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let flag = PyCapsule_GetPointer(token, TOKEN_NAME) as *const AtomicBool;
+        if flag.is_null() {
+            return ptr::null_mut();
+        }
+        (*flag).store(true, Ordering::Relaxed);
+        Py_None().newref()
+    })
+}
+
+static mut WORKER_DEF: PyMethodDef = PyMethodDef {
+    ml_name: b"_offloaded_deepcopy\0".as_ptr().cast(),
+    ml_meth: PyMethodDefPointer {
+        PyCFunction: offloaded_deepcopy,
+    },
+    ml_flags: METH_O,
+    ml_doc: ptr::null(),
+};
+
+static mut CANCEL_DEF: PyMethodDef = PyMethodDef {
+    ml_name: b"_request_cancel\0".as_ptr().cast(),
+    ml_meth: PyMethodDefPointer {
+        PyCFunction: request_cancel,
+    },
+    ml_flags: METH_O,
+    ml_doc: ptr::null(),
+};
+
+unsafe fn completed_future(event_loop: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let future = PyObject_CallMethodObjArgs(
+            event_loop,
+            py_str!("create_future"),
+            ptr::null_mut::<PyObject>(),
+        );
+        if future.is_null() {
+            return ptr::null_mut();
+        }
+        let result = PyObject_CallMethodObjArgs(
+            future,
+            py_str!("set_result"),
+            obj,
+            ptr::null_mut::<PyObject>(),
+        );
+        if result.is_null() {
+            future.decref();
+            return ptr::null_mut();
+        }
+        result.decref();
+        future
+    }
+}
+
+unsafe fn submit(event_loop: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let flag = Box::into_raw(Box::new(AtomicBool::new(false)));
+        let token = PyCapsule_New(flag as *mut c_void, TOKEN_NAME, Some(token_destructor));
+        if token.is_null() {
+            drop(Box::from_raw(flag));
+            return ptr::null_mut();
+        }
+
+        let worker = PyCFunction_NewEx(ptr::addr_of_mut!(WORKER_DEF), token, ptr::null_mut());
+        let cancel = PyCFunction_NewEx(ptr::addr_of_mut!(CANCEL_DEF), token, ptr::null_mut());
+        token.decref();
+        if worker.is_null() || cancel.is_null() {
+            worker.decref_nullable();
+            cancel.decref_nullable();
+            return ptr::null_mut();
+        }
+
+        let future = PyObject_CallMethodObjArgs(
+            event_loop,
+            py_str!("run_in_executor"),
+            Py_None(),
+            worker,
+            obj,
+            ptr::null_mut::<PyObject>(),
+        );
+        worker.decref();
+        if future.is_null() {
+            cancel.decref();
+            return ptr::null_mut();
+        }
+
+        let added = PyObject_CallMethodObjArgs(
+            future,
+            py_str!("add_done_callback"),
+            cancel,
+            ptr::null_mut::<PyObject>(),
+        );
+        cancel.decref();
+        if added.is_null() {
+            future.decref();
+            return ptr::null_mut();
+        }
+        added.decref();
+        future
+    }
+}
+
+pub unsafe extern "C" fn py_adeepcopy(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        let asyncio = PyImport_ImportModule(crate::cstr!("asyncio"));
+        if asyncio.is_null() {
+            return ptr::null_mut();
+        }
+        let get_running_loop = asyncio.getattr(py_str!("get_running_loop"));
+        asyncio.decref();
+        if get_running_loop.is_null() {
+            return ptr::null_mut();
+        }
+        let event_loop = get_running_loop.call();
+        get_running_loop.decref();
+        if event_loop.is_null() {
+            return ptr::null_mut();
+        }
+
+        let future = if obj.class().is_atomic_immutable() {
+            completed_future(event_loop, obj)
+        } else {
+            submit(event_loop, obj)
+        };
+        event_loop.decref();
+        future
+    })
+}
@@ -1,17 +1,121 @@
 use pyo3::exceptions::{PyRuntimeError, PyTypeError};
 use pyo3::prelude::*;
-use pyo3::types::PyFunction;
+use pyo3::sync::MutexExt;
+use pyo3::types::{PyDict, PyFunction, PyList};
 use pyo3_ffi::*;
 use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
 
 use crate::types::PyObjectPtr;
 
 const CAPSULE_NAME: *const std::ffi::c_char = b"copium._original_vectorcall\0".as_ptr().cast();
 
+/// Held across enable()/disable(), from checking the patch to applying or
+/// removing it. Looking up the target may release the GIL (and there is none
+/// on free-threaded builds), so without it two enables could both see the
+/// function unpatched and the second save the first's forwarder as original.
+static PATCH_LOCK: Mutex<()> = Mutex::new(());
+
+// ══════════════════════════════════════════════════════════════
+//  Call statistics
+// ══════════════════════════════════════════════════════════════
+
+/// With `enable(sample_callers=True)`, every this-many forwarded call records its caller.
+const SAMPLE_EVERY: u64 = 1024;
+
+static CALLS_FORWARDED: AtomicU64 = AtomicU64::new(0);
+static CALLS_SINCE_ENABLE: AtomicU64 = AtomicU64::new(0);
+static SAMPLE_CALLERS: AtomicBool = AtomicBool::new(false);
+/// `"filename:lineno"` -> sampled call count. Created with the module, never freed.
+static CALLERS: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+#[inline(always)]
+fn record_forwarded_call() {
+    CALLS_FORWARDED.fetch_add(1, Ordering::Relaxed);
+    let n = CALLS_SINCE_ENABLE.fetch_add(1, Ordering::Relaxed);
+    if SAMPLE_CALLERS.load(Ordering::Relaxed) && n % SAMPLE_EVERY == 0 {
+        unsafe { sample_caller() };
+    }
+}
+
+/// `"filename:lineno"` of the Python code calling `copy.deepcopy`, or
+/// `"<unknown>"` when there is none (e.g. a call from a thread without Python frames).
+unsafe fn caller_site() -> *mut PyObject {
+    unsafe {
+        let frame = PyEval_GetFrame();
+        if !frame.is_null() {
+            frame.cast::<PyObject>().incref();
+        }
+        // On <3.12 the forwarder runs inside the patched function's own frame.
+        #[cfg(not(Py_3_12))]
+        let frame = if frame.is_null() {
+            frame
+        } else {
+            let back = PyFrame_GetBack(frame);
+            frame.cast::<PyObject>().decref();
+            back
+        };
+        if frame.is_null() {
+            return PyUnicode_FromString(crate::cstr!("<unknown>"));
+        }
+
+        let code = PyFrame_GetCode(frame);
+        let line_number = PyFrame_GetLineNumber(frame);
+        frame.cast::<PyObject>().decref();
+        let filename = code
+            .cast::<PyObject>()
+            .getattr(crate::py_str!("co_filename"));
+        code.cast::<PyObject>().decref();
+        if filename.is_null() {
+            return ptr::null_mut();
+        }
+        let site =
+            crate::ffi_ext::PyUnicode_FromFormat(crate::cstr!("%U:%d"), filename, line_number);
+        filename.decref();
+        site
+    }
+}
+
+/// Sampling is best effort: a failure here must not fail the copy.
+#[cold]
+unsafe fn sample_caller() {
+    unsafe {
+        let callers = CALLERS.load(Ordering::Acquire);
+        let site = caller_site();
+        if callers.is_null() || site.is_null() {
+            site.decref_nullable();
+            PyErr_Clear();
+            return;
+        }
+
+        let previous = PyDict_GetItemWithError(callers, site);
+        let count = if previous.is_null() {
+            if !PyErr_Occurred().is_null() {
+                site.decref();
+                PyErr_Clear();
+                return;
+            }
+            1
+        } else {
+            PyLong_AsLongLong(previous) + 1
+        };
+        let count = PyLong_FromLongLong(count);
+        if count.is_null() || PyDict_SetItem(callers, site, count) < 0 {
+            PyErr_Clear();
+        }
+        count.decref_nullable();
+        site.decref();
+    }
+}
+
 // ══════════════════════════════════════════════════════════════
 //  3.12+: swap the vectorcall slot on the function object
 // ══════════════════════════════════════════════════════════════
 
+/// The patched function keeps stdlib's signature, `deepcopy(x, memo=None, _nil=[])`:
+/// `_nil` is accepted and ignored, and copium-only keywords such as `replace` are
+/// rejected the way stdlib rejects them.
 #[cfg(Py_3_12)]
 pub(crate) unsafe extern "C" fn copium_deepcopy_vectorcall(
     _callable: *mut PyObject,
@@ -19,13 +123,117 @@ pub(crate) unsafe extern "C" fn copium_deepcopy_vectorcall(
     nargsf: usize,
     kwnames: *mut PyObject,
 ) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        record_forwarded_call();
+        let nargs = crate::ffi_ext::PyVectorcall_NARGS(nargsf);
+        let kwcount = if kwnames.is_null() {
+            0
+        } else {
+            PyTuple_GET_SIZE(kwnames)
+        };
+
+        if nargs > 3 {
+            crate::ffi_ext::PyErr_Format(
+                PyExc_TypeError,
+                crate::cstr!(
+                    "deepcopy() takes from 1 to 3 positional arguments but %zd were given"
+                ),
+                nargs,
+            );
+            return ptr::null_mut();
+        }
+        if kwcount == 0 {
+            return crate::py_deepcopy(ptr::null_mut(), args, nargs.min(2), ptr::null_mut());
+        }
+
+        // [x, memo, _nil], filled from positionals first, then keywords.
+        let mut bound: [*mut PyObject; 3] = [ptr::null_mut(); 3];
+        for (i, slot) in bound.iter_mut().enumerate().take(nargs as usize) {
+            *slot = *args.add(i);
+        }
+        for i in 0..kwcount {
+            let name = PyTuple_GET_ITEM(kwnames, i);
+            let index = if PyUnicode_CompareWithASCIIString(name, crate::cstr!("x")) == 0 {
+                0
+            } else if PyUnicode_CompareWithASCIIString(name, crate::cstr!("memo")) == 0 {
+                1
+            } else if PyUnicode_CompareWithASCIIString(name, crate::cstr!("_nil")) == 0 {
+                2
+            } else {
+                crate::ffi_ext::PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("deepcopy() got an unexpected keyword argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            };
+            if !bound[index].is_null() {
+                crate::ffi_ext::PyErr_Format(
+                    PyExc_TypeError,
+                    crate::cstr!("deepcopy() got multiple values for argument '%U'"),
+                    name,
+                );
+                return ptr::null_mut();
+            }
+            bound[index] = *args.offset(nargs + i);
+        }
+
+        if bound[0].is_null() {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("deepcopy() missing 1 required positional argument: 'x'"),
+            );
+            return ptr::null_mut();
+        }
+        let forwarded = if bound[1].is_null() { 1 } else { 2 };
+        crate::py_deepcopy(ptr::null_mut(), bound.as_ptr(), forwarded, ptr::null_mut())
+    })
+}
+
+/// Points `__wrapped__` at copium.deepcopy. The `None` `__signature__` stops
+/// inspect.signature() from following it, so the patched function keeps
+/// reporting stdlib's parameters, which are the ones it still accepts.
+unsafe fn set_wrapped(fn_ptr: *mut PyObject, target: *mut PyObject) -> i32 {
     unsafe {
-        crate::py_deepcopy(
-            ptr::null_mut(),
-            args,
-            crate::ffi_ext::PyVectorcall_NARGS(nargsf),
-            kwnames,
-        )
+        if PyObject_SetAttrString(fn_ptr, crate::cstr!("__wrapped__"), target) < 0 {
+            return -1;
+        }
+        PyObject_SetAttrString(fn_ptr, crate::cstr!("__signature__"), Py_None())
+    }
+}
+
+/// Removes the attributes `apply_patch` sets; any of them may already be gone.
+unsafe fn cleanup_patch_attrs(fn_ptr: *mut PyObject) -> i32 {
+    unsafe {
+        for name in [
+            crate::cstr!("__copium_original__"),
+            crate::cstr!("__wrapped__"),
+            crate::cstr!("__signature__"),
+        ] {
+            if PyObject_DelAttrString(fn_ptr, name) < 0 {
+                if PyErr_ExceptionMatches(PyExc_AttributeError) == 0 {
+                    return -1;
+                }
+                PyErr_Clear();
+            }
+        }
+        0
+    }
+}
+
+/// Undoes a partially applied patch without losing the exception that stopped it.
+unsafe fn abandon_patch(fn_ptr: *mut PyObject) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        if cleanup_patch_attrs(fn_ptr) < 0 {
+            PyErr_Clear();
+        }
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
     }
 }
 
@@ -51,6 +259,10 @@ unsafe fn apply_patch(_py: Python<'_>, fn_ptr: *mut PyObject, target: *mut PyObj
                 return -1;
             }
         };
+        // Saving our own forwarder as the original would make disable() a no-op.
+        if original_vc as usize == copium_deepcopy_vectorcall as *const () as usize {
+            return 0;
+        }
 
         let capsule = PyCapsule_New(
             std::mem::transmute::<vectorcallfunc, *mut std::ffi::c_void>(original_vc),
@@ -67,9 +279,8 @@ unsafe fn apply_patch(_py: Python<'_>, fn_ptr: *mut PyObject, target: *mut PyObj
         }
         capsule.decref();
 
-        if PyObject_SetAttrString(fn_ptr, crate::cstr!("__wrapped__"), target) < 0 {
-            PyObject_DelAttrString(fn_ptr, crate::cstr!("__copium_original__"));
-            PyErr_Clear();
+        if set_wrapped(fn_ptr, target) < 0 {
+            abandon_patch(fn_ptr);
             return -1;
         }
 
@@ -83,6 +294,9 @@ unsafe fn unapply_patch(_py: Python<'_>, fn_ptr: *mut PyObject) -> i32 {
     unsafe {
         let capsule = PyObject_GetAttrString(fn_ptr, crate::cstr!("__copium_original__"));
         if capsule.is_null() {
+            if PyErr_ExceptionMatches(PyExc_AttributeError) == 0 {
+                return -1;
+            }
             PyErr_Clear();
             PyErr_SetString(
                 PyExc_RuntimeError,
@@ -98,14 +312,11 @@ unsafe fn unapply_patch(_py: Python<'_>, fn_ptr: *mut PyObject) -> i32 {
         }
         let original_vc = std::mem::transmute::<*mut std::ffi::c_void, vectorcallfunc>(raw);
 
+        // Restore the slot before dropping the attributes: the forwarder never
+        // reads them, so in-flight calls stay valid and new calls go to stdlib.
         crate::ffi_ext::PyFunction_SetVectorcall(fn_ptr, original_vc);
 
-        PyObject_DelAttrString(fn_ptr, crate::cstr!("__copium_original__"));
-        PyErr_Clear();
-        PyObject_DelAttrString(fn_ptr, crate::cstr!("__wrapped__"));
-        PyErr_Clear();
-
-        0
+        cleanup_patch_attrs(fn_ptr)
     }
 }
 
@@ -113,6 +324,30 @@ unsafe fn unapply_patch(_py: Python<'_>, fn_ptr: *mut PyObject) -> i32 {
 //  <3.12: swap __code__ on the function object
 // ══════════════════════════════════════════════════════════════
 
+/// What the swapped code calls: counts the call, then hands it to copium.deepcopy.
+#[cfg(not(Py_3_12))]
+unsafe extern "C" fn forward_deepcopy(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    ffi_guard!(ptr::null_mut(), unsafe {
+        record_forwarded_call();
+        crate::py_deepcopy(ptr::null_mut(), args, nargs, kwnames)
+    })
+}
+
+#[cfg(not(Py_3_12))]
+static mut FORWARD_DEF: PyMethodDef = PyMethodDef {
+    ml_name: b"deepcopy\0".as_ptr().cast(),
+    ml_meth: PyMethodDefPointer {
+        PyCFunctionFastWithKeywords: forward_deepcopy,
+    },
+    ml_flags: METH_FASTCALL | METH_KEYWORDS,
+    ml_doc: ptr::null(),
+};
+
 #[cfg(not(Py_3_12))]
 unsafe fn is_patched(fn_ptr: *mut PyObject) -> bool {
     unsafe { PyObject_HasAttrString(fn_ptr, crate::cstr!("__copium_original__")) != 0 }
@@ -260,19 +495,13 @@ unsafe fn build_patched_code(target: *mut PyObject) -> *mut PyObject {
     }
 }
 
-#[cfg(not(Py_3_12))]
-unsafe fn cleanup_patch_attrs(fn_ptr: *mut PyObject) {
-    unsafe {
-        PyObject_DelAttrString(fn_ptr, crate::cstr!("__copium_original__"));
-        PyErr_Clear();
-        PyObject_DelAttrString(fn_ptr, crate::cstr!("__wrapped__"));
-        PyErr_Clear();
-    }
-}
-
 #[cfg(not(Py_3_12))]
 unsafe fn apply_patch(_py: Python<'_>, fn_ptr: *mut PyObject, target: *mut PyObject) -> i32 {
     unsafe {
+        // Saving our own code as the original would make disable() a no-op.
+        if is_patched(fn_ptr) {
+            return 0;
+        }
         let current_code = PyObject_GetAttrString(fn_ptr, crate::cstr!("__code__"));
         if current_code.is_null() {
             return -1;
@@ -284,20 +513,30 @@ unsafe fn apply_patch(_py: Python<'_>, fn_ptr: *mut PyObject, target: *mut PyObj
         }
         current_code.decref();
 
-        if PyObject_SetAttrString(fn_ptr, crate::cstr!("__wrapped__"), target) < 0 {
-            cleanup_patch_attrs(fn_ptr);
+        if set_wrapped(fn_ptr, target) < 0 {
+            abandon_patch(fn_ptr);
             return -1;
         }
 
-        let new_code = build_patched_code(target);
+        let forwarder = PyCFunction_NewEx(
+            ptr::addr_of_mut!(FORWARD_DEF),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        );
+        if forwarder.is_null() {
+            abandon_patch(fn_ptr);
+            return -1;
+        }
+        let new_code = build_patched_code(forwarder);
+        forwarder.decref();
         if new_code.is_null() {
-            cleanup_patch_attrs(fn_ptr);
+            abandon_patch(fn_ptr);
             return -1;
         }
 
         if PyObject_SetAttrString(fn_ptr, crate::cstr!("__code__"), new_code) < 0 {
             new_code.decref();
-            cleanup_patch_attrs(fn_ptr);
+            abandon_patch(fn_ptr);
             return -1;
         }
         new_code.decref();
@@ -310,6 +549,9 @@ unsafe fn unapply_patch(_py: Python<'_>, fn_ptr: *mut PyObject) -> i32 {
     unsafe {
         let original_code = PyObject_GetAttrString(fn_ptr, crate::cstr!("__copium_original__"));
         if original_code.is_null() {
+            if PyErr_ExceptionMatches(PyExc_AttributeError) == 0 {
+                return -1;
+            }
             PyErr_Clear();
             PyErr_SetString(
                 PyExc_RuntimeError,
@@ -324,8 +566,124 @@ unsafe fn unapply_patch(_py: Python<'_>, fn_ptr: *mut PyObject) -> i32 {
         }
         original_code.decref();
 
-        cleanup_patch_attrs(fn_ptr);
-        0
+        cleanup_patch_attrs(fn_ptr)
+    }
+}
+
+// ══════════════════════════════════════════════════════════════
+//  Incompatible __deepcopy__ scan
+// ══════════════════════════════════════════════════════════════
+
+/// Classes whose `__deepcopy__` rejects copium's memo, found by heuristics:
+/// native implementations outside the stdlib (PyO3 and friends usually want a
+/// real dict), stdlib ones known to, and Python ones annotating the memo as a dict.
+/// Looks only at classes that already exist; never imports anything.
+const SCANNER: &str = r#"
+    import sys
+    import types
+
+    STDLIB = frozenset(getattr(sys, "stdlib_module_names", ())) | {"builtins"}
+    KNOWN = {("xml.etree.ElementTree", "Element")}
+    NATIVE = (types.BuiltinFunctionType, types.MethodDescriptorType, types.WrapperDescriptorType)
+    MRO = type.__dict__["__mro__"]
+
+    def find_deepcopy(cls):
+        for klass in MRO.__get__(cls):
+            attr = type.__dict__["__dict__"].__get__(klass).get("__deepcopy__")
+            if attr is not None:
+                return attr
+        return None
+
+    def memo_annotation(func):
+        code = func.__code__
+        if code.co_argcount < 2:
+            return None
+        try:
+            annotation = func.__annotations__.get(code.co_varnames[1])
+        except Exception:
+            return None
+        if annotation is None or isinstance(annotation, str):
+            return annotation
+        if isinstance(annotation, type):
+            return annotation.__qualname__
+        return repr(annotation)
+
+    def reason_for(cls):
+        attr = find_deepcopy(cls)
+        if attr is None:
+            return None
+        if isinstance(attr, (staticmethod, classmethod)):
+            attr = attr.__func__
+        module = getattr(cls, "__module__", None) or ""
+        if (module, cls.__qualname__) in KNOWN:
+            return "__deepcopy__ is known to require a dict memo"
+        if isinstance(attr, NATIVE):
+            package = module.partition(".")[0]
+            if package in STDLIB or package == "copium":
+                return None
+            return "native __deepcopy__ outside the standard library"
+        if isinstance(attr, types.FunctionType):
+            annotation = memo_annotation(attr)
+            if annotation is not None and "dict" in annotation.lower():
+                return f"__deepcopy__ annotates its memo as {annotation}"
+        return None
+
+    def roots(modules):
+        if modules is None:
+            return [object]
+        if isinstance(modules, str):
+            modules = [modules]
+        found = []
+        for name in modules:
+            module = sys.modules.get(name)
+            if module is None:
+                continue
+            for value in list(vars(module).values()):
+                if isinstance(value, type) and value.__module__ == module.__name__:
+                    found.append(value)
+        return found
+
+    def scan(modules, limit):
+        stack = roots(modules)
+        seen = set()
+        incompatible = []
+        visited = 0
+        while stack and visited < limit:
+            cls = stack.pop()
+            if id(cls) in seen:
+                continue
+            seen.add(id(cls))
+            visited += 1
+            reason = reason_for(cls)
+            if reason is not None:
+                incompatible.append({"type": cls, "reason": reason})
+            if modules is None:
+                try:
+                    stack.extend(type.__subclasses__(cls))
+                except TypeError:
+                    pass
+        truncated = any(id(cls) not in seen for cls in stack)
+        return {"incompatible": incompatible, "visited": visited, "truncated": truncated}
+"#;
+
+unsafe fn run_scan(modules: *mut PyObject, limit: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let globals = crate::cache::exec_str(SCANNER);
+        if globals.is_null() {
+            return ptr::null_mut();
+        }
+        let scan = PyDict_GetItemString(globals as _, crate::cstr!("scan"));
+        let result = if scan.is_null() {
+            PyErr_SetString(
+                PyExc_RuntimeError,
+                crate::cstr!("copium.patch: scanner is missing"),
+            );
+            ptr::null_mut()
+        } else {
+            PyObject_CallFunctionObjArgs(scan, modules, limit, ptr::null_mut::<PyObject>())
+        };
+        globals.decref();
+        result
     }
 }
 
@@ -346,31 +704,132 @@ fn take_py_err(py: Python<'_>) -> PyErr {
     PyErr::take(py).unwrap_or_else(|| PyRuntimeError::new_err("unexpected null error state"))
 }
 
+fn scan_types<'py>(
+    py: Python<'py>,
+    modules: Option<Bound<'py, PyAny>>,
+    register: bool,
+    limit: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let modules = modules.unwrap_or_else(|| py.None().into_bound(py));
+    let limit = limit.into_pyobject(py)?;
+    let report = unsafe { run_scan(modules.as_ptr(), limit.as_ptr()) };
+    if report.is_null() {
+        return Err(take_py_err(py));
+    }
+    let report = unsafe { Bound::from_owned_ptr(py, report) }.cast_into::<PyDict>()?;
+    if register {
+        if let Some(incompatible) = report.get_item("incompatible")? {
+            for entry in incompatible.cast::<PyList>()? {
+                let tp = entry.get_item("type")?;
+                if unsafe { crate::fallback::register_dict_memo_type(tp.as_ptr()) } < 0 {
+                    return Err(take_py_err(py));
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Find classes whose __deepcopy__ likely rejects copium's memo: native ones
+/// outside the stdlib, ones known to, and Python ones annotating the memo as a dict.
+///
+/// modules: names of already imported modules whose classes to scan; None walks
+/// every subclass of object. Nothing is imported.
+/// register: give flagged types a dict memo from their first copy.
+/// limit: stop after this many classes and mark the report truncated.
+///
+/// Returns {"incompatible": [{"type", "reason"}], "visited", "truncated"}.
 #[pyfunction]
-fn enable(py: Python<'_>) -> PyResult<bool> {
+#[pyo3(signature = (modules = None, *, register = true, limit = 100_000))]
+fn check<'py>(
+    py: Python<'py>,
+    modules: Option<Bound<'py, PyAny>>,
+    register: bool,
+    limit: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    scan_types(py, modules, register, limit)
+}
+
+fn warn_incompatible(py: Python<'_>, report: &Bound<'_, PyDict>) -> PyResult<()> {
+    let Some(incompatible) = report.get_item("incompatible")? else {
+        return Ok(());
+    };
+    let mut names = Vec::new();
+    for entry in incompatible.cast::<PyList>()? {
+        let tp = entry.get_item("type")?;
+        let module = tp.getattr("__module__")?;
+        let qualname = tp.getattr("__qualname__")?;
+        names.push(format!("{module}.{qualname}"));
+    }
+    if names.is_empty() {
+        return Ok(());
+    }
+    let message = std::ffi::CString::new(format!(
+        "copium.patch: {} type(s) with a __deepcopy__ that likely needs a dict memo \
+         will be given one: {}",
+        names.len(),
+        names.join(", ")
+    ))?;
+    let category = py.get_type::<pyo3::exceptions::PyUserWarning>();
+    PyErr::warn(py, &category, &message, 1)
+}
+
+/// Patch copy.deepcopy to forward to copium.deepcopy. Idempotent.
+///
+/// sample_callers: record the "filename:lineno" of every 1024th forwarded call in
+/// stats()["callers"]. Applies even if already enabled.
+/// check: run check(modules) first and warn once about the types it registered.
+///
+/// Returns True if the patch was applied, False if it already was: of several
+/// threads enabling at once, exactly one gets True. Raises TypeError if
+/// copy.deepcopy isn't a Python function.
+#[pyfunction]
+#[pyo3(signature = (*, sample_callers = false, check = false, modules = None))]
+fn enable<'py>(
+    py: Python<'py>,
+    sample_callers: bool,
+    check: bool,
+    modules: Option<Bound<'py, PyAny>>,
+) -> PyResult<bool> {
+    if check {
+        let report = scan_types(py, modules, true, 100_000)?;
+        warn_incompatible(py, &report)?;
+    }
+
     let copy_mod = py.import("copy")?;
     let stdlib_dc = require_py_function(copy_mod.getattr("deepcopy")?)?;
     let fn_ptr = stdlib_dc.as_ptr();
+    let target = py.import("copium")?.getattr("deepcopy")?;
 
+    SAMPLE_CALLERS.store(sample_callers, Ordering::Relaxed);
+    let _guard = PATCH_LOCK
+        .lock_py_attached(py)
+        .unwrap_or_else(PoisonError::into_inner);
     unsafe {
-        if is_patched(fn_ptr) {
-            return Ok(false);
-        }
-        let copium_mod = py.import("copium")?;
-        let target = copium_mod.getattr("deepcopy")?;
         match apply_patch(py, fn_ptr, target.as_ptr()) {
-            r if r >= 0 => Ok(true),
+            0 => Ok(false),
+            r if r > 0 => {
+                CALLS_SINCE_ENABLE.store(0, Ordering::Relaxed);
+                PyDict_Clear(CALLERS.load(Ordering::Acquire));
+                Ok(true)
+            }
             _ => Err(take_py_err(py)),
         }
     }
 }
 
+/// Restore the original copy.deepcopy. Idempotent.
+///
+/// Returns True if the patch was removed, False if it wasn't applied.
 #[pyfunction]
 fn disable(py: Python<'_>) -> PyResult<bool> {
     let copy_mod = py.import("copy")?;
     let stdlib_dc = require_py_function(copy_mod.getattr("deepcopy")?)?;
     let fn_ptr = stdlib_dc.as_ptr();
 
+    let _guard = PATCH_LOCK
+        .lock_py_attached(py)
+        .unwrap_or_else(PoisonError::into_inner);
     unsafe {
         if !is_patched(fn_ptr) {
             return Ok(false);
@@ -382,6 +841,7 @@ fn disable(py: Python<'_>) -> PyResult<bool> {
     }
 }
 
+/// Whether copy.deepcopy is patched.
 #[pyfunction]
 fn enabled(py: Python<'_>) -> PyResult<bool> {
     let copy_mod = py.import("copy")?;
@@ -389,6 +849,26 @@ fn enabled(py: Python<'_>) -> PyResult<bool> {
     Ok(unsafe { is_patched(stdlib_dc.as_ptr()) })
 }
 
+/// Calls to the patched copy.deepcopy that were forwarded to copium.
+///
+/// calls_forwarded counts since import, calls_since_enable since the last enable()
+/// that changed state. callers holds sampled call sites, reset by that same enable().
+#[pyfunction]
+fn stats(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let result = PyDict::new(py);
+    result.set_item("calls_forwarded", CALLS_FORWARDED.load(Ordering::Relaxed))?;
+    result.set_item(
+        "calls_since_enable",
+        CALLS_SINCE_ENABLE.load(Ordering::Relaxed),
+    )?;
+    let callers = unsafe { PyDict_Copy(CALLERS.load(Ordering::Acquire)) };
+    if callers.is_null() {
+        return Err(take_py_err(py));
+    }
+    result.set_item("callers", unsafe { Bound::from_owned_ptr(py, callers) })?;
+    Ok(result)
+}
+
 // ══════════════════════════════════════════════════════════════
 //  Submodule registration
 // ══════════════════════════════════════════════════════════════
@@ -396,10 +876,19 @@ fn enabled(py: Python<'_>) -> PyResult<bool> {
 pub unsafe fn create_module(parent: *mut PyObject) -> i32 {
     let py = unsafe { Python::assume_attached() };
     let result: PyResult<()> = (|| {
-        let m = pyo3::types::PyModule::new(py, "patch")?;
+        let callers = unsafe { PyDict_New() };
+        if callers.is_null() {
+            return Err(take_py_err(py));
+        }
+        let previous = CALLERS.swap(callers, Ordering::AcqRel);
+        unsafe { previous.decref_nullable() };
+
+        let m = pyo3::types::PyModule::new(py, "copium.patch")?;
         m.add_function(wrap_pyfunction!(enable, &m)?)?;
         m.add_function(wrap_pyfunction!(disable, &m)?)?;
         m.add_function(wrap_pyfunction!(enabled, &m)?)?;
+        m.add_function(wrap_pyfunction!(stats, &m)?)?;
+        m.add_function(wrap_pyfunction!(check, &m)?)?;
         let ptr = m.into_ptr();
         if unsafe { crate::add_submodule(parent, crate::cstr!("patch"), ptr) } < 0 {
             return Err(
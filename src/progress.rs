@@ -0,0 +1,145 @@
+//! `deepcopy(..., progress=)` and `extra.replicate(..., progress=)`: a
+//! callback reporting how far a long copy has got.
+//!
+//! While a callback is set, the traversal counts containers at the same
+//! place timeouts and offloaded copies check in. Every `progress_every` of
+//! them (`copium.config`, 100000 by default), it calls the callback with the
+//! GIL held: `(objects_copied, None)` for deepcopy, `(copies_done, n)` for
+//! replicate. An exception from the callback stops the copy and propagates.
+//! Calls without a callback never get there.
+
+use pyo3_ffi::*;
+use std::ptr;
+
+use crate::ffi_ext::Py_None;
+use crate::state::STATE;
+use crate::types::PyObjectPtr;
+
+#[derive(Clone, Copy)]
+struct Progress {
+    /// Borrowed from the running call's arguments.
+    callback: *mut PyObject,
+    /// Containers entered so far.
+    copied: u64,
+    /// `(copies_done, n)` for replicate, None for deepcopy.
+    copies: Option<(Py_ssize_t, Py_ssize_t)>,
+}
+
+/// The running call's callback, None while no call on this thread has one.
+#[thread_local]
+static mut PROGRESS: Option<Progress> = None;
+
+#[thread_local]
+static mut SINCE_CHECKPOINT: u64 = 0;
+
+#[inline(always)]
+pub fn is_active() -> bool {
+    unsafe { (*ptr::addr_of!(PROGRESS)).is_some() }
+}
+
+/// Parses `progress=`: None for no callback, otherwise a callable. Returns
+/// Err with an exception set for anything else.
+#[cold]
+pub unsafe fn parse_callback(value: *mut PyObject) -> Result<Option<*mut PyObject>, ()> {
+    unsafe {
+        if value.is_none() {
+            return Ok(None);
+        }
+        if PyCallable_Check(value) == 0 {
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("progress must be a callable or None"),
+            );
+            return Err(());
+        }
+        Ok(Some(value))
+    }
+}
+
+/// Runs `copy` reporting to `callback`; `n` is replicate's count of copies.
+/// The enclosing call's callback, if any, is restored afterwards.
+#[inline(never)]
+pub unsafe fn with_progress(
+    callback: *mut PyObject,
+    n: Option<Py_ssize_t>,
+    copy: impl FnOnce() -> *mut PyObject,
+) -> *mut PyObject {
+    unsafe {
+        let previous = PROGRESS;
+        let previous_count = SINCE_CHECKPOINT;
+        PROGRESS = Some(Progress {
+            callback,
+            copied: 0,
+            copies: n.map(|n| (0, n)),
+        });
+        SINCE_CHECKPOINT = 0;
+
+        let result = copy();
+
+        PROGRESS = previous;
+        SINCE_CHECKPOINT = previous_count;
+        result
+    }
+}
+
+/// Tells a replicate's callback that `done` copies are finished.
+#[inline(always)]
+pub unsafe fn set_copies_done(done: Py_ssize_t) {
+    unsafe {
+        if let Some(Progress {
+            copies: Some((copies_done, _)),
+            ..
+        }) = &mut *ptr::addr_of_mut!(PROGRESS)
+        {
+            *copies_done = done;
+        }
+    }
+}
+
+/// Called on every traversal step while a callback is set.
+#[cold]
+pub unsafe fn checkpoint() -> i32 {
+    unsafe {
+        let Some(progress) = &mut *ptr::addr_of_mut!(PROGRESS) else {
+            return 0;
+        };
+        progress.copied += 1;
+        SINCE_CHECKPOINT += 1;
+        if SINCE_CHECKPOINT < STATE.progress_every {
+            return 0;
+        }
+        SINCE_CHECKPOINT = 0;
+        report(*progress)
+    }
+}
+
+#[cold]
+unsafe fn report(progress: Progress) -> i32 {
+    unsafe {
+        let (done, total) = match progress.copies {
+            Some((done, n)) => (PyLong_FromSsize_t(done), PyLong_FromSsize_t(n)),
+            None => (PyLong_FromUnsignedLongLong(progress.copied), Py_None().newref()),
+        };
+        if done.is_null() || total.is_null() {
+            done.decref_nullable();
+            total.decref_nullable();
+            return -1;
+        }
+        // Copies the callback makes itself don't report back into it.
+        PROGRESS = None;
+        let result = PyObject_CallFunctionObjArgs(
+            progress.callback,
+            done,
+            total,
+            ptr::null_mut::<PyObject>(),
+        );
+        PROGRESS = Some(progress);
+        done.decref();
+        total.decref();
+        if result.is_null() {
+            return -1;
+        }
+        result.decref();
+        0
+    }
+}
@@ -0,0 +1,125 @@
+//! `config.apply(mark_copies=True)`: a record of where a deepcopy result came
+//! from, for debugging.
+//!
+//! Each top-level call that succeeds, one given no memo, gets a record of
+//! `copy_id` (counting up from 1 per process), `timestamp`, `thread_id` and
+//! `source_type`. It becomes this thread's `extra.last_copy_info()` and the
+//! result's `__copium__` attribute. Only the root is marked, and only when it
+//! is a new object that takes the attribute: slotted, builtin and frozen
+//! results, and originals returned as their own copy, are left alone without
+//! an error. Calls with the option off never get there.
+
+use pyo3_ffi::*;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::py_str;
+use crate::types::PyObjectPtr;
+
+static NEXT_COPY_ID: AtomicU64 = AtomicU64::new(1);
+
+unsafe fn thread_id() -> *mut PyObject {
+    unsafe {
+        let threading = PyImport_ImportModule(crate::cstr!("threading"));
+        if threading.is_null() {
+            return ptr::null_mut();
+        }
+        let get_ident = threading.getattr(py_str!("get_ident"));
+        threading.decref();
+        if get_ident.is_null() {
+            return ptr::null_mut();
+        }
+        let ident = get_ident.call();
+        get_ident.decref();
+        ident
+    }
+}
+
+unsafe fn record(source: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let info = PyDict_New();
+        if info.is_null() {
+            return ptr::null_mut();
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+        let fields = [
+            (
+                crate::cstr!("copy_id"),
+                PyLong_FromUnsignedLongLong(NEXT_COPY_ID.fetch_add(1, Ordering::Relaxed)),
+            ),
+            (crate::cstr!("timestamp"), PyFloat_FromDouble(timestamp)),
+            (crate::cstr!("thread_id"), thread_id()),
+            (crate::cstr!("source_type"), (source.class() as *mut PyObject).newref()),
+        ];
+        let mut status = 0;
+        for (name, value) in fields {
+            if status == 0 && (value.is_null() || PyDict_SetItemString(info, name, value) < 0) {
+                status = -1;
+            }
+            value.decref_nullable();
+        }
+        if status < 0 {
+            info.decref();
+            return ptr::null_mut();
+        }
+        info
+    }
+}
+
+/// Records the copy of `source` that produced `result` and marks `result`.
+/// Returns `result`, or null with an exception set (releasing `result`) if
+/// the record couldn't be made.
+#[cold]
+pub unsafe fn mark(source: *mut PyObject, result: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let info = record(source);
+        if info.is_null() {
+            result.decref();
+            return ptr::null_mut();
+        }
+        let thread_dict = PyThreadState_GetDict();
+        let mut status = if thread_dict.is_null() {
+            0
+        } else {
+            PyDict_SetItem(thread_dict, py_str!("copium.last_copy_info"), info)
+        };
+        if status == 0
+            && result != source
+            && PyObject_SetAttr(result, py_str!("__copium__"), info) < 0
+        {
+            if PyErr_ExceptionMatches(PyExc_AttributeError) != 0
+                || PyErr_ExceptionMatches(PyExc_TypeError) != 0
+            {
+                PyErr_Clear();
+            } else {
+                status = -1;
+            }
+        }
+        info.decref();
+        if status < 0 {
+            result.decref();
+            return ptr::null_mut();
+        }
+        result
+    }
+}
+
+/// This thread's latest record, or None.
+pub unsafe fn last_copy_info() -> *mut PyObject {
+    unsafe {
+        let thread_dict = PyThreadState_GetDict();
+        if !thread_dict.is_null() {
+            let info = PyDict_GetItemWithError(thread_dict, py_str!("copium.last_copy_info"));
+            if !info.is_null() {
+                return info.newref();
+            }
+            if !PyErr_Occurred().is_null() {
+                return ptr::null_mut();
+            }
+        }
+        Py_None().newref()
+    }
+}
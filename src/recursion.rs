@@ -105,6 +105,21 @@ unsafe fn init_stack_bounds() {
 
 #[inline(always)]
 pub unsafe fn enter() -> i32 {
+    if unlikely(crate::offload::is_active()) && unsafe { crate::offload::checkpoint() } < 0 {
+        return -1;
+    }
+    if unlikely(crate::deadline::is_active()) && unsafe { crate::deadline::checkpoint() } < 0 {
+        return -1;
+    }
+    if unlikely(crate::cooperative::is_active())
+        && unsafe { crate::cooperative::checkpoint() } < 0
+    {
+        return -1;
+    }
+    if unlikely(crate::progress::is_active()) && unsafe { crate::progress::checkpoint() } < 0 {
+        return -1;
+    }
+
     let d = unsafe {
         DEPTH = DEPTH.wrapping_add(1);
         DEPTH
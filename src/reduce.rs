@@ -1,5 +1,6 @@
-use std::os::raw::c_int;
+use std::os::raw::{c_char, c_int};
 use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 
 use pyo3_ffi::*;
 
@@ -8,6 +9,7 @@ use crate::ffi_ext;
 use crate::memo::Memo;
 use crate::py_obj;
 use crate::py_str;
+use crate::state::STATE;
 use crate::types::*;
 
 macro_rules! bail {
@@ -60,6 +62,184 @@ pub(crate) unsafe fn chain_type_error(msg: *mut PyObject) {
     }
 }
 
+// ── CopyError ──────────────────────────────────────────────
+
+/// `copium.CopyError`. Created on each runtime init, never freed.
+static COPY_ERROR: AtomicPtr<PyObject> = AtomicPtr::new(ptr::null_mut());
+
+/// Borrowed `copium.CopyError`.
+#[inline(always)]
+pub(crate) fn copy_error() -> *mut PyObject {
+    COPY_ERROR.load(Ordering::Acquire)
+}
+
+const COPY_ERROR_DOC: &str = "\
+Raised by copium for objects it doesn't know how to copy.
+
+Subclasses copy.Error, so `except copy.Error` keeps catching it.
+
+Attributes:
+    obj_type: qualified name of the type that couldn't be copied.
+    path: access steps leading to the object, or None when paths aren't tracked.
+    original: the underlying exception, also chained as __cause__, or None.\0";
+
+pub(crate) unsafe fn init_copy_error() -> c_int {
+    unsafe {
+        let defaults = PyDict_New();
+        if defaults.is_null() {
+            return -1;
+        }
+        for name in [py_str!("obj_type"), py_str!("path"), py_str!("original")] {
+            if PyDict_SetItem(defaults, name, Py_None()) < 0 {
+                defaults.decref();
+                return -1;
+            }
+        }
+        let copy_error = PyErr_NewExceptionWithDoc(
+            crate::cstr!("copium.CopyError"),
+            COPY_ERROR_DOC.as_ptr().cast(),
+            py_obj!("copy.Error"),
+            defaults,
+        );
+        defaults.decref();
+        if copy_error.is_null() {
+            return -1;
+        }
+
+        let previous = COPY_ERROR.swap(copy_error, Ordering::AcqRel);
+        previous.decref_nullable();
+        0
+    }
+}
+
+pub(crate) unsafe fn add_copy_error(module: *mut PyObject) -> c_int {
+    unsafe {
+        let copy_error = COPY_ERROR.load(Ordering::Acquire).newref();
+        if PyModule_AddObject(module, crate::cstr!("CopyError"), copy_error) < 0 {
+            copy_error.decref();
+            return -1;
+        }
+        0
+    }
+}
+
+/// Raises `CopyError` for `object`, worded like `copy.copy`/`copy.deepcopy`.
+#[cold]
+unsafe fn raise_copy_error(object: *mut PyObject, deep: bool) {
+    unsafe {
+        let tp = object.class() as *mut PyObject;
+        let msg = ffi_ext::PyUnicode_FromFormat(
+            crate::cstr!("un(%s)copyable object of type %R"),
+            if deep {
+                crate::cstr!("deep")
+            } else {
+                crate::cstr!("shallow")
+            },
+            tp,
+        );
+        if !msg.is_null() {
+            set_copy_error(tp, msg);
+        }
+    }
+}
+
+/// Raises `CopyError` under `config.apply(strict=True)` where the original
+/// would otherwise be returned as its own deep copy.
+#[cold]
+unsafe fn raise_strict_pass_through(object: *mut PyObject, reason: *const c_char) {
+    unsafe {
+        let tp = object.class() as *mut PyObject;
+        let msg = ffi_ext::PyUnicode_FromFormat(
+            crate::cstr!("strict mode: %s object would be shared instead of copied: %s"),
+            (*object.class()).tp_name,
+            reason,
+        );
+        if !msg.is_null() {
+            set_copy_error(tp, msg);
+        }
+    }
+}
+
+/// Steals `msg`.
+unsafe fn set_copy_error(tp: *mut PyObject, msg: *mut PyObject) {
+    unsafe {
+        let copy_error = COPY_ERROR.load(Ordering::Acquire);
+        let exc = copy_error.call_one(msg);
+        msg.decref();
+        if exc.is_null() {
+            return;
+        }
+
+        let obj_type = qualified_type_name(tp);
+        if obj_type.is_null() || exc.set_attr(py_str!("obj_type"), obj_type) < 0 {
+            obj_type.decref_nullable();
+            exc.decref();
+            return;
+        }
+        obj_type.decref();
+
+        PyErr_SetObject(copy_error, exc);
+        exc.decref();
+    }
+}
+
+/// Replaces the pending exception with `CopyError(message)`, keeping the
+/// former as `original` and `__cause__`.
+#[cold]
+pub(crate) unsafe fn reraise_as_copy_error(message: *const c_char) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        #[allow(deprecated)]
+        PyErr_NormalizeException(&mut exc_type, &mut exc_value, &mut exc_tb);
+        if !exc_tb.is_null() {
+            PyException_SetTraceback(exc_value, exc_tb);
+        }
+        exc_type.decref_nullable();
+        exc_tb.decref_nullable();
+
+        let copy_error = COPY_ERROR.load(Ordering::Acquire);
+        let exc = PyObject_CallFunction(copy_error, crate::cstr!("s"), message);
+        if exc.is_null() || exc.set_attr(py_str!("original"), exc_value) < 0 {
+            exc.decref_nullable();
+            exc_value.decref();
+            return;
+        }
+        PyException_SetCause(exc, exc_value);
+        PyErr_SetObject(copy_error, exc);
+        exc.decref();
+    }
+}
+
+/// `module.qualname`, without the module for builtins.
+pub(crate) unsafe fn qualified_type_name(tp: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let qualname = tp.getattr(py_str!("__qualname__"));
+        if qualname.is_null() {
+            return ptr::null_mut();
+        }
+        let module = tp.getattr(py_str!("__module__"));
+        if module.is_null() {
+            qualname.decref();
+            return ptr::null_mut();
+        }
+        let name = if PyUnicode_Check(module) == 0
+            || PyUnicode_CompareWithASCIIString(module, crate::cstr!("builtins")) == 0
+        {
+            qualname.newref()
+        } else {
+            ffi_ext::PyUnicode_FromFormat(crate::cstr!("%U.%U"), module, qualname)
+        };
+        module.decref();
+        qualname.decref();
+        name
+    }
+}
+
 // ── Registry & reduce dispatch ─────────────────────────────
 
 pub(crate) unsafe fn try_reduce_via_registry(
@@ -68,25 +248,24 @@ pub(crate) unsafe fn try_reduce_via_registry(
 ) -> *mut PyObject {
     unsafe {
         let reducer = py_obj!(PyDictObject, "copyreg.dispatch_table").get_item(tp as *mut PyObject);
-        if reducer.is_null() {
-            return ptr::null_mut();
-        }
-        if PyCallable_Check(reducer) == 0 {
-            PyErr_SetString(
-                PyExc_TypeError,
-                crate::cstr!("copyreg.dispatch_table value is not callable"),
-            );
+        // stdlib skips a None entry and calls anything else, callable or not
+        if reducer.is_null() || reducer.is_none() {
             return ptr::null_mut();
         }
         PyObject_CallOneArg(reducer, obj)
     }
 }
 
-pub(crate) unsafe fn call_reduce_method_preferring_ex(obj: *mut PyObject) -> *mut PyObject {
+pub(crate) unsafe fn call_reduce_method_preferring_ex(
+    obj: *mut PyObject,
+    deep: bool,
+) -> *mut PyObject {
     unsafe {
         let mut reduce_ex: *mut PyObject = ptr::null_mut();
         let has = obj.get_optional_attr(py_str!("__reduce_ex__"), &mut reduce_ex);
-        if has > 0 {
+        if has > 0 && reduce_ex.is_none() {
+            reduce_ex.decref();
+        } else if has > 0 {
             let four = PyLong_FromLong(4);
             let res = reduce_ex.call_one(four);
             four.decref();
@@ -98,7 +277,9 @@ pub(crate) unsafe fn call_reduce_method_preferring_ex(obj: *mut PyObject) -> *mu
         }
         let mut reduce: *mut PyObject = ptr::null_mut();
         let has = obj.get_optional_attr(py_str!("__reduce__"), &mut reduce);
-        if has > 0 {
+        if has > 0 && reduce.is_none() {
+            reduce.decref();
+        } else if has > 0 {
             let res = reduce.call();
             reduce.decref();
             return res;
@@ -106,10 +287,7 @@ pub(crate) unsafe fn call_reduce_method_preferring_ex(obj: *mut PyObject) -> *mu
         if has < 0 {
             return ptr::null_mut();
         }
-        PyErr_SetString(
-            py_obj!("copy.Error"),
-            crate::cstr!("un(deep)copyable object (no reduce protocol)"),
-        );
+        raise_copy_error(obj, deep);
         ptr::null_mut()
     }
 }
@@ -130,8 +308,11 @@ pub(crate) enum ReduceKind {
     String,
 }
 
+/// Splits a `__reduce__` result into borrowed parts. A non-tuple `args` is
+/// coerced into a fresh result tuple that replaces `*reduce_result`, since the
+/// one returned may be shared with the reducing object's own state.
 pub(crate) unsafe fn validate_reduce_tuple(
-    reduce_result: *mut PyObject,
+    reduce_result: &mut *mut PyObject,
     reducing_type: *mut PyTypeObject,
 ) -> (ReduceKind, ReduceParts) {
     unsafe {
@@ -143,8 +324,8 @@ pub(crate) unsafe fn validate_reduce_tuple(
             dictitems: ptr::null_mut(),
         };
 
-        if !reduce_result.is_tuple() {
-            if reduce_result.is_unicode() || reduce_result.is_bytes() {
+        if !(*reduce_result).is_tuple() {
+            if (*reduce_result).is_unicode() {
                 return (ReduceKind::String, empty);
             }
             PyErr_SetString(
@@ -154,7 +335,7 @@ pub(crate) unsafe fn validate_reduce_tuple(
             return (ReduceKind::Error, empty);
         }
 
-        let tup = reduce_result as *mut PyTupleObject;
+        let tup = *reduce_result as *mut PyTupleObject;
         let size = tup.length();
         if size < 2 || size > 5 {
             PyErr_SetString(
@@ -198,9 +379,26 @@ pub(crate) unsafe fn validate_reduce_tuple(
                 }
                 return (ReduceKind::Error, empty);
             }
-            let old = argtup;
-            tup.set_slot_steal_unchecked(1, coerced);
-            old.decref();
+            // A new tuple, not a slice: slicing an exact tuple whole returns
+            // the same object, which __reduce__ may share with other callers.
+            let fresh = PyTuple_New(size);
+            if fresh.is_null() {
+                coerced.decref();
+                return (ReduceKind::Error, empty);
+            }
+            let fresh_tuple = fresh as *mut PyTupleObject;
+            for i in 0..size {
+                let item = if i == 1 {
+                    coerced
+                } else {
+                    tup.get_borrowed_unchecked(i).newref()
+                };
+                fresh_tuple.set_slot_steal_unchecked(i, item);
+            }
+            // The copy holds its own references to the other parts, so the
+            // borrowed pointers above stay valid once the original is released.
+            (*reduce_result).decref();
+            *reduce_result = fresh;
             argtup = coerced;
         }
 
@@ -231,7 +429,7 @@ pub(crate) unsafe fn validate_reduce_tuple(
 
 // ── Instance reconstruction ────────────────────────────────
 
-unsafe fn call_tp_new(
+pub(crate) unsafe fn call_tp_new(
     cls: *mut PyTypeObject,
     args: *mut PyObject,
     kwargs: *mut PyObject,
@@ -389,6 +587,9 @@ unsafe fn reconstruct_newobj_ex<M: Memo>(
     }
 }
 
+/// Most reconstructor arguments `reconstruct_callable` passes without a tuple.
+const STACK_ARGS: usize = 8;
+
 unsafe fn reconstruct_callable<M: Memo>(
     callable: *mut PyObject,
     argtup: *mut PyObject,
@@ -402,6 +603,29 @@ unsafe fn reconstruct_callable<M: Memo>(
             return callable.call();
         }
 
+        // Most reconstructors take a few arguments: copy them onto the stack
+        // and skip building a tuple the callee would only unpack again.
+        // Callables without vectorcall get one made by CPython as before.
+        if nargs as usize <= STACK_ARGS {
+            let mut stack = [ptr::null_mut::<PyObject>(); STACK_ARGS + 1];
+            for i in 0..nargs as usize {
+                let copied = deepcopy::deepcopy(tup.get_borrowed_unchecked(i as Py_ssize_t), memo);
+                if copied.is_error() {
+                    for &arg in &stack[1..=i] {
+                        arg.decref();
+                    }
+                    return ptr::null_mut();
+                }
+                stack[i + 1] = copied.into_raw();
+            }
+
+            let instance = callable.vectorcall(stack.as_mut_ptr().add(1), nargs as usize);
+            for &arg in &stack[1..=nargs as usize] {
+                arg.decref();
+            }
+            return instance;
+        }
+
         let copied_args = bail!(PyTuple_New(nargs));
         let copied_tup = copied_args as *mut PyTupleObject;
 
@@ -423,6 +647,41 @@ unsafe fn reconstruct_callable<M: Memo>(
 
 // ── State application ──────────────────────────────────────
 
+/// `instance.__dict__.update(state)` for a state that is not a dict: mappings
+/// merge through keys(), anything else is read as an iterable of pairs.
+pub(crate) unsafe fn merge_dict_state(instance: *mut PyObject, state: *mut PyObject) -> c_int {
+    unsafe {
+        let instance_dict = instance.getattr(py_str!("__dict__"));
+        if instance_dict.is_null() {
+            return -1;
+        }
+
+        let mut keys: *mut PyObject = ptr::null_mut();
+        let mut ret = state.get_optional_attr(py_str!("keys"), &mut keys);
+        if ret > 0 {
+            keys.decref();
+            ret = PyDict_Merge(instance_dict, state, 1);
+        } else if ret == 0 {
+            ret = PyDict_MergeFromSeq2(instance_dict, state, 1);
+        }
+        instance_dict.decref();
+
+        if ret < 0 {
+            let msg = ffi_ext::PyUnicode_FromFormat(
+                crate::cstr!(
+                    "dict state from %s.__reduce__ must be a mapping or an iterable of pairs, got %.200s"
+                ),
+                (*instance.class()).tp_name,
+                (*state.class()).tp_name,
+            );
+            if !msg.is_null() {
+                chain_type_error(msg);
+            }
+        }
+        ret
+    }
+}
+
 /// Returns 1 if __setstate__ found and applied, 0 if not found, -1 on error.
 unsafe fn apply_setstate<M: Memo>(
     instance: *mut PyObject,
@@ -457,7 +716,7 @@ unsafe fn apply_setstate<M: Memo>(
     }
 }
 
-unsafe fn apply_dict_state<M: Memo>(
+pub(crate) unsafe fn apply_dict_state<M: Memo>(
     instance: *mut PyObject,
     dict_state: *mut PyObject,
     memo: &mut M,
@@ -474,25 +733,7 @@ unsafe fn apply_dict_state<M: Memo>(
         let copied = copied.into_raw();
 
         if !copied.is_dict() {
-            let instance_dict = instance.getattr(py_str!("__dict__"));
-            if instance_dict.is_null() {
-                copied.decref();
-                return -1;
-            }
-            let ret = PyDict_Merge(instance_dict, copied, 1);
-            instance_dict.decref();
-            if ret < 0 {
-                let msg = ffi_ext::PyUnicode_FromFormat(
-                    crate::cstr!(
-                        "dict state from %s.__reduce__ must be a dict or mapping, got %.200s"
-                    ),
-                    (*instance.class()).tp_name,
-                    (*copied.class()).tp_name,
-                );
-                if !msg.is_null() {
-                    chain_type_error(msg);
-                }
-            }
+            let ret = merge_dict_state(instance, copied);
             copied.decref();
             return ret;
         }
@@ -797,11 +1038,317 @@ unsafe fn apply_dictitems<M: Memo>(
 
 // ── Main entry point ───────────────────────────────────────
 
+// ── Struct sequences ───────────────────────────────────────
+
+/// `tp_new` shared by every struct sequence type, read off one made for the
+/// purpose; 0 until the first check needs it.
+static STRUCTSEQ_NEW: AtomicUsize = AtomicUsize::new(0);
+
+unsafe fn structseq_new() -> Option<usize> {
+    unsafe {
+        let known = STRUCTSEQ_NEW.load(Ordering::Acquire);
+        if known != 0 {
+            return Some(known);
+        }
+        let mut fields = [
+            PyStructSequence_Field {
+                name: crate::cstr!("value"),
+                doc: crate::cstr!("value"),
+            },
+            PyStructSequence_Field {
+                name: ptr::null(),
+                doc: ptr::null(),
+            },
+        ];
+        let mut desc = PyStructSequence_Desc {
+            name: crate::cstr!("copium.structseq_probe"),
+            doc: crate::cstr!("Reference for recognizing struct sequence types."),
+            fields: fields.as_mut_ptr(),
+            n_in_sequence: 1,
+        };
+        let probe = PyStructSequence_NewType(&mut desc);
+        if probe.is_null() {
+            return None;
+        }
+        let new = (*probe).tp_new.map_or(0, |f| f as usize);
+        (probe as *mut PyObject).decref();
+        STRUCTSEQ_NEW.store(new, Ordering::Release);
+        Some(new)
+    }
+}
+
+/// Struct sequences (os.stat_result, time.struct_time, ...) carry no flag of
+/// their own; they are direct tuple subclasses sharing structseq's `tp_new`,
+/// which a namedtuple or a tuple subclass with an `n_fields` attribute doesn't.
+/// Returns 1 or 0, or -1 with an exception set if the reference type couldn't
+/// be made.
+pub(crate) unsafe fn is_structseq(tp: *mut PyTypeObject) -> c_int {
+    unsafe {
+        if (*tp).tp_base != std::ptr::addr_of_mut!(PyTuple_Type) || (*tp).tp_members.is_null() {
+            return 0;
+        }
+        let Some(tp_new) = (*tp).tp_new else {
+            return 0;
+        };
+        match structseq_new() {
+            Some(reference) => (reference != 0 && tp_new as usize == reference) as c_int,
+            None => -1,
+        }
+    }
+}
+
+unsafe fn structseq_count(tp: *mut PyTypeObject, name: *mut PyObject) -> Py_ssize_t {
+    unsafe {
+        let value = (tp as *mut PyObject).getattr(name);
+        if value.is_null() {
+            return -1;
+        }
+        let count = PyLong_AsSsize_t(value);
+        value.decref();
+        count
+    }
+}
+
+/// Equivalent of `type(obj)(deepcopy(tuple(obj)), deepcopy(extra_fields))`,
+/// mirroring structseq's own __reduce__ without building the reduce tuple.
+/// Fields past the visible sequence live in ob_item but are keyed by name.
+unsafe fn reconstruct_structseq<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+) -> *mut PyObject {
+    unsafe {
+        let n_fields = structseq_count(tp, py_str!("n_fields"));
+        if n_fields < 0 {
+            return ptr::null_mut();
+        }
+        let n_unnamed_fields = structseq_count(tp, py_str!("n_unnamed_fields"));
+        if n_unnamed_fields < 0 {
+            return ptr::null_mut();
+        }
+
+        let tup = original as *mut PyTupleObject;
+        let n_visible_fields = tup.length();
+        let items = (*tup).ob_item.as_mut_ptr();
+
+        let sequence = bail!(PyTuple_New(n_visible_fields));
+        let sequence_tup = sequence as *mut PyTupleObject;
+        for i in 0..n_visible_fields {
+            let copied = deepcopy::deepcopy(*items.offset(i), memo);
+            if copied.is_error() {
+                sequence.decref();
+                return ptr::null_mut();
+            }
+            sequence_tup.set_slot_steal_unchecked(i, copied.into_raw());
+        }
+
+        let extra = PyDict_New();
+        if extra.is_null() {
+            sequence.decref();
+            return ptr::null_mut();
+        }
+        for i in n_visible_fields..n_fields {
+            let name = (*(*tp).tp_members.offset(i - n_unnamed_fields)).name;
+            let mut value = *items.offset(i);
+            if value.is_null() {
+                value = ffi_ext::Py_None();
+            }
+            let copied = deepcopy::deepcopy(value, memo);
+            if copied.is_error() {
+                sequence.decref();
+                extra.decref();
+                return ptr::null_mut();
+            }
+            let copied = copied.into_raw();
+            let status = PyDict_SetItemString(extra, name, copied);
+            copied.decref();
+            if status < 0 {
+                sequence.decref();
+                extra.decref();
+                return ptr::null_mut();
+            }
+        }
+
+        let args = PyTuple_New(2);
+        if args.is_null() {
+            sequence.decref();
+            extra.decref();
+            return ptr::null_mut();
+        }
+        let args_tup = args as *mut PyTupleObject;
+        args_tup.set_slot_steal_unchecked(0, sequence);
+        args_tup.set_slot_steal_unchecked(1, extra);
+
+        let instance = (tp as *mut PyObject).call_with(args);
+        args.decref();
+        instance
+    }
+}
+
+// ── Counter ────────────────────────────────────────────────
+
+/// Equivalent of `Counter(deepcopy(dict(obj)))`, which is what Counter's own
+/// __reduce__ leads to, minus the Python-level __init__ and update() calls.
+unsafe fn reconstruct_counter<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+) -> *mut PyObject {
+    unsafe {
+        let items = bail!(PyDict_Copy(original));
+        let copied = deepcopy::deepcopy(items, memo);
+        items.decref();
+        if copied.is_error() {
+            return ptr::null_mut();
+        }
+        let copied = copied.into_raw();
+
+        let Some(tp_new) = (*tp).tp_new else {
+            copied.decref();
+            PyErr_SetString(
+                PyExc_TypeError,
+                crate::cstr!("cannot create Counter instances"),
+            );
+            return ptr::null_mut();
+        };
+        let no_args = PyTuple_New(0);
+        if no_args.is_null() {
+            copied.decref();
+            return ptr::null_mut();
+        }
+        let instance = tp_new(tp, no_args, ptr::null_mut());
+        no_args.decref();
+        if instance.is_null() {
+            copied.decref();
+            return ptr::null_mut();
+        }
+
+        let status = PyDict_Update(instance, copied);
+        copied.decref();
+        if status < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+        instance
+    }
+}
+
+// ── decimal.Context ────────────────────────────────────────
+
+/// `Context.copy()`: a new context with the same precision, rounding,
+/// exponent limits, capitals, clamp, flags and traps, independent of the
+/// original like the one its __reduce__ rebuilds, without the argument tuple
+/// and flag lists that takes. Everything it holds is atomic, so there is
+/// nothing left to deep-copy.
+unsafe fn reconstruct_decimal_context(original: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let copy = bail!(original.getattr(py_str!("copy")));
+        let instance = copy.call();
+        copy.decref();
+        instance
+    }
+}
+
+/// Repr for error messages: skipped for builtin containers, whose repr grows with
+/// their contents, and swallowed if it fails. Expects no exception to be set.
+unsafe fn diagnostic_repr(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        if PyList_Check(object) != 0
+            || PyTuple_Check(object) != 0
+            || PyDict_Check(object) != 0
+            || PyAnySet_Check(object) != 0
+        {
+            return ptr::null_mut();
+        }
+        let repr = PyObject_Repr(object);
+        if repr.is_null() {
+            PyErr_Clear();
+            return ptr::null_mut();
+        }
+        let truncated = ffi_ext::PyUnicode_FromFormat(crate::cstr!("%.200U"), repr);
+        repr.decref();
+        if truncated.is_null() {
+            PyErr_Clear();
+        }
+        truncated
+    }
+}
+
+/// Chains a TypeError naming the object whose reconstruction failed. Errors that
+/// already carry a cause (a deeper object's, or the user's own) are left alone,
+/// so only the innermost failing object is reported. `CopyError` names its type
+/// already and keeps `__cause__` for its `original`.
+#[cold]
+unsafe fn annotate_reconstruct_failure(original: *mut PyObject) {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        #[allow(deprecated)]
+        PyErr_NormalizeException(&mut exc_type, &mut exc_value, &mut exc_tb);
+
+        let mut annotate = !exc_value.is_null()
+            && PyErr_GivenExceptionMatches(exc_value, PyExc_Exception) != 0
+            && PyErr_GivenExceptionMatches(exc_value, PyExc_MemoryError) == 0
+            && PyErr_GivenExceptionMatches(exc_value, COPY_ERROR.load(Ordering::Acquire)) == 0;
+        if annotate {
+            let cause = PyException_GetCause(exc_value);
+            annotate = cause.is_null();
+            cause.decref_nullable();
+        }
+        let repr = if annotate {
+            diagnostic_repr(original)
+        } else {
+            ptr::null_mut()
+        };
+
+        #[allow(deprecated)]
+        PyErr_Restore(exc_type, exc_value, exc_tb);
+        if !annotate {
+            return;
+        }
+
+        let tp_name = (*original.class()).tp_name;
+        let msg = if repr.is_null() {
+            ffi_ext::PyUnicode_FromFormat(crate::cstr!("failed to deep-copy %s object"), tp_name)
+        } else {
+            let msg = ffi_ext::PyUnicode_FromFormat(
+                crate::cstr!("failed to deep-copy %s object: %U"),
+                tp_name,
+                repr,
+            );
+            repr.decref();
+            msg
+        };
+        if !msg.is_null() {
+            chain_type_error(msg);
+        }
+    }
+}
+
 pub unsafe fn reconstruct<M: Memo>(
     original: *mut PyObject,
     tp: *mut PyTypeObject,
     memo: &mut M,
     probe: M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let instance = reconstruct_reduced(original, tp, memo, probe);
+        if instance.is_null() {
+            annotate_reconstruct_failure(original);
+        }
+        instance
+    }
+}
+
+unsafe fn reconstruct_reduced<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: M::Probe,
 ) -> *mut PyObject {
     unsafe {
         let mut reduce_result = try_reduce_via_registry(original, tp);
@@ -809,13 +1356,46 @@ pub unsafe fn reconstruct<M: Memo>(
             if !PyErr_Occurred().is_null() {
                 return ptr::null_mut();
             }
-            reduce_result = call_reduce_method_preferring_ex(original);
+            if STATE.compat_warnings && crate::instance_hooks::warn_ignored(original, tp) < 0 {
+                return ptr::null_mut();
+            }
+            if let Some(instance) = crate::dataclasses::reconstruct(original, tp, memo, &probe) {
+                return instance;
+            }
+            if let Some(instance) = crate::subclasses::reconstruct(original, tp, memo, &probe) {
+                return instance;
+            }
+            let structseq = is_structseq(tp);
+            if structseq < 0 {
+                return ptr::null_mut();
+            }
+            let direct = if structseq > 0 {
+                Some(reconstruct_structseq(original, tp, memo))
+            } else if COLLECTIONS_COUNTER.contains(tp) {
+                Some(reconstruct_counter(original, tp, memo))
+            } else if DECIMAL_CONTEXT.contains(tp) {
+                Some(reconstruct_decimal_context(original))
+            } else {
+                None
+            };
+            if let Some(instance) = direct {
+                if !instance.is_null() && memo.memoize(original, instance, &probe) < 0 {
+                    instance.decref();
+                    return ptr::null_mut();
+                }
+                return instance;
+            }
+            if crate::negative_cache::raise_cached(tp) {
+                return ptr::null_mut();
+            }
+            reduce_result = call_reduce_method_preferring_ex(original, true);
             if reduce_result.is_null() {
+                crate::negative_cache::remember(tp);
                 return ptr::null_mut();
             }
         }
 
-        let (kind, parts) = validate_reduce_tuple(reduce_result, tp);
+        let (kind, parts) = validate_reduce_tuple(&mut reduce_result, tp);
 
         match kind {
             ReduceKind::Error => {
@@ -824,6 +1404,13 @@ pub unsafe fn reconstruct<M: Memo>(
             }
             ReduceKind::String => {
                 reduce_result.decref();
+                if STATE.strict {
+                    raise_strict_pass_through(
+                        original,
+                        crate::cstr!("__reduce__ returned a string"),
+                    );
+                    return ptr::null_mut();
+                }
                 return original.newref();
             }
             ReduceKind::Tuple => {}
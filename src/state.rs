@@ -18,11 +18,58 @@ pub enum OnIncompatible {
     Silent = 2,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DeepcopyNotImplemented {
+    Raise = 0,
+    Fallback = 1,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CopyBuffers {
+    Raise = 0,
+    Owned = 1,
+}
+
 pub struct ModuleState {
     pub sentinel: *mut PyObject,
 
     pub memo_mode: MemoMode,
     pub on_incompatible: OnIncompatible,
+    /// Raise instead of returning an original that copium couldn't copy.
+    pub strict: bool,
+    /// Retry a `__deepcopy__` that rejects the memo argument without it.
+    pub deepcopy_arg_compat: bool,
+    /// What a `NotImplementedError` from `__deepcopy__` does.
+    pub deepcopy_not_implemented: DeepcopyNotImplemented,
+    /// Most canonical objects one `deepcopy(..., intern=...)` keeps.
+    pub intern_cap: Py_ssize_t,
+    /// Fail fast for types whose `__reduce_ex__` already raised.
+    pub negative_cache: bool,
+    /// Most entries a native memo takes before the copy fails; `usize::MAX`
+    /// for no limit, so the insert path compares against it unconditionally.
+    pub max_memo_entries: usize,
+    /// Most originals a native memo keeps alive, likewise.
+    pub max_keepalive: usize,
+    /// Largest byte buffer one deepcopy duplicates, `usize::MAX` for no limit.
+    pub max_leaf_bytes: usize,
+    /// Most buffer bytes one deepcopy duplicates in all, likewise.
+    pub max_total_bytes: usize,
+    /// What deepcopy does with objects that only expose the buffer protocol.
+    pub copy_buffers: CopyBuffers,
+    /// Record top-level copies and mark their results with `__copium__`.
+    pub mark_copies: bool,
+    /// Warn about copy hooks set on instances, which copying ignores.
+    pub compat_warnings: bool,
+    /// Refuse memo proxy keys that aren't the id() of an object the copy has seen.
+    pub memo_key_check: bool,
+    /// Containers between calls to a copy's `progress` callback.
+    pub progress_every: u64,
+    /// Containers between calls to `on_checkpoint`; 0 while it is unset.
+    pub yield_every: u32,
+    /// Owned callback for cooperative schedulers, or null.
+    pub on_checkpoint: *mut PyObject,
     pub ignored_errors: *mut PyObject,
     pub ignored_errors_joined: *mut PyObject,
 }
@@ -30,10 +77,30 @@ pub struct ModuleState {
 unsafe impl Sync for ModuleState {}
 unsafe impl Send for ModuleState {}
 
+pub const DEFAULT_INTERN_CAP: Py_ssize_t = 1 << 16;
+
+pub const DEFAULT_PROGRESS_EVERY: u64 = 100_000;
+
 pub static mut STATE: ModuleState = ModuleState {
     sentinel: ptr::null_mut(),
     memo_mode: MemoMode::Native,
     on_incompatible: OnIncompatible::Warn,
+    strict: false,
+    deepcopy_arg_compat: false,
+    deepcopy_not_implemented: DeepcopyNotImplemented::Raise,
+    intern_cap: DEFAULT_INTERN_CAP,
+    negative_cache: false,
+    max_memo_entries: usize::MAX,
+    max_keepalive: usize::MAX,
+    max_leaf_bytes: usize::MAX,
+    max_total_bytes: usize::MAX,
+    copy_buffers: CopyBuffers::Raise,
+    mark_copies: false,
+    compat_warnings: false,
+    memo_key_check: false,
+    progress_every: DEFAULT_PROGRESS_EVERY,
+    yield_every: 0,
+    on_checkpoint: ptr::null_mut(),
     ignored_errors: ptr::null_mut(),
     ignored_errors_joined: ptr::null_mut(),
 };
@@ -119,11 +186,25 @@ pub unsafe fn update_suppress_warnings(new_tuple: *mut PyObject) -> i32 {
     }
 }
 
+/// Installs `callback` (a new reference, or null) to run every `every`
+/// containers, dropping the previous one last.
+pub unsafe fn set_on_checkpoint(every: u32, callback: *mut PyObject) {
+    unsafe {
+        let s = std::ptr::addr_of_mut!(STATE);
+        let previous = (*s).on_checkpoint;
+        (*s).yield_every = if callback.is_null() { 0 } else { every };
+        (*s).on_checkpoint = callback;
+        previous.decref_nullable();
+    }
+}
+
 pub unsafe fn load_config_from_env() -> i32 {
     unsafe {
         let s = std::ptr::addr_of_mut!(STATE);
         let use_dict = std::env::var("COPIUM_USE_DICT_MEMO").ok();
         let no_fallback = std::env::var("COPIUM_NO_MEMO_FALLBACK").ok();
+        let strict = std::env::var("COPIUM_STRICT").ok();
+        let arg_compat = std::env::var("COPIUM_DEEPCOPY_ARG_COMPAT").ok();
 
         (*s).memo_mode = if use_dict.as_deref().is_some_and(|value| !value.is_empty()) {
             MemoMode::Dict
@@ -138,6 +219,21 @@ pub unsafe fn load_config_from_env() -> i32 {
         } else {
             OnIncompatible::Warn
         };
+        (*s).strict = strict.as_deref().is_some_and(|value| !value.is_empty());
+        (*s).deepcopy_arg_compat = arg_compat.as_deref().is_some_and(|value| !value.is_empty());
+        (*s).deepcopy_not_implemented = DeepcopyNotImplemented::Raise;
+        (*s).intern_cap = DEFAULT_INTERN_CAP;
+        (*s).negative_cache = false;
+        (*s).max_memo_entries = usize::MAX;
+        (*s).max_keepalive = usize::MAX;
+        (*s).max_leaf_bytes = usize::MAX;
+        (*s).max_total_bytes = usize::MAX;
+        (*s).copy_buffers = CopyBuffers::Raise;
+        (*s).mark_copies = false;
+        (*s).progress_every = DEFAULT_PROGRESS_EVERY;
+        (*s).compat_warnings = false;
+        (*s).memo_key_check = false;
+        set_on_checkpoint(0, ptr::null_mut());
 
         let parsed_ignored_errors = parse_ignored_errors_from_environment();
         if parsed_ignored_errors.is_null() {
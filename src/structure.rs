@@ -0,0 +1,1001 @@
+//! `copium.extra.dump_structure` / `load_structure`: an anonymized record of an
+//! object graph's shape, for bug reports on graphs that can't be shared.
+//!
+//! The dump walks the graph the way deepcopy dispatches it, without copying,
+//! and keeps type names, attribute names, container sizes, which path each
+//! node takes and which node refers to which. Leaf values and dict keys become
+//! nodes whose values are never written. Reduction is performed, since its
+//! result is the shape deepcopy sees; an exception raised on the way is
+//! recorded by type on the node instead of propagating.
+//!
+//! `load_structure` rebuilds a graph of the same shape from generated classes.
+
+use pyo3_ffi::*;
+use std::ffi::c_char;
+use std::ptr;
+
+use crate::ffi_ext::{
+    PyMethod_GET_SELF, PyMethod_Type, PySequence_Fast, PySequence_Fast_GET_ITEM,
+    PySequence_Fast_GET_SIZE, Py_None,
+};
+use crate::reduce::{self, ReduceKind};
+use crate::types::{PyObjectPtr, PyTypeObjectPtr, COLLECTIONS_COUNTER};
+use crate::{py_obj, py_str};
+
+const FORMAT_VERSION: i64 = 1;
+
+struct Walker {
+    /// `{address: node index}` for every object seen so far.
+    ids: *mut PyObject,
+    nodes: *mut PyObject,
+    /// Reduction creates objects nothing else holds; keeping every visited
+    /// object alive stops a later one from reusing its address.
+    keepalive: Vec<*mut PyObject>,
+    pending: Vec<(Py_ssize_t, *mut PyObject)>,
+}
+
+impl Drop for Walker {
+    fn drop(&mut self) {
+        unsafe {
+            self.ids.decref_nullable();
+            self.nodes.decref_nullable();
+            for object in self.keepalive.drain(..) {
+                object.decref();
+            }
+        }
+    }
+}
+
+/// Steals `value`.
+unsafe fn set_field(node: *mut PyObject, key: *const c_char, value: *mut PyObject) -> i32 {
+    unsafe {
+        if value.is_null() {
+            return -1;
+        }
+        let status = PyDict_SetItemString(node, key, value);
+        value.decref();
+        status
+    }
+}
+
+unsafe fn set_str(node: *mut PyObject, key: *const c_char, value: *const c_char) -> i32 {
+    unsafe { set_field(node, key, PyUnicode_FromString(value)) }
+}
+
+/// Replaces the pending exception with its type's name under `"error"`.
+unsafe fn record_error(node: *mut PyObject) -> i32 {
+    unsafe {
+        let mut exc_type: *mut PyObject = ptr::null_mut();
+        let mut exc_value: *mut PyObject = ptr::null_mut();
+        let mut exc_tb: *mut PyObject = ptr::null_mut();
+        #[allow(deprecated)]
+        PyErr_Fetch(&mut exc_type, &mut exc_value, &mut exc_tb);
+        let name = reduce::qualified_type_name(exc_type);
+        exc_type.decref_nullable();
+        exc_value.decref_nullable();
+        exc_tb.decref_nullable();
+        set_field(node, crate::cstr!("error"), name)
+    }
+}
+
+impl Walker {
+    unsafe fn new() -> Option<Self> {
+        unsafe {
+            let ids = PyDict_New();
+            let nodes = PyList_New(0);
+            let walker = Self {
+                ids,
+                nodes,
+                keepalive: Vec::new(),
+                pending: Vec::new(),
+            };
+            if ids.is_null() || nodes.is_null() {
+                return None;
+            }
+            Some(walker)
+        }
+    }
+
+    /// Index of `object`'s node as a new reference, queueing it when unseen.
+    unsafe fn node_ref(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let key = PyLong_FromVoidPtr(object.cast());
+            if key.is_null() {
+                return ptr::null_mut();
+            }
+            let found = PyDict_GetItemWithError(self.ids, key);
+            if !found.is_null() || !PyErr_Occurred().is_null() {
+                key.decref();
+                if found.is_null() {
+                    return ptr::null_mut();
+                }
+                return found.newref();
+            }
+
+            let index = PyList_GET_SIZE(self.nodes);
+            let index_object = PyLong_FromSsize_t(index);
+            if index_object.is_null()
+                || PyDict_SetItem(self.ids, key, index_object) < 0
+                || PyList_Append(self.nodes, Py_None()) < 0
+            {
+                key.decref();
+                index_object.decref_nullable();
+                return ptr::null_mut();
+            }
+            key.decref();
+            self.keepalive.push(object.newref());
+            self.pending.push((index, object));
+            index_object
+        }
+    }
+
+    /// `[index, ...]` for everything `iterable` yields; null with the
+    /// iteration's exception pending.
+    unsafe fn item_refs(&mut self, iterable: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let iterator = PyObject_GetIter(iterable);
+            if iterator.is_null() {
+                return ptr::null_mut();
+            }
+            let refs = PyList_New(0);
+            if refs.is_null() {
+                iterator.decref();
+                return ptr::null_mut();
+            }
+            loop {
+                let item = PyIter_Next(iterator);
+                if item.is_null() {
+                    break;
+                }
+                let index = self.node_ref(item);
+                item.decref();
+                if index.is_null() || PyList_Append(refs, index) < 0 {
+                    index.decref_nullable();
+                    iterator.decref();
+                    refs.decref();
+                    return ptr::null_mut();
+                }
+                index.decref();
+            }
+            iterator.decref();
+            if !PyErr_Occurred().is_null() {
+                refs.decref();
+                return ptr::null_mut();
+            }
+            refs
+        }
+    }
+
+    /// `[[key index, value index], ...]` for `iterable`'s pairs.
+    unsafe fn pair_refs(&mut self, iterable: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let pairs = self.item_refs(iterable);
+            if pairs.is_null() {
+                return ptr::null_mut();
+            }
+            // Each pair was recorded as a node of its own; swap in its items.
+            for i in 0..PyList_GET_SIZE(pairs) {
+                let index = PyLong_AsSsize_t(PyList_GET_ITEM(pairs, i));
+                let pair = self.keepalive[index as usize];
+                if PyTuple_Check(pair) == 0 || PyTuple_GET_SIZE(pair) != 2 {
+                    PyErr_SetString(
+                        PyExc_ValueError,
+                        crate::cstr!("dict items must be (key, value) pairs"),
+                    );
+                    pairs.decref();
+                    return ptr::null_mut();
+                }
+                let refs = self.item_refs(pair);
+                if refs.is_null() {
+                    pairs.decref();
+                    return ptr::null_mut();
+                }
+                PyList_SetItem(pairs, i, refs);
+            }
+            pairs
+        }
+    }
+
+    unsafe fn dict_pairs(&mut self, dict: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let pairs = PyList_New(0);
+            if pairs.is_null() {
+                return ptr::null_mut();
+            }
+            let mut pos: Py_ssize_t = 0;
+            let mut key: *mut PyObject = ptr::null_mut();
+            let mut value: *mut PyObject = ptr::null_mut();
+            while PyDict_Next(dict, &mut pos, &mut key, &mut value) != 0 {
+                let key_index = self.node_ref(key);
+                let value_index = self.node_ref(value);
+                let pair = if key_index.is_null() || value_index.is_null() {
+                    ptr::null_mut()
+                } else {
+                    PyList_New(2)
+                };
+                if pair.is_null() {
+                    key_index.decref_nullable();
+                    value_index.decref_nullable();
+                    pairs.decref();
+                    return ptr::null_mut();
+                }
+                PyList_SET_ITEM(pair, 0, key_index);
+                PyList_SET_ITEM(pair, 1, value_index);
+                let status = PyList_Append(pairs, pair);
+                pair.decref();
+                if status < 0 {
+                    pairs.decref();
+                    return ptr::null_mut();
+                }
+            }
+            pairs
+        }
+    }
+
+    /// `{name: index}` for the str-keyed entries of `mapping`, a dict.
+    unsafe fn named_refs(&mut self, mapping: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let named = PyDict_New();
+            if named.is_null() {
+                return ptr::null_mut();
+            }
+            let mut pos: Py_ssize_t = 0;
+            let mut key: *mut PyObject = ptr::null_mut();
+            let mut value: *mut PyObject = ptr::null_mut();
+            while PyDict_Next(mapping, &mut pos, &mut key, &mut value) != 0 {
+                if PyUnicode_Check(key) == 0 {
+                    continue;
+                }
+                let index = self.node_ref(value);
+                if index.is_null() || PyDict_SetItem(named, key, index) < 0 {
+                    index.decref_nullable();
+                    named.decref();
+                    return ptr::null_mut();
+                }
+                index.decref();
+            }
+            named
+        }
+    }
+
+    /// `"attrs"` from the instance dict and `"slots"` from the slot names
+    /// pickle would use.
+    unsafe fn describe_attributes(&mut self, node: *mut PyObject, object: *mut PyObject) -> i32 {
+        unsafe {
+            let mut dict: *mut PyObject = ptr::null_mut();
+            let has = object.get_optional_attr(py_str!("__dict__"), &mut dict);
+            if has < 0 {
+                return record_error(node);
+            }
+            if has > 0 {
+                let attrs = if PyDict_Check(dict) != 0 {
+                    self.named_refs(dict)
+                } else {
+                    PyDict_New()
+                };
+                dict.decref();
+                if set_field(node, crate::cstr!("attrs"), attrs) < 0 {
+                    return -1;
+                }
+            }
+
+            let names = py_obj!("copyreg._slotnames").call_one(object.class() as _);
+            if names.is_null() {
+                return record_error(node);
+            }
+            let slots = PyDict_New();
+            if slots.is_null() {
+                names.decref();
+                return -1;
+            }
+            let names_list = PySequence_Fast(names, crate::cstr!("slot names must be a sequence"));
+            names.decref();
+            if names_list.is_null() {
+                slots.decref();
+                return record_error(node);
+            }
+            for i in 0..PySequence_Fast_GET_SIZE(names_list) {
+                let name = PySequence_Fast_GET_ITEM(names_list, i);
+                let mut value: *mut PyObject = ptr::null_mut();
+                let has = object.get_optional_attr(name, &mut value);
+                if has < 0 {
+                    names_list.decref();
+                    slots.decref();
+                    return record_error(node);
+                }
+                if has == 0 {
+                    continue;
+                }
+                let index = self.node_ref(value);
+                value.decref();
+                if index.is_null() || PyDict_SetItem(slots, name, index) < 0 {
+                    index.decref_nullable();
+                    names_list.decref();
+                    slots.decref();
+                    return -1;
+                }
+                index.decref();
+            }
+            names_list.decref();
+            if PyDict_Size(slots) == 0 {
+                slots.decref();
+                return 0;
+            }
+            set_field(node, crate::cstr!("slots"), slots)
+        }
+    }
+
+    unsafe fn describe_state(&mut self, state: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let described = PyDict_New();
+            if described.is_null() {
+                return ptr::null_mut();
+            }
+            let (attrs, slots) = if PyDict_CheckExact(state) != 0 {
+                (state, ptr::null_mut())
+            } else if PyTuple_CheckExact(state) != 0
+                && PyTuple_GET_SIZE(state) == 2
+                && (PyTuple_GET_ITEM(state, 0).is_none()
+                    || PyDict_CheckExact(PyTuple_GET_ITEM(state, 0)) != 0)
+                && PyDict_CheckExact(PyTuple_GET_ITEM(state, 1)) != 0
+            {
+                let attrs = PyTuple_GET_ITEM(state, 0);
+                let attrs = if attrs.is_none() {
+                    ptr::null_mut()
+                } else {
+                    attrs
+                };
+                (attrs, PyTuple_GET_ITEM(state, 1))
+            } else {
+                let status = set_field(described, crate::cstr!("object"), self.node_ref(state));
+                if status < 0 {
+                    described.decref();
+                    return ptr::null_mut();
+                }
+                return described;
+            };
+
+            for (key, mapping) in [
+                (crate::cstr!("attrs"), attrs),
+                (crate::cstr!("slots"), slots),
+            ] {
+                if !mapping.is_null() && set_field(described, key, self.named_refs(mapping)) < 0 {
+                    described.decref();
+                    return ptr::null_mut();
+                }
+            }
+            described
+        }
+    }
+
+    /// `"reduce"`: the parts of `__reduce_ex__`'s result with every object
+    /// replaced by its node.
+    unsafe fn describe_reduced(
+        &mut self,
+        node: *mut PyObject,
+        reduced: &mut *mut PyObject,
+        tp: *mut PyTypeObject,
+    ) -> i32 {
+        unsafe {
+            let (kind, parts) = reduce::validate_reduce_tuple(reduced, tp);
+            let described = match kind {
+                ReduceKind::Error => return record_error(node),
+                ReduceKind::String => {
+                    let described = PyDict_New();
+                    if described.is_null()
+                        || set_str(described, crate::cstr!("callable"), crate::cstr!("global")) < 0
+                    {
+                        described.decref_nullable();
+                        return -1;
+                    }
+                    described
+                }
+                ReduceKind::Tuple => match self.describe_reduce_parts(&parts) {
+                    Some(described) => described,
+                    None if PyErr_Occurred().is_null() => return -1,
+                    None => return record_error(node),
+                },
+            };
+            set_field(node, crate::cstr!("reduce"), described)
+        }
+    }
+
+    unsafe fn describe_reduce_parts(
+        &mut self,
+        parts: &reduce::ReduceParts,
+    ) -> Option<*mut PyObject> {
+        unsafe {
+            let described = PyDict_New();
+            if described.is_null() {
+                return None;
+            }
+            let argtup = parts.argtup;
+            let nargs = PyTuple_GET_SIZE(argtup);
+            let (callable, args, kwargs) =
+                if parts.callable == py_obj!("copyreg.__newobj__") && nargs >= 1 {
+                    (
+                        crate::cstr!("newobj"),
+                        PyTuple_GetSlice(argtup, 1, nargs),
+                        ptr::null_mut(),
+                    )
+                } else if parts.callable == py_obj!("copyreg.__newobj_ex__")
+                    && nargs == 3
+                    && PyDict_Check(PyTuple_GET_ITEM(argtup, 2)) != 0
+                {
+                    (
+                        crate::cstr!("newobj_ex"),
+                        PyTuple_GET_ITEM(argtup, 1).newref(),
+                        PyTuple_GET_ITEM(argtup, 2),
+                    )
+                } else {
+                    (crate::cstr!("callable"), argtup.newref(), ptr::null_mut())
+                };
+
+            let mut status = set_str(described, crate::cstr!("callable"), callable);
+            if status == 0 {
+                status = if args.is_null() {
+                    -1
+                } else {
+                    set_field(described, crate::cstr!("args"), self.item_refs(args))
+                };
+            }
+            args.decref_nullable();
+            if status == 0 && !kwargs.is_null() {
+                status = set_field(described, crate::cstr!("kwargs"), self.named_refs(kwargs));
+            }
+            if status == 0 && !parts.state.is_null() {
+                status = set_field(
+                    described,
+                    crate::cstr!("state"),
+                    self.describe_state(parts.state),
+                );
+            }
+            if status == 0 && !parts.listitems.is_null() {
+                status = set_field(
+                    described,
+                    crate::cstr!("listitems"),
+                    self.item_refs(parts.listitems),
+                );
+            }
+            if status == 0 && !parts.dictitems.is_null() {
+                status = set_field(
+                    described,
+                    crate::cstr!("dictitems"),
+                    self.pair_refs(parts.dictitems),
+                );
+            }
+            if status < 0 {
+                described.decref();
+                return None;
+            }
+            Some(described)
+        }
+    }
+
+    /// Objects deepcopy hands to `__deepcopy__` or reconstruction.
+    unsafe fn describe_object(&mut self, node: *mut PyObject, object: *mut PyObject) -> i32 {
+        unsafe {
+            let tp = object.class();
+            let mut method: *mut PyObject = ptr::null_mut();
+            let mut has = object.get_optional_attr(py_str!("__deepcopy__"), &mut method);
+            if has > 0 && method.is_none() {
+                method.decref();
+                has = 0;
+            }
+            if has != 0 {
+                method.decref_nullable();
+                if set_str(node, crate::cstr!("dispatch"), crate::cstr!("deepcopy")) < 0 {
+                    return -1;
+                }
+                if has < 0 {
+                    return record_error(node);
+                }
+                return self.describe_attributes(node, object);
+            }
+
+            let mut reduced = reduce::try_reduce_via_registry(object, tp);
+            if !reduced.is_null() || !PyErr_Occurred().is_null() {
+                if set_str(node, crate::cstr!("dispatch"), crate::cstr!("copyreg")) < 0 {
+                    reduced.decref_nullable();
+                    return -1;
+                }
+                if reduced.is_null() {
+                    return record_error(node);
+                }
+                let status = self.describe_reduced(node, &mut reduced, tp);
+                reduced.decref();
+                return status;
+            }
+
+            match crate::dataclasses::has_fast_path(tp) {
+                Err(()) => {
+                    if set_str(node, crate::cstr!("dispatch"), crate::cstr!("dataclass")) < 0 {
+                        return -1;
+                    }
+                    return record_error(node);
+                }
+                Ok(true) => {
+                    let params = (tp as *mut PyObject).getattr(py_str!("__dataclass_params__"));
+                    let frozen = if params.is_null() {
+                        ptr::null_mut()
+                    } else {
+                        let frozen = params.getattr(py_str!("frozen"));
+                        params.decref();
+                        frozen
+                    };
+                    if set_str(node, crate::cstr!("dispatch"), crate::cstr!("dataclass")) < 0
+                        || set_field(node, crate::cstr!("frozen"), frozen) < 0
+                    {
+                        return -1;
+                    }
+                    return self.describe_attributes(node, object);
+                }
+                Ok(false) => {}
+            }
+
+            let structseq = reduce::is_structseq(tp);
+            if structseq < 0 {
+                return -1;
+            }
+            if structseq > 0 {
+                if set_str(node, crate::cstr!("dispatch"), crate::cstr!("structseq")) < 0 {
+                    return -1;
+                }
+                return set_field(node, crate::cstr!("items"), self.item_refs(object));
+            }
+            if COLLECTIONS_COUNTER.contains(tp) {
+                if set_str(node, crate::cstr!("dispatch"), crate::cstr!("counter")) < 0 {
+                    return -1;
+                }
+                return set_field(node, crate::cstr!("pairs"), self.dict_pairs(object));
+            }
+
+            if set_str(node, crate::cstr!("dispatch"), crate::cstr!("reduce")) < 0 {
+                return -1;
+            }
+            let setstate = PyObject_HasAttr(tp as *mut PyObject, py_str!("__setstate__")) != 0;
+            if setstate && set_field(node, crate::cstr!("setstate"), Py_True().newref()) < 0 {
+                return -1;
+            }
+            let mut reduced = reduce::call_reduce_method_preferring_ex(object, true);
+            if reduced.is_null() {
+                return record_error(node);
+            }
+            let status = self.describe_reduced(node, &mut reduced, tp);
+            reduced.decref();
+            status
+        }
+    }
+
+    unsafe fn describe(&mut self, object: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let tp = object.class();
+            let node = PyDict_New();
+            if node.is_null()
+                || set_field(
+                    node,
+                    crate::cstr!("type"),
+                    reduce::qualified_type_name(tp as _),
+                ) < 0
+            {
+                node.decref_nullable();
+                return ptr::null_mut();
+            }
+
+            // Same order as deepcopy's dispatch with the native memo.
+            let (dispatch, items): (*const c_char, *mut PyObject) = if tp.is_literal_immutable() {
+                (crate::cstr!("atomic"), ptr::null_mut())
+            } else if PyTuple_CheckExact(object) != 0 {
+                (crate::cstr!("tuple"), self.item_refs(object))
+            } else if PyDict_CheckExact(object) != 0 {
+                (crate::cstr!("dict"), self.dict_pairs(object))
+            } else if PyList_CheckExact(object) != 0 {
+                (crate::cstr!("list"), self.item_refs(object))
+            } else if PySet_CheckExact(object) != 0 {
+                (crate::cstr!("set"), self.item_refs(object))
+            } else if tp.is_builtin_immutable() || tp.is_type_subclass() || tp.is_stdlib_immutable()
+            {
+                (crate::cstr!("atomic"), ptr::null_mut())
+            } else if PyFrozenSet_CheckExact(object) != 0 {
+                (crate::cstr!("frozenset"), self.item_refs(object))
+            } else if PyByteArray_CheckExact(object) != 0 {
+                let status = set_field(
+                    node,
+                    crate::cstr!("size"),
+                    PyLong_FromSsize_t(PyByteArray_Size(object)),
+                );
+                (
+                    crate::cstr!("bytearray"),
+                    if status < 0 { node } else { ptr::null_mut() },
+                )
+            } else if tp == ptr::addr_of_mut!(PyMethod_Type) {
+                let status = set_field(
+                    node,
+                    crate::cstr!("self"),
+                    self.node_ref(PyMethod_GET_SELF(object)),
+                );
+                (
+                    crate::cstr!("method"),
+                    if status < 0 { node } else { ptr::null_mut() },
+                )
+            } else {
+                if self.describe_object(node, object) < 0 {
+                    node.decref();
+                    return ptr::null_mut();
+                }
+                return node;
+            };
+
+            // `node` stands in for "failed" where there is no item list.
+            let failed = items == node || (items.is_null() && !PyErr_Occurred().is_null());
+            if failed || set_str(node, crate::cstr!("dispatch"), dispatch) < 0 {
+                node.decref();
+                return ptr::null_mut();
+            }
+            let key = if PyDict_CheckExact(object) != 0 {
+                crate::cstr!("pairs")
+            } else {
+                crate::cstr!("items")
+            };
+            if !items.is_null() && set_field(node, key, items) < 0 {
+                node.decref();
+                return ptr::null_mut();
+            }
+            node
+        }
+    }
+
+    unsafe fn walk(mut self, root: *mut PyObject) -> *mut PyObject {
+        unsafe {
+            let root_index = self.node_ref(root);
+            if root_index.is_null() {
+                return ptr::null_mut();
+            }
+            root_index.decref();
+
+            while let Some((index, object)) = self.pending.pop() {
+                let node = self.describe(object);
+                if node.is_null() {
+                    return ptr::null_mut();
+                }
+                PyList_SetItem(self.nodes, index, node);
+            }
+
+            let description = PyDict_New();
+            if description.is_null()
+                || set_field(
+                    description,
+                    crate::cstr!("copium_structure"),
+                    PyLong_FromLongLong(FORMAT_VERSION),
+                ) < 0
+                || set_field(description, crate::cstr!("root"), PyLong_FromLong(0)) < 0
+                || PyDict_SetItemString(description, crate::cstr!("nodes"), self.nodes) < 0
+            {
+                description.decref_nullable();
+                return ptr::null_mut();
+            }
+            description
+        }
+    }
+}
+
+/// The JSON-ready description of `object`'s graph.
+pub unsafe fn describe(object: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        match Walker::new() {
+            Some(walker) => walker.walk(object),
+            None => ptr::null_mut(),
+        }
+    }
+}
+
+const LOADER: &str = r#"
+    import builtins
+    import collections
+    import copy
+    import copyreg
+    import dataclasses
+    import itertools
+    import types
+
+
+    def _function():
+        pass
+
+
+    def _split(name):
+        module, _, qualname = name.rpartition(".")
+        return module or "builtins", qualname
+
+
+    class _Builder:
+        def __init__(self, nodes):
+            self.nodes = nodes
+            self.objects = {}
+            self.classes = {}
+            self.leaves = itertools.count()
+            self.bools = iter((True, False))
+
+        def build(self, root):
+            for index, node in enumerate(self.nodes):
+                shell = self.shell(node)
+                if shell is not None:
+                    self.objects[index] = shell
+            for index in range(len(self.nodes)):
+                self.settle(index)
+            for index, node in enumerate(self.nodes):
+                self.fill(self.objects[index], node)
+            return self.objects[root]
+
+        def named_class(self, node, bases, namespace):
+            module, qualname = _split(node["type"])
+            cls = type(qualname, bases, namespace)
+            cls.__module__ = module
+            cls.__qualname__ = qualname
+            return cls
+
+        def error_class(self, name):
+            module, qualname = _split(name)
+            found = getattr(builtins, qualname, None) if module == "builtins" else None
+            if isinstance(found, type) and issubclass(found, BaseException):
+                return found
+            cls = type(qualname, (Exception,), {})
+            cls.__module__ = module
+            return cls
+
+        def class_for(self, node):
+            dispatch = node["dispatch"]
+            reduced = node.get("reduce") or {}
+            slots = tuple(node.get("slots") or (reduced.get("state") or {}).get("slots") or ())
+            key = (
+                node["type"],
+                dispatch,
+                node.get("error"),
+                node.get("frozen"),
+                slots,
+                "listitems" in reduced,
+                "dictitems" in reduced,
+                self.natural(node),
+            )
+            if key in self.classes:
+                return self.classes[key]
+
+            bases = (object,)
+            if "listitems" in reduced:
+                bases = (list,)
+            elif "dictitems" in reduced:
+                bases = (dict,)
+            namespace = {}
+            if slots:
+                namespace["__slots__"] = (*slots, "__dict__")
+            error = node.get("error")
+
+            if dispatch == "dataclass" and error is None:
+                module, qualname = _split(node["type"])
+                fields = [*(node.get("attrs") or ()), *slots]
+                cls = dataclasses.make_dataclass(
+                    qualname, fields, frozen=bool(node.get("frozen")), eq=False, slots=bool(slots)
+                )
+                cls.__module__ = module
+            elif dispatch == "deepcopy":
+                if error is None:
+                    def __deepcopy__(self, memo):
+                        new = object.__new__(type(self))
+                        memo[id(self)] = new
+                        for name, value in vars(self).items():
+                            object.__setattr__(new, name, copy.deepcopy(value, memo))
+                        return new
+                else:
+                    exception = self.error_class(error)
+
+                    def __deepcopy__(self):
+                        raise exception("redacted")
+
+                    __deepcopy__ = property(__deepcopy__)
+                namespace["__deepcopy__"] = __deepcopy__
+                cls = self.named_class(node, bases, namespace)
+            else:
+                if error is not None:
+                    exception = self.error_class(error)
+
+                    def __reduce_ex__(self, protocol):
+                        raise exception("redacted")
+
+                    namespace["__reduce_ex__"] = __reduce_ex__
+                elif not self.natural(node):
+                    namespace["__reduce_ex__"] = _custom_reduce_ex
+                    namespace["_copium_rebuild"] = classmethod(lambda cls, *args: cls.__new__(cls))
+                    if node.get("setstate") or "object" in (reduced.get("state") or {}):
+                        namespace["__setstate__"] = _setstate
+                cls = self.named_class(node, bases, namespace)
+                if dispatch == "copyreg":
+                    copyreg.pickle(cls, lambda obj: obj.__reduce_ex__(2))
+            self.classes[key] = cls
+            return cls
+
+        def natural(self, node):
+            """Whether object.__reduce_ex__ on the generated class gives this shape."""
+            reduced = node.get("reduce")
+            return (
+                reduced is not None
+                and reduced["callable"] == "newobj"
+                and not reduced["args"]
+                and not node.get("setstate")
+                and "object" not in (reduced.get("state") or {})
+            )
+
+        def shell(self, node):
+            dispatch = node["dispatch"]
+            if dispatch == "list":
+                return []
+            if dispatch == "dict":
+                return {}
+            if dispatch == "set":
+                return set()
+            if dispatch == "bytearray":
+                return bytearray(node["size"])
+            if dispatch == "counter":
+                return collections.Counter()
+            if dispatch in ("deepcopy", "dataclass", "reduce", "copyreg"):
+                cls = self.class_for(node)
+                return cls.__new__(cls)
+            return None
+
+        def children(self, node):
+            dispatch = node["dispatch"]
+            if dispatch in ("tuple", "frozenset", "structseq"):
+                return node["items"]
+            if dispatch == "method":
+                return [node["self"]]
+            return []
+
+        def settle(self, index):
+            """Creates immutable nodes after their children, without recursing."""
+            stack = [index]
+            waiting = set()
+            while stack:
+                current = stack[-1]
+                if current in self.objects:
+                    stack.pop()
+                    continue
+                node = self.nodes[current]
+                missing = [i for i in self.children(node) if i not in self.objects]
+                if missing:
+                    if current in waiting:
+                        raise ValueError("cycle through immutable objects")
+                    waiting.add(current)
+                    stack.extend(missing)
+                    continue
+                stack.pop()
+                self.objects[current] = self.immutable(node)
+
+        def immutable(self, node):
+            dispatch = node["dispatch"]
+            items = [self.objects[i] for i in self.children(node)]
+            if dispatch == "tuple":
+                return tuple(items)
+            if dispatch == "frozenset":
+                return frozenset(items)
+            if dispatch == "structseq":
+                return self.named_class(node, (tuple,), {})(items)
+            if dispatch == "method":
+                return types.MethodType(types.FunctionType(_function.__code__, {}), items[0])
+            return self.leaf(node["type"])
+
+        def leaf(self, name):
+            n = next(self.leaves)
+            if name == "bool":
+                return next(self.bools)
+            makers = {
+                "NoneType": lambda: None,
+                "ellipsis": lambda: ...,
+                "NotImplementedType": lambda: NotImplemented,
+                "int": lambda: 1_000_000 + n,
+                "float": lambda: n + 0.5,
+                "complex": lambda: complex(n, 1),
+                "str": lambda: f"s{n}",
+                "bytes": lambda: b"b%d" % n,
+                "range": lambda: range(n),
+                "function": lambda: types.FunctionType(_function.__code__, {}),
+            }
+            if name in makers:
+                return makers[name]()
+            return type(f"Leaf{n}", (), {})
+
+        def refs(self, indexes):
+            return [self.objects[i] for i in indexes]
+
+        def named(self, mapping):
+            return {name: self.objects[i] for name, i in (mapping or {}).items()}
+
+        def fill(self, obj, node):
+            dispatch = node["dispatch"]
+            if dispatch == "list":
+                obj.extend(self.refs(node["items"]))
+            elif dispatch in ("dict", "counter"):
+                for key, value in node["pairs"]:
+                    dict.__setitem__(obj, self.objects[key], self.objects[value])
+            elif dispatch == "set":
+                obj.update(self.refs(node["items"]))
+            elif dispatch in ("deepcopy", "dataclass"):
+                for name, value in {**self.named(node.get("attrs")), **self.named(node.get("slots"))}.items():
+                    object.__setattr__(obj, name, value)
+            elif dispatch in ("reduce", "copyreg") and "reduce" in node:
+                self.fill_reduced(obj, node)
+
+        def fill_reduced(self, obj, node):
+            reduced = node["reduce"]
+            state = reduced.get("state") or {}
+            listitems = self.refs(reduced.get("listitems") or ())
+            dictitems = [(self.objects[k], self.objects[v]) for k, v in reduced.get("dictitems") or ()]
+            if self.natural(node):
+                for name, value in {**self.named(state.get("attrs")), **self.named(state.get("slots"))}.items():
+                    object.__setattr__(obj, name, value)
+                if "listitems" in reduced:
+                    list.extend(obj, listitems)
+                if "dictitems" in reduced:
+                    dict.update(obj, dictitems)
+                return
+            if "object" in state:
+                state_object = self.objects[state["object"]]
+            elif "slots" in state:
+                state_object = (self.named(state.get("attrs")) or None, self.named(state["slots"]))
+            elif "attrs" in state:
+                state_object = self.named(state["attrs"])
+            else:
+                state_object = None
+            object.__setattr__(obj, "_copium_reduce", (
+                reduced["callable"],
+                tuple(self.refs(reduced.get("args") or ())),
+                self.named(reduced.get("kwargs")),
+                state_object,
+                listitems if "listitems" in reduced else None,
+                dictitems if "dictitems" in reduced else None,
+            ))
+
+
+    def _custom_reduce_ex(self, protocol):
+        callable, args, kwargs, state, listitems, dictitems = vars(self)["_copium_reduce"]
+        listitems = None if listitems is None else iter(listitems)
+        dictitems = None if dictitems is None else iter(dictitems)
+        cls = type(self)
+        if callable == "global":
+            return cls.__qualname__
+        if callable == "newobj":
+            return copyreg.__newobj__, (cls, *args), state, listitems, dictitems
+        if callable == "newobj_ex":
+            return copyreg.__newobj_ex__, (cls, args, kwargs), state, listitems, dictitems
+        return cls._copium_rebuild, args, state, listitems, dictitems
+
+
+    def _setstate(self, state):
+        object.__setattr__(self, "_copium_state", state)
+
+
+    def load(description):
+        if not isinstance(description, dict) or description.get("copium_structure") != 1:
+            raise ValueError("not a copium structure dump")
+        return _Builder(description["nodes"]).build(description["root"])
+"#;
+
+/// Rebuilds a graph shaped like `description`, as made by `describe`.
+pub unsafe fn rebuild(description: *mut PyObject) -> *mut PyObject {
+    unsafe {
+        let globals = crate::cache::exec_str(LOADER);
+        if globals.is_null() {
+            return ptr::null_mut();
+        }
+        let load = PyDict_GetItemString(globals as _, crate::cstr!("load"));
+        let result = if load.is_null() {
+            PyErr_SetString(
+                PyExc_RuntimeError,
+                crate::cstr!("structure loader is missing"),
+            );
+            ptr::null_mut()
+        } else {
+            load.call_one(description)
+        };
+        globals.decref();
+        result
+    }
+}
@@ -0,0 +1,590 @@
+//! Deepcopy of list, set and frozenset subclasses, and of `OrderedDict`,
+//! `defaultdict`, `deque` and their subclasses, that keep the builtin pickle
+//! protocol.
+//!
+//! stdlib copies such an instance through `__reduce_ex__`: a list subclass is
+//! created empty with `cls.__new__(cls)`, memoized, given a copy of its
+//! `__dict__` and then its copied items appended; a set or frozenset subclass
+//! is created with `cls(copied_items)`, memoized, then given its `__dict__`;
+//! an `OrderedDict` is created with `cls()`, memoized, given its `__dict__`,
+//! then its copied items set in order; a `defaultdict` is created with
+//! `cls(copied_default_factory)`, or `cls()` when that is None, memoized, then
+//! given its copied items, and no `__dict__`; a `deque` is created empty with
+//! the same `maxlen`, memoized, given its `__dict__`, then its copied items
+//! appended. When a type overrides nothing those steps depend on, they are
+//! taken here directly instead of building and unpacking the reduce tuple.
+
+use pyo3_ffi::*;
+use std::os::raw::c_int;
+use std::ptr;
+
+use crate::deepcopy;
+use crate::memo::Memo;
+use crate::py_str;
+use crate::types::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Other,
+    List,
+    Set,
+    OrderedDict,
+    DefaultDict,
+    Deque,
+}
+
+// ── Per-type cache ─────────────────────────────────────────
+
+const KIND_CACHE_SIZE: usize = 64;
+
+struct KindCacheEntry {
+    tp: CachedType,
+    version: u32,
+    kind: Kind,
+}
+
+/// Direct-mapped and per-thread, keyed by version tag like the dataclass cache:
+/// overriding a method on the type or a base later is a miss.
+#[thread_local]
+static mut KIND_CACHE: [KindCacheEntry; KIND_CACHE_SIZE] = [const {
+    KindCacheEntry {
+        tp: CachedType::EMPTY,
+        version: 0,
+        kind: Kind::Other,
+    }
+}; KIND_CACHE_SIZE];
+
+unsafe fn kind_of(tp: *mut PyTypeObject) -> Result<Kind, ()> {
+    unsafe {
+        let slot = ((tp as usize) >> 4) % KIND_CACHE_SIZE;
+        let cached = &*ptr::addr_of!(KIND_CACHE[slot]);
+        if cached.tp.get() == tp && version_of(tp) == Some(cached.version) {
+            return Ok(cached.kind);
+        }
+
+        let kind = classify(tp)?;
+        if let Some(version) = version_of(tp) {
+            let entry = KindCacheEntry {
+                tp: CachedType::new(tp),
+                version,
+                kind,
+            };
+            // Releasing the evicted type may run arbitrary code; do it last.
+            let evicted = std::mem::replace(&mut *ptr::addr_of_mut!(KIND_CACHE[slot]), entry);
+            drop(evicted);
+        }
+        Ok(kind)
+    }
+}
+
+/// Empties this thread's cache, releasing the types it held.
+pub unsafe fn clear_cache() {
+    unsafe {
+        let evicted = std::mem::replace(
+            &mut *ptr::addr_of_mut!(KIND_CACHE),
+            [const {
+                KindCacheEntry {
+                    tp: CachedType::EMPTY,
+                    version: 0,
+                    kind: Kind::Other,
+                }
+            }; KIND_CACHE_SIZE],
+        );
+        drop(evicted);
+    }
+}
+
+unsafe fn classify(tp: *mut PyTypeObject) -> Result<Kind, ()> {
+    unsafe {
+        let (base, kind) = if let Some(found) = collections_dict_base(tp) {
+            found
+        } else if let Some(base) = deque_base(tp) {
+            (base, Kind::Deque)
+        } else if PyType_GetFlags(tp) & Py_TPFLAGS_HEAPTYPE == 0 {
+            return Ok(Kind::Other);
+        } else if PyType_IsSubtype(tp, ptr::addr_of_mut!(PyList_Type)) != 0 {
+            (ptr::addr_of_mut!(PyList_Type), Kind::List)
+        } else if PyType_IsSubtype(tp, ptr::addr_of_mut!(PySet_Type)) != 0 {
+            (ptr::addr_of_mut!(PySet_Type), Kind::Set)
+        } else if PyType_IsSubtype(tp, ptr::addr_of_mut!(PyFrozenSet_Type)) != 0 {
+            (ptr::addr_of_mut!(PyFrozenSet_Type), Kind::Set)
+        } else {
+            return Ok(Kind::Other);
+        };
+
+        // A custom __new__ or __init__ may expect other arguments, or none.
+        if (*tp).tp_new.map(|f| f as usize) != (*base).tp_new.map(|f| f as usize)
+            || (*tp).tp_init.map(|f| f as usize) != (*base).tp_init.map(|f| f as usize)
+        {
+            return Ok(Kind::Other);
+        }
+
+        let inherited = [
+            py_str!("__reduce_ex__"),
+            py_str!("__reduce__"),
+            py_str!("__getstate__"),
+            py_str!("__setstate__"),
+            py_str!("__getnewargs_ex__"),
+            py_str!("__getnewargs__"),
+            py_str!("__slots__"),
+        ];
+        // What the direct path calls in place of the reduce tuple's items.
+        let used = match kind {
+            Kind::List => [py_str!("append"), ptr::null_mut(), ptr::null_mut()],
+            Kind::OrderedDict => [py_str!("items"), py_str!("__setitem__"), ptr::null_mut()],
+            Kind::DefaultDict => [
+                py_str!("items"),
+                py_str!("__setitem__"),
+                py_str!("default_factory"),
+            ],
+            Kind::Deque => [py_str!("append"), py_str!("maxlen"), ptr::null_mut()],
+            _ => [ptr::null_mut(); 3],
+        };
+        let used = used.into_iter().filter(|name| !name.is_null());
+        for name in inherited.into_iter().chain(used) {
+            if !resolves_like(tp, base, name)? {
+                return Ok(Kind::Other);
+            }
+        }
+        Ok(kind)
+    }
+}
+
+/// `collections.OrderedDict` or `collections.defaultdict` if `tp` is one or
+/// inherits from one. Found by name along the MRO, so `collections` is never
+/// imported for them.
+unsafe fn collections_dict_base(tp: *mut PyTypeObject) -> Option<(*mut PyTypeObject, Kind)> {
+    unsafe {
+        if PyType_GetFlags(tp) & Py_TPFLAGS_DICT_SUBCLASS == 0 || (*tp).tp_mro.is_null() {
+            return None;
+        }
+        let mro = (*tp).tp_mro as *mut PyTupleObject;
+        for i in 0..mro.length() {
+            let base = mro.get_borrowed_unchecked(i) as *mut PyTypeObject;
+            if COLLECTIONS_ORDERED_DICT.contains(base) {
+                return Some((base, Kind::OrderedDict));
+            }
+            if COLLECTIONS_DEFAULT_DICT.contains(base) {
+                return Some((base, Kind::DefaultDict));
+            }
+        }
+        None
+    }
+}
+
+/// `collections.deque` if `tp` is it or inherits from it, found like
+/// [`collections_dict_base`].
+unsafe fn deque_base(tp: *mut PyTypeObject) -> Option<*mut PyTypeObject> {
+    unsafe {
+        if (*tp).tp_mro.is_null() {
+            return None;
+        }
+        let mro = (*tp).tp_mro as *mut PyTupleObject;
+        for i in 0..mro.length() {
+            let base = mro.get_borrowed_unchecked(i) as *mut PyTypeObject;
+            if COLLECTIONS_DEQUE.contains(base) {
+                return Some(base);
+            }
+        }
+        None
+    }
+}
+
+/// Whether `name` looked up on `tp` finds what it finds on `base`, absence included.
+pub(crate) unsafe fn resolves_like(
+    tp: *mut PyTypeObject,
+    base: *mut PyTypeObject,
+    name: *mut PyObject,
+) -> Result<bool, ()> {
+    unsafe {
+        let mut own: *mut PyObject = ptr::null_mut();
+        if type_lookup_optional(tp, name, &mut own) < 0 {
+            return Err(());
+        }
+        let mut inherited: *mut PyObject = ptr::null_mut();
+        if type_lookup_optional(base, name, &mut inherited) < 0 {
+            own.decref_nullable();
+            return Err(());
+        }
+        let same = own == inherited;
+        own.decref_nullable();
+        inherited.decref_nullable();
+        Ok(same)
+    }
+}
+
+// ── Reconstruction ─────────────────────────────────────────
+
+/// Gives `instance` a deep copy of the attributes in `original.__dict__`, as
+/// reduce's state would. An empty dict, or none (a plain `deque`), is no
+/// state at all.
+unsafe fn copy_instance_dict<M: Memo>(
+    original: *mut PyObject,
+    instance: *mut PyObject,
+    memo: &mut M,
+) -> c_int {
+    unsafe {
+        if (*original.class()).tp_dictoffset == 0 {
+            return 0;
+        }
+        let dict = PyObject_GenericGetDict(original, ptr::null_mut());
+        if dict.is_null() {
+            return -1;
+        }
+        let ret = if PyDict_Size(dict) == 0 {
+            0
+        } else {
+            crate::reduce::apply_dict_state(instance, dict, memo)
+        };
+        dict.decref();
+        ret
+    }
+}
+
+/// `cls.__new__(cls)`, memoized before anything is copied so items can refer
+/// back to it, then state and items in stdlib's order.
+unsafe fn reconstruct_list<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let no_args = PyTuple_New(0);
+        if no_args.is_null() {
+            return ptr::null_mut();
+        }
+        let instance = crate::reduce::call_tp_new(tp, no_args, ptr::null_mut());
+        no_args.decref();
+        if instance.is_null() {
+            return ptr::null_mut();
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+
+        if copy_instance_dict(original, instance, memo) < 0
+            || append_copies(original, memo, |copied| PyList_Append(instance, copied)) < 0
+        {
+            memo.forget(original, probe);
+            instance.decref();
+            return ptr::null_mut();
+        }
+        instance
+    }
+}
+
+unsafe fn append_copies<M: Memo>(
+    original: *mut PyObject,
+    memo: &mut M,
+    mut append: impl FnMut(*mut PyObject) -> c_int,
+) -> c_int {
+    unsafe {
+        let iterator = original.get_iter();
+        if iterator.is_null() {
+            return -1;
+        }
+        let mut ret: c_int = 0;
+        loop {
+            let item = PyIter_Next(iterator);
+            if item.is_null() {
+                break;
+            }
+            let copied = deepcopy::deepcopy(item, memo);
+            item.decref();
+            if copied.is_error() {
+                ret = -1;
+                break;
+            }
+            let copied = copied.into_raw();
+            let status = append(copied);
+            copied.decref();
+            if status < 0 {
+                ret = -1;
+                break;
+            }
+        }
+        if ret == 0 && !PyErr_Occurred().is_null() {
+            ret = -1;
+        }
+        iterator.decref();
+        ret
+    }
+}
+
+/// `cls()`, memoized before anything is copied so values can refer back to it,
+/// then state and the copied items in the original's order.
+unsafe fn reconstruct_ordered_dict<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let instance = (tp as *mut PyObject).call();
+        if instance.is_null() {
+            return ptr::null_mut();
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+
+        if copy_instance_dict(original, instance, memo) < 0
+            || set_copied_items(original, instance, memo) < 0
+        {
+            memo.forget(original, probe);
+            instance.decref();
+            return ptr::null_mut();
+        }
+        instance
+    }
+}
+
+/// `cls((), maxlen)`, or `cls()` without one, memoized before anything is
+/// copied so items can refer back to it, then state and items in stdlib's order.
+unsafe fn reconstruct_deque<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let maxlen = original.getattr(py_str!("maxlen"));
+        if maxlen.is_null() {
+            return ptr::null_mut();
+        }
+        let instance = if maxlen.is_none() {
+            (tp as *mut PyObject).call()
+        } else {
+            let no_items = PyTuple_New(0);
+            if no_items.is_null() {
+                maxlen.decref();
+                return ptr::null_mut();
+            }
+            let instance = PyObject_CallFunctionObjArgs(
+                tp as *mut PyObject,
+                no_items,
+                maxlen,
+                ptr::null_mut::<PyObject>(),
+            );
+            no_items.decref();
+            instance
+        };
+        maxlen.decref();
+        if instance.is_null() {
+            return ptr::null_mut();
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+
+        let append = instance.getattr(py_str!("append"));
+        if append.is_null()
+            || copy_instance_dict(original, instance, memo) < 0
+            || append_copies(original, memo, |copied| {
+                let result = append.call_one(copied);
+                if result.is_null() {
+                    return -1;
+                }
+                result.decref();
+                0
+            }) < 0
+        {
+            append.decref_nullable();
+            memo.forget(original, probe);
+            instance.decref();
+            return ptr::null_mut();
+        }
+        append.decref();
+        instance
+    }
+}
+
+/// `cls(copied_default_factory)`, memoized before the items are copied so
+/// values can refer back to it. Like defaultdict's `__reduce__`, the factory is
+/// copied first and the instance `__dict__` is not carried over.
+unsafe fn reconstruct_default_dict<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let factory = original.getattr(py_str!("default_factory"));
+        if factory.is_null() {
+            return ptr::null_mut();
+        }
+        let instance = if factory.is_none() {
+            factory.decref();
+            (tp as *mut PyObject).call()
+        } else {
+            let copied = deepcopy::deepcopy(factory, memo);
+            factory.decref();
+            if copied.is_error() {
+                return ptr::null_mut();
+            }
+            let copied = copied.into_raw();
+            let instance = (tp as *mut PyObject).call_one(copied);
+            copied.decref();
+            instance
+        };
+        if instance.is_null() {
+            return ptr::null_mut();
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+
+        if set_copied_items(original, instance, memo) < 0 {
+            memo.forget(original, probe);
+            instance.decref();
+            return ptr::null_mut();
+        }
+        instance
+    }
+}
+
+unsafe fn set_copied_items<M: Memo>(
+    original: *mut PyObject,
+    instance: *mut PyObject,
+    memo: &mut M,
+) -> c_int {
+    unsafe {
+        // items() rather than the dict's own storage, which doesn't follow
+        // move_to_end(); iterating it also raises if a copy mutates the original.
+        let items_method = original.getattr(py_str!("items"));
+        if items_method.is_null() {
+            return -1;
+        }
+        let items = items_method.call();
+        items_method.decref();
+        if items.is_null() {
+            return -1;
+        }
+        let iterator = items.get_iter();
+        items.decref();
+        if iterator.is_null() {
+            return -1;
+        }
+
+        let mut ret: c_int = 0;
+        loop {
+            let pair = PyIter_Next(iterator);
+            if pair.is_null() {
+                break;
+            }
+            let pair_tuple = pair as *mut PyTupleObject;
+            let key = deepcopy::deepcopy(pair_tuple.get_borrowed_unchecked(0), memo);
+            if key.is_error() {
+                pair.decref();
+                ret = -1;
+                break;
+            }
+            let key = key.into_raw();
+            let value = deepcopy::deepcopy(pair_tuple.get_borrowed_unchecked(1), memo);
+            pair.decref();
+            if value.is_error() {
+                key.decref();
+                ret = -1;
+                break;
+            }
+            let value = value.into_raw();
+            let status = PyObject_SetItem(instance, key, value);
+            key.decref();
+            value.decref();
+            if status < 0 {
+                ret = -1;
+                break;
+            }
+        }
+        if ret == 0 && !PyErr_Occurred().is_null() {
+            ret = -1;
+        }
+        iterator.decref();
+        ret
+    }
+}
+
+/// `cls(copied_items)`, which needs the items first, so it is memoized after
+/// them: like stdlib, a member leading back to the set is unbounded recursion.
+unsafe fn reconstruct_set<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> *mut PyObject {
+    unsafe {
+        let items = PySequence_List(original);
+        if items.is_null() {
+            return ptr::null_mut();
+        }
+        let count = PyList_GET_SIZE(items);
+        for i in 0..count {
+            let copied = deepcopy::deepcopy(PyList_GET_ITEM(items, i), memo);
+            if copied.is_error() {
+                items.decref();
+                return ptr::null_mut();
+            }
+            // Steals the copy and drops the original item, which the set still holds.
+            PyList_SetItem(items, i, copied.into_raw());
+        }
+
+        let instance = (tp as *mut PyObject).call_one(items);
+        items.decref();
+        if instance.is_null() {
+            return ptr::null_mut();
+        }
+
+        if memo.memoize(original, instance, probe) < 0 {
+            instance.decref();
+            return ptr::null_mut();
+        }
+
+        if copy_instance_dict(original, instance, memo) < 0 {
+            memo.forget(original, probe);
+            instance.decref();
+            return ptr::null_mut();
+        }
+        instance
+    }
+}
+
+/// Deepcopy for list, set, frozenset, OrderedDict, defaultdict and deque
+/// subclasses (and the last three themselves) that override none of the copy
+/// protocol. Returns None for any other type, otherwise the memoized copy or
+/// null with an exception set.
+pub unsafe fn reconstruct<M: Memo>(
+    original: *mut PyObject,
+    tp: *mut PyTypeObject,
+    memo: &mut M,
+    probe: &M::Probe,
+) -> Option<*mut PyObject> {
+    unsafe {
+        let kind = match kind_of(tp) {
+            Ok(Kind::Other) => return None,
+            Ok(kind) => kind,
+            Err(()) => return Some(ptr::null_mut()),
+        };
+
+        if crate::recursion::enter() < 0 {
+            return Some(ptr::null_mut());
+        }
+        let instance = match kind {
+            Kind::List => reconstruct_list(original, tp, memo, probe),
+            Kind::OrderedDict => reconstruct_ordered_dict(original, tp, memo, probe),
+            Kind::DefaultDict => reconstruct_default_dict(original, tp, memo, probe),
+            Kind::Deque => reconstruct_deque(original, tp, memo, probe),
+            _ => reconstruct_set(original, tp, memo, probe),
+        };
+        crate::recursion::leave();
+        Some(instance)
+    }
+}
@@ -3,6 +3,7 @@ use pyo3_ffi::*;
 use std::hint::unlikely;
 use std::os::raw::{c_char, c_int};
 use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 
 use crate::compat;
 use crate::ffi_ext;
@@ -67,6 +68,9 @@ pub unsafe trait PyObjectPtr {
     unsafe fn call(self) -> *mut PyObject;
     unsafe fn call_one(self, arg: *mut PyObject) -> *mut PyObject;
     unsafe fn call_with(self, args: *mut PyObject) -> *mut PyObject;
+    /// Calls with `nargs` positional arguments read from `args`, whose slot
+    /// `args[-1]` must be writable: a bound method borrows it to prepend self.
+    unsafe fn vectorcall(self, args: *mut *mut PyObject, nargs: usize) -> *mut PyObject;
     unsafe fn set_attr(self, name: *mut PyObject, value: *mut PyObject) -> c_int;
     unsafe fn get_iter(self) -> *mut PyObject;
 
@@ -74,8 +78,8 @@ pub unsafe trait PyObjectPtr {
     unsafe fn is_tuple(self) -> bool;
     unsafe fn is_dict(self) -> bool;
     unsafe fn is_unicode(self) -> bool;
-    unsafe fn is_bytes(self) -> bool;
     unsafe fn is_none(self) -> bool;
+    unsafe fn may_be_gc_tracked(self) -> bool;
 }
 
 unsafe impl<T: PyTypeInfo> PyObjectPtr for *mut T {
@@ -126,6 +130,15 @@ unsafe impl<T: PyTypeInfo> PyObjectPtr for *mut T {
         PyObject_CallObject(self as *mut PyObject, args)
     }
     #[inline(always)]
+    unsafe fn vectorcall(self, args: *mut *mut PyObject, nargs: usize) -> *mut PyObject {
+        PyObject_Vectorcall(
+            self as *mut PyObject,
+            args,
+            nargs | PY_VECTORCALL_ARGUMENTS_OFFSET,
+            ptr::null_mut(),
+        )
+    }
+    #[inline(always)]
     unsafe fn set_attr(self, name: *mut PyObject, value: *mut PyObject) -> c_int {
         PyObject_SetAttr(self as *mut PyObject, name, value)
     }
@@ -150,13 +163,20 @@ unsafe impl<T: PyTypeInfo> PyObjectPtr for *mut T {
         (crate::ffi_ext::tp_flags_of(self.class()) & (Py_TPFLAGS_UNICODE_SUBCLASS as c_ulong)) != 0
     }
     #[inline(always)]
-    unsafe fn is_bytes(self) -> bool {
-        (crate::ffi_ext::tp_flags_of(self.class()) & (Py_TPFLAGS_BYTES_SUBCLASS as c_ulong)) != 0
-    }
-    #[inline(always)]
     unsafe fn is_none(self) -> bool {
         self as *mut PyObject == ffi_ext::Py_None()
     }
+    /// CPython's `_PyObject_GC_MAY_BE_TRACKED`: whether holding this object
+    /// keeps a tuple tracked. Types with `tp_is_gc` are treated as GC objects.
+    #[inline(always)]
+    unsafe fn may_be_gc_tracked(self) -> bool {
+        let tp = self.class();
+        if ffi_ext::tp_flags_of(tp) & Py_TPFLAGS_HAVE_GC as c_ulong == 0 {
+            return false;
+        }
+        tp != std::ptr::addr_of_mut!(PyTuple_Type)
+            || PyObject_GC_IsTracked(self as *mut PyObject) != 0
+    }
 }
 
 pub unsafe trait PyObjectSlotPtr {
@@ -216,6 +236,72 @@ pub unsafe fn py_method_new(
     ffi_ext::PyMethod_New(func, self_) as _
 }
 
+// ── Type attribute lookup ──────────────────────────────────
+
+/// The type's version tag, if valid. CPython invalidates it whenever the type
+/// (or a base) is modified and never reuses one, so it keys per-type caches.
+#[inline(always)]
+pub unsafe fn version_of(tp: *mut PyTypeObject) -> Option<u32> {
+    unsafe {
+        if PyType_GetFlags(tp) & Py_TPFLAGS_VALID_VERSION_TAG != 0 {
+            Some((*tp).tp_version_tag)
+        } else {
+            None
+        }
+    }
+}
+
+/// A strong reference to a type, held by the per-type caches: the version tag
+/// already tells a recycled address apart, and holding the type besides means
+/// an entry can never point at freed memory at all. Released with the entry.
+pub struct CachedType(*mut PyTypeObject);
+
+impl CachedType {
+    pub const EMPTY: Self = Self(ptr::null_mut());
+
+    pub unsafe fn new(tp: *mut PyTypeObject) -> Self {
+        unsafe { (tp as *mut PyObject).incref() };
+        Self(tp)
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> *mut PyTypeObject {
+        self.0
+    }
+}
+
+impl Drop for CachedType {
+    fn drop(&mut self) {
+        unsafe { (self.0 as *mut PyObject).decref_nullable() }
+    }
+}
+
+/// `getattr(tp, name)` as `type.__getattribute__` resolves it, skipping any
+/// metaclass `__getattr__`. For copium's own probes of a type, which stdlib
+/// doesn't make and lazy-loading hooks shouldn't observe. Returns like
+/// `get_optional_attr`.
+pub unsafe fn type_lookup_optional(
+    tp: *mut PyTypeObject,
+    name: *mut PyObject,
+    out: &mut *mut PyObject,
+) -> c_int {
+    unsafe {
+        let value = match (*ptr::addr_of!(PyType_Type)).tp_getattro {
+            Some(getattro) => getattro(tp as *mut PyObject, name),
+            None => PyObject_GetAttr(tp as *mut PyObject, name),
+        };
+        *out = value;
+        if !value.is_null() {
+            return 1;
+        }
+        if PyErr_ExceptionMatches(PyExc_AttributeError) != 0 {
+            PyErr_Clear();
+            return 0;
+        }
+        -1
+    }
+}
+
 // ── Indexed sequence ops (list, tuple) ─────────────────────
 #[inline(always)]
 unsafe fn valid_index(i: Py_ssize_t, limit: Py_ssize_t) -> bool {
@@ -388,6 +474,9 @@ unsafe impl PyBoundMethodPtr for *mut ffi_ext::PyMethodObject {
 
 // ── Immutability checks ────────────────────────────────────
 
+const LITERAL_SUBCLASS_FLAGS: c_ulong =
+    (Py_TPFLAGS_LONG_SUBCLASS | Py_TPFLAGS_UNICODE_SUBCLASS | Py_TPFLAGS_BYTES_SUBCLASS) as c_ulong;
+
 pub unsafe trait PyTypeObjectPtr {
     unsafe fn is_literal_immutable(self) -> bool;
     unsafe fn is_builtin_immutable(self) -> bool;
@@ -400,12 +489,17 @@ pub unsafe trait PyTypeObjectPtr {
 unsafe impl PyTypeObjectPtr for *mut PyTypeObject {
     #[inline(always)]
     unsafe fn is_literal_immutable(self) -> bool {
+        // int, bool, str and bytes carry a subclass flag, so one flags load
+        // settles most types before any pointer is compared.
+        let flags = crate::ffi_ext::tp_flags_of(self);
+        if flags & LITERAL_SUBCLASS_FLAGS != 0 {
+            return (self == std::ptr::addr_of_mut!(PyLong_Type))
+                | (self == std::ptr::addr_of_mut!(PyUnicode_Type))
+                | (self == std::ptr::addr_of_mut!(PyBool_Type))
+                | (self == std::ptr::addr_of_mut!(PyBytes_Type));
+        }
         (self == std::ptr::addr_of_mut!(_PyNone_Type))
-            | (self == std::ptr::addr_of_mut!(PyLong_Type))
-            | (self == std::ptr::addr_of_mut!(PyUnicode_Type))
-            | (self == std::ptr::addr_of_mut!(PyBool_Type))
             | (self == std::ptr::addr_of_mut!(PyFloat_Type))
-            | (self == std::ptr::addr_of_mut!(PyBytes_Type))
     }
 
     #[inline(always)]
@@ -423,9 +517,7 @@ unsafe impl PyTypeObjectPtr for *mut PyTypeObject {
 
     #[inline(always)]
     unsafe fn is_stdlib_immutable(self) -> bool {
-        (self == py_type!("re.Pattern"))
-            || (self == py_type!("decimal.Decimal"))
-            || (self == py_type!("fractions.Fraction"))
+        (self == py_type!("re.Pattern")) || is_stdlib_atomic(self)
     }
 
     #[inline(always)]
@@ -448,3 +540,333 @@ unsafe impl PyTypeObjectPtr for *mut PyTypeObject {
             || self.is_stdlib_immutable()
     }
 }
+
+// ── Lazily resolved types ──────────────────────────────────
+
+/// A type known by (module, qualname) whose pointer is only filled in once an
+/// object of that type is actually seen, so its module is never imported by us.
+pub struct LazyType {
+    module: &'static str,
+    qualname: &'static str,
+    slot: AtomicPtr<PyTypeObject>,
+}
+
+impl LazyType {
+    pub const fn new(module: &'static str, qualname: &'static str) -> Self {
+        Self {
+            module,
+            qualname,
+            slot: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+enum Lookup {
+    Resolved,
+    Unknown,
+    Pending,
+}
+
+pub struct LazyTypeRegistry<const N: usize> {
+    entries: [LazyType; N],
+    resolved: AtomicUsize,
+    /// {(module, qualname): entry index}, built on the first miss.
+    index: AtomicPtr<PyObject>,
+}
+
+pub static STDLIB_ATOMIC_TYPES: LazyTypeRegistry<2> = LazyTypeRegistry::new([
+    LazyType::new("decimal", "Decimal"),
+    LazyType::new("fractions", "Fraction"),
+]);
+
+/// Types with a dedicated reconstruction path in place of their __reduce__.
+pub static COLLECTIONS_COUNTER: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("collections", "Counter")]);
+
+pub static COLLECTIONS_ORDERED_DICT: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("collections", "OrderedDict")]);
+
+pub static COLLECTIONS_DEFAULT_DICT: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("collections", "defaultdict")]);
+
+pub static COLLECTIONS_DEQUE: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("collections", "deque")]);
+
+pub static DECIMAL_CONTEXT: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("decimal", "Context")]);
+
+pub static ARRAY_ARRAY: LazyTypeRegistry<1> =
+    LazyTypeRegistry::new([LazyType::new("array", "array")]);
+
+const NEGATIVE_CACHE_SIZE: usize = 64;
+
+/// (registry, type) pairs already known not to match. Direct-mapped and
+/// per-thread, so it needs no synchronization; a stale slot can only cause a
+/// false negative.
+#[thread_local]
+static mut NEGATIVE_CACHE: [(usize, *mut PyTypeObject); NEGATIVE_CACHE_SIZE] =
+    [(0, ptr::null_mut()); NEGATIVE_CACHE_SIZE];
+
+/// Forgets which types this thread found not to match a registry.
+pub fn clear_lazy_type_negative_cache() {
+    unsafe {
+        *ptr::addr_of_mut!(NEGATIVE_CACHE) = [(0, ptr::null_mut()); NEGATIVE_CACHE_SIZE];
+    }
+}
+
+#[inline(always)]
+fn negative_cache_slot(registry: usize, tp: *mut PyTypeObject) -> usize {
+    (((tp as usize) ^ registry) >> 4) % NEGATIVE_CACHE_SIZE
+}
+
+// ── Per-type stdlib-atomic cache ───────────────────────────
+
+const MISS_CACHE_SIZE: usize = 256;
+
+struct MissEntry {
+    tp: CachedType,
+    version: u32,
+}
+
+/// Types already found not to be in `STDLIB_ATOMIC_TYPES`. Every object that
+/// isn't otherwise atomic asks, so until both entries are resolved this keeps a
+/// type to one lookup by name instead of one per object. Direct-mapped and
+/// per-thread, keyed by version tag like the other per-type caches.
+#[thread_local]
+static mut STDLIB_MISS_CACHE: [MissEntry; MISS_CACHE_SIZE] = [const {
+    MissEntry {
+        tp: CachedType::EMPTY,
+        version: 0,
+    }
+}; MISS_CACHE_SIZE];
+
+#[inline(always)]
+unsafe fn is_stdlib_atomic(tp: *mut PyTypeObject) -> bool {
+    match STDLIB_ATOMIC_TYPES.contains_resolved(tp) {
+        Some(found) => found,
+        None => unsafe { is_stdlib_atomic_slow(tp) },
+    }
+}
+
+#[cold]
+unsafe fn is_stdlib_atomic_slow(tp: *mut PyTypeObject) -> bool {
+    unsafe {
+        let slot = ((tp as usize) >> 4) % MISS_CACHE_SIZE;
+        let cached = &*ptr::addr_of!(STDLIB_MISS_CACHE[slot]);
+        if cached.tp.get() == tp && version_of(tp) == Some(cached.version) {
+            return false;
+        }
+        match STDLIB_ATOMIC_TYPES.lookup(tp) {
+            Lookup::Resolved => true,
+            Lookup::Pending => false,
+            Lookup::Unknown => {
+                if let Some(version) = version_of(tp) {
+                    let entry = MissEntry {
+                        tp: CachedType::new(tp),
+                        version,
+                    };
+                    // Releasing the evicted type may run arbitrary code; do it last.
+                    let evicted =
+                        std::mem::replace(&mut *ptr::addr_of_mut!(STDLIB_MISS_CACHE[slot]), entry);
+                    drop(evicted);
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Empties this thread's stdlib-atomic cache, releasing the types it held.
+pub unsafe fn clear_stdlib_atomic_cache() {
+    unsafe {
+        let evicted = std::mem::replace(
+            &mut *ptr::addr_of_mut!(STDLIB_MISS_CACHE),
+            [const {
+                MissEntry {
+                    tp: CachedType::EMPTY,
+                    version: 0,
+                }
+            }; MISS_CACHE_SIZE],
+        );
+        drop(evicted);
+    }
+}
+
+impl<const N: usize> LazyTypeRegistry<N> {
+    pub const fn new(entries: [LazyType; N]) -> Self {
+        Self {
+            entries,
+            resolved: AtomicUsize::new(0),
+            index: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    #[inline(always)]
+    pub unsafe fn contains(&self, tp: *mut PyTypeObject) -> bool {
+        match self.contains_resolved(tp) {
+            Some(found) => found,
+            None => unsafe { self.contains_slow(tp) },
+        }
+    }
+
+    /// The answer when the resolved entries alone decide it, without a lookup.
+    #[inline(always)]
+    fn contains_resolved(&self, tp: *mut PyTypeObject) -> Option<bool> {
+        for entry in &self.entries {
+            if entry.slot.load(Ordering::Acquire) == tp {
+                return Some(true);
+            }
+        }
+        if self.resolved.load(Ordering::Acquire) == N {
+            return Some(false);
+        }
+        None
+    }
+
+    #[cold]
+    unsafe fn contains_slow(&self, tp: *mut PyTypeObject) -> bool {
+        unsafe {
+            let registry = self as *const Self as usize;
+            let slot = negative_cache_slot(registry, tp);
+            if NEGATIVE_CACHE[slot] == (registry, tp) {
+                return false;
+            }
+            match self.lookup(tp) {
+                Lookup::Resolved => true,
+                Lookup::Unknown => {
+                    NEGATIVE_CACHE[slot] = (registry, tp);
+                    false
+                }
+                Lookup::Pending => false,
+            }
+        }
+    }
+
+    unsafe fn index(&self) -> *mut PyObject {
+        unsafe {
+            let existing = self.index.load(Ordering::Acquire);
+            if !existing.is_null() {
+                return existing;
+            }
+
+            let index = PyDict_New();
+            if index.is_null() {
+                return ptr::null_mut();
+            }
+            for (position, entry) in self.entries.iter().enumerate() {
+                let key = PyTuple_New(2);
+                let value = PyLong_FromSsize_t(position as Py_ssize_t);
+                let module = PyUnicode_FromStringAndSize(
+                    entry.module.as_ptr().cast(),
+                    entry.module.len() as Py_ssize_t,
+                );
+                let qualname = PyUnicode_FromStringAndSize(
+                    entry.qualname.as_ptr().cast(),
+                    entry.qualname.len() as Py_ssize_t,
+                );
+                if key.is_null() || value.is_null() || module.is_null() || qualname.is_null() {
+                    key.decref_nullable();
+                    value.decref_nullable();
+                    module.decref_nullable();
+                    qualname.decref_nullable();
+                    index.decref();
+                    return ptr::null_mut();
+                }
+                PyTuple_SET_ITEM(key, 0, module);
+                PyTuple_SET_ITEM(key, 1, qualname);
+                let status = PyDict_SetItem(index, key, value);
+                key.decref();
+                value.decref();
+                if status < 0 {
+                    index.decref();
+                    return ptr::null_mut();
+                }
+            }
+
+            match self.index.compare_exchange(
+                ptr::null_mut(),
+                index,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => index,
+                Err(winner) => {
+                    index.decref();
+                    winner
+                }
+            }
+        }
+    }
+
+    /// Never leaves an exception set: an unimportable or half-imported module
+    /// just means the entry stays pending.
+    unsafe fn lookup(&self, tp: *mut PyTypeObject) -> Lookup {
+        unsafe {
+            let index = self.index();
+            if index.is_null() {
+                PyErr_Clear();
+                return Lookup::Pending;
+            }
+
+            let tp_object = tp as *mut PyObject;
+            let module = tp_object.getattr(crate::py_str!("__module__"));
+            if module.is_null() {
+                PyErr_Clear();
+                return Lookup::Unknown;
+            }
+            let qualname = tp_object.getattr(crate::py_str!("__qualname__"));
+            if qualname.is_null() {
+                PyErr_Clear();
+                module.decref();
+                return Lookup::Unknown;
+            }
+
+            let key = PyTuple_Pack(2, module, qualname);
+            let position = if key.is_null() {
+                ptr::null_mut()
+            } else {
+                PyDict_GetItemWithError(index, key)
+            };
+            key.decref_nullable();
+            if position.is_null() {
+                PyErr_Clear();
+                module.decref();
+                qualname.decref();
+                return Lookup::Unknown;
+            }
+            let entry = &self.entries[PyLong_AsSsize_t(position) as usize];
+
+            // Only consult sys.modules: if an instance exists, its module does too.
+            let owner = PyImport_GetModule(module);
+            module.decref();
+            if owner.is_null() {
+                PyErr_Clear();
+                qualname.decref();
+                return Lookup::Pending;
+            }
+            let candidate = owner.getattr(qualname);
+            owner.decref();
+            qualname.decref();
+            if candidate.is_null() {
+                PyErr_Clear();
+                return Lookup::Pending;
+            }
+            candidate.decref();
+            if candidate != tp_object {
+                return Lookup::Unknown;
+            }
+
+            tp_object.incref();
+            if entry
+                .slot
+                .compare_exchange(ptr::null_mut(), tp, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                self.resolved.fetch_add(1, Ordering::AcqRel);
+            } else {
+                tp_object.decref();
+            }
+            Lookup::Resolved
+        }
+    }
+}
@@ -33,6 +33,8 @@ def test_copy() -> None:
     assert_type(copium.deepcopy(X, memo={}), XT)
     assert_type(copium.deepcopy(X, memo=None), XT)
     assert_type(copium.deepcopy(x=X, memo={}), XT)
+    assert_type(copium.deepcopy(X, replace=[(X[2], {})]), XT)
+    assert_type(copium.deepcopy(X, {}, replace={1: 2}), XT)
 
     if sys.version_info >= (3, 13):
 
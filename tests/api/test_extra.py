@@ -2,6 +2,8 @@
 #
 # SPDX-License-Identifier: MIT
 
+from typing import Any
+
 import pytest
 
 try:
@@ -18,3 +20,9 @@ from tests.api import X
 def test_extra() -> None:
     assert_type(copium.extra.replicate(X, 1), list[XT])
     assert_type(copium.extra.repeatcall(lambda: X, 1), list[XT])
+    assert_type(copium.extra.deepcopy_with_memo(X), tuple[XT, dict[int, Any]])
+
+    async def awaiting() -> None:
+        assert_type(await copium.extra.adeepcopy(X), XT)
+
+    awaiting().close()
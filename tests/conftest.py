@@ -11,6 +11,8 @@ import os
 import subprocess
 import sys
 import textwrap
+import threading
+import tracemalloc
 from pathlib import Path
 from types import FunctionType
 from types import MappingProxyType
@@ -37,7 +39,10 @@ COPIUM_ENV: Final = MappingProxyType(
             "COPIUM_NO_MEMO_FALLBACK_WARNING",
             "COPIUM_NO_MEMO_FALLBACK",
             "COPIUM_USE_DICT_MEMO",
+            "COPIUM_STRICT",
+            "COPIUM_DEEPCOPY_ARG_COMPAT",
             "COPIUM_PATCH_ENABLE",
+            "COPIUM_TEST_HOOKS",
         )
     )
 )
@@ -48,6 +53,26 @@ def _clear_copium_env():
         os.environ.pop(key, None)
 
 
+def _noop_tracer(frame, event, arg):
+    return _noop_tracer
+
+
+@pytest.fixture(autouse=True)
+def traced(request):
+    """With --traced, every test runs the way it would under a coverage tool."""
+    if not request.config.getoption("--traced"):
+        yield
+        return
+    previous = sys.gettrace()
+    sys.settrace(_noop_tracer)
+    threading.settrace(_noop_tracer)
+    try:
+        yield
+    finally:
+        threading.settrace(None)
+        sys.settrace(previous)
+
+
 @pytest.fixture(autouse=True)
 def restore_config():
     _clear_copium_env()
@@ -65,6 +90,12 @@ def pytest_addoption(parser):
         default=False,
         help="Run memory leak tests (slow, requires psutil)",
     )
+    parser.addoption(
+        "--traced",
+        action="store_true",
+        default=False,
+        help="Run tests under a no-op sys.settrace tracer with tracemalloc enabled",
+    )
 
 
 def pytest_configure(config):
@@ -76,6 +107,8 @@ def pytest_configure(config):
         "markers",
         "subprocess(environ=None): run the body of the test in a fresh Python subprocess",
     )
+    if config.getoption("--traced") and not tracemalloc.is_tracing():
+        tracemalloc.start()
 
 
 def pytest_collection_modifyitems(config, items):
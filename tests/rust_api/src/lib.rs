@@ -0,0 +1,2 @@
+//! Integration tests for `copium::api`, run in-process with an embedded
+//! interpreter: `cargo test --manifest-path tests/rust_api/Cargo.toml`.
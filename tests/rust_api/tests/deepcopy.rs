@@ -0,0 +1,95 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+#[test]
+fn deepcopy_bound_copies_without_importing_copium() {
+    Python::attach(|py| {
+        let inner = PyList::new(py, [1, 2]).unwrap();
+        let outer = PyList::new(py, [inner.as_any(), inner.as_any()]).unwrap();
+
+        let copied = copium::api::deepcopy_bound(outer.as_any()).unwrap();
+        let copied = copied.bind(py).cast::<PyList>().unwrap();
+
+        assert!(!copied.is(&outer));
+        assert!(copied.eq(&outer).unwrap());
+        let first = copied.get_item(0).unwrap();
+        assert!(!first.is(&inner));
+        assert!(first.is(&copied.get_item(1).unwrap()));
+
+        let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+        assert!(!modules.contains("copium").unwrap());
+    });
+}
+
+#[test]
+fn deepcopy_bound_runs_python_hooks_and_keeps_cycles() {
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        py.run(
+            c"
+import dataclasses
+
+@dataclasses.dataclass
+class Node:
+    name: str
+    children: list
+
+root = Node('root', [])
+root.children.append(root)
+",
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let root = globals.get_item("root").unwrap().unwrap();
+
+        let copied = copium::api::deepcopy_bound(&root).unwrap();
+        let copied = copied.bind(py);
+
+        assert!(!copied.is(&root));
+        assert!(copied.get_type().is(root.get_type()));
+        let children = copied.getattr("children").unwrap();
+        assert!(children.get_item(0).unwrap().is(copied));
+    });
+}
+
+#[test]
+fn deepcopy_bound_raises_like_copium() {
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        py.run(
+            c"
+class Broken:
+    def __reduce_ex__(self, protocol):
+        raise LookupError('nope')
+",
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let broken = globals
+            .get_item("Broken")
+            .unwrap()
+            .unwrap()
+            .call0()
+            .unwrap();
+
+        let error = copium::api::deepcopy_bound(&broken).unwrap_err();
+
+        assert!(error.is_instance_of::<pyo3::exceptions::PyLookupError>(py));
+    });
+}
+
+#[test]
+fn init_is_idempotent() {
+    Python::attach(|py| {
+        copium::api::init(py).unwrap();
+        copium::api::init(py).unwrap();
+
+        let value = PyDict::new(py);
+        value.set_item("k", PyList::empty(py)).unwrap();
+        let copied = copium::api::deepcopy_bound(value.as_any()).unwrap();
+
+        assert!(copied.bind(py).eq(&value).unwrap());
+    });
+}
@@ -0,0 +1,45 @@
+# SPDX-FileCopyrightText: 2025-present Arseny Boykov (Bobronium) <hi@bobronium.me>
+#
+# SPDX-License-Identifier: MIT
+import sys
+import sysconfig
+
+import copium
+import copium.__about__
+
+BUILD_KEYS = {
+    "version",
+    "git_hash",
+    "build_hash",
+    "rustc",
+    "pyo3",
+    "patch_strategy",
+    "abi3",
+    "free_threaded",
+    "debug",
+}
+
+
+def test_version_matches_about() -> None:
+    assert copium.__version__ == copium.__about__.__version__
+    assert copium.__build__["version"] == copium.__version__
+
+
+def test_build_info_keys() -> None:
+    assert set(copium.__build__) == BUILD_KEYS
+    assert copium.__build__["rustc"].startswith("rustc ")
+    assert copium.__build__["build_hash"] == copium.__about__.__build_hash__
+
+
+def test_patch_strategy_matches_runtime() -> None:
+    expected = "vectorcall" if sys.version_info >= (3, 12) else "code"
+    assert copium.__build__["patch_strategy"] == expected
+    assert f"{expected}_patch" in copium.features
+
+
+def test_features_match_runtime() -> None:
+    assert isinstance(copium.features, frozenset)
+    free_threaded = bool(sysconfig.get_config_var("Py_GIL_DISABLED"))
+    assert copium.__build__["free_threaded"] is free_threaded
+    assert ("free_threaded" in copium.features) is free_threaded
+    assert ("immortal_objects" in copium.features) is (sys.version_info >= (3, 12))
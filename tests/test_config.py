@@ -4,9 +4,14 @@ Tests for copium configuration: both programmatic (configure/get_config) and env
 
 from __future__ import annotations
 
+import array
+import copy as stdlib_copy
 import os
+import pickle
 import re
+import sys
 import warnings
+from collections.abc import Callable
 from pathlib import Path
 from typing import Any
 from typing import ClassVar
@@ -56,7 +61,27 @@ class StubbornTracking:
 class TestGetConfig:
     def test_returns_dict_with_expected_keys(self):
         cfg = copium.config.get()
-        assert set(cfg) == {"memo", "on_incompatible", "suppress_warnings"}
+        assert set(cfg) == {
+            "memo",
+            "on_incompatible",
+            "suppress_warnings",
+            "strict",
+            "deepcopy_arg_compat",
+            "deepcopy_not_implemented",
+            "intern_cap",
+            "negative_cache",
+            "max_memo_entries",
+            "max_keepalive",
+            "max_leaf_bytes",
+            "max_total_bytes",
+            "copy_buffers",
+            "mark_copies",
+            "compat_warnings",
+            "memo_key_check",
+            "progress_every",
+            "yield_every",
+            "on_checkpoint",
+        }
 
     def test_default_values(self):
         copium.config.apply()
@@ -64,6 +89,22 @@ class TestGetConfig:
         assert cfg["memo"] == "native"
         assert cfg["on_incompatible"] == "warn"
         assert cfg["suppress_warnings"] == ()
+        assert cfg["strict"] is False
+        assert cfg["deepcopy_arg_compat"] is False
+        assert cfg["deepcopy_not_implemented"] == "raise"
+        assert cfg["intern_cap"] == 65536
+        assert cfg["negative_cache"] is False
+        assert cfg["max_memo_entries"] == 0
+        assert cfg["max_keepalive"] == 0
+        assert cfg["max_leaf_bytes"] == 0
+        assert cfg["max_total_bytes"] == 0
+        assert cfg["copy_buffers"] == "raise"
+        assert cfg["mark_copies"] is False
+        assert cfg["compat_warnings"] is False
+        assert cfg["memo_key_check"] is False
+        assert cfg["progress_every"] == 100_000
+        assert cfg["yield_every"] == 0
+        assert cfg["on_checkpoint"] is None
 
 
 # ===========================================================================
@@ -231,6 +272,12 @@ class TestConfigureIncremental:
         copium.config.apply(on_incompatible="raise")
         assert copium.config.get()["memo"] == "dict"
 
+    def test_setting_strict_preserves_memo(self):
+        copium.config.apply(memo="dict")
+        copium.config.apply(strict=True)
+        assert copium.config.get()["memo"] == "dict"
+        assert copium.config.get()["strict"] is True
+
     def test_settings_persist_across_memo_switch(self):
         """on_incompatible='silent' survives memo round-trip."""
         copium.config.apply(memo="native", on_incompatible="silent")
@@ -239,6 +286,468 @@ class TestConfigureIncremental:
         assert copium.config.get()["on_incompatible"] == "silent"
 
 
+# ===========================================================================
+#  configure() — deepcopy_arg_compat
+# ===========================================================================
+
+
+class NoArgDeepcopy:
+    def __deepcopy__(self):
+        return "no-arg"
+
+
+class KeywordMemoDeepcopy:
+    def __deepcopy__(self, *, memo=None):
+        return "keyword-memo"
+
+
+ARG_COMPAT_CASES = [
+    pytest.param(NoArgDeepcopy, "no-arg", id="no-arg"),
+    pytest.param(KeywordMemoDeepcopy, "keyword-memo", id="keyword-memo"),
+]
+MEMO_ARGS = [pytest.param({}, id="native-memo"), pytest.param({"memo": {}}, id="dict-memo")]
+
+
+class TestConfigureDeepcopyArgCompat:
+    @pytest.mark.parametrize("memo_kwargs", MEMO_ARGS)
+    @pytest.mark.parametrize(("cls", "_expected"), ARG_COMPAT_CASES)
+    def test_off_raises_like_stdlib(self, cls, _expected, memo_kwargs):
+        with pytest.raises(TypeError) as stdlib_error:
+            stdlib_copy.deepcopy(cls())
+        with warnings.catch_warnings():
+            warnings.simplefilter("ignore")
+            with pytest.raises(TypeError) as copium_error:
+                copium.deepcopy(cls(), **memo_kwargs)
+
+        assert str(copium_error.value) == str(stdlib_error.value)
+
+    @pytest.mark.parametrize("memo_kwargs", MEMO_ARGS)
+    @pytest.mark.parametrize(("cls", "expected"), ARG_COMPAT_CASES)
+    def test_on_calls_without_memo(self, cls, expected, memo_kwargs):
+        copium.config.apply(deepcopy_arg_compat=True)
+
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            assert copium.deepcopy([cls(), cls()], **memo_kwargs) == [expected, expected]
+
+    def test_retry_is_remembered_per_type(self):
+        class Remembered:
+            def __deepcopy__(self):
+                return "first"
+
+        class Fresh:
+            pass
+
+        arg_counts = []
+
+        def recording(self, *args):
+            arg_counts.append(len(args))
+            return "recorded"
+
+        copium.config.apply(deepcopy_arg_compat=True)
+        assert copium.deepcopy(Remembered()) == "first"
+
+        Remembered.__deepcopy__ = recording
+        Fresh.__deepcopy__ = recording
+        copium.deepcopy([Remembered(), Fresh()])
+        assert arg_counts == [0, 1]
+
+        copium.config.apply(deepcopy_arg_compat=False)
+        copium.deepcopy(Remembered())
+        assert arg_counts == [0, 1, 1]
+
+    def test_type_error_from_inside_deepcopy_is_not_retried(self):
+        calls = []
+
+        class Raising:
+            def __deepcopy__(self, memo=None):
+                calls.append(memo)
+                raise TypeError("f() takes 1 positional argument but 2 were given")
+
+        copium.config.apply(deepcopy_arg_compat=True, on_incompatible="raise")
+
+        with pytest.raises(TypeError, match="positional argument"):
+            copium.deepcopy(Raising())
+        assert len(calls) == 1
+        assert calls[0] is not None
+
+
+# ===========================================================================
+#  configure() — deepcopy_not_implemented
+# ===========================================================================
+
+
+class SometimesUnsupported:
+    """Refuses to deep-copy itself while `remote` is set."""
+
+    def __init__(self, remote: bool) -> None:
+        self.remote = remote
+        self.payload = [1]
+
+    def __deepcopy__(self, memo):
+        if self.remote:
+            raise NotImplementedError("remote handles can't copy themselves")
+        copied = SometimesUnsupported(self.remote)
+        copied.payload = ["custom"]
+        return copied
+
+
+class TestConfigureDeepcopyNotImplemented:
+    @pytest.mark.parametrize("memo_kwargs", MEMO_ARGS)
+    def test_raise_propagates_like_stdlib(self, memo_kwargs):
+        with pytest.raises(NotImplementedError) as stdlib_error:
+            stdlib_copy.deepcopy(SometimesUnsupported(remote=True))
+        with pytest.raises(NotImplementedError) as copium_error:
+            copium.deepcopy(SometimesUnsupported(remote=True), **memo_kwargs)
+
+        assert type(copium_error.value) is NotImplementedError
+        assert copium_error.value.args == stdlib_error.value.args
+        assert copium_error.traceback[-1].name == "__deepcopy__"
+
+    @pytest.mark.parametrize("memo_kwargs", MEMO_ARGS)
+    def test_fallback_reduces_only_the_refusing_objects(self, memo_kwargs):
+        copium.config.apply(deepcopy_not_implemented="fallback")
+        local, remote = SometimesUnsupported(remote=False), SometimesUnsupported(remote=True)
+
+        copied_local, copied_remote = copium.deepcopy([local, remote], **memo_kwargs)
+
+        assert copied_local.payload == ["custom"]
+        assert type(copied_remote) is SometimesUnsupported
+        assert copied_remote.remote is True
+        assert copied_remote.payload == [1]
+        assert copied_remote.payload is not remote.payload
+
+    def test_fallback_undoes_what_deepcopy_memoized(self):
+        class Scribbling(SometimesUnsupported):
+            def __deepcopy__(self, memo):
+                memo[id(self.payload)] = ["stale"]
+                return super().__deepcopy__(memo)
+
+        copium.config.apply(deepcopy_not_implemented="fallback")
+        original = Scribbling(remote=True)
+
+        copied = copium.deepcopy(original)
+
+        assert copied.payload == [1]
+
+    def test_fallback_leaves_other_errors_alone(self):
+        class Broken:
+            def __deepcopy__(self, memo):
+                raise ValueError("broken")
+
+        copium.config.apply(deepcopy_not_implemented="fallback")
+        with pytest.raises(ValueError, match="broken"):
+            copium.deepcopy(Broken())
+
+    def test_rejects_unknown_values(self):
+        with pytest.raises(ValueError, match="deepcopy_not_implemented must be"):
+            copium.config.apply(deepcopy_not_implemented="retry")  # type: ignore[arg-type]
+        assert copium.config.get()["deepcopy_not_implemented"] == "raise"
+
+
+# ===========================================================================
+#  configure() — copy_buffers
+# ===========================================================================
+
+
+class TestConfigureCopyBuffers:
+    def test_raise_by_default_like_stdlib(self):
+        view = memoryview(b"abc")
+        with pytest.raises(TypeError) as stdlib_error:
+            stdlib_copy.deepcopy(view)
+        with pytest.raises(TypeError) as copium_error:
+            copium.deepcopy(view)
+        assert str(copium_error.value) == str(stdlib_error.value)
+
+    def test_owned_copies_read_only_buffers_to_bytes(self):
+        copium.config.apply(copy_buffers="owned")
+        view = memoryview(b"abc")
+
+        copied = copium.deepcopy({"a": view, "b": view})
+
+        assert copied == {"a": b"abc", "b": b"abc"}
+        assert type(copied["a"]) is bytes
+        assert copied["a"] is copied["b"]
+
+    def test_owned_copies_writable_buffers_to_independent_bytearrays(self):
+        copium.config.apply(copy_buffers="owned")
+        source = bytearray(b"abc")
+
+        copied = copium.deepcopy(memoryview(source))
+        source[0] = ord("z")
+
+        assert copied == bytearray(b"abc")
+        assert type(copied) is bytearray
+
+    def test_owned_flattens_in_c_order(self):
+        copium.config.apply(copy_buffers="owned")
+        grid = memoryview(bytearray(range(6))).cast("B", (2, 3))
+        strided = memoryview(b"abcdef")[::2]
+        numbers = memoryview(array.array("i", [1, 2, 3]))
+
+        assert copium.deepcopy(grid) == grid.tobytes()
+        assert copium.deepcopy(strided) == b"ace"
+        assert copium.deepcopy(numbers) == numbers.tobytes()
+
+    def test_owned_copies_c_buffer_types(self):
+        copium.config.apply(copy_buffers="owned")
+
+        assert copium.deepcopy(pickle.PickleBuffer(b"abc")) == b"abc"
+
+    def test_owned_leaves_types_with_their_own_copying_alone(self):
+        copium.config.apply(copy_buffers="owned")
+        numbers = array.array("i", [1, 2, 3])
+
+        copied = copium.deepcopy(numbers)
+
+        assert type(copied) is array.array
+        assert copied == numbers
+        assert copied is not numbers
+
+    @pytest.mark.skipif(sys.version_info < (3, 12), reason="__buffer__ is 3.12+")
+    def test_owned_leaves_python_buffer_classes_alone(self):
+        class Blob:
+            def __init__(self, data):
+                self.data = bytearray(data)
+
+            def __buffer__(self, flags):
+                return memoryview(self.data)
+
+        copium.config.apply(copy_buffers="owned")
+        copied = copium.deepcopy(Blob(b"abc"))
+
+        assert type(copied) is Blob
+        assert copied.data == bytearray(b"abc")
+
+    def test_rejects_unknown_values(self):
+        with pytest.raises(ValueError, match="copy_buffers must be 'raise' or 'owned'"):
+            copium.config.apply(copy_buffers="copy")
+
+
+# ===========================================================================
+#  configure() — compat_warnings
+# ===========================================================================
+
+
+def _with_instance_reduce(cls: type) -> Any:
+    obj = cls()
+    obj.payload = [1]
+    obj.__reduce__ = lambda: (list, ())
+    return obj
+
+
+class TestConfigureCompatWarnings:
+    def test_warns_once_per_type_about_ignored_instance_reduce(self):
+        class Framework:
+            pass
+
+        class Other:
+            pass
+
+        copium.config.apply(compat_warnings=True)
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            first = copium.deepcopy(_with_instance_reduce(Framework))
+            copium.deepcopy(_with_instance_reduce(Framework))
+            copium.deepcopy(_with_instance_reduce(Other))
+
+        assert [str(w.message) for w in caught] == [
+            "copium: ignoring instance-level __reduce__ on Framework; define it on the class",
+            "copium: ignoring instance-level __reduce__ on Other; define it on the class",
+        ]
+        # Still ignored, like stdlib does.
+        assert type(first) is Framework
+        assert first.payload == [1]
+
+    def test_silent_by_default_and_when_the_class_defines_the_hook(self):
+        class Framework:
+            pass
+
+        class Defines:
+            def __reduce__(self):
+                return (Defines, ())
+
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            copium.deepcopy(_with_instance_reduce(Framework))
+            copium.config.apply(compat_warnings=True)
+            copium.deepcopy(_with_instance_reduce(Defines))
+
+    def test_error_filter_fails_the_copy(self):
+        class Framework:
+            pass
+
+        copium.config.apply(compat_warnings=True)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            with pytest.raises(UserWarning, match="instance-level __reduce__ on Framework"):
+                copium.deepcopy(_with_instance_reduce(Framework))
+
+
+# ===========================================================================
+#  configure() — memo_key_check
+# ===========================================================================
+
+
+class _KeyedByHook:
+    """Memoizes its copy under ``key(self, memo)`` from its own __deepcopy__."""
+
+    def __init__(self, key: Callable[[Any, Any], int]) -> None:
+        self.key = key
+        self.child = [1]
+
+    def __deepcopy__(self, memo):
+        new = _KeyedByHook.__new__(_KeyedByHook)
+        new.child = copium.deepcopy(self.child, memo)
+        memo[self.key(self, memo)] = new
+        memo.setdefault(self.key(self, memo), new)
+        return new
+
+
+class TestConfigureMemoKeyCheck:
+    @pytest.mark.parametrize(
+        ("key", "replaces_child"),
+        [
+            pytest.param(lambda obj, memo: id(obj), False, id="object-being-copied"),
+            pytest.param(lambda obj, memo: id(obj.child), True, id="memoized-original"),
+        ],
+    )
+    def test_accepts_ids_the_copy_has_seen(self, key, replaces_child):
+        copium.config.apply(memo_key_check=True)
+        original = _KeyedByHook(key)
+
+        copied = copium.deepcopy([original, original.child])
+
+        assert copied[0].child == [1]
+        # Storing under the child's id replaces its entry, as with a dict memo.
+        assert copied[1] is (copied[0] if replaces_child else copied[0].child)
+
+    def test_accepts_the_keepalive_key(self):
+        class KeepsAlive:
+            def __deepcopy__(self, memo):
+                memo.setdefault(id(memo), []).append(self)
+                return KeepsAlive()
+
+        copium.config.apply(memo_key_check=True)
+        assert type(copium.deepcopy([KeepsAlive()])[0]) is KeepsAlive
+
+    def test_rejects_hash_keys(self):
+        original = _KeyedByHook(lambda obj, memo: hash(obj))
+        assert copium.deepcopy(original).child == [1]
+
+        copium.config.apply(memo_key_check=True)
+        with pytest.raises(KeyError, match=r"memo keys are id\(original\), not hash\(original\)"):
+            copium.deepcopy(original)
+
+    def test_rejects_setdefault_with_unknown_keys(self):
+        class SetsDefault:
+            def __deepcopy__(self, memo):
+                memo.setdefault(hash(self), self)
+                return self
+
+        copium.config.apply(memo_key_check=True)
+        with pytest.raises(KeyError, match="is not id\\(\\) of an object this copy has seen"):
+            copium.deepcopy(SetsDefault())
+
+
+# ===========================================================================
+#  configure() — yield_every / on_checkpoint
+# ===========================================================================
+
+
+class TestConfigureOnCheckpoint:
+    def test_called_every_n_containers(self):
+        calls = []
+        copium.config.apply(yield_every=10, on_checkpoint=lambda: calls.append(None))
+
+        copium.deepcopy([[i] for i in range(99)])
+
+        assert len(calls) == 10
+
+    def test_removed_by_zero_and_by_reset(self):
+        calls = []
+        copium.config.apply(yield_every=1, on_checkpoint=lambda: calls.append(None))
+        copium.config.apply(yield_every=0)
+        copium.deepcopy([[1], [2]])
+        assert calls == []
+        assert copium.config.get()["on_checkpoint"] is None
+
+        copium.config.apply(yield_every=1, on_checkpoint=lambda: calls.append(None))
+        copium.config.apply()
+        copium.deepcopy([[1], [2]])
+        assert calls == []
+
+    def test_callback_error_fails_the_copy(self):
+        def stop():
+            raise KeyboardInterrupt
+
+        copium.config.apply(yield_every=1, on_checkpoint=stop)
+        with pytest.raises(KeyboardInterrupt):
+            copium.deepcopy([[1]])
+
+    def test_yield_every_can_change_without_repeating_the_callback(self):
+        calls = []
+        callback = calls.append
+        copium.config.apply(yield_every=1, on_checkpoint=lambda: callback(None))
+        copium.config.apply(yield_every=3)
+
+        copium.deepcopy([[i] for i in range(8)])
+
+        assert len(calls) == 3
+        assert copium.config.get()["yield_every"] == 3
+
+    @pytest.mark.parametrize(
+        ("kwargs", "message"),
+        [
+            ({"yield_every": 5}, "yield_every needs an on_checkpoint callback"),
+            ({"on_checkpoint": print}, "on_checkpoint needs yield_every > 0"),
+            ({"yield_every": 5, "on_checkpoint": 5}, "on_checkpoint must be callable"),
+        ],
+    )
+    def test_rejects_incomplete_settings(self, kwargs, message):
+        with pytest.raises(TypeError, match=message):
+            copium.config.apply(**kwargs)
+        assert copium.config.get()["yield_every"] == 0
+
+    def test_lets_greenlets_run(self):
+        gevent = pytest.importorskip("gevent")
+        progress = []
+
+        def other():
+            for i in range(3):
+                progress.append(i)
+                gevent.sleep(0)
+
+        copium.config.apply(yield_every=50, on_checkpoint=gevent.sleep)
+        other_greenlet = gevent.spawn(other)
+        gevent.sleep(0)
+        progress.append("copy started")
+        copium.deepcopy([[i] for i in range(1000)])
+        progress.append("copy done")
+        other_greenlet.join()
+
+        assert progress.index(2) < progress.index("copy done")
+
+
+# ===========================================================================
+#  configure() — intern_cap
+# ===========================================================================
+
+
+class TestConfigureInternCap:
+    def test_sets_and_resets(self):
+        copium.config.apply(intern_cap=4)
+        assert copium.config.get()["intern_cap"] == 4
+        copium.config.apply()
+        assert copium.config.get()["intern_cap"] == 65536
+
+    @pytest.mark.parametrize("cap", [1, 0, -1])
+    def test_rejects_caps_below_two(self, cap):
+        with pytest.raises(ValueError, match="intern_cap must be at least 2"):
+            copium.config.apply(intern_cap=cap)
+        assert copium.config.get()["intern_cap"] == 65536
+
+
 # ===========================================================================
 #  configure() — reset
 # ===========================================================================
@@ -610,6 +1119,22 @@ def test_env_suppress_maps_to_get_config():
     assert cfg["suppress_warnings"] == ("TypeError: x",)
 
 
+@pytest.mark.subprocess(environ=env(COPIUM_STRICT="1"))
+def test_env_strict_maps_to_get_config():
+    import copium
+
+    assert copium.config.get()["strict"] is True
+    copium.config.apply()
+    assert copium.config.get()["strict"] is True
+
+
+@pytest.mark.subprocess(environ=env(COPIUM_DEEPCOPY_ARG_COMPAT="1"))
+def test_env_deepcopy_arg_compat_maps_to_get_config():
+    import copium
+
+    assert copium.config.get()["deepcopy_arg_compat"] is True
+
+
 @pytest.mark.subprocess(environ=env(COPIUM_USE_DICT_MEMO="1"))
 def test_env_configure_overrides_env():
     """configure() overrides env-var defaults."""
@@ -3,8 +3,13 @@
 # SPDX-License-Identifier: MIT
 
 import collections
+import contextvars
 import copy as stdlib_copy
+import copyreg
+import datetime
 import gc
+import os
+import pickle
 import random
 import sys
 import threading
@@ -13,10 +18,14 @@ import weakref
 from collections.abc import Callable
 from collections.abc import Generator
 from collections.abc import MutableMapping
+from contextlib import closing
 from contextlib import contextmanager
 from dataclasses import dataclass
+from dataclasses import field
 from types import MappingProxyType
+from types import SimpleNamespace
 from typing import Any
+from typing import ClassVar
 from typing import Literal
 
 import pytest
@@ -25,6 +34,7 @@ from indifference import assert_equivalent_transformations
 import copium
 from datamodelzoo import Case
 from tests.conftest import CASE_PARAMS
+from tests.conftest import COPIUM_ENV
 from tests.conftest import EVIL_CASE_PARAMS
 from tests.conftest import CopyModule
 
@@ -134,7 +144,9 @@ def test_deepcopy_memo_implementation_details(copy, memo_option) -> None:
     assert keepalive in memo.values()
 
     if memo_option in {"absent", "None"} and copy is copium:
-        assert repr(memo) == f"memo({dict(memo)})"
+        assert repr(memo) == (
+            f"memo({dict(memo)}, attached=False, entries={len(memo) - 1}, keepalive=2)"
+        )
 
 
 @pytest.mark.parametrize("memo_option", VALID_MEMO_PARAMS)
@@ -415,6 +427,184 @@ def test_no_extra_refs_post_deepcopy(copy):
     assert copied_refcounts == original_refcounts_before_copying
 
 
+class _Injected(Exception):
+    pass
+
+
+def _raise_injected(*args: Any) -> None:
+    raise _Injected
+
+
+class _Reduces:
+    def __init__(self, reduced: tuple = ()) -> None:
+        self.reduced = reduced
+
+    def __reduce__(self) -> tuple:
+        return self.reduced
+
+
+class _RaisesOnSetstate:
+    def __setstate__(self, state: Any) -> None:
+        raise _Injected
+
+
+class _RaisesOnItems:
+    def items(self) -> Any:
+        raise _Injected
+
+
+class _RaisesOnAppend(list):
+    def append(self, item: Any) -> None:
+        raise _Injected
+
+
+class _Plain:
+    pass
+
+
+def test_shared_reduce_result_is_left_intact(copy: CopyModule) -> None:
+    args = []
+    reduced = (_Plain, args, {"a": [1]})
+    obj = _Reduces(reduced)
+    copy.deepcopy(obj)
+    refcounts_before = [sys.getrefcount(part) for part in reduced]
+
+    for _ in range(3):
+        assert copy.copy(obj).a == [1]
+        assert copy.deepcopy(obj).a == [1]
+
+    assert reduced[1] is args
+    assert [sys.getrefcount(part) for part in reduced] == refcounts_before
+
+
+def _fails_to_copy(copy_function: Callable[[Any], Any], obj: Any) -> bool:
+    try:
+        copy_function(obj)
+    except (_Injected, ValueError):
+        return True
+    return False
+
+
+@pytest.mark.parametrize(
+    "reduced",
+    [
+        pytest.param((_raise_injected, [1, []]), id="callable"),
+        pytest.param((_RaisesOnSetstate, (), {"a": [1]}), id="setstate"),
+        pytest.param((_Plain, (), (None, _RaisesOnItems())), id="slot-state"),
+        pytest.param((_RaisesOnAppend, (), None, [[1], [2]]), id="listitems"),
+        pytest.param((dict, (), None, None, [("k", [2]), (3,)]), id="dictitems"),
+    ],
+)
+def test_reduce_error_paths_keep_reference_counts(copy: CopyModule, reduced: tuple) -> None:
+    obj = _Reduces(reduced)
+    # The first round may fill per-type caches.
+    assert _fails_to_copy(copy.copy, obj)
+    assert _fails_to_copy(copy.deepcopy, obj)
+    refcounts_before = [sys.getrefcount(part) for part in reduced]
+
+    for _ in range(3):
+        assert _fails_to_copy(copy.copy, obj)
+        assert _fails_to_copy(copy.deepcopy, obj)
+
+    assert [sys.getrefcount(part) for part in reduced] == refcounts_before
+
+
+class _Token:
+    pass
+
+
+def test_no_leaked_copies_when_memo_grows(copy):
+    references: list[weakref.ref[_Token]] = []
+
+    def copy_in_fresh_thread() -> None:
+        copied = copy.deepcopy([[_Token()] for _ in range(1000)])
+        references.extend(weakref.ref(item[0]) for item in copied)
+
+    thread = threading.Thread(target=copy_in_fresh_thread)
+    thread.start()
+    thread.join()
+    gc.collect()
+
+    assert len(references) == 1000
+    assert all(ref() is None for ref in references)
+
+
+def test_weakref_callback_is_not_copied(copy):
+    fired = []
+    target = _Token()
+    reference = weakref.ref(target, fired.append)
+
+    copied = copy.deepcopy({"registry": [reference]})
+    del target
+    gc.collect()
+
+    assert copied["registry"][0] is reference
+    assert fired == [reference]
+
+
+class _ReducesToTemporaries:
+    """Hands deepcopy fresh objects that only the memo keeps alive."""
+
+    def __init__(self, on_release):
+        self.on_release = on_release
+        # Dead weakrefs don't call back, so these must outlive the copy.
+        self.references = []
+
+    def __reduce__(self):
+        first, second = _Token(), _Token()
+        self.references.append(weakref.ref(second, self.on_release))
+        return (_Pair, (first, second))
+
+
+class _Pair:
+    def __init__(self, first, second):
+        self.first = first
+        self.second = second
+
+
+def test_weakref_callback_fires_once_after_copy(copy):
+    fired = []
+    returned = []
+
+    def record_release(reference):
+        fired.append(len(returned))
+
+    original = _ReducesToTemporaries(record_release)
+    returned.append(copy.deepcopy(original))
+    gc.collect()
+
+    assert fired == [0]
+
+
+def test_weakref_callback_deepcopy_gets_fresh_memo(copy):
+    results = []
+
+    def copy_on_release(reference):
+        # Likely allocated where the first temporary lived until a moment ago.
+        fresh = _Token()
+        results.append((fresh, copy.deepcopy(fresh)))
+
+    original = _ReducesToTemporaries(copy_on_release)
+    copied = copy.deepcopy(original)
+
+    [(fresh, fresh_copy)] = results
+    assert fresh_copy is not copied.first
+    assert fresh_copy is not fresh
+    assert type(fresh_copy) is _Token
+
+
+def test_nested_deepcopy_from_reduce_keeps_outer_memo(copy):
+    class CopiesWhileReducing:
+        def __reduce__(self):
+            copy.deepcopy([[1], [2]])
+            return (CopiesWhileReducing, ())
+
+    shared = [1]
+    copied = copy.deepcopy([shared, CopiesWhileReducing(), shared])
+
+    assert copied[0] is copied[2]
+
+
 def test_holding_extra_refs_post_deepcopy(copy):
     memories = []
 
@@ -588,6 +778,204 @@ def test_deepcopy_detects_self_mutation(copy) -> None:
         assert not failures, f"{len(failures)}/{total_runs} runs didn't raise RuntimeError"
 
 
+@pytest.mark.parametrize("view", ["keys", "values", "items"])
+def test_deepcopy_dict_holding_its_own_view(copy, view: str) -> None:
+    value: dict[str, Any] = {"a": [1], "b": 2}
+    value["view"] = getattr(value, view)()
+
+    with pytest.raises(TypeError, match=f"cannot pickle 'dict_{view}' object"):
+        copy.deepcopy(value)
+    assert list(value) == ["a", "b", "view"]
+
+
+def test_deepcopy_error_after_host_mutation_propagates(copy) -> None:
+    value: dict[Any, Any] = {}
+
+    def mutate_then_fail():
+        value[object()] = 1
+        raise ValueError("gave up")
+
+    value["a"] = 1
+    value["trigger"] = DeepcopyRuntimeError(mutate_then_fail)
+
+    with pytest.raises(ValueError, match="gave up"):
+        copy.deepcopy(value)
+
+
+def test_deepcopy_detects_shrink_on_last_value(copy) -> None:
+    value: dict[str, Any] = {"a": 1, "b": 2}
+    value["trigger"] = DeepcopyRuntimeError(lambda: value.pop("a"))
+
+    with pytest.raises(RuntimeError, match="dictionary changed size during iteration"):
+        copy.deepcopy(value)
+
+
+def _str_keyed_with_holes() -> dict[str, Any]:
+    value = {f"k{i}": [i] if i % 2 else i for i in range(50)}
+    for i in range(0, 50, 3):
+        del value[f"k{i}"]
+    return value
+
+
+class _Attrs:
+    def __init__(self) -> None:
+        self.number = 1
+        self.items = [1, 2]
+        self.nested = {"a": [3]}
+
+
+@pytest.mark.parametrize(
+    "make",
+    [
+        pytest.param(lambda: {}, id="empty"),
+        pytest.param(lambda: {"a": 1, "b": "x", "c": None, "d": 2.5}, id="atomic-values"),
+        pytest.param(lambda: {"a": [1], "b": {"c": [2]}, "d": (3, [4]), "e": 5}, id="mixed-values"),
+        pytest.param(_str_keyed_with_holes, id="with-deletions"),
+        pytest.param(lambda: _Attrs().__dict__, id="instance-dict"),
+        pytest.param(lambda: {f"k{i}": [i] for i in range(5000)}, id="large"),
+    ],
+)
+def test_str_keyed_dict_matches_stdlib(make: Callable[[], dict[str, Any]]) -> None:
+    original = make()
+
+    copied = copium.deepcopy(original)
+
+    assert copied == stdlib_copy.deepcopy(original) == original
+    assert list(copied) == list(original)
+    assert all(a is b for a, b in zip(copied, original))
+    for key, value in original.items():
+        if isinstance(value, (list, dict)):
+            assert copied[key] is not value
+        elif not isinstance(value, tuple):
+            assert copied[key] is value
+
+
+def test_str_keyed_dict_keeps_aliasing_and_cycles() -> None:
+    shared = [1]
+    original: dict[str, Any] = {"first": shared, "second": shared, "plain": 1}
+    original["self"] = original
+
+    copied = copium.deepcopy(original)
+
+    assert copied["first"] is copied["second"] is not shared
+    assert copied["self"] is copied
+    assert copied["plain"] == 1
+
+
+def test_str_keyed_dict_failure_is_not_memoized() -> None:
+    class Failing:
+        def __deepcopy__(self, memo: Any) -> Any:
+            raise ValueError("boom")
+
+    original = {"ok": [1], "bad": Failing(), "after": [2]}
+    memo: dict[int, Any] = {}
+
+    with pytest.raises(ValueError, match="boom"):
+        copium.deepcopy(original, memo)
+    assert id(original) not in memo
+
+
+def test_str_keyed_dict_consults_dict_memo_for_keys_like_stdlib() -> None:
+    key = "".join(["se", "eded"])
+    original = {key: [1], "other": 2}
+
+    expected = stdlib_copy.deepcopy(original, {id(key): "replaced"})
+    copied = copium.deepcopy(original, {id(key): "replaced"})
+
+    assert copied == expected
+    assert list(copied) == list(expected)
+
+
+def test_dict_memo_written_by_deepcopy_hook_is_consulted_for_atomics() -> None:
+    key = "".join(["late", "key"])
+
+    class Aliasing:
+        def __deepcopy__(self, memo: Any) -> Any:
+            memo[id(key)] = "replaced"
+            return self
+
+    original = [Aliasing(), {key: 1}]
+
+    expected = stdlib_copy.deepcopy(original, {})
+    copied = copium.deepcopy(original, {})
+
+    assert list(copied[1]) == list(expected[1])
+
+
+def test_dict_memo_wide_fanout_shares_one_copy() -> None:
+    shared = [1]
+    original = {i: shared for i in range(10_000)}
+    memo: dict[int, Any] = {}
+
+    copied = copium.deepcopy(original, memo)
+
+    values = list(copied.values())
+    assert values[0] == shared
+    assert values[0] is not shared
+    assert all(value is values[0] for value in values)
+    assert memo[id(shared)] is values[0]
+    assert memo[id(original)] is copied
+
+
+def _rows_with_tail(tail: list[Any]) -> list[Any]:
+    return [{"id": i, "tags": [i]} for i in range(10)] + tail
+
+
+@pytest.mark.parametrize(
+    "make",
+    [
+        pytest.param(lambda: _rows_with_tail([]), id="rows"),
+        pytest.param(lambda: _rows_with_tail([[1], "x", None, {"id": 10}]), id="rows-mixed-tail"),
+        pytest.param(
+            lambda: _rows_with_tail([collections.OrderedDict(a=[1])]), id="rows-dict-subclass"
+        ),
+        pytest.param(lambda: {"a": [1, 2], "b": [3], "c": 4, "d": {"e": [5]}}, id="columns-mixed"),
+        pytest.param(lambda: {f"c{i}": list(range(i)) for i in range(12)}, id="columns"),
+    ],
+)
+def test_rows_and_columns_match_stdlib(make: Callable[[], Any]) -> None:
+    original = make()
+
+    copied = copium.deepcopy(original)
+
+    assert copied == stdlib_copy.deepcopy(original) == original
+    assert copied is not original
+    values = copied if isinstance(copied, list) else list(copied.values())
+    originals = original if isinstance(original, list) else list(original.values())
+    for value, source in zip(values, originals):
+        assert type(value) is type(source)
+        if isinstance(source, (list, dict)):
+            assert value is not source
+
+
+def test_rows_and_columns_keep_aliasing() -> None:
+    shared_row = {"id": 0, "tags": [0]}
+    shared_column = [1, 2]
+    rows = [shared_row, {"id": 1, "tags": shared_row["tags"]}] * 6
+    columns = {"a": shared_column, "b": [3], "c": shared_column, "rows": rows}
+
+    copied = copium.deepcopy(columns)
+
+    assert copied["a"] is copied["c"] is not shared_column
+    copied_rows = copied["rows"]
+    assert all(row is copied_rows[0] for row in copied_rows[::2])
+    assert copied_rows[1]["tags"] is copied_rows[0]["tags"] is not shared_row["tags"]
+    assert copied_rows[0] is not shared_row
+
+
+def test_rows_shrunk_while_copying_raise() -> None:
+    class Truncates:
+        def __deepcopy__(self, memo):
+            del rows[5:]
+            return self
+
+    rows: list[Any] = [{"id": i} for i in range(10)]
+    rows[3]["hook"] = Truncates()
+
+    with pytest.raises(RuntimeError, match="list changed size during iteration"):
+        copium.deepcopy(rows)
+
+
 def test_cross_thread_mutation_detection(copy) -> None:
     iterator_ready = threading.Event()
     mutation_done = threading.Event()
@@ -631,3 +1019,3571 @@ def test_cross_thread_mutation_detection(copy) -> None:
         correctly_handled += result["raised"]
 
     assert correctly_handled == total_attempts
+
+
+def test_deepcopy_frozenset_of_immutables_preserves_identity() -> None:
+    """
+    Deliberate divergence from stdlib: frozensets of atomic members are deeply immutable,
+    so copium returns them as is, the same way both implementations treat tuples.
+    """
+    original = frozenset({1, "two", 3.0, (4, 5), frozenset({6})})
+
+    assert copium.deepcopy(original) is original
+    assert stdlib_copy.deepcopy(original) is not original
+
+
+def test_deepcopy_frozenset_with_mutable_member_is_copied() -> None:
+    class Mutable:
+        def __init__(self) -> None:
+            self.items: list[int] = []
+
+    member = Mutable()
+    original = frozenset({member, 1})
+    copied = copium.deepcopy(original)
+
+    assert copied is not original
+    assert len(copied) == 2
+    assert member not in copied
+
+
+def test_deepcopy_frozenset_memo_aliasing() -> None:
+    class Mutable:
+        pass
+
+    mutable = frozenset({Mutable()})
+    immutable = frozenset({1, 2})
+    copied = copium.deepcopy([mutable, mutable, immutable, immutable])
+
+    assert copied[0] is copied[1]
+    assert copied[0] is not mutable
+    assert copied[2] is copied[3] is immutable
+
+
+def _shallow_identity_samples() -> list[Any]:
+    Point = collections.namedtuple("Point", "x y")
+
+    @dataclass
+    class Mutable:
+        x: int = 1
+
+    @dataclass(frozen=True)
+    class Frozen:
+        x: int = 1
+
+    builtins = [
+        None,
+        1,
+        1.5,
+        True,
+        2j,
+        "str",
+        (1, [2]),
+        b"bytes",
+        frozenset({1}),
+        range(3),
+        slice(1, 2),
+        [1],
+        {1: 2},
+        {1},
+        bytearray(b"x"),
+    ]
+    subclassable = [1, 1.5, 2j, "str", (1, [2]), b"bytes", frozenset({1}), [1], {1: 2}, {1}]
+    subclassable.append(bytearray(b"x"))
+    subclasses = [type(f"Sub{type(v).__name__}", (type(v),), {})(v) for v in subclassable]
+    return [*builtins, *subclasses, Point(1, 2), Mutable(), Frozen()]
+
+
+@pytest.mark.parametrize(
+    "value", [pytest.param(v, id=type(v).__name__) for v in _shallow_identity_samples()]
+)
+def test_copy_identity_parity_with_stdlib(value: Any) -> None:
+    expected = stdlib_copy.copy(value)
+    copied = copium.copy(value)
+
+    assert (copied is value) is (expected is value)
+    assert type(copied) is type(expected)
+
+
+def test_copy_ignores_instance_level_dunder_copy(copy) -> None:
+    class Plain:
+        pass
+
+    instance = Plain()
+    instance.__copy__ = lambda: pytest.fail("instance __copy__ must not be used")
+
+    copied = copy.copy(instance)
+    assert type(copied) is Plain
+    assert copied is not instance
+
+
+def _structseq_samples() -> list[Any]:
+    return [os.stat("."), time.localtime(), time.gmtime(0), sys.float_info]
+
+
+@pytest.mark.parametrize(
+    "value", [pytest.param(v, id=type(v).__name__) for v in _structseq_samples()]
+)
+def test_deepcopy_structseq_parity_with_stdlib(value: Any) -> None:
+    expected = stdlib_copy.deepcopy(value)
+    copied = copium.deepcopy(value)
+
+    assert type(copied) is type(expected)
+    assert copied == expected
+    assert (copied is value) is (expected is value)
+    for name in dir(type(value)):
+        if name.startswith(("n_", "_")) or callable(getattr(value, name)):
+            continue
+        assert getattr(copied, name) == getattr(expected, name), name
+
+
+def test_deepcopy_structseq_preserves_hidden_fields() -> None:
+    stat = os.stat(".")
+    copied = copium.deepcopy(stat)
+
+    assert copied.st_mtime == stat.st_mtime
+    assert copied.st_mtime_ns == stat.st_mtime_ns
+    assert copied[7:10] == stat[7:10]
+
+    local = time.localtime()
+    assert copium.deepcopy(local).tm_zone == local.tm_zone
+
+
+def test_deepcopy_structseq_memo_aliasing() -> None:
+    stat = os.stat(".")
+    copied = copium.deepcopy([stat, stat])
+
+    assert copied[0] is copied[1]
+    assert copied[0] is not stat
+
+
+def test_deepcopy_uninstantiable_structseq_matches_stdlib() -> None:
+    with pytest.raises(TypeError) as expected:
+        stdlib_copy.deepcopy(sys.version_info)
+    with pytest.raises(TypeError) as actual:
+        copium.deepcopy(sys.version_info)
+
+    assert str(actual.value) == str(expected.value)
+
+
+_NFieldsPair = collections.namedtuple("_NFieldsPair", ["n_fields", "items"])
+
+
+class _NFieldsTuple(tuple):
+    __slots__ = ()
+    n_fields = 2
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        pytest.param(_NFieldsPair(2, [1]), id="namedtuple-field"),
+        pytest.param(_NFieldsTuple((2, [1])), id="tuple-subclass-attribute"),
+    ],
+)
+def test_n_fields_attribute_does_not_make_a_structseq(value: Any) -> None:
+    expected = stdlib_copy.deepcopy(value)
+    copied = copium.deepcopy(value)
+
+    assert type(copied) is type(expected) is type(value)
+    assert copied == expected == value
+    assert copied[1] is not value[1]
+
+
+def test_deepcopy_structseq_honors_copyreg(monkeypatch: pytest.MonkeyPatch) -> None:
+    sentinel = object()
+    monkeypatch.setitem(copyreg.dispatch_table, os.stat_result, lambda _: (lambda: sentinel, ()))
+
+    assert copium.deepcopy(os.stat(".")) is sentinel
+
+
+class _WithDescriptors:
+    __slots__ = ("slot",)
+
+    @staticmethod
+    def static() -> None: ...
+
+    @classmethod
+    def klass(cls) -> None: ...
+
+    @property
+    def prop(self) -> int:
+        return 1
+
+
+def _descriptor_samples() -> list[Any]:
+    namespace = _WithDescriptors.__dict__
+    return [
+        object.__init__,
+        object().__str__,
+        str.join,
+        dict.__dict__["fromkeys"],
+        type(lambda: None).__dict__["__name__"],
+        namespace["slot"],
+        namespace["static"],
+        namespace["klass"],
+        namespace["prop"],
+        [].append,
+        len,
+        {"init": object.__init__, "join": str.join},
+    ]
+
+
+def _outcome(function: Callable[[Any], Any], value: Any) -> tuple[Any, ...]:
+    try:
+        result = function(value)
+    except Exception as error:
+        return ("raised", type(error))
+    if isinstance(value, dict):
+        return ("copied", type(result), [result[key] is value[key] for key in value])
+    return ("copied", type(result), result is value)
+
+
+@pytest.mark.parametrize(
+    "value", [pytest.param(v, id=type(v).__name__) for v in _descriptor_samples()]
+)
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_descriptor_round_trip_parity_with_stdlib(value: Any, operation: str) -> None:
+    expected = _outcome(getattr(stdlib_copy, operation), value)
+    actual = _outcome(getattr(copium, operation), value)
+
+    assert actual == expected
+
+
+class _SynthesizesEverything:
+    """Lazy-proxy style: every missing attribute, dunders included, is synthesized."""
+
+    lookups: ClassVar[list[str]] = []
+
+    def __init__(self) -> None:
+        self.data = [1]
+
+    def __getattr__(self, name: str) -> Any:
+        type(self).lookups.append(name)
+        return lambda *args, **kwargs: ("synthesized", name)
+
+
+class _SynthesizesNonDunders(_SynthesizesEverything):
+    def __getattr__(self, name: str) -> Any:
+        type(self).lookups.append(name)
+        if name.startswith("__"):
+            raise AttributeError(name)
+        return lambda *args, **kwargs: ("synthesized", name)
+
+
+class _SynthesizesWithState(_SynthesizesEverything):
+    def __reduce_ex__(self, protocol: int) -> Any:
+        return (type(self), (), {"data": self.data})
+
+
+class _SynthesizingMeta(type):
+    def __getattr__(cls, name: str) -> Any:
+        _SynthesizesEverything.lookups.append(f"type.{name}")
+        return lambda *args, **kwargs: ("synthesized", f"type.{name}")
+
+
+class _SynthesizedByMetaclass(metaclass=_SynthesizingMeta):
+    def __init__(self) -> None:
+        self.data = [1]
+
+
+def _getattr_outcome(function: Callable[[Any], Any], cls: type) -> tuple[Any, ...]:
+    # Let copyreg cache __slotnames__ first, so its own lookups aren't recorded.
+    copyreg._slotnames(cls)
+    _SynthesizesEverything.lookups = []
+    original = cls()
+    try:
+        result = function(original)
+    except Exception as error:
+        outcome: Any = ("raised", type(error), str(error))
+    else:
+        if isinstance(result, tuple):
+            outcome = result
+        else:
+            state = vars(result)
+            outcome = (type(result), state, state.get("data") is original.data)
+    # stdlib's hasattr() + call looks __setstate__ up twice.
+    return outcome, list(dict.fromkeys(_SynthesizesEverything.lookups))
+
+
+@pytest.mark.parametrize(
+    "cls",
+    [
+        _SynthesizesEverything,
+        _SynthesizesNonDunders,
+        _SynthesizesWithState,
+        _SynthesizedByMetaclass,
+    ],
+)
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_synthesizing_getattr_parity_with_stdlib(cls: type, operation: str) -> None:
+    # stdlib looks __deepcopy__, __reduce_ex__ and __setstate__ up on the
+    # instance, so __getattr__ sees them, and __copy__ on the class. copium
+    # must make the same lookups, and no others.
+    expected = _getattr_outcome(getattr(stdlib_copy, operation), cls)
+    actual = _getattr_outcome(getattr(copium, operation), cls)
+
+    assert actual == expected
+
+
+@pytest.mark.subprocess(environ=dict(COPIUM_ENV))
+def test_copy_before_any_deepcopy_in_fresh_interpreter():
+    import copium
+
+    value = {"a": [1, 2]}
+    copied = copium.copy(value)
+    assert copied == value
+    assert copied is not value
+    assert copied["a"] is value["a"]
+    assert not hasattr(copium, "_panic")
+
+
+@pytest.mark.subprocess(environ={**COPIUM_ENV, "COPIUM_TEST_HOOKS": "1"})
+def test_rust_panic_surfaces_as_system_error():
+    import pytest
+
+    import copium
+
+    with pytest.raises(SystemError, match="copium panicked: boom"):
+        copium._panic("boom")
+
+    assert copium.deepcopy({"still": ["working"]}) == {"still": ["working"]}
+
+
+def _capture_memo_proxies() -> tuple[Any, Any, dict[str, Any]]:
+    seen: dict[str, Any] = {}
+
+    class Spy:
+        def __deepcopy__(self, memo):
+            keepalive = memo[id(memo)]
+            seen["memo_repr"] = repr(memo)
+            seen["keepalive_repr"] = repr(keepalive)
+            seen["snapshot"] = stdlib_copy.copy(memo)
+            seen["memo"] = memo
+            seen["keepalive"] = keepalive
+            return self
+
+    shared = [1]
+    copium.deepcopy([shared, Spy(), shared])
+    return seen.pop("memo"), seen.pop("keepalive"), seen
+
+
+@pytest.mark.parametrize("protocol", range(pickle.HIGHEST_PROTOCOL + 1))
+def test_memo_proxies_refuse_pickling(protocol: int) -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+
+    with pytest.raises(TypeError, match="cannot pickle copium memo proxy"):
+        pickle.dumps(memo, protocol)
+    with pytest.raises(TypeError, match="cannot pickle copium memo proxy"):
+        pickle.dumps(keepalive, protocol)
+
+
+def test_memo_proxies_refuse_subclassing_and_point_to_custom_memo() -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+
+    for proxy_type, name in [(type(memo), "copium.memo"), (type(keepalive), "copium.keepalive")]:
+        with pytest.raises(TypeError, match=f"{name} cannot be subclassed") as error:
+
+            class LoggingProxy(proxy_type):  # type: ignore[misc, valid-type]
+                pass
+
+        assert "copium.extra.CustomMemo" in str(error.value)
+
+    # The supported way to observe memo traffic.
+    memo = _CountingMemo()
+    shared = [1]
+    copied = copium.deepcopy([shared, shared], memo)
+    assert copied[0] is copied[1]
+    assert memo.inserts[id(shared)] == 1
+
+
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_memo_proxies_copy_to_plain_snapshots(copy, operation: str) -> None:
+    memo, keepalive, seen = _capture_memo_proxies()
+
+    memo_snapshot = getattr(copy, operation)(memo)
+    keepalive_snapshot = getattr(copy, operation)(keepalive)
+
+    assert type(memo_snapshot) is dict
+    assert type(keepalive_snapshot) is list
+    assert memo_snapshot.keys() == set(memo)
+    assert memo_snapshot[id(memo)] == keepalive_snapshot == list(keepalive)
+
+    memo.clear()
+    assert len(memo) == 0
+    assert len(memo_snapshot) == len(seen["snapshot"]) == 3
+    assert len(keepalive_snapshot) == 2
+
+
+def test_graphs_holding_memo_proxies_copy_them_to_snapshots(copy) -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+    # Stashed in an attribute, the way user __deepcopy__ methods tend to keep it.
+    stash = SimpleNamespace(memo=memo, keepalive=keepalive)
+    graph = {"stash": stash, "memo": memo, "keepalive": keepalive}
+
+    copied = copy.deepcopy(graph)
+
+    assert type(copied["memo"]) is dict
+    assert type(copied["keepalive"]) is list
+    assert copied["stash"].memo is copied["memo"]
+    assert copied["stash"].keepalive is copied["keepalive"]
+    assert copied["memo"].keys() == set(memo)
+    assert copied["keepalive"] == list(keepalive)
+
+
+def test_memo_proxies_repr_shows_state() -> None:
+    memo, keepalive, seen = _capture_memo_proxies()
+
+    assert seen["memo_repr"].endswith("attached=True, entries=2, keepalive=2)")
+    assert seen["keepalive_repr"].endswith(", attached=True)")
+    assert repr(memo).endswith("attached=False, entries=2, keepalive=2)")
+    assert repr(keepalive).endswith(", attached=False)")
+
+
+def test_memo_proxy_keeps_values_stored_from_temporaries() -> None:
+    seen: dict[str, Any] = {}
+
+    class Spy:
+        def __deepcopy__(self, memo):
+            memo[1] = _Token()
+            memo.setdefault(2, _Token())
+            seen["default"] = weakref.ref(memo.get(3, _Token()))
+            seen["memo"] = memo
+            return self
+
+    copium.deepcopy([Spy()])
+    memo = seen.pop("memo")
+    gc.collect()
+
+    assert seen["default"]() is None
+    stored = [weakref.ref(memo[1]), weakref.ref(memo.get(2))]
+    assert all(ref() is not None for ref in stored)
+    assert type(memo.setdefault(2, None)) is _Token
+
+    memo.clear()
+    assert all(ref() is None for ref in stored)
+
+
+def test_memo_proxy_lookups_do_not_leak() -> None:
+    seen: dict[str, Any] = {}
+
+    class Spy:
+        def __deepcopy__(self, memo):
+            stored = _Token()
+            default = _Token()
+            memo[1] = stored
+            baseline = sys.getrefcount(stored), sys.getrefcount(default)
+            for _ in range(100):
+                memo[1]
+                memo.get(1)
+                memo.get(2, default)
+                memo.setdefault(1, default)
+            seen["counts"] = baseline, (sys.getrefcount(stored), sys.getrefcount(default))
+            return self
+
+    copium.deepcopy([Spy()])
+
+    before, after = seen["counts"]
+    assert after == before
+
+
+def test_memo_proxy_never_hashes_or_compares_values() -> None:
+    calls: list[str] = []
+    seen: dict[str, Any] = {}
+
+    class Touchy:
+        def __eq__(self, other: object) -> bool:
+            calls.append("__eq__")
+            raise AssertionError("memo compared a value")
+
+        def __hash__(self) -> int:
+            calls.append("__hash__")
+            raise AssertionError("memo hashed a value")
+
+    default, other = Touchy(), Touchy()
+    baseline = sys.getrefcount(default), sys.getrefcount(other)
+
+    class Spy:
+        def __deepcopy__(self, memo):
+            seen["identity"] = (
+                memo.setdefault(1, default) is default,
+                memo.setdefault(1, other) is default,
+                memo.get(2, other) is other,
+            )
+            memo[3] = other
+            seen["values"] = [value for value in memo.values() if isinstance(value, Touchy)]
+            seen["memo"] = memo
+            return self
+
+    copium.deepcopy([Spy()])
+    stored = [value is default or value is other for value in seen.pop("values")]
+    memo = seen.pop("memo")
+
+    assert calls == []
+    assert seen["identity"] == (True, True, True)
+    assert stored == [True, True]
+    assert (sys.getrefcount(default), sys.getrefcount(other)) == (baseline[0] + 1, baseline[1] + 1)
+    memo.clear()
+    assert (sys.getrefcount(default), sys.getrefcount(other)) == baseline
+
+
+def test_memo_get_returns_keepalive_for_memo_id() -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+
+    assert list(memo.get(id(memo))) == list(keepalive) == list(memo[id(memo)])
+
+    memo.clear()
+    assert memo.get(id(memo)) is None
+    assert memo.get(id(memo), "missing") == "missing"
+
+
+def test_memo_clear_tolerates_finalizers_touching_memo() -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+    observed = []
+
+    class Finalizer:
+        def __del__(self):
+            observed.append([type(value).__name__ for value in memo.values()])
+            keepalive.append(object())
+
+    for key in range(10, 20):
+        memo[key] = Finalizer()
+    for _ in range(100):
+        keepalive.append(Finalizer())
+
+    memo.clear()
+    gc.collect()
+
+    assert len(observed) == 110
+    assert list(memo) == [id(memo)]
+
+
+def test_keepalive_assignment_is_atomic() -> None:
+    memo, keepalive, _ = _capture_memo_proxies()
+    before = list(keepalive)
+
+    def failing():
+        yield "partial"
+        raise RuntimeError("boom")
+
+    with pytest.raises(RuntimeError, match="boom"):
+        memo[id(memo)] = failing()
+    assert list(keepalive) == before
+
+    memo[id(memo)] = memo[id(memo)]
+    assert list(keepalive) == before
+
+
+@pytest.mark.parametrize(
+    "wipe",
+    [
+        lambda memo: memo.clear(),
+        lambda memo: memo.__del__(),
+        lambda memo: memo[id(memo)].clear(),
+        lambda memo: memo.__delitem__(id(memo)),
+    ],
+    ids=["clear", "__del__", "keepalive-clear", "del-keepalive"],
+)
+def test_memo_refuses_clearing_while_copy_is_running(wipe: Callable[[Any], None]) -> None:
+    seen: dict[str, Any] = {}
+
+    class Wiper:
+        def __deepcopy__(self, memo):
+            seen["memo"] = memo
+            wipe(memo)
+            return self
+
+    parent = [[1], Wiper()]
+    parent.append(parent)
+
+    with pytest.raises(RuntimeError, match="cannot clear memo while a deepcopy"):
+        copium.deepcopy(parent)
+
+    memo = seen.pop("memo")
+    wipe(memo)
+    assert id(memo) not in memo or not memo[id(memo)]
+
+
+def test_memo_refuses_clearing_while_passed_back_in() -> None:
+    memo, _, _ = _capture_memo_proxies()
+
+    class Wiper:
+        def __deepcopy__(self, memo):
+            memo.clear()
+            return self
+
+    with pytest.raises(RuntimeError, match="cannot clear memo while a deepcopy"):
+        copium.deepcopy([Wiper()], memo)
+
+    copied = copium.deepcopy([[1]], memo)
+    memo.clear()
+    assert copied == [[1]]
+
+
+def test_memo_refuses_replacing_keepalive_while_copy_is_running() -> None:
+    class Replacer:
+        def __deepcopy__(self, memo):
+            memo[id(memo)] = []
+            return self
+
+    with pytest.raises(RuntimeError, match="cannot clear memo while a deepcopy"):
+        copium.deepcopy([[1], Replacer()])
+
+
+def test_memo_keys_are_not_recycled_mid_copy(copy) -> None:
+    class Recycler:
+        def __init__(self, holder: list[list[int]]) -> None:
+            self.holder = holder
+
+        def __deepcopy__(self, memo):
+            # The copy memoized every list in holder; drop the graph's only
+            # references to them and allocate lookalikes that could reuse their
+            # addresses if the memo let go of them.
+            self.holder.clear()
+            gc.collect()
+            fresh = [[i] for i in range(1000)]
+            return copy.deepcopy(fresh, memo)
+
+    holder = [[i] for i in range(1000)]
+    original = [holder, Recycler(holder)]
+
+    copied = copy.deepcopy(original)
+
+    assert copied[0] == [[i] for i in range(1000)]
+    assert copied[1] == [[i] for i in range(1000)]
+
+
+def test_originals_a_hook_keys_the_memo_by_stay_alive_for_the_call() -> None:
+    class Keyed:
+        def __deepcopy__(self, memo):
+            memo[id(self)] = self
+            return self
+
+    holder = [Keyed()]
+    ref = weakref.ref(holder[0])
+    alive_mid_copy = []
+
+    class Dropper:
+        def __deepcopy__(self, memo):
+            holder.clear()
+            gc.collect()
+            alive_mid_copy.append(ref() is not None)
+            return self
+
+    copium.deepcopy([holder, Dropper()])
+
+    assert alive_mid_copy == [True]
+    gc.collect()
+    assert ref() is None
+
+
+def test_class_reassigned_mid_copy_still_hits_the_memo(copy) -> None:
+    class Before:
+        pass
+
+    class After:
+        pass
+
+    shared = Before()
+
+    class Switcher:
+        def __deepcopy__(self, memo):
+            shared.__class__ = After
+            return self
+
+    copied = copy.deepcopy([shared, Switcher(), shared])
+
+    assert copied[0] is copied[2]
+    assert type(copied[0]) is Before
+
+
+def test_deepcopy_honors_preseeded_substitution(copy) -> None:
+    shared = [1]
+    replacement = ["replacement"]
+    memo = {id(shared): replacement}
+
+    copied = copy.deepcopy({"direct": shared, "nested": [shared, (shared,)]}, memo)
+
+    assert copied["direct"] is replacement
+    assert copied["nested"][0] is replacement
+    assert copied["nested"][1][0] is replacement
+
+
+def test_deepcopy_appends_to_preexisting_keepalive(copy) -> None:
+    memo: dict[Any, Any] = {}
+    user_keepalive = ["user"]
+    memo[id(memo)] = user_keepalive
+    shared = [1]
+
+    copy.deepcopy([shared, shared], memo)
+
+    assert memo[id(memo)] is user_keepalive
+    assert user_keepalive[0] == "user"
+    assert any(item is shared for item in user_keepalive)
+
+
+def test_deepcopy_keepalive_only_needs_append(copy) -> None:
+    class Appendable:
+        def __init__(self) -> None:
+            self.items: list[Any] = []
+
+        def append(self, item: Any) -> None:
+            self.items.append(item)
+
+    memo: dict[Any, Any] = {}
+    memo[id(memo)] = keepalive = Appendable()
+    shared = [1]
+
+    copy.deepcopy([shared], memo)
+    assert any(item is shared for item in keepalive.items)
+
+    memo[id(memo)] = "not appendable"
+    with pytest.raises(AttributeError):
+        copy.deepcopy([[1]], memo)
+
+
+def test_deepcopy_tolerates_non_int_memo_keys(copy) -> None:
+    memo: dict[Any, Any] = {"label": "kept", (1, 2): "pair", None: 0}
+    value = [[1], {"a": [2]}]
+
+    copied = copy.deepcopy(value, memo)
+
+    assert copied == value
+    assert copied[0] is not value[0]
+    assert memo["label"] == "kept"
+    assert memo[(1, 2)] == "pair"
+    assert memo[None] == 0
+
+
+def test_deepcopy_survives_keepalive_replaced_mid_copy(copy) -> None:
+    class Replacer:
+        def __deepcopy__(self, memo):
+            memo[id(memo)] = ["replaced"]
+            return Replacer()
+
+    memo: dict[Any, Any] = {}
+    shared = [1]
+    copied = copy.deepcopy([shared, Replacer(), shared, [2]], memo)
+
+    assert copied[0] is copied[2]
+    assert memo[id(memo)][0] == "replaced"
+    del memo
+    gc.collect()
+    assert copied[3] == [2]
+
+
+def test_deepcopy_replace_substitutes_every_occurrence() -> None:
+    shared = [1]
+    replacement = ["replacement"]
+    value = {"direct": shared, "nested": [shared, (shared,)], "other": [2]}
+
+    copied = copium.deepcopy(value, replace=[(shared, replacement)])
+
+    assert copied["direct"] is replacement
+    assert copied["nested"][0] is replacement
+    assert copied["nested"][1][0] is replacement
+    assert copied["other"] == [2]
+    assert copied["other"] is not value["other"]
+
+
+def test_deepcopy_replace_multiple_substitutions() -> None:
+    class Node:
+        def __init__(self, *children: Any) -> None:
+            self.children = list(children)
+
+    first, second = Node(), Node()
+    first_replacement, second_replacement = Node(), Node()
+    tree = Node(first, Node(second, first), second)
+
+    copied = copium.deepcopy(tree, replace={first: first_replacement, second: second_replacement})
+
+    assert copied.children[0] is first_replacement
+    assert copied.children[1].children == [second_replacement, first_replacement]
+    assert copied.children[2] is second_replacement
+
+
+def test_deepcopy_replace_accepts_unhashable_originals() -> None:
+    unhashable = {"k": [1]}
+    copied = copium.deepcopy([unhashable, [unhashable]], replace=[(unhashable, "x")])
+
+    assert copied == ["x", ["x"]]
+
+
+def test_deepcopy_replace_with_itself_shares_original() -> None:
+    shared = [1]
+    copied = copium.deepcopy([shared, [shared]], replace=[(shared, shared)])
+
+    assert copied[0] is shared
+    assert copied[1][0] is shared
+
+
+def test_deepcopy_replacement_elsewhere_in_graph_is_copied() -> None:
+    original = [1]
+    replacement = [2]
+
+    copied = copium.deepcopy([original, replacement], replace=[(original, replacement)])
+
+    assert copied[0] is replacement
+    assert copied[1] == replacement
+    assert copied[1] is not replacement
+
+
+@pytest.mark.parametrize("memo_factory", [dict, lambda: None], ids=["dict", "None"])
+def test_deepcopy_replace_with_explicit_memo(memo_factory: Callable[[], Any]) -> None:
+    shared = [1]
+    other = [2]
+    replacement = ["replacement"]
+    memo = memo_factory()
+
+    copied = copium.deepcopy([shared, other, other], memo, replace=[(shared, replacement)])
+
+    assert copied[0] is replacement
+    assert copied[1] is copied[2]
+    if memo is not None:
+        assert memo[id(shared)] is replacement
+        assert memo[id(other)] is copied[1]
+
+
+def test_deepcopy_replace_overrides_existing_memo_entry() -> None:
+    shared = [1]
+    memo = {id(shared): "from memo"}
+
+    copied = copium.deepcopy([shared], memo, replace=[(shared, "from replace")])
+
+    assert copied == ["from replace"]
+
+
+def test_copy_replace_substitutes_top_level_entries() -> None:
+    old, new = [1], [2]
+    nested = [old]
+
+    copied_list = copium.copy([old, nested, old], replace=[(old, new)])
+    copied_dict = copium.copy({"a": old, "b": nested}, replace=[(old, new)])
+    copied_set = copium.copy({1, (2, 3)}, replace={1: "one"})
+
+    assert copied_list == [new, nested, new] and copied_list[0] is new
+    assert copied_list[1] is nested and nested[0] is old
+    assert copied_dict == {"a": new, "b": nested} and copied_dict["a"] is new
+    assert copied_set == {"one", (2, 3)}
+
+
+def test_copy_replace_substitutes_attributes_of_reduced_copies() -> None:
+    old, new = [1], [2]
+    original = SimpleNamespace(direct=old, nested=[old])
+
+    copied = copium.copy(original, replace=[(old, new)])
+
+    assert copied.direct is new
+    assert copied.nested is original.nested and copied.nested[0] is old
+    assert original.direct is old
+
+
+def test_copy_replace_leaves_originals_and_shared_copies_alone() -> None:
+    old, new = [1], [2]
+    original = [old]
+    frozen = (old,)
+
+    assert copium.copy(original, replace=[(old, new)]) == [new]
+    assert original == [old] and original[0] is old
+    assert copium.copy(frozen, replace=[(old, new)]) is frozen
+    assert copium.copy(old, replace=[(old, new)]) is new
+    assert copium.copy(original, replace=None) == original
+
+
+def test_copy_replace_rejects_bad_arguments() -> None:
+    with pytest.raises(TypeError, match="pairs"):
+        copium.copy([1], replace=[1])
+    with pytest.raises(TypeError, match="unexpected keyword"):
+        copium.copy([1], memo={})
+    with pytest.raises(TypeError, match="positional"):
+        copium.copy()
+
+
+class _CountingMemo(copium.extra.CustomMemo):
+    def __init__(self) -> None:
+        self.entries: dict[int, Any] = {}
+        self.inserts: collections.Counter[int] = collections.Counter()
+        self.kept: list[Any] = []
+        self.discarded: list[int] = []
+
+    def lookup(self, key: int) -> Any:
+        return self.entries.get(key)
+
+    def insert(self, key: int, value: Any) -> None:
+        self.inserts[key] += 1
+        self.entries[key] = value
+
+    def keepalive(self, obj: Any) -> None:
+        self.kept.append(obj)
+
+    def discard(self, key: int) -> None:
+        self.discarded.append(key)
+        self.entries.pop(key, None)
+
+
+class _StdlibHook:
+    def __init__(self, child: Any) -> None:
+        self.child = child
+
+    def __deepcopy__(self, memo: Any) -> "_StdlibHook":
+        return _StdlibHook(stdlib_copy.deepcopy(self.child, memo))
+
+
+def test_custom_memo_sees_each_mutable_node_once() -> None:
+    shared = [1]
+    inner = {"shared": shared}
+    original = [shared, inner, (shared,), shared]
+    memo = _CountingMemo()
+
+    copied = copium.deepcopy(original, memo)
+
+    assert copied == original
+    assert copied[0] is not shared
+    assert copied[0] is copied[3] is copied[1]["shared"] is copied[2][0]
+    assert {id(original), id(shared), id(inner)} <= memo.inserts.keys()
+    assert set(memo.inserts.values()) == {1}
+    assert any(kept is shared for kept in memo.kept)
+    assert copium.deepcopy(shared, memo) is copied[0]
+
+
+def test_custom_memo_is_handed_to_deepcopy_hooks() -> None:
+    shared = [1]
+    original = [shared, _StdlibHook(shared)]
+    memo = _CountingMemo()
+
+    copied = copium.deepcopy(original, memo)
+
+    assert copied[1].child is copied[0]
+    assert copium.deepcopy(original, memo, intern="str") is copied
+
+
+def test_custom_memo_discards_failed_copies() -> None:
+    class Failing:
+        def __deepcopy__(self, memo: Any) -> Any:
+            raise ValueError("boom")
+
+    original = [[1], Failing()]
+    memo = _CountingMemo()
+
+    with pytest.raises(ValueError, match="boom"):
+        copium.deepcopy(original, memo)
+
+    assert id(original) in memo.discarded
+    assert id(original) not in memo.entries
+
+
+def test_custom_memo_base_requires_overrides() -> None:
+    with pytest.raises(NotImplementedError, match="lookup"):
+        copium.deepcopy([1], copium.extra.CustomMemo())
+
+
+class _UserMemoWriter:
+    """Writes its own memo entry for ``child``, optionally refusing non-dict memos."""
+
+    def __init__(self, child: Any, *, order: str, strict: bool) -> None:
+        self.child = child
+        self.order = order
+        self.strict = strict
+
+    def __deepcopy__(self, memo: Any) -> "_UserMemoWriter":
+        if self.strict and type(memo) is not dict:
+            raise TypeError("dict memo required")
+        new = _UserMemoWriter.__new__(_UserMemoWriter)
+        if self.order == "before":
+            new.child = memo[id(self.child)] = ["user"]
+        elif self.order == "after":
+            new.child = stdlib_copy.deepcopy(self.child, memo)
+            memo[id(self.child)] = ["user"]
+        else:
+            # __deepcopy__ -> container -> same child, then overwrite
+            new.child = stdlib_copy.deepcopy([self.child, (self.child,)], memo)
+            memo[id(self.child)] = ["user"]
+        return new
+
+
+def _memo_writer_shape(obj: Any) -> Any:
+    if isinstance(obj, _UserMemoWriter):
+        return ("writer", _memo_writer_shape(obj.child))
+    if isinstance(obj, (list, tuple)):
+        return type(obj)(_memo_writer_shape(item) for item in obj)
+    return obj
+
+
+def _memo_writer_graphs(strict: bool) -> dict[str, tuple[Any, Any]]:
+    """Graph and the expected shape of its copy for each ordering."""
+    child = [1]
+    pair = (child,)
+
+    def writer(target: Any, order: str) -> _UserMemoWriter:
+        return _UserMemoWriter(target, order=order, strict=strict)
+
+    return {
+        "written-before-container": (
+            [writer(child, "before"), child, (child,)],
+            [("writer", ["user"]), ["user"], (["user"],)],
+        ),
+        "written-after-container": (
+            [child, writer(child, "after"), child, (child,)],
+            [[1], ("writer", [1]), ["user"], (["user"],)],
+        ),
+        "written-after-tuple": (
+            [(child,), writer(child, "after"), (child,)],
+            [([1],), ("writer", [1]), (["user"],)],
+        ),
+        "nested-container": (
+            [writer(child, "nested"), child],
+            [("writer", [[1], ([1],)]), ["user"]],
+        ),
+        "tuple-parent": (
+            [writer(pair, "before"), pair, [pair]],
+            [("writer", ["user"]), ["user"], [["user"]]],
+        ),
+    }
+
+
+@pytest.mark.filterwarnings(r"ignore:\s+Seems like 'copium.memo' was rejected")
+@pytest.mark.parametrize("strict", [False, True], ids=["any-memo", "dict-only"])
+@pytest.mark.parametrize("graph", list(_memo_writer_graphs(strict=False)))
+@pytest.mark.parametrize("memo", VALID_MEMO_PARAMS)
+def test_user_written_memo_entries_win(
+    copy, memo: ValidMemoOptions, graph: str, strict: bool
+) -> None:
+    if strict and memo == "mutable_mapping":
+        pytest.skip("dict-only __deepcopy__ can't accept a user-supplied mapping")
+    original, expected = _memo_writer_graphs(strict)[graph]
+
+    copied = copy.deepcopy(original, **memo_kwargs(memo))
+
+    assert _memo_writer_shape(copied) == expected
+
+
+class _MemoAnchor:
+    """Keeps the memo it was handed, like schemes that stash it for later lookups."""
+
+    captured: ClassVar[list[Any]] = []
+
+    def __init__(self, *, strict: bool) -> None:
+        self.strict = strict
+
+    def __deepcopy__(self, memo: Any) -> "_MemoAnchor":
+        if self.strict and type(memo) is not dict:
+            raise TypeError("dict memo required")
+        _MemoAnchor.captured.append(memo)
+        return _MemoAnchor(strict=self.strict)
+
+
+class _SiblingRef:
+    """Rebuilt through __reduce__; __setstate__ finds its sibling's copy in the kept memo."""
+
+    def __init__(self, sibling: Any) -> None:
+        self.sibling = sibling
+
+    def __reduce__(self) -> Any:
+        return (_SiblingRef.__new__, (_SiblingRef,), {"sibling_id": id(self.sibling)})
+
+    def __setstate__(self, state: dict[str, int]) -> None:
+        self.sibling = _MemoAnchor.captured[0][state["sibling_id"]]
+
+
+@pytest.mark.filterwarnings(r"ignore:\s+Seems like 'copium.memo' was rejected")
+@pytest.mark.parametrize("strict", [False, True], ids=["any-memo", "dict-only"])
+@pytest.mark.parametrize("memo", VALID_MEMO_PARAMS)
+def test_memo_handed_to_user_code_is_one_live_mapping(
+    copy, memo: ValidMemoOptions, strict: bool
+) -> None:
+    if strict and memo == "mutable_mapping":
+        pytest.skip("dict-only __deepcopy__ can't accept a user-supplied mapping")
+    _MemoAnchor.captured.clear()
+    target = [1]
+    original = [
+        _MemoAnchor(strict=strict),
+        [target, [_MemoAnchor(strict=strict), _SiblingRef(target)]],
+        _SiblingRef(target),
+    ]
+    kwargs = memo_kwargs(memo)
+
+    copied = copy.deepcopy(original, **kwargs)
+
+    first, second = _MemoAnchor.captured
+    assert first is second
+    if kwargs.get("memo") is not None:
+        assert first is kwargs["memo"]
+    copied_target = copied[1][0]
+    assert copied_target == target and copied_target is not target
+    assert copied[1][1][1].sibling is copied_target
+    assert copied[2].sibling is copied_target
+
+
+class _FragmentModel:
+    def __init__(self, first: Any, second: Any) -> None:
+        self.first = first
+        self.second = second
+        self.owner = self
+
+    def __deepcopy__(self, memo: Any) -> "_FragmentModel":
+        copied = _FragmentModel.__new__(_FragmentModel)
+        memo[id(self)] = copied
+        copied.first = copium.deepcopy_fragment(self.first, memo)
+        copied.second = copium.deepcopy_fragment(self.second, memo)
+        copied.owner = copium.deepcopy_fragment(self.owner, memo)
+        return copied
+
+
+@pytest.mark.parametrize(
+    "entry",
+    [
+        pytest.param(lambda value: copium.deepcopy(value), id="copium"),
+        pytest.param(lambda value: copium.deepcopy(value, {}), id="copium-dict-memo"),
+        pytest.param(lambda value: copium.deepcopy(value, collections.UserDict()), id="copium-mapping-memo"),
+        pytest.param(lambda value: stdlib_copy.deepcopy(value), id="stdlib"),
+    ],
+)
+def test_deepcopy_fragment_keeps_aliasing_across_fields(entry: Callable[[Any], Any]) -> None:
+    shared = [1, {"nested": [2]}]
+    model = _FragmentModel(shared, shared)
+    graph = [model, shared, model]
+
+    copied = entry(graph)
+
+    copied_model = copied[0]
+    assert copied[2] is copied_model and copied_model is not model
+    assert copied_model.first is copied_model.second is copied[1]
+    assert copied_model.first == shared and copied_model.first is not shared
+    assert copied_model.owner is copied_model
+
+
+def test_deepcopy_fragment_outside_deepcopy_matches_deepcopy() -> None:
+    value = [[1], {"a": [2]}]
+    memo: dict[int, Any] = {}
+
+    copied = copium.deepcopy_fragment(value, memo)
+
+    assert copied == value and copied[0] is not value[0]
+    assert memo[id(value)] is copied
+    assert copium.deepcopy_fragment(value, memo) is copied
+    assert copium.deepcopy_fragment(value, None) == value
+    with pytest.raises(TypeError, match="deepcopy_fragment"):
+        copium.deepcopy_fragment(value)
+
+
+def test_deepcopy_replace_rejects_malformed_pairs() -> None:
+    with pytest.raises(TypeError, match="pairs"):
+        copium.deepcopy([1], replace=[1])
+    with pytest.raises(ValueError, match="pairs"):
+        copium.deepcopy([1], replace=[(1, 2, 3)])
+
+
+@dataclass
+class _TreeLeaf:
+    name: str
+    values: list[int] = field(default_factory=list)
+
+
+class _TreeNodeWithDeepcopy:
+    def __init__(self, children: list[Any]) -> None:
+        self.children = children
+
+    def __deepcopy__(self, memo: dict[int, Any]) -> "_TreeNodeWithDeepcopy":
+        return _TreeNodeWithDeepcopy(copium.deepcopy(self.children, memo))
+
+    def __eq__(self, other: object) -> bool:
+        return isinstance(other, _TreeNodeWithDeepcopy) and other.children == self.children
+
+
+def _mutable_ids(value: Any) -> set[int]:
+    ids = set()
+    pending = [value]
+    while pending:
+        node = pending.pop()
+        if isinstance(node, (list, dict, set, _TreeLeaf, _TreeNodeWithDeepcopy)):
+            ids.add(id(node))
+        if isinstance(node, dict):
+            pending.extend(node.values())
+        elif isinstance(node, (list, tuple, set, frozenset)):
+            pending.extend(node)
+        elif isinstance(node, _TreeLeaf):
+            pending.append(node.values)
+        elif isinstance(node, _TreeNodeWithDeepcopy):
+            pending.append(node.children)
+    return ids
+
+
+def _nested_pairs(depth: int) -> list[Any]:
+    nested: list[Any] = []
+    for i in range(depth):
+        nested = [i, nested]
+    return nested
+
+
+def test_deepcopy_assume_tree_copies_trees() -> None:
+    tree = {
+        "rows": [[i, str(i), (i, [i])] for i in range(50)],
+        "leaves": [_TreeLeaf(f"leaf{i}", [i, i + 1]) for i in range(5)],
+        "custom": _TreeNodeWithDeepcopy([{"a": [1]}, _TreeLeaf("nested")]),
+        "sets": ({1, 2}, frozenset({3})),
+        "deep": _nested_pairs(200),
+    }
+
+    copied = copium.deepcopy(tree, assume_tree=True)
+
+    assert copied == stdlib_copy.deepcopy(tree)
+    assert not _mutable_ids(copied) & _mutable_ids(tree)
+
+
+def test_deepcopy_assume_tree_copies_shared_references_separately() -> None:
+    shared = [1, 2]
+    copied = copium.deepcopy([shared, {"again": shared}], assume_tree=True)
+
+    assert copied == [shared, {"again": shared}]
+    assert copied[0] is not copied[1]["again"]
+
+
+def _self_containing_list() -> list[Any]:
+    cycle: list[Any] = []
+    cycle.append(cycle)
+    return cycle
+
+
+def _self_containing_dict() -> dict[str, Any]:
+    cycle: dict[str, Any] = {}
+    cycle["self"] = cycle
+    return cycle
+
+
+def _self_referencing_object() -> SimpleNamespace:
+    cycle = SimpleNamespace()
+    cycle.self = [cycle]
+    return cycle
+
+
+@pytest.mark.parametrize(
+    "make_cycle", [_self_containing_list, _self_containing_dict, _self_referencing_object]
+)
+def test_deepcopy_assume_tree_aborts_on_cycles(make_cycle: Callable[[], Any]) -> None:
+    with pytest.raises(copium.CopyError, match="cycle or size limit exceeded") as info:
+        copium.deepcopy(make_cycle(), assume_tree=True)
+
+    assert isinstance(info.value, stdlib_copy.Error)
+    assert isinstance(info.value.original, RecursionError)
+    assert info.value.__cause__ is info.value.original
+    assert copium.deepcopy(make_cycle(), assume_tree=False) is not None
+
+
+def test_deepcopy_assume_tree_rejects_memo_and_replace() -> None:
+    with pytest.raises(TypeError, match="memo"):
+        copium.deepcopy([1], {}, assume_tree=True)
+    with pytest.raises(TypeError, match="replace"):
+        copium.deepcopy([1], replace={}, assume_tree=True)
+    assert copium.deepcopy([1], {}, assume_tree=False) == [1]
+
+
+class _Sleeper:
+    """Takes 2ms to deepcopy and records each copy it makes in its own memo entry."""
+
+    copies: ClassVar[weakref.WeakSet] = weakref.WeakSet()
+
+    def __deepcopy__(self, memo: Any) -> "_Sleeper":
+        time.sleep(0.002)
+        new = memo[id(self)] = _Sleeper()
+        _Sleeper.copies.add(new)
+        return new
+
+
+def _slow_graph() -> list[list[_Sleeper]]:
+    # About a second to copy in full.
+    return [[_Sleeper()] for _ in range(500)]
+
+
+def test_deepcopy_timeout_aborts_within_budget() -> None:
+    original = _slow_graph()
+
+    started = time.monotonic()
+    with pytest.raises(copium.TimeoutError, match=r"timeout of 0\.05s") as info:
+        copium.deepcopy(original, timeout=0.05)
+    elapsed = time.monotonic() - started
+
+    assert isinstance(info.value, stdlib_copy.Error)
+    assert isinstance(info.value, TimeoutError)
+    assert elapsed < 0.5
+    del info
+    gc.collect()
+    assert not _Sleeper.copies
+
+
+def test_deepcopy_timeout_rolls_back_caller_memo() -> None:
+    import copium.extra
+
+    _, memo = copium.extra.deepcopy_with_memo([])
+    original = _slow_graph()
+
+    with pytest.raises(copium.TimeoutError):
+        copium.deepcopy(original, memo, timeout=0.05)
+
+    assert not any(id(row[0]) in memo for row in original)
+    assert not any(id(row) in memo for row in original)
+
+
+def test_deepcopy_timeout_arguments() -> None:
+    assert copium.deepcopy([[1]], timeout=None) == [[1]]
+    assert copium.deepcopy([[1]], timeout=60) == [[1]]
+    assert copium.deepcopy([[1]], timeout=1e300) == [[1]]
+    with pytest.raises(ValueError, match="non-negative"):
+        copium.deepcopy([1], timeout=-1)
+    with pytest.raises(TypeError):
+        copium.deepcopy([1], timeout="1")
+
+
+def test_deepcopy_progress_cadence() -> None:
+    copium.config.apply(progress_every=10)
+    calls = []
+
+    copied = copium.deepcopy([[i] for i in range(99)], progress=lambda *args: calls.append(args))
+
+    assert copied == [[i] for i in range(99)]
+    assert calls
+    assert [total for _, total in calls] == [None] * len(calls)
+    assert [done for done, _ in calls] == [10 * k for k in range(1, len(calls) + 1)]
+    assert copium.deepcopy([[1]], progress=None) == [[1]]
+    with pytest.raises(TypeError, match="progress must be a callable"):
+        copium.deepcopy([1], progress=1)
+
+
+def test_replicate_progress_reports_copies_done() -> None:
+    copium.config.apply(progress_every=1)
+    calls = []
+
+    copies = copium.extra.replicate([[1], [2], [3]], 3, progress=lambda *args: calls.append(args))
+
+    assert copies == [[[1], [2], [3]]] * 3
+    assert calls
+    assert {total for _, total in calls} == {3}
+    done = [done for done, _ in calls]
+    assert done == sorted(done)
+    assert set(done) <= {0, 1, 2}
+
+
+def test_progress_exception_aborts_the_copy() -> None:
+    copium.config.apply(progress_every=5)
+    calls = []
+
+    def cancel(done: int, total: Any) -> None:
+        calls.append(done)
+        raise KeyboardInterrupt
+
+    with pytest.raises(KeyboardInterrupt):
+        copium.deepcopy([[i] for i in range(100)], progress=cancel)
+    with pytest.raises(KeyboardInterrupt):
+        copium.extra.replicate([[i] for i in range(100)], 100, progress=cancel)
+    assert calls == [5, 0]
+
+
+def test_progress_every_must_be_positive() -> None:
+    with pytest.raises(ValueError, match="progress_every must be at least 1"):
+        copium.config.apply(progress_every=0)
+
+
+class _Blob(bytes):
+    pass
+
+
+def test_deepcopy_max_leaf_bytes_rejects_large_buffers() -> None:
+    import array
+
+    small = bytearray(16)
+    leaves = [bytearray(2048), _Blob(2048), array.array("d", bytes(2048))]
+
+    message = r"of 2\.0 KiB exceeds max_leaf_bytes \(1\.0 KiB\)"
+    for leaf in leaves:
+        with pytest.raises(copium.CopyError, match=message):
+            copium.deepcopy([small, leaf], max_leaf_bytes=1024)
+
+    copied = copium.deepcopy([small, *leaves], max_leaf_bytes=2048)
+    assert copied == [small, *leaves]
+
+
+def test_deepcopy_max_total_bytes_counts_each_copy_once() -> None:
+    shared = bytearray(600)
+    original = [shared, shared, [shared], bytearray(300)]
+
+    copied = copium.deepcopy(original, max_total_bytes=900)
+    assert copied == original
+
+    with pytest.raises(copium.CopyError, match=r"bring the copy to 1000 B, over max_total_bytes"):
+        copium.deepcopy([*original, bytearray(100)], max_total_bytes=900)
+
+
+def test_deepcopy_byte_limits_from_config() -> None:
+    copium.config.apply(max_leaf_bytes=100)
+
+    with pytest.raises(copium.CopyError, match="max_leaf_bytes"):
+        copium.deepcopy([bytearray(200)])
+    assert copium.deepcopy([bytearray(200)], max_leaf_bytes=0) == [bytearray(200)]
+    shared = b"x" * 200
+    assert copium.deepcopy([shared])[0] is shared
+
+
+def test_deepcopy_byte_limits_hold_inside_nested_deepcopy() -> None:
+    class Copier:
+        def __init__(self) -> None:
+            self.payload = [bytearray(600)]
+
+        def __deepcopy__(self, memo):
+            copied = Copier()
+            copied.payload = copium.deepcopy(self.payload)
+            return copied
+
+    with pytest.raises(copium.CopyError, match="max_total_bytes"):
+        copium.deepcopy([bytearray(600), Copier()], max_total_bytes=1000)
+
+
+def test_deepcopy_byte_limit_arguments() -> None:
+    assert copium.deepcopy([bytearray(1)], max_leaf_bytes=None, max_total_bytes=None)
+    with pytest.raises(ValueError, match="max_leaf_bytes must be a non-negative int"):
+        copium.deepcopy([1], max_leaf_bytes=-1)
+    with pytest.raises(TypeError):
+        copium.deepcopy([1], max_total_bytes="1")
+
+
+def test_deepcopy_of_sqlite_rows_fails_like_stdlib(copy) -> None:
+    import sqlite3
+
+    with closing(sqlite3.connect(":memory:")) as connection:
+        connection.row_factory = sqlite3.Row
+        rows = connection.execute("select 1 as id, 'x' as name").fetchall()
+
+    with pytest.raises(TypeError, match="sqlite3.Row"):
+        copy.deepcopy({"rows": rows})
+
+    converted = {"rows": [dict(row) for row in rows]}
+    assert copy.deepcopy(converted) == converted
+
+
+def _decoded_rows(count: int) -> list[dict[str, Any]]:
+    # f-strings build a new str each time, like a decoder does per record.
+    return [
+        {f"na{'me'}": f"user-{i % 10}", f"ta{'gs'}": (f"t{i % 3}", f"t{i % 5}")}
+        for i in range(count)
+    ]
+
+
+def _distinct(objects: Any) -> int:
+    return len({id(obj) for obj in objects})
+
+
+INTERN_MEMOS = [
+    pytest.param(lambda: None, id="native"),
+    pytest.param(dict, id="dict"),
+    pytest.param(lambda: type("Memo", (dict,), {})(), id="dict-subclass"),
+]
+
+
+@pytest.mark.parametrize("make_memo", INTERN_MEMOS)
+@pytest.mark.parametrize("intern", ["none", None, "str", "str+tuple"])
+def test_deepcopy_intern_preserves_equality(intern: Any, make_memo: Callable[[], Any]) -> None:
+    rows = _decoded_rows(50)
+    rows.append({"flags": [(1,), (True,), (1.0,)], "row": rows[0]})
+
+    copied = copium.deepcopy(rows, make_memo(), intern=intern)
+
+    assert copied == rows
+    assert copied[-1]["row"] is copied[0]
+    flags = copied[-1]["flags"]
+    assert [type(flag[0]) for flag in flags] == [int, bool, float]
+
+
+def test_deepcopy_intern_str_canonicalizes_strings_only() -> None:
+    rows = _decoded_rows(100)
+    assert _distinct(row["name"] for row in rows) == 100
+
+    plain = copium.deepcopy(rows)
+    interned = copium.deepcopy(rows, intern="str")
+
+    assert _distinct(row["name"] for row in plain) == 100
+    assert _distinct(row["name"] for row in interned) == 10
+    assert _distinct(key for row in interned for key in row) == 2
+    assert _distinct(row["tags"] for row in interned) == 100
+
+
+def test_deepcopy_intern_str_tuple_canonicalizes_tuples_of_literals() -> None:
+    rows = _decoded_rows(100)
+    rows.append({"name": (object(),), "tags": (object(),)})
+
+    copied = copium.deepcopy(rows, intern="str+tuple")
+
+    assert _distinct(row["tags"] for row in copied[:-1]) == 15
+    assert _distinct(tag for row in copied[:-1] for tag in row["tags"]) == 5
+    assert copied[-1]["name"] is not copied[-1]["tags"]
+
+
+def test_deepcopy_intern_reduces_retained_memory() -> None:
+    import tracemalloc
+
+    def retained(intern: str) -> int:
+        gc.collect()
+        tracemalloc.start()
+        try:
+            rows = _decoded_rows(5_000)
+            copied = copium.deepcopy(rows, intern=intern)
+            del rows
+            gc.collect()
+            size = tracemalloc.get_traced_memory()[0]
+        finally:
+            tracemalloc.stop()
+        assert len(copied) == 5_000
+        return size
+
+    plain = retained("none")
+    assert retained("str") < plain * 0.8
+    assert retained("str+tuple") < plain * 0.6
+
+
+def test_deepcopy_intern_cap_evicts_least_recently_used() -> None:
+    values = [f"v{i}{''}" for i in range(10)]
+    again = [f"v{i}{''}" for i in range(10)]
+
+    interleaved = [value for pair in zip(values, again) for value in pair]
+
+    copium.config.apply(intern_cap=4)
+    adjacent = copium.deepcopy(interleaved, intern="str")
+    spread = copium.deepcopy(values + again, intern="str")
+    copium.config.apply(intern_cap=64)
+    roomy = copium.deepcopy(values + again, intern="str")
+
+    assert adjacent == interleaved
+    assert spread == roomy == values + again
+    assert _distinct(adjacent) == 10
+    assert _distinct(spread) == 20
+    assert _distinct(roomy) == 10
+
+
+def test_deepcopy_intern_rejects_unknown_modes_and_assume_tree() -> None:
+    with pytest.raises(ValueError, match="intern must be 'none', 'str' or 'str\\+tuple'"):
+        copium.deepcopy([], intern="tuple")
+    with pytest.raises(ValueError, match="intern must be"):
+        copium.deepcopy([], intern=True)
+    with pytest.raises(TypeError, match="intern with assume_tree"):
+        copium.deepcopy([], intern="str", assume_tree=True)
+    assert copium.deepcopy([], intern="none", assume_tree=True) == []
+
+
+_request_id: contextvars.ContextVar[str] = contextvars.ContextVar("_request_id")
+
+
+def _contextvars_samples() -> list[Any]:
+    token = _request_id.set("outer")
+    try:
+        return [contextvars.copy_context(), _request_id, token]
+    finally:
+        _request_id.reset(token)
+
+
+@pytest.mark.parametrize(
+    "value", [pytest.param(v, id=type(v).__name__) for v in _contextvars_samples()]
+)
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+@pytest.mark.parametrize("nested", [False, True], ids=["bare", "nested"])
+def test_contextvars_errors_match_stdlib(value: Any, operation: str, nested: bool) -> None:
+    if nested and operation == "copy":
+        pytest.skip("shallow copy of a container does not touch its items")
+    target = {"state": [value]} if nested else value
+
+    with pytest.raises(TypeError) as expected:
+        getattr(stdlib_copy, operation)(target)
+    with pytest.raises(TypeError) as actual:
+        getattr(copium, operation)(target)
+
+    assert str(actual.value) == str(expected.value)
+    if operation == "copy":
+        assert type(actual.value.__cause__) is type(expected.value.__cause__)
+    else:
+        assert expected.value.__cause__ is None
+        assert f"failed to deep-copy _contextvars.{type(value).__name__} object" in str(
+            actual.value.__cause__
+        )
+
+
+def test_context_copies_through_copyreg(copy, monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setitem(copyreg.dispatch_table, contextvars.Context, lambda ctx: (ctx.copy, ()))
+    token = _request_id.set("request-1")
+    try:
+        context = contextvars.copy_context()
+    finally:
+        _request_id.reset(token)
+
+    state = {"context": context, "payload": [1]}
+    copied = copy.deepcopy(state)
+
+    assert type(copied["context"]) is contextvars.Context
+    assert copied["context"] is not context
+    assert copied["context"][_request_id] == "request-1"
+    assert copied["payload"] == [1]
+    assert copy.copy(context) is not context
+
+
+class _ChainMapState:
+    def __init__(self) -> None:
+        self.items = [1]
+        self.extra = [2]
+
+    def __getstate__(self):
+        return collections.ChainMap({"items": self.items}, {"extra": self.extra})
+
+
+class _ChainMapSlotState:
+    __slots__ = ("slot", "__dict__")
+
+    def __init__(self) -> None:
+        self.slot = [3]
+        self.items = [1]
+
+    def __getstate__(self):
+        return collections.ChainMap({"items": self.items}), collections.ChainMap({"slot": self.slot})
+
+
+class _PairsState:
+    def __init__(self) -> None:
+        self.items = [1]
+
+    def __reduce_ex__(self, protocol):
+        return type(self), (), [("items", self.items)]
+
+
+@pytest.mark.parametrize("cls", [_ChainMapState, _ChainMapSlotState, _PairsState])
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_non_dict_state_matches_stdlib(cls: type, operation: str) -> None:
+    original = cls()
+
+    expected = getattr(stdlib_copy, operation)(original)
+    actual = getattr(copium, operation)(original)
+
+    assert vars(actual) == vars(expected)
+    assert getattr(actual, "slot", None) == getattr(expected, "slot", None)
+    shares_items = operation == "copy"
+    assert (actual.items is original.items) is (expected.items is original.items) is shares_items
+
+
+def _collections_wrapper_graphs() -> dict[str, Any]:
+    shared_map = {"level": [1]}
+    first = collections.ChainMap({"own": [0]}, shared_map)
+    second = collections.ChainMap(shared_map)
+
+    key = _Token()
+    counter = collections.Counter({key: 2, "plain": 1})
+
+    user_list = collections.UserList([[1], [2]])
+    return {
+        "chainmap-shared-submap": [first, second, shared_map],
+        "chainmap-self-alias": [first, first],
+        "counter-object-keys": [counter, key, counter],
+        "counter-empty": collections.Counter(),
+        "userdict": collections.UserDict({"nested": [1]}),
+        "userlist-aliased": [user_list, user_list.data],
+        "userstring": [collections.UserString("text"), "text"],
+    }
+
+
+def _identity_shape(value: Any, seen: dict[int, int] | None = None) -> Any:
+    """Structure plus aliasing pattern, independent of object identities."""
+    seen = {} if seen is None else seen
+    if isinstance(value, (str, int, float)):
+        return value
+    if id(value) in seen:
+        return ("alias", seen[id(value)])
+    seen[id(value)] = len(seen)
+    if isinstance(value, collections.ChainMap):
+        inner = [_identity_shape(m, seen) for m in value.maps]
+    elif isinstance(value, (collections.UserDict, collections.UserList)):
+        inner = _identity_shape(value.data, seen)
+    elif isinstance(value, collections.UserString):
+        inner = value.data
+    elif isinstance(value, dict):
+        inner = [(_identity_shape(k, seen), _identity_shape(v, seen)) for k, v in value.items()]
+    elif isinstance(value, (list, tuple)):
+        inner = [_identity_shape(item, seen) for item in value]
+    else:
+        inner = None
+    return type(value).__name__, inner
+
+
+@pytest.mark.parametrize("name", list(_collections_wrapper_graphs()))
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_collections_wrappers_match_stdlib(name: str, operation: str) -> None:
+    original = _collections_wrapper_graphs()[name]
+
+    expected = getattr(stdlib_copy, operation)(original)
+    actual = getattr(copium, operation)(original)
+
+    assert _identity_shape(actual) == _identity_shape(expected)
+    assert (actual is original) is (expected is original)
+
+
+def test_counter_deepcopy_copies_keys_and_drops_attributes() -> None:
+    key = _Token()
+    counter = collections.Counter({key: 2, "plain": [1]})
+    counter.label = "instance attribute"
+
+    copied = copium.deepcopy(counter)
+
+    assert type(copied) is collections.Counter
+    assert copied["plain"] == [1] and copied["plain"] is not counter["plain"]
+    assert key not in copied and len(copied) == 2
+    assert not hasattr(copied, "label") and not hasattr(stdlib_copy.deepcopy(counter), "label")
+
+
+def test_counter_subclass_goes_through_reduce() -> None:
+    class Tally(collections.Counter):
+        def __reduce__(self):
+            return type(self), (dict(self),), {"reduced": True}
+
+    copied = copium.deepcopy(Tally(a=1))
+
+    assert type(copied) is Tally
+    assert copied == Tally(a=1)
+    assert copied.reduced is True
+
+
+def _public_attributes(value: Any, *hidden: str) -> dict[str, Any]:
+    """Every public, non-callable attribute, plus `hidden` ones dir() leaves out,
+    for comparing a fast path's copy with stdlib's."""
+    names = [name for name in dir(value) if not name.startswith("_")] + list(hidden)
+    return {name: attr for name in names if not callable(attr := getattr(value, name))}
+
+
+def _decimal_contexts() -> dict[str, Any]:
+    import decimal
+
+    touched = decimal.Context(prec=12, rounding=decimal.ROUND_DOWN, Emin=-99, Emax=99)
+    touched.capitals = 0
+    touched.clamp = 1
+    touched.traps[decimal.Inexact] = True
+    touched.flags[decimal.Rounded] = True
+    return {"default": decimal.Context(), "basic": decimal.BasicContext, "touched": touched}
+
+
+@pytest.mark.parametrize("name", list(_decimal_contexts()))
+def test_decimal_context_fast_path_matches_stdlib(name: str) -> None:
+    original = _decimal_contexts()[name]
+
+    copied = copium.deepcopy(original)
+    expected = stdlib_copy.deepcopy(original)
+
+    assert type(copied) is type(expected)
+    assert copied is not original
+    assert _public_attributes(copied, "flags", "traps") == _public_attributes(
+        expected, "flags", "traps"
+    )
+
+
+def test_decimal_context_copies_are_independent_and_memoized() -> None:
+    import decimal
+
+    original = decimal.Context(prec=5)
+    copied = copium.deepcopy({"a": original, "b": [original]})
+
+    assert copied["a"] is copied["b"][0]
+    copied["a"].prec = 30
+    copied["a"].flags[decimal.Inexact] = True
+    copied["a"].traps[decimal.Overflow] = False
+    assert original.prec == 5
+    assert not original.flags[decimal.Inexact]
+    assert original.traps[decimal.Overflow]
+
+
+def test_decimal_context_subclass_goes_through_reduce() -> None:
+    import decimal
+
+    class Tagged(decimal.Context):
+        pass
+
+    original = Tagged(prec=7)
+    original.tag = ["x"]
+
+    copied = copium.deepcopy(original)
+    expected = stdlib_copy.deepcopy(original)
+
+    assert type(copied) is Tagged
+    assert copied.prec == 7
+    assert hasattr(copied, "tag") is hasattr(expected, "tag")
+
+
+class _ReducesToName:
+    def __init__(self, name: Any) -> None:
+        self.name = name
+        self.items: list[int] = []
+
+    def __reduce__(self) -> Any:
+        return self.name
+
+
+def test_strict_mode_rejects_string_reduce_pass_through() -> None:
+    value = _ReducesToName("_ReducesToName")
+
+    assert copium.deepcopy([value])[0] is value
+
+    copium.config.apply(strict=True)
+    with pytest.raises(copium.CopyError, match="__reduce__ returned a string") as excinfo:
+        copium.deepcopy([value])
+    assert excinfo.value.obj_type == f"{__name__}._ReducesToName"
+    assert copium.copy(value) is value
+
+
+def test_bytes_reduce_result_is_not_a_pass_through() -> None:
+    # Like stdlib, only a str names a global; bytes are unpacked as a tuple would be.
+    value = _ReducesToName(b"_ReducesToName")
+
+    with pytest.raises(TypeError):
+        stdlib_copy.deepcopy([value])
+    with pytest.raises(TypeError):
+        copium.deepcopy([value])
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        pytest.param(len, id="builtin"),
+        pytest.param(_ReducesToName, id="type"),
+        pytest.param((1, "a", (None, 2.0)), id="immutable-tuple"),
+        pytest.param(range(3), id="range"),
+    ],
+)
+def test_strict_mode_still_shares_atomic_objects(value: Any) -> None:
+    copium.config.apply(strict=True)
+
+    assert copium.deepcopy(value) is stdlib_copy.deepcopy(value)
+
+
+def test_self_referencing_counter_matches_stdlib() -> None:
+    counter = collections.Counter()
+    counter["self"] = counter
+
+    with pytest.raises(RecursionError):
+        stdlib_copy.deepcopy(counter)
+    with pytest.raises(RecursionError):
+        copium.deepcopy(counter)
+
+
+_SHARED_CONFIG = {"retries": [3]}
+
+
+@dataclass
+class _MarkedJob:
+    name: str
+    config: dict = field(default_factory=dict, metadata={"copium": "share"})
+    cache: list = field(default_factory=lambda: ["fresh"], metadata={"copium": "skip"})
+    attempts: int = field(default=0, metadata={"copium": "skip"})
+    payload: list = field(default_factory=list)
+    unrelated: list = field(default_factory=list, metadata={"other": "share"})
+    kind: ClassVar[str] = "job"
+
+
+@dataclass
+class _OverridingJob(_MarkedJob):
+    config: dict = field(default_factory=dict)
+    cache: list = field(default_factory=list, metadata={"copium": "share"})
+
+
+@dataclass(frozen=True)
+class _FrozenMarked:
+    shared: list = field(metadata={"copium": "share"})
+    copied: list = field(default_factory=list)
+
+
+def _marked_job(cls: type[_MarkedJob] = _MarkedJob) -> _MarkedJob:
+    return cls("job", _SHARED_CONFIG, ["stale"], 5, [[1]], [[2]])
+
+
+def test_dataclass_markers_share_skip_and_copy() -> None:
+    job = _marked_job()
+    job.note = ["dynamic"]
+
+    copied = copium.deepcopy(job)
+
+    assert copied.config is _SHARED_CONFIG
+    assert copied.cache == ["fresh"]
+    assert copied.attempts == 0
+    assert copied.payload == [[1]] and copied.payload[0] is not job.payload[0]
+    assert copied.unrelated == [[2]] and copied.unrelated is not job.unrelated
+    assert copied.note == ["dynamic"] and copied.note is not job.note
+    assert copied.name == "job" and copied.kind == "job"
+
+
+def test_dataclass_markers_skip_factory_runs_per_copy() -> None:
+    job = _marked_job()
+
+    first, second = copium.deepcopy(job), copium.deepcopy(job)
+
+    assert first.cache == second.cache == ["fresh"]
+    assert first.cache is not second.cache
+
+
+def test_dataclass_markers_follow_subclass_overrides() -> None:
+    job = _marked_job(_OverridingJob)
+
+    copied = copium.deepcopy(job)
+
+    assert copied.config == _SHARED_CONFIG and copied.config is not _SHARED_CONFIG
+    assert copied.cache is job.cache
+    assert copied.attempts == 0
+
+
+def test_dataclass_markers_on_frozen_dataclass() -> None:
+    frozen = _FrozenMarked([1], [[2]])
+
+    copied = copium.deepcopy([frozen, frozen])
+
+    assert copied[0] is copied[1]
+    assert copied[0].shared is frozen.shared
+    assert copied[0].copied == [[2]] and copied[0].copied[0] is not frozen.copied[0]
+    assert copied[0] == frozen
+
+
+@dataclass(kw_only=True, slots=True, frozen=True)
+class _StrictMarked:
+    shared: list = field(metadata={"copium": "share"})
+    copied: list
+    skipped: list = field(default_factory=list, metadata={"copium": "skip"})
+
+
+def test_dataclass_markers_on_kw_only_slots_frozen_dataclass() -> None:
+    strict = _StrictMarked(shared=[1], copied=[[2]], skipped=[3])
+
+    copied = copium.deepcopy(strict)
+
+    assert copied.shared is strict.shared
+    assert copied.copied == [[2]] and copied.copied[0] is not strict.copied[0]
+    assert copied.skipped == []
+
+
+def test_dataclass_markers_preserve_cycles() -> None:
+    job = _marked_job()
+    job.payload.append(job)
+
+    copied = copium.deepcopy(job)
+
+    assert copied.payload[-1] is copied
+
+
+def test_dataclass_markers_leave_shallow_copy_alone(copy) -> None:
+    job = _marked_job()
+
+    copied = copy.copy(job)
+
+    assert copied.cache is job.cache
+    assert copied.attempts == 5
+
+
+@dataclass(frozen=True)
+class _FrozenKey:
+    name: str
+    version: tuple
+    notes: list = field(default_factory=list, compare=False)
+
+
+@dataclass(frozen=True)
+class _FrozenKeyChild(_FrozenKey):
+    extra: int = 0
+
+
+@dataclass(frozen=True)
+class _FrozenKeyCustomHash:
+    name: str
+
+    def __hash__(self):
+        return hash(self.name) ^ 1
+
+
+@dataclass(unsafe_hash=True)
+class _UnsafeHashKey:
+    name: str
+
+
+class _IdentityHashed:
+    """Mutable, hashed by identity: its copy hashes differently."""
+
+
+@dataclass(frozen=True)
+class _FrozenKeyWithIdentityField:
+    owner: _IdentityHashed
+
+
+def _frozen_keyed_dict() -> dict:
+    return {
+        _FrozenKey("a", (1, 2)): [1],
+        _FrozenKey("b", (3,), notes=["mutable, not hashed"]): [2],
+        _FrozenKeyChild("c", (), extra=4): [3],
+        _FrozenKeyCustomHash("d"): [4],
+        _UnsafeHashKey("e"): [5],
+        _FrozenKeyWithIdentityField(_IdentityHashed()): [6],
+        "plain": [7],
+        (1, 2): [8],
+    }
+
+
+@pytest.mark.parametrize("memo", ["native", "dict"])
+def test_frozen_dataclass_keys_match_stdlib(memo: str) -> None:
+    original = _frozen_keyed_dict()
+
+    copied = copium.deepcopy(original, {} if memo == "dict" else None)
+    expected = stdlib_copy.deepcopy(original)
+
+    assert [type(key) for key in copied] == [type(key) for key in expected]
+    assert list(copied.values()) == list(expected.values())
+    for key, value in copied.items():
+        assert copied[key] is value
+
+
+def test_frozen_dataclass_key_with_changed_hash_is_rehashed() -> None:
+    key = _FrozenKeyWithIdentityField(_IdentityHashed())
+    original = {key: "value"}
+
+    copied = copium.deepcopy(original)
+
+    (copied_key,) = copied
+    assert copied_key.owner is not key.owner
+    assert hash(copied_key) != hash(key)
+    assert copied[copied_key] == "value"
+    assert key not in copied
+
+
+def test_frozen_dataclass_key_sharing_and_unhashed_fields_are_copied() -> None:
+    key = _FrozenKey("a", (1,), notes=["n"])
+    original = {key: [key], "again": key}
+
+    copied = copium.deepcopy(original)
+
+    copied_key = copied["again"]
+    assert copied_key is not key and copied_key == key
+    assert copied[copied_key][0] is copied_key
+    assert copied_key.notes == ["n"] and copied_key.notes is not key.notes
+
+
+class _StatefulNode:
+    def __init__(self, children):
+        self.children = children
+
+    def __getstate__(self):
+        # A fresh dict on every call: only the memo keeps it alive.
+        return {"children": self.children}
+
+
+def test_deepcopy_with_memo_maps_every_mutable_node() -> None:
+    import copium.extra
+
+    leaf = [1, 2]
+    original = {"leaf": leaf, "again": leaf, "nested": ({"x": [3]},), "node": _StatefulNode([4])}
+
+    copied, memo = copium.extra.deepcopy_with_memo(original)
+
+    assert copied == {"leaf": [1, 2], "again": [1, 2], "nested": ({"x": [3]},), "node": copied["node"]}
+    assert copied["leaf"] is copied["again"]
+    pairs = [
+        (original, copied),
+        (leaf, copied["leaf"]),
+        (original["nested"], copied["nested"]),
+        (original["nested"][0], copied["nested"][0]),
+        (original["nested"][0]["x"], copied["nested"][0]["x"]),
+        (original["node"], copied["node"]),
+        (original["node"].children, copied["node"].children),
+    ]
+    for node, copy_of_node in pairs:
+        assert memo[id(node)] is copy_of_node
+
+
+def _first_visit_fixture() -> tuple[list[Any], list[Any]]:
+    """A graph and its mutable nodes in the order deepcopy first reaches them."""
+    leaf = [1]
+    node = _StatefulNode([2])
+    inner = {"k": [3], "again": leaf}
+    pair = ([4],)
+    namespace = SimpleNamespace(x=[5])
+    graph = [node, inner, pair, leaf, namespace]
+    # Tuples are memoized after their items.
+    visits = [graph, node, node.children, inner, inner["k"], leaf, pair[0], pair, namespace, namespace.x]
+    return graph, visits
+
+
+def _visit_order(keys: list[int], visits: list[Any]) -> list[int]:
+    expected = {id(node) for node in visits}
+    return [key for key in keys if key in expected]
+
+
+def test_deepcopy_with_memo_reports_first_visit_order() -> None:
+    import copium.extra
+
+    original, visits = _first_visit_fixture()
+
+    _, memo = copium.extra.deepcopy_with_memo(original)
+
+    assert _visit_order(list(memo), visits) == [id(node) for node in visits]
+
+
+class _MemoOrderSpy:
+    def __deepcopy__(self, memo: Any) -> "_MemoOrderSpy":
+        _MemoOrderSpy.seen = (list(memo), memo.keys(), memo.values(), memo.items())
+        return self
+
+
+def test_memo_proxy_iterates_in_first_visit_order() -> None:
+    graph, visits = _first_visit_fixture()
+
+    copium.deepcopy([graph, _MemoOrderSpy()])
+
+    keys, listed_keys, values, items = _MemoOrderSpy.seen
+    assert _visit_order(keys, visits) == [id(node) for node in visits]
+    assert listed_keys == keys
+    assert [key for key, _ in items] == keys
+    assert [value for _, value in items] == values
+
+
+def test_memo_proxy_order_follows_removals_and_growth() -> None:
+    def mutate(memo: Any) -> list[int]:
+        values = [[i] for i in range(40)]
+        for value in values:
+            memo[id(value)] = value
+        for value in values[::3]:
+            del memo[id(value)]
+        memo[id(values[0])] = values[0]
+        return [memo[key][0] for key in memo if key != id(memo)]
+
+    class Capture:
+        def __deepcopy__(self, memo: Any) -> "Capture":
+            Capture.order = mutate(memo)
+            return self
+
+    copium.deepcopy(Capture())
+
+    assert Capture.order == mutate({})
+
+
+def test_deepcopy_with_memo_keeps_originals_alive() -> None:
+    import copium.extra
+
+    original = [_StatefulNode([1]), _StatefulNode([2])]
+
+    copied, memo = copium.extra.deepcopy_with_memo(original)
+    gc.collect()
+
+    kept = memo[id(memo)]
+    assert {id(node) for node in kept} == set(memo) - {id(memo)}
+    assert stdlib_copy.deepcopy(original, memo) is copied
+
+
+class _Relay:
+    """Copies its payload with whichever module ``via`` names, on the memo it got."""
+
+    def __init__(self, payload: Any, via: str) -> None:
+        self.payload = payload
+        self.via = via
+
+    def __deepcopy__(self, memo: Any) -> "_Relay":
+        module = stdlib_copy if self.via == "stdlib" else copium
+        new = _Relay.__new__(_Relay)
+        memo[id(self)] = new
+        new.via = self.via
+        new.payload = module.deepcopy(self.payload, memo)
+        return new
+
+
+def test_stdlib_and_copium_interleave_on_one_memo() -> None:
+    import copium.extra
+
+    shared = [1]
+    inner = _Relay([shared, (shared,)], via="copium")
+    outer = _Relay([inner, shared], via="stdlib")
+    original = [outer, inner, shared, outer]
+
+    copied, memo = copium.extra.deepcopy_with_memo(original)
+    new_outer, new_inner, new_shared, again = copied
+
+    assert again is new_outer
+    assert new_outer.payload[0] is new_inner
+    assert new_outer.payload[1] is new_shared
+    assert new_inner.payload[0] is new_shared
+    assert new_inner.payload[1][0] is new_shared
+    assert new_shared == shared and new_shared is not shared
+
+    # stdlib's _keep_alive went to the memo's own keepalive, under id(memo).
+    assert any(kept is inner for kept in memo[id(memo)])
+    assert stdlib_copy.deepcopy(original, memo) is copied
+    assert copium.deepcopy(original, memo) is copied
+
+
+def test_deepcopy_with_memo_atomic_and_followup_calls() -> None:
+    import copium.extra
+
+    assert copium.extra.deepcopy_with_memo("atomic") == ("atomic", {})
+
+    inner = [1]
+    _, memo = copium.extra.deepcopy_with_memo([inner])
+    observed = []
+
+    class Probe:
+        def __deepcopy__(self, memo):
+            observed.append(id(inner) in memo)
+            return Probe()
+
+    copium.deepcopy([Probe()])
+    assert id(inner) in memo
+    assert observed == [False]
+
+
+def test_replicate_into_overwrites_then_appends() -> None:
+    import copium.extra
+
+    original = {"a": [1, 2], "b": ({"c": 3},)}
+    pooled = ["stale", "stale"]
+
+    assert copium.extra.replicate_into(original, pooled, 4) == 4
+    assert pooled == [original] * 4
+    assert len({id(copy) for copy in pooled}) == 4
+    assert all(copy["a"] is not original["a"] for copy in pooled)
+
+    longer = [None] * 5
+    assert copium.extra.replicate_into(original, longer, 2) == 2
+    assert longer[:2] == [original] * 2
+    assert longer[2:] == [None] * 3
+
+    assert copium.extra.replicate_into("atomic", longer, 1) == 1
+    assert longer[0] == "atomic"
+
+
+def test_replicate_into_custom_sequence() -> None:
+    import copium.extra
+
+    class Pool:
+        def __init__(self, size: int) -> None:
+            self.slots: list[Any] = [None] * size
+            self.writes: list[int] = []
+            self.appended: list[Any] = []
+
+        def __len__(self) -> int:
+            return len(self.slots)
+
+        def __setitem__(self, index: int, value: Any) -> None:
+            self.writes.append(index)
+            self.slots[index] = value
+
+        def append(self, value: Any) -> None:
+            self.appended.append(value)
+
+    original = [[1], {"k": "v"}]
+    pool = Pool(2)
+
+    assert copium.extra.replicate_into(original, pool, 3) == 3
+    assert pool.writes == [0, 1]
+    assert pool.slots == [original, original]
+    assert pool.appended == [original]
+    assert pool.slots[0] is not pool.slots[1]
+
+
+def test_replicate_into_short_target_keeps_written_items() -> None:
+    import copium.extra
+
+    class FixedSlots:
+        def __init__(self, size: int) -> None:
+            self.slots: list[Any] = [None] * size
+
+        def __len__(self) -> int:
+            return len(self.slots)
+
+        def __setitem__(self, index: int, value: Any) -> None:
+            self.slots[index] = value
+
+    target = FixedSlots(2)
+
+    with pytest.raises(AttributeError) as error:
+        copium.extra.replicate_into([1], target, 3)
+
+    assert target.slots == [[1], [1]]
+    assert error.value.__notes__ == ["replicate_into() failed at index 2"]
+
+
+def test_replicate_into_copy_failure_notes_index() -> None:
+    import copium.extra
+
+    class FailsThird:
+        copies = 0
+
+        def __deepcopy__(self, memo: Any) -> Any:
+            FailsThird.copies += 1
+            if FailsThird.copies == 3:
+                raise ValueError("boom")
+            return FailsThird()
+
+    target: list[Any] = []
+
+    with pytest.raises(ValueError, match="boom") as error:
+        copium.extra.replicate_into(FailsThird(), target, 5)
+
+    assert len(target) == 2
+    assert error.value.__notes__ == ["replicate_into() failed at index 2"]
+
+
+class _TreeNode:
+    __slots__ = ("parent", "children")
+
+    def __init__(self, parent: Any = None) -> None:
+        self.parent = parent
+        self.children: list[Any] = []
+        if parent is not None:
+            parent.children.append(self)
+
+
+class _LabelledTreeNode(_TreeNode):
+    pass
+
+
+@dataclass(slots=True, eq=False)
+class _SlotsDataclassNode:
+    parent: Any = None
+    children: list[Any] = field(default_factory=list)
+
+
+@pytest.mark.parametrize("node_type", [_TreeNode, _LabelledTreeNode, _SlotsDataclassNode])
+@pytest.mark.parametrize("memo", [None, "dict"])
+def test_slots_parent_links_point_into_the_copy(node_type: type, memo: str | None) -> None:
+    root = node_type()
+    for _ in range(3):
+        child = node_type(root)
+        if node_type is _SlotsDataclassNode:
+            root.children.append(child)
+    if node_type is _LabelledTreeNode:
+        root.first = root.children[0]
+
+    copied = copium.deepcopy(root, {} if memo == "dict" else None)
+
+    assert copied is not root
+    assert all(child.parent is copied for child in copied.children)
+    assert not any(child is original for child, original in zip(copied.children, root.children))
+    if node_type is _LabelledTreeNode:
+        assert copied.first is copied.children[0]
+
+
+def test_replicate_verify_first_stops_before_fanning_out() -> None:
+    import copium.extra
+
+    class Drifting:
+        copies = 0
+
+        def __init__(self) -> None:
+            self.value = 1
+
+        def __deepcopy__(self, memo: Any) -> Any:
+            Drifting.copies += 1
+            drifted = Drifting()
+            drifted.value = 2
+            return drifted
+
+    original = {"rows": [[1, 2], Drifting()]}
+
+    with pytest.raises(copium.CopyError, match=r"differs .* in 1 place\(s\)") as error:
+        copium.extra.replicate(original, 1_000, verify_first=True)
+
+    assert "['rows'][1].value" in str(error.value)
+    assert Drifting.copies == 1
+    assert len(copium.extra.replicate(original, 3)) == 3
+
+
+def test_replicate_verify_first_passes_faithful_copies() -> None:
+    import copium.extra
+
+    original = {"a": [1, {2, 3}], "b": (_Point(1, [2]),)}
+    original["self"] = original
+
+    copies = copium.extra.replicate(original, 5, verify_first=True)
+
+    assert len({id(copy) for copy in copies}) == 5
+    assert all(copy["self"] is copy and copy["b"][0].y == [2] for copy in copies)
+    assert copium.extra.replicate("atomic", 2, verify_first=True) == ["atomic"] * 2
+    assert copium.extra.replicate(original, 0, verify_first=True) == []
+    with pytest.raises(TypeError, match="unexpected keyword argument 'verify'"):
+        copium.extra.replicate(original, 1, verify=True)
+
+
+def _json_document() -> Any:
+    return {
+        "users": [
+            {"id": i, "name": f"user{i}", "score": i / 3, "active": i % 2 == 0, "manager": None}
+            for i in range(20)
+        ],
+        "matrix": [[i * j for j in range(5)] for i in range(5)],
+        "nested": {"a": {"b": {"c": [{"d": [1, "2", 3.0, True, None]}]}}},
+        "": [],
+        1: {},
+        None: "null key",
+    }
+
+
+def _json_containers(value: Any) -> list[Any]:
+    found = []
+    pending = [value]
+    while pending:
+        node = pending.pop()
+        if isinstance(node, (dict, list)):
+            found.append(node)
+            pending.extend(node.values() if isinstance(node, dict) else node)
+    return found
+
+
+def test_deepcopy_json_copies_json_documents() -> None:
+    import copium.extra
+
+    document = _json_document()
+    copied = copium.extra.deepcopy_json(document)
+
+    assert copied == document == stdlib_copy.deepcopy(document)
+    assert not {id(node) for node in _json_containers(copied)} & {
+        id(node) for node in _json_containers(document)
+    }
+
+
+def test_deepcopy_json_deep_nesting() -> None:
+    import copium.extra
+
+    document: Any = "leaf"
+    for i in range(900):
+        document = [document] if i % 2 else {"child": document}
+
+    copied = copium.extra.deepcopy_json(document)
+
+    original_node, copied_node = document, copied
+    while original_node != "leaf":
+        assert type(copied_node) is type(original_node) and copied_node is not original_node
+        original_node = original_node[0] if isinstance(original_node, list) else original_node["child"]
+        copied_node = copied_node[0] if isinstance(copied_node, list) else copied_node["child"]
+    assert copied_node == "leaf"
+
+
+def test_deepcopy_json_graceful_recursion_error() -> None:
+    import copium.extra
+
+    with pytest.raises(RecursionError):
+        copium.extra.deepcopy_json(make_nested(999999))
+
+
+def test_deepcopy_json_sharing_is_opt_in() -> None:
+    import copium.extra
+
+    shared = {"x": [1]}
+    document = [shared, {"again": shared}]
+
+    duplicated = copium.extra.deepcopy_json(document)
+    preserved = copium.extra.deepcopy_json(document, preserve_sharing=True)
+
+    assert duplicated == preserved == document
+    assert duplicated[0] is not duplicated[1]["again"]
+    assert preserved[0] is preserved[1]["again"]
+
+    cycle: list[Any] = [1]
+    cycle.append(cycle)
+    copied = copium.extra.deepcopy_json(cycle, preserve_sharing=True)
+    assert copied[1] is copied and copied is not cycle
+
+
+@pytest.mark.parametrize(
+    "document, message",
+    [
+        pytest.param([1, (2, 3)], "object of type tuple", id="tuple"),
+        pytest.param({"a": {1, 2}}, "object of type set", id="set"),
+        pytest.param({"a": [b"x"]}, "object of type bytes", id="bytes"),
+        pytest.param({"a": collections.OrderedDict()}, "object of type collections.OrderedDict", id="dict-subclass"),
+        pytest.param([datetime.date(2024, 1, 1)], "object of type datetime.date", id="date"),
+        pytest.param({(1, 2): "tuple key"}, "dict key of type tuple", id="tuple-key"),
+        pytest.param({frozenset(): "frozenset key"}, "dict key of type frozenset", id="frozenset-key"),
+        pytest.param(object(), "object of type object", id="top-level"),
+    ],
+)
+def test_deepcopy_json_rejects_non_json_types(document: Any, message: str) -> None:
+    import copium.extra
+
+    with pytest.raises(TypeError, match=f"deepcopy_json\\(\\) can't copy {message}"):
+        copium.extra.deepcopy_json(document)
+
+
+# Structure dumps: shape-only records of a graph for bug reports.
+
+
+class _StructurePlain:
+    def __init__(self, secret):
+        self.secret = secret
+        self.me = self
+
+
+class _StructureSlots:
+    __slots__ = ("left", "right")
+
+
+@dataclass
+class _StructureRecord:
+    name: str
+    tags: list
+
+
+class _StructureCustom:
+    def __init__(self, payload):
+        self.payload = payload
+
+    def __deepcopy__(self, memo):
+        raise AssertionError("dump_structure must not call __deepcopy__")
+
+
+class _StructureList(list):
+    pass
+
+
+class _StructureBroken:
+    def __reduce_ex__(self, protocol):
+        raise LookupError("hunter2")
+
+
+def _dump_structure(obj: Any) -> str:
+    import io
+
+    import copium.extra
+
+    buffer = io.StringIO()
+    assert copium.extra.dump_structure(obj, buffer) is None
+    return buffer.getvalue()
+
+
+def _load_structure(text: str) -> Any:
+    import io
+
+    import copium.extra
+
+    return copium.extra.load_structure(io.StringIO(text))
+
+
+def _structure_graph() -> Any:
+    shared = ["hunter2", 42]
+    slots = _StructureSlots()
+    slots.left = shared
+    slots.right = 3.25
+    plain = _StructurePlain("hunter2")
+    listish = _StructureList([shared, plain])
+    listish.note = "hunter2"
+    return {
+        "hunter2-key": [shared, shared, (shared, 1), {7}],
+        "objects": [plain, slots, _StructureRecord("hunter2", shared), _StructureCustom([1])],
+        "listish": listish,
+        "buffer": bytearray(b"hunter2"),
+        "method": plain.__init__,
+        "counter": collections.Counter(hunter2=1),
+    }
+
+
+def test_structure_round_trips_through_load() -> None:
+    text = _dump_structure(_structure_graph())
+
+    assert _dump_structure(_load_structure(text)) == text
+
+
+def test_structure_leaves_out_values_and_keys() -> None:
+    text = _dump_structure(_structure_graph())
+
+    assert "hunter2" not in text
+    assert "42" not in text and "3.25" not in text
+    assert "_StructureSlots" in text and '"secret"' in text
+
+
+def test_structure_reproduces_copy_paths() -> None:
+    rebuilt = _load_structure(_dump_structure(_structure_graph()))
+
+    copied = copium.deepcopy(rebuilt)
+
+    assert _dump_structure(copied) == _dump_structure(stdlib_copy.deepcopy(rebuilt))
+    aliased, objects = list(copied.values())[:2]
+    assert objects[0].me is objects[0]
+    assert aliased[0] is aliased[1] is aliased[2][0]
+
+
+def test_structure_records_reduce_errors_by_type() -> None:
+    text = _dump_structure([_StructureBroken()])
+
+    assert "LookupError" in text and "hunter2" not in text
+    with pytest.raises(LookupError, match="redacted"):
+        copium.deepcopy(_load_structure(text))
+
+
+def test_structure_deep_nesting_and_cycles() -> None:
+    nested: Any = []
+    for _ in range(100_000):
+        nested = [nested]
+    cycle: list[Any] = []
+    cycle.append(cycle)
+    text = _dump_structure([nested, cycle])
+
+    rebuilt = _load_structure(text)
+
+    assert rebuilt[1][0] is rebuilt[1]
+    assert _dump_structure(rebuilt) == text
+
+
+def test_load_structure_rejects_other_json() -> None:
+    with pytest.raises(ValueError, match="not a copium structure dump"):
+        _load_structure('{"nodes": []}')
+
+
+class _Point:
+    def __init__(self, x: Any, y: Any) -> None:
+        self.x = x
+        self.y = y
+
+
+class _SlottedPoint:
+    __slots__ = ("x", "y")
+
+    def __init__(self, x: Any, y: Any) -> None:
+        self.x = x
+        self.y = y
+
+
+class _EqRaises:
+    __slots__ = ()
+
+    def __eq__(self, other: object) -> bool:
+        raise ValueError("no comparing")
+
+    def __repr__(self) -> str:
+        return "_EqRaises()"
+
+
+def test_diff_reports_nested_changes() -> None:
+    original = {"user": {"name": "a", "tags": ["x", "y"]}, "count": 1}
+    changed = copium.deepcopy(original)
+    changed["user"]["name"] = "b"
+    changed["user"]["tags"].append("z")
+    del changed["count"]
+    changed["new"] = 2
+
+    assert copium.extra.diff(original, changed) == [
+        ("['user']['name']", "changed", "'a'", "'b'"),
+        ("['user']['tags'][2]", "added", None, "'z'"),
+        ("['count']", "removed", "1", None),
+        ("['new']", "added", None, "2"),
+    ]
+    assert copium.extra.diff(original, copium.deepcopy(original)) == []
+
+
+@pytest.mark.parametrize("cls", [_Point, _SlottedPoint])
+def test_diff_compares_objects_by_attribute(cls: Any) -> None:
+    original = cls(1, [1, {2}])
+    changed = cls(1.0, [1, {3}])
+
+    assert copium.extra.diff(original, changed) == [
+        (".x", "type", "1", "1.0"),
+        (".y[1]", "removed", "2", None),
+        (".y[1]", "added", None, "3"),
+    ]
+
+
+def test_diff_skips_identical_objects_and_walks_shared_pairs_once() -> None:
+    looked_at = []
+
+    class Spy:
+        def __getattribute__(self, name: str) -> Any:
+            looked_at.append(name)
+            return object.__getattribute__(self, name)
+
+    spy = Spy()
+    left, right = [1], [2]
+
+    assert copium.extra.diff([spy, left, left], [spy, right, right]) == [
+        ("[1][0]", "changed", "1", "2"),
+    ]
+    assert looked_at == []
+
+
+def test_diff_handles_cycles() -> None:
+    original: list[Any] = [1]
+    original.append(original)
+    changed: list[Any] = [2]
+    changed.append(changed)
+
+    assert copium.extra.diff(original, changed) == [("[0]", "changed", "1", "2")]
+
+
+def test_diff_max_depth_compares_with_eq() -> None:
+    original, changed = {"a": [1], "b": [2]}, {"a": [1], "b": [3]}
+
+    assert copium.extra.diff(original, changed, max_depth=0) == [
+        ("", "changed", repr(original), repr(changed)),
+    ]
+    assert copium.extra.diff(original, changed, max_depth=1) == [
+        ("['b']", "changed", "[2]", "[3]"),
+    ]
+    with pytest.raises(ValueError, match="max_depth"):
+        copium.extra.diff(original, changed, max_depth=-1)
+
+
+def test_diff_treats_raising_eq_as_changed() -> None:
+    assert copium.extra.diff([_EqRaises()], [_EqRaises()]) == [
+        ("[0]", "changed", "_EqRaises()", "_EqRaises()"),
+    ]
+
+
+def test_fingerprint_equal_for_equal_graphs() -> None:
+    original = {
+        "name": "svc",
+        "ports": [80, 443],
+        "tags": {"a", "b"},
+        "blob": bytearray(b"\x00" * 20),
+        "point": _Point(1, (2.5, None)),
+    }
+    reordered = {key: original[key] for key in reversed(list(original))}
+    fingerprint = copium.extra.fingerprint
+
+    assert fingerprint(original) == fingerprint(copium.deepcopy(original))
+    assert fingerprint(original) == fingerprint(reordered)
+    assert 0 <= fingerprint(original) < 2**128
+
+
+@pytest.mark.parametrize(
+    ("a", "b"),
+    [
+        ([1, 2], [2, 1]),
+        ([1, 2], (1, 2)),
+        ([1], [1.0]),
+        ({"a": 1}, {"a": 2}),
+        ({"a": 1}, {"b": 1}),
+        ({1, 2}, {1, 3}),
+        ([[]], [[[]]]),
+        (bytearray(b"ab"), bytearray(b"ac")),
+        (_Point(1, 2), _Point(2, 1)),
+        (_Point(1, 2), _SlottedPoint(1, 2)),
+    ],
+)
+def test_fingerprint_differs_for_different_graphs(a: Any, b: Any) -> None:
+    assert copium.extra.fingerprint(a) != copium.extra.fingerprint(b)
+
+
+def test_fingerprint_counts_sharing_and_ends_on_cycles() -> None:
+    shared = [1]
+    assert copium.extra.fingerprint([shared, shared]) != copium.extra.fingerprint([[1], [1]])
+
+    original: list[Any] = [1, {}]
+    original[1]["self"] = original
+    copied = copium.deepcopy(original)
+    other: list[Any] = [2, {}]
+    other[1]["self"] = other
+
+    assert copium.extra.fingerprint(original) == copium.extra.fingerprint(copied)
+    assert copium.extra.fingerprint(original) != copium.extra.fingerprint(other)
+
+
+def _in_new_thread(function: Callable[[], Any]) -> Any:
+    results = []
+    thread = threading.Thread(target=lambda: results.append(function()))
+    thread.start()
+    thread.join()
+    return results[0]
+
+
+def test_mark_copies_records_the_root() -> None:
+    copium.config.apply(mark_copies=True)
+    original = _Point([1], {"a": [2]})
+
+    before = time.time()
+    copied = copium.deepcopy(original)
+    info = copium.extra.last_copy_info()
+
+    assert copied.__copium__ is info
+    assert info["source_type"] is _Point
+    assert info["thread_id"] == threading.get_ident()
+    assert before <= info["timestamp"] <= time.time()
+    assert not hasattr(copied.x, "__copium__")
+    assert not hasattr(original, "__copium__")
+
+    copium.deepcopy(original)
+    assert copium.extra.last_copy_info()["copy_id"] == info["copy_id"] + 1
+
+
+@pytest.mark.parametrize(
+    "original",
+    [[1, 2], _SlottedPoint(1, 2), (1, "atomic tuple is returned as is")],
+    ids=["builtin", "slots", "same-object"],
+)
+def test_mark_copies_skips_roots_without_attributes(original: Any) -> None:
+    copium.config.apply(mark_copies=True)
+
+    copied = copium.deepcopy(original)
+
+    assert not hasattr(copied, "__copium__")
+    assert copium.extra.last_copy_info()["source_type"] is type(original)
+
+
+def test_mark_copies_only_top_level_calls_and_off_by_default() -> None:
+    class Outer:
+        def __init__(self) -> None:
+            self.child = _Point(1, 2)
+
+        def __deepcopy__(self, memo: Any) -> Any:
+            clone = Outer.__new__(Outer)
+            clone.child = copium.deepcopy(self.child, memo)
+            return clone
+
+    copied = copium.deepcopy(_Point(1, 2))
+    assert not hasattr(copied, "__copium__")
+    assert _in_new_thread(copium.extra.last_copy_info) is None
+
+    copium.config.apply(mark_copies=True)
+    copied = copium.deepcopy(Outer())
+    assert copium.extra.last_copy_info()["source_type"] is Outer
+    assert not hasattr(copied.child, "__copium__")
+
+
+# Construction side effects: copies are built with __new__ (via __reduce_ex__)
+# and never re-run __init__, __post_init__, __init_subclass__ or __set_name__.
+
+_REGISTRY: list[Any] = []
+
+
+class _RegisteringInit:
+    def __init__(self, payload):
+        self.payload = payload
+        _REGISTRY.append(self)
+
+
+class _RegisteringSlots:
+    __slots__ = ("payload", "__weakref__")
+
+    def __init__(self, payload):
+        self.payload = payload
+        _REGISTRY.append(self)
+
+
+@dataclass
+class _RegisteringPostInit:
+    payload: list
+
+    def __post_init__(self):
+        _REGISTRY.append(self)
+
+
+@dataclass
+class _RegisteringMarkedPostInit:
+    payload: list
+    cache: dict = field(default_factory=dict, metadata={"copium": "share"})
+
+    def __post_init__(self):
+        _REGISTRY.append(self)
+
+
+class _RegisteringDescriptor:
+    def __set_name__(self, owner, name):
+        _REGISTRY.append((owner, name))
+
+    def __get__(self, instance, owner=None):
+        return self if instance is None else instance.__dict__.get("described")
+
+    def __set__(self, instance, value):
+        instance.__dict__["described"] = value
+
+
+class _RegisteringBase:
+    def __init_subclass__(cls, **kwargs):
+        super().__init_subclass__(**kwargs)
+        _REGISTRY.append(cls)
+
+    def __init__(self, payload):
+        self.payload = payload
+        _REGISTRY.append(self)
+
+
+class _RegisteringChild(_RegisteringBase):
+    described = _RegisteringDescriptor()
+
+
+def _registering_instances() -> list[Any]:
+    child = _RegisteringChild([1])
+    child.described = [2]
+    return [
+        _RegisteringInit([1]),
+        _RegisteringSlots([1]),
+        _RegisteringPostInit([1]),
+        _RegisteringMarkedPostInit([1]),
+        child,
+    ]
+
+
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+def test_copying_does_not_rerun_construction_hooks(copy, operation: str) -> None:
+    instances = _registering_instances()
+    graph = [*instances, type(instances[-1]), {"nested": instances}]
+    _REGISTRY.clear()
+
+    copied = getattr(copy, operation)(graph)
+    for instance in instances:
+        getattr(copy, operation)(instance)
+
+    assert _REGISTRY == []
+    if operation == "deepcopy":
+        assert [type(item) for item in copied[:5]] == [type(item) for item in instances]
+        assert copied[4].described == [2] and copied[4].described is not instances[4].described
+        assert copied[5] is _RegisteringChild
+
+
+class _RegisteringCounter(collections.Counter):
+    def __init__(self, *args, **kwargs):
+        super().__init__(*args, **kwargs)
+        _REGISTRY.append(self)
+
+
+class _RegisteringOrderedDict(collections.OrderedDict):
+    def __init__(self, *args, **kwargs):
+        super().__init__(*args, **kwargs)
+        _REGISTRY.append(self)
+
+
+@pytest.mark.parametrize("operation", ["copy", "deepcopy"])
+@pytest.mark.parametrize("factory", [_RegisteringCounter, _RegisteringOrderedDict])
+def test_reduce_callables_run_as_often_as_stdlib(factory, operation: str) -> None:
+    # These reduce to (cls, args): stdlib calls the class, so __init__ does run.
+    def mutations(module) -> int:
+        original = factory(a=1)
+        _REGISTRY.clear()
+        copied = getattr(module, operation)(original)
+        assert type(copied) is factory and copied == original
+        return len(_REGISTRY)
+
+    assert mutations(copium) == mutations(stdlib_copy)
+
+
+class _ItemFailure(Exception):
+    pass
+
+
+class _FailsToCopy:
+    def __deepcopy__(self, memo: dict[int, Any]) -> Any:
+        raise _ItemFailure
+
+
+def _one_shot_items_class(slot: str, fail_at: int | None, fail_by: str) -> tuple[type, list[Any]]:
+    """A class reducing to one-shot listitems or dictitems, logging each step of its rebuild."""
+    log: list[Any] = []
+
+    def items() -> Any:
+        for i in range(5):
+            log.append(("yield", i))
+            if i == fail_at and fail_by == "iterator":
+                raise _ItemFailure
+            value = _FailsToCopy() if i == fail_at else i
+            yield value if slot == "listitems" else (f"k{i}", value)
+
+    class OneShotItems:
+        def __reduce_ex__(self, protocol: int) -> Any:
+            listitems = items() if slot == "listitems" else None
+            dictitems = items() if slot == "dictitems" else None
+            return (OneShotItems, (), {"state": True}, listitems, dictitems)
+
+        def __setstate__(self, state: dict[str, Any]) -> None:
+            log.append("state")
+
+        def append(self, item: Any) -> None:
+            log.append(("append", item))
+
+        def __setitem__(self, key: str, value: Any) -> None:
+            log.append(("setitem", key, value))
+
+    return OneShotItems, log
+
+
+@pytest.mark.parametrize("slot", ["listitems", "dictitems"])
+def test_reduce_items_are_applied_after_state_like_copy(slot: str) -> None:
+    def rebuild_log(module) -> list[Any]:
+        cls, log = _one_shot_items_class(slot, None, "item")
+        module.deepcopy(cls())
+        return log
+
+    log = rebuild_log(copium)
+
+    assert log == rebuild_log(stdlib_copy)
+    assert log[0] == "state"
+
+
+@pytest.mark.parametrize("fail_by", ["item", "iterator"])
+@pytest.mark.parametrize("slot", ["listitems", "dictitems"])
+def test_reduce_items_failure_stops_the_iterator(slot: str, fail_by: str) -> None:
+    def rebuild_log(module) -> list[Any]:
+        cls, log = _one_shot_items_class(slot, 2, fail_by)
+        with pytest.raises(_ItemFailure):
+            module.deepcopy([cls()])
+        return log
+
+    log = rebuild_log(copium)
+
+    assert log == rebuild_log(stdlib_copy)
+    assert log[-1] == ("yield", 2)
+
+
+@pytest.mark.parametrize("memo_factory", [dict, collections.UserDict], ids=["dict", "mapping"])
+@pytest.mark.parametrize("slot", ["listitems", "dictitems"])
+def test_reduce_items_failure_keeps_partial_object_out_of_memo(
+    slot: str, memo_factory: Callable[[], Any]
+) -> None:
+    cls, _ = _one_shot_items_class(slot, 2, "item")
+    original = cls()
+    memo = memo_factory()
+
+    with pytest.raises(_ItemFailure):
+        copium.deepcopy([original], memo)
+
+    assert id(original) not in memo
+    assert not any(isinstance(value, cls) for value in memo.values())
+
+
+def test_adeepcopy_lets_the_event_loop_run() -> None:
+    import asyncio
+
+    import copium.extra
+
+    payload = [bytearray(1 << 20) for _ in range(256)] + [[i] for i in range(10_000)]
+    started = time.perf_counter()
+    copium.deepcopy(payload)
+    blocking = time.perf_counter() - started
+
+    async def main():
+        ticks = []
+        done = False
+
+        async def ticker():
+            while not done:
+                ticks.append(time.perf_counter())
+                await asyncio.sleep(0)
+
+        task = asyncio.create_task(ticker())
+        await asyncio.sleep(0)
+        begin = time.perf_counter()
+        copied = await copium.extra.adeepcopy(payload)
+        end = time.perf_counter()
+        done = True
+        await task
+        points = [begin, *(tick for tick in ticks if begin < tick < end), end]
+        return copied, max(b - a for a, b in zip(points, points[1:]))
+
+    copied, longest_stall = asyncio.run(main())
+
+    assert copied == payload
+    assert copied[0] is not payload[0]
+    assert longest_stall < blocking / 4
+
+
+def test_adeepcopy_of_atomic_needs_no_worker() -> None:
+    import asyncio
+
+    import copium.extra
+
+    async def main():
+        future = copium.extra.adeepcopy("atomic")
+        assert future.done()
+        return await future
+
+    assert asyncio.run(main()) == "atomic"
+    with pytest.raises(RuntimeError):
+        copium.extra.adeepcopy([1])
+
+
+class _CopyProgress:
+    copied: ClassVar[int] = 0
+
+    def __deepcopy__(self, memo):
+        type(self).copied += 1
+        return _CopyProgress()
+
+
+def test_adeepcopy_cancellation_stops_the_copy() -> None:
+    import asyncio
+
+    import copium.extra
+
+    total = 200_000
+    payload = [[_CopyProgress()] for _ in range(total)]
+    _CopyProgress.copied = 0
+    copies = weakref.WeakSet()
+    original_deepcopy = _CopyProgress.__deepcopy__
+
+    def tracking_deepcopy(self, memo):
+        copy = original_deepcopy(self, memo)
+        copies.add(copy)
+        return copy
+
+    async def main():
+        future = copium.extra.adeepcopy(payload)
+        while not _CopyProgress.copied:
+            await asyncio.sleep(0.001)
+        future.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await future
+
+    _CopyProgress.__deepcopy__ = tracking_deepcopy
+    try:
+        # asyncio.run() waits for the default executor, so the worker has returned.
+        asyncio.run(main())
+    finally:
+        _CopyProgress.__deepcopy__ = original_deepcopy
+    gc.collect()
+
+    assert 0 < _CopyProgress.copied < total
+    assert len(copies) == 0
+
+
+class _IntSubclass(int):
+    pass
+
+
+class _StrSubclass(str):
+    pass
+
+
+class _BytesSubclass(bytes):
+    pass
+
+
+class _FloatSubclass(float):
+    pass
+
+
+class _ComplexSubclass(complex):
+    pass
+
+
+class _FrozensetSubclass(frozenset):
+    pass
+
+
+class _TupleSubclass(tuple):
+    pass
+
+
+class _ListSubclass(list):
+    pass
+
+
+class _DictSubclass(dict):
+    pass
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        _IntSubclass(7),
+        _StrSubclass("text"),
+        _BytesSubclass(b"raw"),
+        _FloatSubclass(1.5),
+        _ComplexSubclass(1, 2),
+        _FrozensetSubclass({1, (2, "three")}),
+        _TupleSubclass(([1], 2)),
+        _ListSubclass([[1], 2]),
+        _DictSubclass(key=[1]),
+    ],
+    ids=lambda value: type(value).__name__,
+)
+def test_builtin_subclasses_take_the_reduce_path(value) -> None:
+    value.extra = [3]
+    holder = [value, value]
+
+    expected = stdlib_copy.deepcopy(holder)
+    copied = copium.deepcopy(holder)
+
+    assert type(copied[0]) is type(value)
+    assert copied[0] is copied[1]
+    assert copied[0] is not value
+    assert copied[0] == expected[0] == value
+    assert copied[0].extra == [3] and copied[0].extra is not value.extra
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        _IntSubclass(7),
+        _StrSubclass("text"),
+        _BytesSubclass(b"raw"),
+        _FloatSubclass(1.5),
+        _ComplexSubclass(1, 2),
+        _FrozensetSubclass({1, (2, "three")}),
+    ],
+    ids=lambda value: type(value).__name__,
+)
+def test_immutable_builtin_subclasses_are_copied_not_returned(copy, value) -> None:
+    # Their bases are returned as-is; a subclass instance may carry state, so
+    # stdlib reduces it, and exactness must come from more than a subclass flag.
+    value.extra = [3]
+
+    shallow = copy.copy(value)
+    deep = copy.deepcopy(value)
+
+    assert type(shallow) is type(deep) is type(value)
+    assert shallow is not value and deep is not value
+    assert shallow == deep == value
+    assert shallow.extra is value.extra
+    assert deep.extra == [3] and deep.extra is not value.extra
+
+
+@pytest.mark.parametrize("base", [set, frozenset])
+def test_set_subclasses_keep_type_attributes_and_aliasing(copy, base) -> None:
+    class Tags(base):
+        pass
+
+    class LabeledTags(base):
+        def __new__(cls, items=(), label="copied"):
+            instance = super().__new__(cls, items)
+            instance.label = label
+            return instance
+
+        def __init__(self, items=(), label="copied"):
+            if base is set:
+                super().__init__(items)
+
+    member = _Plain()
+    for original in [Tags([member, 1]), LabeledTags([member, 1], label="original")]:
+        original.extra = [3]
+        copied, copied_member, again = copy.deepcopy([original, member, original])
+
+        assert type(copied) is type(original)
+        assert again is copied
+        assert copied_member in copied and member not in copied
+        assert len(copied) == 2 and 1 in copied
+        assert copied.extra == [3] and copied.extra is not original.extra
+        if isinstance(original, LabeledTags):
+            assert copied.label == "original"
+
+
+class _Ordered(collections.OrderedDict):
+    pass
+
+
+@pytest.mark.parametrize("factory", [collections.OrderedDict, _Ordered])
+def test_ordered_dict_deepcopy_keeps_order_type_cycles_and_state(factory) -> None:
+    shared = [1]
+    original = factory(b=[shared], a={"nested": shared})
+    original["self"] = original
+    original.move_to_end("b")
+    original.extra = [3]
+
+    expected = stdlib_copy.deepcopy(original)
+    copied = copium.deepcopy(original)
+
+    assert type(copied) is type(expected) is factory
+    assert list(copied) == list(expected) == ["a", "self", "b"]
+    assert copied["self"] is copied
+    assert copied["a"]["nested"] is copied["b"][0] and copied["b"][0] is not shared
+    assert copied["a"] == original["a"] and copied["b"] == original["b"]
+    assert copied.extra == expected.extra == [3] and copied.extra is not original.extra
+
+
+class _Defaulting(collections.defaultdict):
+    pass
+
+
+class _Missing(collections.defaultdict):
+    def __missing__(self, key):
+        return f"missing {key}"
+
+
+@pytest.mark.parametrize("factory", [collections.defaultdict, _Defaulting, _Missing])
+@pytest.mark.parametrize("default_factory", [list, None], ids=["list", "none"])
+def test_defaultdict_deepcopy_keeps_type_factory_and_cycles(factory, default_factory) -> None:
+    shared = [1]
+    original = factory(default_factory, b=[shared], a={"nested": shared})
+    original["self"] = original
+
+    expected = stdlib_copy.deepcopy(original)
+    copied = copium.deepcopy(original)
+
+    assert type(copied) is type(expected) is factory
+    assert copied.default_factory is expected.default_factory is default_factory
+    assert list(copied) == list(expected) == ["b", "a", "self"]
+    assert copied["self"] is copied
+    assert copied["a"]["nested"] is copied["b"][0] and copied["b"][0] is not shared
+    if factory is _Missing:
+        assert copied["unknown"] == "missing unknown"
+    elif default_factory is list:
+        assert copied["unknown"] == [] and "unknown" not in original
+    else:
+        with pytest.raises(KeyError):
+            copied["unknown"]
+
+
+def test_defaultdict_factory_is_copied_by_the_usual_rules() -> None:
+    class Factory:
+        def __init__(self) -> None:
+            self.calls = []
+
+        def __call__(self) -> list:
+            self.calls.append(None)
+            return []
+
+    factory = Factory()
+    original = collections.defaultdict(factory, {"factory": factory})
+
+    copied = copium.deepcopy(original)
+
+    assert copied.default_factory is not factory
+    assert copied["factory"] is copied.default_factory
+    assert copied["new"] == [] and copied.default_factory.calls == [None]
+    assert factory.calls == []
+
+
+class _Queue(collections.deque):
+    pass
+
+
+@pytest.mark.parametrize("factory", [collections.deque, _Queue])
+@pytest.mark.parametrize("maxlen", [None, 0, 3, 10])
+def test_deque_deepcopy_keeps_type_maxlen_order_and_cycles(factory, maxlen) -> None:
+    shared = [1]
+    original = factory([[shared], {"nested": shared}, "tail"], maxlen=maxlen)
+    original.append(original)
+    if factory is _Queue:
+        original.extra = [3]
+
+    expected = stdlib_copy.deepcopy(original)
+    copied = copium.deepcopy(original)
+
+    assert type(copied) is type(expected) is factory
+    assert copied.maxlen == expected.maxlen == maxlen
+    assert len(copied) == len(expected) == len(original)
+    assert [type(item) for item in copied] == [type(item) for item in expected]
+    if original:
+        assert copied[-1] is copied
+    if len(original) == 4:
+        assert copied[0][0] is copied[1]["nested"] and copied[0][0] is not shared
+    if factory is _Queue:
+        assert copied.extra == [3] and copied.extra is not original.extra
+
+
+def test_deque_subclass_deepcopy_hook_is_used(copy) -> None:
+    class Hooked(collections.deque):
+        def __deepcopy__(self, memo):
+            return "hooked"
+
+    assert copy.deepcopy([Hooked([1])]) == ["hooked"]
+
+
+@pytest.mark.parametrize("base", [list, set, frozenset, collections.deque])
+def test_container_subclass_overrides_added_later_are_honored(copy, base) -> None:
+    class Subclass(base):
+        pass
+
+    original = Subclass([1, 2])
+    assert type(copy.deepcopy(original)) is Subclass
+
+    Subclass.__reduce_ex__ = lambda self, protocol: (tuple, (sorted(self),))
+    assert copy.deepcopy(original) == (1, 2)
+
+
+_EPOCH = datetime.datetime(2000, 1, 1)
+
+
+def test_copies_of_untrackable_containers_are_untracked() -> None:
+    # datetime is not a GC type but is copied to a new object, so the copied
+    # tuples are new too and hold nothing the collector could follow.
+    record = (_EPOCH, datetime.timedelta(seconds=1), "label")
+    original = {"at": record, "nested": (record, (record,)), "count": 1}
+
+    copied = copium.deepcopy(original)
+
+    assert copied == original
+    assert copied["at"] is not record
+    assert not gc.is_tracked(copied["at"])
+    assert not gc.is_tracked(copied["nested"])
+    assert not gc.is_tracked(copied)
+
+
+def test_copies_holding_trackable_items_stay_tracked() -> None:
+    container = {}
+    copied = copium.deepcopy([(_EPOCH, []), (_EPOCH, container), {"key": (_EPOCH, [])}])
+
+    assert gc.is_tracked(copied[0])
+    # An empty dict is untracked but can become tracked later, so CPython keeps
+    # tuples holding one tracked; the copy must not be more eager than that.
+    assert not gc.is_tracked(copied[1][1])
+    assert gc.is_tracked(copied[1])
+    assert gc.is_tracked(copied[2])
+
+
+@pytest.mark.skipif(sys.version_info < (3, 13), reason="copy.replace() is new in 3.13")
+def test_replace_propagates_unexpected_lookup_errors(copy) -> None:
+    class Meta(type):
+        def __getattribute__(cls, name):
+            if name == "__replace__":
+                raise ValueError("lookup failed")
+            return super().__getattribute__(name)
+
+    class Record(metaclass=Meta):
+        pass
+
+    with pytest.raises(ValueError, match="lookup failed"):
+        copy.replace(Record())
+
+
+def _unreducible_class() -> tuple[type, list[int]]:
+    calls: list[int] = []
+
+    class Unreducible:
+        def __reduce_ex__(self, protocol: int) -> Any:
+            calls.append(protocol)
+            raise TypeError("cannot pickle 'Unreducible' object")
+
+    return Unreducible, calls
+
+
+def test_failed_reduce_is_not_retried_for_the_same_type() -> None:
+    cls, calls = _unreducible_class()
+    originals = [cls() for _ in range(5)]
+    copium.config.apply(negative_cache=True)
+
+    for _ in range(3):
+        with pytest.raises(TypeError, match="cannot pickle 'Unreducible' object") as excinfo:
+            copium.deepcopy(originals)
+
+    assert len(calls) == 1
+    if sys.version_info >= (3, 11):
+        assert any("cached failure" in note for note in excinfo.value.__notes__)
+
+    cls.__reduce_ex__ = object.__reduce_ex__
+    copied = copium.deepcopy(originals)
+
+    assert [type(value) for value in copied] == [cls] * 5
+    assert len(calls) == 1
+
+
+def test_failed_reduce_is_retried_by_default() -> None:
+    cls, calls = _unreducible_class()
+
+    for _ in range(3):
+        with pytest.raises(TypeError, match="cannot pickle 'Unreducible' object") as excinfo:
+            copium.deepcopy([cls(), cls()])
+
+    assert len(calls) == 3
+    assert not getattr(excinfo.value, "__notes__", [])
+
+
+class _ReducibleWhenOk:
+    def __init__(self, ok: bool) -> None:
+        self.ok = ok
+
+    def __reduce_ex__(self, protocol: int) -> Any:
+        if not self.ok:
+            raise ValueError("not reducible in this state")
+        return _ReducibleWhenOk, (self.ok,)
+
+
+def test_instance_dependent_reduce_failure_does_not_fail_other_instances(copy) -> None:
+    with pytest.raises(ValueError, match="not reducible in this state"):
+        copy.deepcopy(_ReducibleWhenOk(False))
+
+    copied = copy.deepcopy(_ReducibleWhenOk(True))
+
+    assert type(copied) is _ReducibleWhenOk and copied.ok is True
+
+
+def _fresh_classes(default_tag: int) -> list[type]:
+    @dataclass
+    class Point:
+        x: list
+        tag: int = default_tag
+
+    class Items(list):
+        pass
+
+    class Members(set):
+        pass
+
+    return [Point, Items, Members]
+
+
+def _instance_of(cls: type, tag: int) -> Any:
+    if issubclass(cls, list):
+        return cls([tag, [tag]])
+    if issubclass(cls, set):
+        return cls({tag})
+    return cls([tag])
+
+
+def test_clear_caches_releases_deleted_classes() -> None:
+    classes = _fresh_classes(0)
+    for cls in classes:
+        copium.deepcopy(_instance_of(cls, 0))
+    unreducible, _ = _unreducible_class()
+    copium.config.apply(negative_cache=True)
+    with pytest.raises(TypeError):
+        copium.deepcopy(unreducible())
+    refs = [weakref.ref(cls) for cls in [*classes, unreducible]]
+
+    del classes, cls, unreducible
+    copium.extra.clear_caches()
+    gc.collect()
+
+    assert [ref() for ref in refs] == [None] * len(refs)
+
+
+def test_classes_created_after_others_died_are_not_misdispatched() -> None:
+    for tag in range(200):
+        classes = _fresh_classes(tag)
+        for cls in classes:
+            original = _instance_of(cls, tag)
+            copied = copium.deepcopy(original)
+
+            assert type(copied) is cls
+            assert copied == original
+        if tag % 50 == 0:
+            copium.extra.clear_caches()
+        del classes, cls, original, copied
+        gc.collect()
+
+
+class _LateRegistered:
+    def __init__(self, value: str) -> None:
+        self.value = value
+
+
+def _reduce_registered(obj: _LateRegistered) -> Any:
+    return _LateRegistered, ("registered",)
+
+
+@pytest.fixture
+def _late_registered() -> Generator[None, None, None]:
+    yield
+    copyreg.dispatch_table.pop(_LateRegistered, None)
+
+
+@pytest.mark.usefixtures("_late_registered")
+def test_dispatch_table_changes_apply_within_the_same_call(copy) -> None:
+    class Registers:
+        def __reduce__(self) -> Any:
+            copyreg.pickle(_LateRegistered, _reduce_registered)
+            return Registers, ()
+
+    class Unregisters:
+        def __reduce__(self) -> Any:
+            del copyreg.dispatch_table[_LateRegistered]
+            return Unregisters, ()
+
+    originals = [
+        _LateRegistered("a"),
+        Registers(),
+        _LateRegistered("b"),
+        Unregisters(),
+        _LateRegistered("c"),
+    ]
+
+    copied = copy.deepcopy(originals)
+
+    assert [copied[i].value for i in (0, 2, 4)] == ["a", "registered", "c"]
+
+
+@pytest.mark.usefixtures("_late_registered")
+def test_dispatch_table_entry_takes_over_a_cached_reduce_failure() -> None:
+    def refuse(self: _LateRegistered, protocol: int) -> Any:
+        raise TypeError("not today")
+
+    _LateRegistered.__reduce_ex__ = refuse
+    try:
+        with pytest.raises(TypeError, match="not today"):
+            copium.deepcopy(_LateRegistered("a"))
+
+        copyreg.pickle(_LateRegistered, _reduce_registered)
+
+        assert copium.deepcopy(_LateRegistered("a")).value == "registered"
+    finally:
+        del _LateRegistered.__reduce_ex__
@@ -0,0 +1,111 @@
+"""
+Garbage collector interplay: copies made while the collector runs after every
+allocation, containers CPython untracks (dicts and tuples of atomics) getting
+tracked once copied children go in, and no garbage left behind by copium's own
+memo and keepalive bookkeeping.
+"""
+
+from __future__ import annotations
+
+import copy as stdlib_copy
+import gc
+import weakref
+from collections.abc import Callable
+from collections.abc import Generator
+from typing import Any
+
+import pytest
+
+import copium
+from datamodelzoo import Case
+from tests.conftest import CASE_PARAMS
+
+
+@pytest.fixture
+def gc_stress() -> Generator[None, None, None]:
+    thresholds = gc.get_threshold()
+    gc.collect()
+    gc.set_threshold(1, 1, 1)
+    try:
+        yield
+    finally:
+        gc.set_threshold(*thresholds)
+    gc.collect()
+    uncollectable = list(gc.garbage)
+    gc.garbage.clear()
+    assert uncollectable == []
+
+
+def _garbage_left_by(deepcopy: Callable[[Any], Any], obj: Any) -> list[str] | None:
+    """Type names of what collecting a deleted copy of obj found, or None if copying raised."""
+    gc.collect()
+    gc.set_debug(gc.DEBUG_SAVEALL)
+    try:
+        try:
+            copied = deepcopy(obj)
+        except Exception:
+            return None
+        del copied
+        gc.collect()
+        return sorted(type(garbage).__qualname__ for garbage in gc.garbage)
+    finally:
+        gc.set_debug(0)
+        gc.garbage.clear()
+
+
+@pytest.mark.parametrize("case", CASE_PARAMS)
+def test_deepcopy_under_gc_stress(case: Case, gc_stress: None) -> None:
+    obj = case.obj
+    try:
+        expected = stdlib_copy.deepcopy(obj)
+    except Exception as error:
+        with pytest.raises(type(error)):
+            copium.deepcopy(obj)
+    else:
+        assert type(copium.deepcopy(obj)) is type(expected)
+
+
+@pytest.mark.parametrize("case", CASE_PARAMS)
+def test_deepcopy_leaves_no_more_cyclic_garbage_than_stdlib(case: Case) -> None:
+    obj = case.obj
+    expected = _garbage_left_by(stdlib_copy.deepcopy, obj)
+    if expected is None:
+        pytest.skip("stdlib can't copy this case")
+
+    actual = _garbage_left_by(copium.deepcopy, obj)
+
+    assert actual is not None
+    assert len(actual) <= len(expected), actual
+
+
+class _Leaf:
+    pass
+
+
+def test_copies_of_untracked_containers_are_tracked_once_they_hold_containers(
+    gc_stress: None,
+) -> None:
+    # Whether CPython untracks these depends on the version; their copies
+    # must end up tracked wherever they hold something that is.
+    atomics_only = {"a": 1, "b": (2, "three")}
+    gc.collect()
+
+    copied = copium.deepcopy({"plain": atomics_only, "with_list": ({"k": 1}, [1])})
+
+    assert copied["plain"] == atomics_only
+    assert gc.is_tracked(copied)
+    assert gc.is_tracked(copied["with_list"])
+
+
+def test_cycles_through_copied_dicts_and_tuples_are_collected(gc_stress: None) -> None:
+    original: dict[str, Any] = {"leaf": _Leaf()}
+    holder = [original]
+    original["cycle"] = (holder,)
+
+    copied = copium.deepcopy(original)
+    leaf = weakref.ref(copied["leaf"])
+    assert copied["cycle"][0][0] is copied
+
+    del copied
+    gc.collect()
+    assert leaf() is None
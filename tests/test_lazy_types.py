@@ -0,0 +1,160 @@
+# SPDX-FileCopyrightText: 2025-present Arseny Boykov (Bobronium) <hi@bobronium.me>
+#
+# SPDX-License-Identifier: MIT
+"""
+Lazily resolved atomic types (decimal.Decimal, fractions.Fraction).
+
+Each test runs in a fresh interpreter: resolution happens once per process, on
+the first deepcopy of an instance, so in-process tests would observe leftovers.
+Fraction is used as the probe because it is a Python class whose __deepcopy__
+can be replaced to tell "treated as atomic" apart from "went through __deepcopy__".
+"""
+
+import pytest
+
+
+@pytest.mark.subprocess
+def test_import_does_not_import_registered_modules():
+    import sys
+
+    before = {name for name in ("decimal", "fractions") if name in sys.modules}
+
+    import copium
+
+    assert copium.deepcopy([1]) == [1]
+    after = {name for name in ("decimal", "fractions") if name in sys.modules}
+    assert after == before
+
+
+@pytest.mark.subprocess
+def test_resolves_on_first_use():
+    import fractions
+
+    import copium
+
+    calls = []
+    fractions.Fraction.__deepcopy__ = lambda self, memo: calls.append(self) or self
+
+    value = fractions.Fraction(1, 3)
+    assert copium.deepcopy(value) is value
+    assert copium.deepcopy([value, value]) == [value, value]
+    assert calls == []
+
+
+@pytest.mark.subprocess
+def test_unavailable_module_stays_pending():
+    import fractions
+    import sys
+
+    import copium
+
+    calls = []
+    fractions.Fraction.__deepcopy__ = lambda self, memo: calls.append(self) or self
+    value = fractions.Fraction(1, 3)
+
+    module = sys.modules.pop("fractions")
+    try:
+        assert copium.deepcopy(value) is value
+    finally:
+        sys.modules["fractions"] = module
+    assert calls == [value]
+
+    assert copium.deepcopy(value) is value
+    assert calls == [value]
+
+
+@pytest.mark.subprocess
+def test_lookalike_type_is_not_atomic():
+    import fractions
+
+    import copium
+
+    class Fraction:
+        pass
+
+    Fraction.__module__ = "fractions"
+    Fraction.__qualname__ = "Fraction"
+
+    before = Fraction()
+    assert copium.deepcopy(before) is not before
+
+    value = fractions.Fraction(1, 3)
+    assert copium.deepcopy(value) is value
+
+    after = Fraction()
+    assert copium.deepcopy(after) is not after
+
+
+@pytest.mark.subprocess
+def test_concurrent_first_use():
+    import fractions
+    import threading
+
+    import copium
+
+    calls = []
+    fractions.Fraction.__deepcopy__ = lambda self, memo: calls.append(self) or self
+
+    value = fractions.Fraction(1, 3)
+    threads_count = 8
+    barrier = threading.Barrier(threads_count)
+    results = []
+    errors = []
+
+    def worker():
+        try:
+            barrier.wait()
+            results.append(copium.deepcopy(value))
+        except BaseException as error:
+            errors.append(error)
+
+    threads = [threading.Thread(target=worker) for _ in range(threads_count)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert errors == []
+    assert len(results) == threads_count
+    assert all(result is value for result in results)
+    assert calls == []
+
+
+@pytest.mark.subprocess
+def test_counter_fast_path_resolves_after_atomic_miss():
+    import collections
+
+    import copium
+
+    calls = []
+    collections.Counter.__reduce__ = lambda self: calls.append(self) or (dict, ())
+
+    value = collections.Counter(a=1)
+    copied = copium.deepcopy(value)
+
+    assert type(copied) is collections.Counter
+    assert copied == value
+    assert calls == []
+
+
+@pytest.mark.subprocess
+def test_unknown_type_is_looked_up_once():
+    import copium
+
+    lookups = []
+
+    class Meta(type):
+        @property
+        def __module__(cls):
+            lookups.append(cls)
+            return "tests"
+
+    class Plain(metaclass=Meta):
+        pass
+
+    copium.deepcopy(Plain())
+    looked_up = len(lookups)
+    assert looked_up > 0
+
+    copium.deepcopy([Plain() for _ in range(100)])
+    assert len(lookups) == looked_up
@@ -16,14 +16,19 @@ Strategy: two-phase observation.
             via ctypes using PyMemoObject's #[repr(C)] layout.
 """
 
+import collections.abc
+import copy as stdlib_copy
 import ctypes
 import gc
 import struct
 import sys
+import threading
+import weakref
 
 import pytest
 
 import copium
+import copium.extra
 
 MEMO_RETAIN_MAX_SLOTS = 1 << 17
 EXPECTED_SHRINK_SIZE = 1 << 13
@@ -32,16 +37,17 @@ _PTR = struct.calcsize("P")
 
 # PyMemoObject is #[repr(C)]:
 #   PyObject      ob_refcnt  ob_type
-#   MemoTable     slots      size     used     filled
+#   MemoTable     slots      size     used     filled   order (Vec internals)
 #   KeepaliveVec  (Vec internals)
 #   UndoLog       (Vec internals)
-#   dict_proxy
+#   dict_proxy  dict_view
 #
-# MemoTable fields are all pointer-sized — no padding, no reorder benefit.
+# MemoTable is #[repr(C)] too, so its leading fields keep their offsets.
 _OFF_REFCNT = 0
 _OFF_TABLE_SLOTS = 2 * _PTR
 _OFF_TABLE_SIZE = 3 * _PTR
 _OFF_TABLE_USED = 4 * _PTR
+_OFF_TABLE_FILLED = 5 * _PTR
 
 
 def _usize_at(addr, offset):
@@ -171,6 +177,253 @@ class TestTableRetention:
 
         assert table_size == EXPECTED_SHRINK_SIZE
 
+    def test_shrink_releases_dropped_entries(self):
+        """Copies referenced only by the discarded oversized table are freed."""
+        N = 200_000
+
+        class Node:
+            pass
+
+        originals = [Node() for _ in range(N)]
+        copies = copium.deepcopy(originals)
+        refs = [weakref.ref(copies[0]), weakref.ref(copies[-1])]
+        del copies
+        gc.collect()
+
+        assert [ref() for ref in refs] == [None, None]
+        assert sys.getrefcount(originals[0]) == sys.getrefcount(originals[-1])
+
+        memo = _capture_tss_after(lambda: None)
+        assert _usize_at(id(memo), _OFF_TABLE_SIZE) == EXPECTED_SHRINK_SIZE
+
+
+@needs_64bit
+class TestTableGrowth:
+    def test_resizes_keep_counts_and_references(self):
+        _, memo = copium.extra.deepcopy_with_memo([])
+        reason = _check_offset_model(memo)
+        if reason:
+            pytest.skip(f"ctypes offset model invalid: {reason}")
+        addr = id(memo)
+        baseline = _usize_at(addr, _OFF_TABLE_USED)
+
+        keys = [object() for _ in range(5_000)]
+        values = [object() for _ in range(5_000)]
+        before = [sys.getrefcount(value) for value in values]
+        sizes = set()
+        live = 0
+        for i, (key, value) in enumerate(zip(keys, values)):
+            memo[id(key)] = value
+            live += 1
+            if i % 3 == 0:
+                del memo[id(key)]
+                live -= 1
+
+            size = _usize_at(addr, _OFF_TABLE_SIZE)
+            used = _usize_at(addr, _OFF_TABLE_USED)
+            filled = _usize_at(addr, _OFF_TABLE_FILLED)
+            sizes.add(size)
+            assert used == baseline + live
+            assert used <= filled < size * 7 // 10 + 1
+
+        assert len(sizes) >= 5
+        kept = [i % 3 != 0 for i in range(len(keys))]
+        assert [id(key) in memo for key in keys] == kept
+        assert [sys.getrefcount(value) for value in values] == [
+            count + held for count, held in zip(before, kept)
+        ]
+
+        memo.clear()
+        assert [sys.getrefcount(value) for value in values] == before
+        assert _usize_at(addr, _OFF_TABLE_USED) == _usize_at(addr, _OFF_TABLE_FILLED) == 0
+
+
+class _SizeofSpy:
+    def __init__(self):
+        self.size = None
+
+    def __deepcopy__(self, memo):
+        self.size = sys.getsizeof(memo)
+        return self
+
+
+class TestMemoStats:
+    def test_highwater_reflects_large_copy(self):
+        N = 200_000
+        copium.extra.reset_memo_stats()
+
+        copium.deepcopy([[] for _ in range(N)])
+        stats = copium.extra.memo_stats(include_highwater=True)
+
+        assert stats["max_used"] == N + 1
+        assert stats["max_keepalive"] == N + 1
+        assert stats["max_slots"] > stats["max_used"]
+        assert stats["slots"] == EXPECTED_SHRINK_SIZE
+
+    def test_highwater_keeps_maximum_until_reset(self):
+        copium.extra.reset_memo_stats()
+        copium.deepcopy([[] for _ in range(1_000)])
+        copium.deepcopy([[]])
+
+        assert copium.extra.memo_stats(include_highwater=True)["max_used"] == 1_001
+
+        copium.extra.reset_memo_stats()
+        stats = copium.extra.memo_stats(include_highwater=True)
+        assert (stats["max_used"], stats["max_slots"], stats["max_keepalive"]) == (0, 0, 0)
+
+        copium.deepcopy([[]])
+        assert copium.extra.memo_stats(include_highwater=True)["max_used"] == 2
+
+    def test_highwater_is_opt_in(self):
+        assert set(copium.extra.memo_stats()) == {"slots", "keepalive", "bytes"}
+
+    def test_retained_bytes_cover_retained_slots(self):
+        copium.deepcopy([[] for _ in range(200_000)])
+        stats = copium.extra.memo_stats()
+
+        assert stats["bytes"] >= stats["slots"] * 2 * _PTR
+
+    def test_sizeof_includes_native_table(self):
+        small, large = _SizeofSpy(), _SizeofSpy()
+        copium.deepcopy([[], small])
+        copium.deepcopy([[] for _ in range(100_000)] + [large])
+
+        assert large.size >= 100_000 * 2 * _PTR
+        assert small.size < large.size // 10
+
+
+class _Node:
+    pass
+
+
+class TestMemoLimits:
+    def test_memo_limit_fails_the_copy_and_releases_everything(self):
+        nodes = [_Node() for _ in range(100)]
+        refs = [weakref.ref(node) for node in nodes]
+        copium.config.apply(max_memo_entries=10)
+        copium.extra.reset_memo_stats()
+
+        with pytest.raises(copium.CopyError, match=r"memo limit exceeded \(10 entries\)"):
+            copium.deepcopy(nodes)
+
+        assert copium.extra.memo_stats(include_highwater=True)["max_used"] == 10
+        del nodes
+        gc.collect()
+        assert all(ref() is None for ref in refs)
+
+    def test_keepalive_limit_fails_the_copy(self):
+        copium.config.apply(max_keepalive=10)
+
+        with pytest.raises(copium.CopyError, match=r"keepalive limit exceeded \(10 entries\)"):
+            copium.deepcopy([[] for _ in range(100)])
+
+    def test_limits_leave_smaller_copies_alone(self):
+        copium.config.apply(max_memo_entries=10, max_keepalive=10)
+
+        assert copium.deepcopy([[] for _ in range(8)]) == [[] for _ in range(8)]
+
+        copium.config.apply(max_memo_entries=0, max_keepalive=0)
+        assert len(copium.deepcopy([[] for _ in range(100)])) == 100
+
+
+class _StarvedMemo(collections.abc.MutableMapping):
+    """A memo whose inserts run out of memory after ``capacity`` of them."""
+
+    def __init__(self, capacity):
+        self.entries = {}
+        self.capacity = capacity
+
+    def __getitem__(self, key):
+        return self.entries[key]
+
+    def __setitem__(self, key, value):
+        if len(self.entries) >= self.capacity:
+            raise MemoryError
+        self.entries[key] = value
+
+    def __delitem__(self, key):
+        del self.entries[key]
+
+    def __iter__(self):
+        return iter(self.entries)
+
+    def __len__(self):
+        return len(self.entries)
+
+
+class _StarvedCustomMemo(copium.extra.CustomMemo):
+    def __init__(self, capacity):
+        self.entries = {}
+        self.discarded = []
+        self.capacity = capacity
+
+    def lookup(self, key):
+        return self.entries.get(key)
+
+    def insert(self, key, value):
+        if len(self.entries) >= self.capacity:
+            raise MemoryError
+        self.entries[key] = value
+
+    def keepalive(self, obj):
+        pass
+
+    def discard(self, key):
+        self.discarded.append(key)
+        self.entries.pop(key, None)
+
+
+class TestMemoryPressure:
+    @pytest.mark.parametrize("module", [copium, stdlib_copy], ids=["copium", "stdlib"])
+    def test_failed_user_memo_insert_aborts_the_copy(self, module):
+        nodes = [[_Node()] for _ in range(20)]
+
+        with pytest.raises(MemoryError):
+            module.deepcopy(nodes, _StarvedMemo(capacity=5))
+
+        copium._debug.assert_idle()
+        assert len(copium.deepcopy(nodes)) == 20
+
+    def test_failed_custom_memo_insert_aborts_and_discards(self):
+        nodes = [[_Node()] for _ in range(20)]
+        memo = _StarvedCustomMemo(capacity=5)
+
+        with pytest.raises(MemoryError):
+            copium.deepcopy(nodes, memo)
+
+        assert id(nodes) in memo.discarded
+        copium._debug.assert_idle()
+
+
+@pytest.mark.skipif(sys.platform != "linux", reason="needs RLIMIT_AS and /proc")
+@pytest.mark.subprocess
+def test_running_out_of_address_space_raises_memory_error():
+    import resource
+
+    import copium
+
+    original = [[i] for i in range(100_000)]
+    copium.deepcopy(original)
+
+    with open("/proc/self/status") as status:
+        vm_size = next(int(line.split()[1]) * 1024 for line in status if line.startswith("VmSize:"))
+    _, hard = resource.getrlimit(resource.RLIMIT_AS)
+    resource.setrlimit(resource.RLIMIT_AS, (vm_size + 64 * 1024 * 1024, hard))
+
+    held = []
+    try:
+        for _ in range(1_000):
+            held.append(copium.deepcopy(original))
+    except MemoryError:
+        pass
+    else:
+        raise AssertionError("the copies never ran out of memory")
+
+    held.clear()
+    # Whatever failed was unwound: the next copy starts from an idle memo.
+    copium._debug.assert_idle()
+    assert copium.deepcopy(original) == original
+
 
 class TestTSSLifecycle:
     def test_reused_when_not_borrowed(self):
@@ -215,3 +468,103 @@ class TestTSSLifecycle:
 
         assert observed is not None
         assert observed < 10, f"stale entries from previous call: len={observed}"
+
+
+def _run_threads(target, count=100):
+    for _ in range(count):
+        thread = threading.Thread(target=target)
+        thread.start()
+        thread.join()
+    gc.collect()
+
+
+def _resident_mb():
+    with open("/proc/self/statm") as statm:
+        return int(statm.read().split()[1]) * 4096 // (1 << 20)
+
+
+class TestThreadExit:
+    """Each thread's memo is freed with the thread, not kept for the process."""
+
+    def test_memo_objects_are_released(self):
+        payload = [[] for _ in range(100)]
+        _run_threads(lambda: None, count=10)
+
+        before = sys.getallocatedblocks()
+        _run_threads(lambda: None)
+        idle = sys.getallocatedblocks() - before
+
+        before = sys.getallocatedblocks()
+        _run_threads(lambda: copium.deepcopy(payload))
+        copying = sys.getallocatedblocks() - before
+
+        assert copying - idle < 50
+
+    @pytest.mark.skipif(not sys.platform.startswith("linux"), reason="reads /proc/self/statm")
+    def test_retained_tables_are_released(self):
+        # Each thread grows its table to the largest size kept between calls:
+        # 2 MiB of slots per thread if they outlived it.
+        payload = [[] for _ in range(50_000)]
+        _run_threads(lambda: copium.deepcopy(payload), count=5)
+
+        before = _resident_mb()
+        _run_threads(lambda: copium.deepcopy(payload))
+
+        assert _resident_mb() - before < 50
+
+
+class TestDebugIntrospection:
+    """copium._debug reports what the thread's memo holds between calls."""
+
+    def test_idle_after_copies(self):
+        copium.deepcopy([[1], {"a": [2]}, (3, [4])])
+        assert copium._debug.keepalive_contents() == []
+        copium._debug.assert_idle()
+
+    def test_detects_simulated_leak(self):
+        leaked = ["giant"]
+        copium._debug.simulate_leak(leaked)
+
+        contents = copium._debug.keepalive_contents()
+        assert contents == [("list", id(leaked), sys.getrefcount(leaked) - 1)]
+        with pytest.raises(AssertionError, match=r"retains 1 objects on this thread: \[\('list'"):
+            copium._debug.assert_idle()
+
+        copium.deepcopy([])
+        copium._debug.assert_idle()
+        assert sys.getrefcount(leaked) == 2
+
+    def test_sees_live_entries_during_a_copy(self):
+        seen = []
+
+        class Probe:
+            def __deepcopy__(self, memo):
+                seen.append(copium._debug.keepalive_contents())
+                with pytest.raises(AssertionError, match="a deepcopy\\(\\) is running"):
+                    copium._debug.assert_idle()
+                with pytest.raises(RuntimeError, match="memo is in use"):
+                    copium._debug.simulate_leak(self)
+                return self
+
+        inner = [1]
+        outer = [inner, Probe()]
+        copium.deepcopy(outer)
+
+        kept = {object_id for _, object_id, _ in seen[0]}
+        assert {id(outer), id(inner)} <= kept
+        assert len(seen[0]) == 4  # both originals, then both copies
+        copium._debug.assert_idle()
+
+    def test_is_per_thread(self):
+        leaked = object()
+        copium._debug.simulate_leak(leaked)
+
+        other = []
+        thread = threading.Thread(target=lambda: other.append(copium._debug.keepalive_contents()))
+        thread.start()
+        thread.join()
+
+        assert other == [[]]
+        assert len(copium._debug.keepalive_contents()) == 1
+        copium.deepcopy([])
+        copium._debug.assert_idle()
@@ -1,6 +1,14 @@
 import copy
 import copy as stdlib_copy
+import inspect
+import os
+import subprocess
 import sys
+import threading
+import typing
+import warnings
+
+import pytest
 
 import copium.patch
 
@@ -71,6 +79,20 @@ def test_no_copium_attributes_after_disable():
     fn = copy.deepcopy
     assert not hasattr(fn, "__copium_original__")
     assert not hasattr(fn, "__wrapped__")
+    assert not hasattr(fn, "__signature__")
+
+
+def test_patched_deepcopy_keeps_stdlib_signature():
+    copium.patch.disable()
+    expected = inspect.signature(copy.deepcopy)
+
+    copium.patch.enable()
+    try:
+        assert copy.deepcopy.__wrapped__ is copium.deepcopy
+        assert inspect.signature(copy.deepcopy) == expected
+        assert typing.get_type_hints(copy.deepcopy) == {}
+    finally:
+        copium.patch.disable()
 
 
 def test_idempotent_enable():
@@ -86,3 +108,347 @@ def test_idempotent_disable():
     assert copium.patch.disable() is True
     assert copium.patch.disable() is False
     assert copium.patch.enabled() is False
+
+
+def test_toggle_while_deepcopying_from_other_threads():
+    """
+    Every call must land either in stdlib or in copium while patch state flips under it.
+    """
+
+    class MemoKind:
+        def __deepcopy__(self, memo):
+            return type(memo).__name__
+
+    payload = {"kind": MemoKind(), "data": [1, [2, 3]]}
+    stop = threading.Event()
+    seen: set[str] = set()
+    errors: list[BaseException] = []
+
+    def hammer():
+        try:
+            while not stop.is_set():
+                copied = stdlib_copy.deepcopy(payload)
+                assert copied["data"] == payload["data"]
+                seen.add(copied["kind"])
+        except BaseException as e:  # noqa: BLE001
+            errors.append(e)
+
+    workers = [threading.Thread(target=hammer) for _ in range(4)]
+    for worker in workers:
+        worker.start()
+    try:
+        for _ in range(2000):
+            copium.patch.enable()
+            copium.patch.disable()
+    finally:
+        stop.set()
+        for worker in workers:
+            worker.join(timeout=10)
+
+    assert not errors
+    assert seen <= {"dict", "memo"}
+
+
+def test_concurrent_enable_patches_once_and_disable_restores():
+    copium.patch.disable()
+    threads = 8
+    for _ in range(50):
+        barrier = threading.Barrier(threads)
+        results: list[bool] = []
+
+        def enable():
+            barrier.wait()
+            results.append(copium.patch.enable())
+
+        workers = [threading.Thread(target=enable) for _ in range(threads)]
+        for worker in workers:
+            worker.start()
+        for worker in workers:
+            worker.join(timeout=10)
+
+        assert sorted(results) == [False] * (threads - 1) + [True]
+        assert copium.patch.disable() is True
+        assert copium.patch.enabled() is False
+        assert set(trace_calls()) & STDLIB_EXCLUSIVE_CALLS
+
+
+def _call_outcome(args, kwargs):
+    try:
+        return "ok", stdlib_copy.deepcopy(*args, **kwargs)
+    except TypeError as error:
+        return "error", str(error)
+
+
+@pytest.mark.parametrize(
+    ("args", "kwargs"),
+    [
+        pytest.param(([1],), {}, id="x"),
+        pytest.param(([1], None), {}, id="x-memo"),
+        pytest.param(([1], {}), {}, id="x-dict-memo"),
+        pytest.param(([1], None, []), {}, id="x-memo-nil"),
+        pytest.param((), {"x": [1]}, id="kw-x"),
+        pytest.param(([1],), {"memo": {}}, id="kw-memo"),
+        pytest.param(([1],), {"_nil": []}, id="kw-nil"),
+        pytest.param((), {"x": [1], "memo": None, "_nil": []}, id="kw-all"),
+        pytest.param(([1], None), {"_nil": []}, id="mixed"),
+        pytest.param((), {}, id="missing-x"),
+        pytest.param(([1], None, [], None), {}, id="too-many-positional"),
+        pytest.param(([1],), {"x": [2]}, id="duplicate-x"),
+        pytest.param(([1], None), {"memo": None}, id="duplicate-memo"),
+        pytest.param(([1], None, []), {"_nil": []}, id="duplicate-nil"),
+        pytest.param(([1],), {"replace": None}, id="copium-only-keyword"),
+        pytest.param(([1],), {"bogus": 1}, id="unknown-keyword"),
+    ],
+)
+def test_patched_call_forms_match_stdlib(args, kwargs):
+    copium.patch.disable()
+    expected = _call_outcome(args, kwargs)
+
+    copium.patch.enable()
+    try:
+        actual = _call_outcome(args, kwargs)
+    finally:
+        copium.patch.disable()
+
+    assert actual == expected
+
+
+class _Node:
+    memos_seen: typing.ClassVar[list] = []
+
+    def __init__(self, children):
+        self.children = children
+
+    def __deepcopy__(self, memo):
+        _Node.memos_seen.append(memo)
+        clone = _Node.__new__(_Node)
+        memo[id(self)] = clone
+        clone.children = copy.deepcopy(self.children, memo)
+        return clone
+
+
+def test_patched_recursion_through_deepcopy_keeps_one_memo():
+    shared = [1]
+    leaf = _Node([shared])
+    root = _Node([leaf, shared, leaf])
+    root.children.append(root)
+    original = {"root": root, "shared": shared, "leaf": leaf}
+    _Node.memos_seen.clear()
+
+    copium.patch.enable()
+    try:
+        copied = copy.deepcopy(original)
+    finally:
+        copium.patch.disable()
+
+    new_root, new_shared, new_leaf = copied["root"], copied["shared"], copied["leaf"]
+    assert new_root.children == [new_leaf, new_shared, new_leaf, new_root]
+    assert new_root.children[0] is new_leaf is new_root.children[2]
+    assert new_root.children[1] is new_shared is new_leaf.children[0]
+    assert new_root.children[3] is new_root
+    assert new_shared is not shared and new_leaf is not leaf and new_root is not root
+    # Both __deepcopy__ calls were handed copium's own memo, not a wrapper around it.
+    assert len(_Node.memos_seen) == 2
+    assert _Node.memos_seen[0] is _Node.memos_seen[1]
+    assert type(_Node.memos_seen[0]) is not dict
+
+
+def test_patched_recursion_through_deepcopy_with_a_dict_memo():
+    shared = [1]
+    leaf = _Node([shared])
+    original = [_Node([leaf, shared]), leaf]
+    memo = {}
+    _Node.memos_seen.clear()
+
+    copium.patch.enable()
+    try:
+        copied = copy.deepcopy(original, memo)
+    finally:
+        copium.patch.disable()
+
+    assert copied[0].children[0] is copied[1]
+    assert copied[0].children[1] is copied[1].children[0]
+    assert all(seen is memo for seen in _Node.memos_seen)
+    assert memo[id(shared)] is copied[1].children[0]
+
+
+def test_stats_count_forwarded_calls():
+    copium.patch.disable()
+    total_before = copium.patch.stats()["calls_forwarded"]
+    copy.deepcopy(COPIED)
+    copium.deepcopy(COPIED)
+    assert copium.patch.stats()["calls_forwarded"] == total_before
+
+    copium.patch.enable()
+    try:
+        for _ in range(3):
+            copy.deepcopy(COPIED)
+        copy.deepcopy(COPIED, memo={})
+        copium.deepcopy(COPIED)
+        stats = copium.patch.stats()
+    finally:
+        copium.patch.disable()
+
+    assert stats["calls_since_enable"] == 4
+    assert stats["calls_forwarded"] == total_before + 4
+    assert stats["callers"] == {}
+
+
+def test_stats_sample_callers():
+    copium.patch.disable()
+    copium.patch.enable(sample_callers=True)
+    try:
+        line = sys._getframe().f_lineno + 2
+        for _ in range(2048):
+            copy.deepcopy(COPIED)
+        # A C caller in between still attributes the call to the Python line.
+        list(map(copy.deepcopy, [COPIED]))
+        map_line = sys._getframe().f_lineno - 1
+        stats = copium.patch.stats()
+    finally:
+        copium.patch.disable()
+
+    assert stats["calls_since_enable"] == 2049
+    assert stats["callers"] == {f"{__file__}:{line}": 2, f"{__file__}:{map_line}": 1}
+
+
+def test_stats_sample_callers_without_python_frames():
+    import _thread
+    import time
+
+    copium.patch.disable()
+    copium.patch.enable(sample_callers=True)
+    try:
+        # The new thread calls copy.deepcopy straight from C: no Python frame to report.
+        _thread.start_new_thread(copy.deepcopy, (COPIED,))
+        deadline = time.monotonic() + 10
+        while not copium.patch.stats()["callers"] and time.monotonic() < deadline:
+            time.sleep(0.001)
+        stats = copium.patch.stats()
+    finally:
+        copium.patch.disable()
+
+    assert stats["callers"] == {"<unknown>": 1}
+
+
+class _DictMemoAnnotated:
+    def __deepcopy__(self, memo: dict):
+        if not isinstance(memo, dict):
+            raise TypeError("memo must be a dict")
+        memo[id(self)] = copied = _DictMemoAnnotated()
+        return copied
+
+
+class _NativeDeepcopy:
+    __deepcopy__ = dict.get
+
+
+class _OpaqueMemo:
+    def __deepcopy__(self, memo):
+        return _OpaqueMemo()
+
+
+def test_check_flags_dict_memo_candidates():
+    report = copium.patch.check(__name__, register=False)
+
+    flagged = {entry["type"]: entry["reason"] for entry in report["incompatible"]}
+    assert set(flagged) == {_DictMemoAnnotated, _NativeDeepcopy}
+    assert "dict" in flagged[_DictMemoAnnotated]
+    assert "native" in flagged[_NativeDeepcopy]
+    assert not report["truncated"]
+
+
+def test_check_registers_types_for_dict_memo():
+    copium.patch.check(__name__)
+
+    original = _DictMemoAnnotated()
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        first, second = copium.deepcopy([original, original])
+
+    assert isinstance(first, _DictMemoAnnotated)
+    assert first is second
+
+
+def test_check_limit_truncates():
+    report = copium.patch.check(register=False, limit=3)
+
+    assert report["visited"] == 3
+    assert report["truncated"]
+
+
+def test_check_never_imports():
+    before = set(sys.modules)
+    copium.patch.check(["copium_not_a_module", "wave"], register=False)
+    copium.patch.check(register=False)
+
+    assert set(sys.modules) == before
+
+
+def test_enable_check_warns_once():
+    copium.patch.disable()
+    try:
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            copium.patch.enable(check=True, modules=__name__)
+    finally:
+        copium.patch.disable()
+
+    assert len(caught) == 1
+    assert f"{__name__}._DictMemoAnnotated" in str(caught[0].message)
+
+
+def _run_with_sitecustomize(tmp_path, sitecustomize: str, script: str, **environ: str):
+    (tmp_path / "sitecustomize.py").write_text(sitecustomize)
+    env = {**os.environ, **environ}
+    env.pop("COPIUM_PATCH_ENABLE", None)
+    env["PYTHONPATH"] = os.pathsep.join(filter(None, [str(tmp_path), env.get("PYTHONPATH")]))
+    return subprocess.run(
+        [sys.executable, "-c", script],
+        cwd=tmp_path,
+        env=env,
+        capture_output=True,
+        text=True,
+        check=False,
+    )
+
+
+PATCH_STATE_SCRIPT = """\
+import copy, copium, copium.patch
+print(copium.patch.enabled(), copy.deepcopy([[1]]) == [[1]])
+"""
+
+
+def test_autopatch_from_sitecustomize_patches_before_user_code(tmp_path):
+    result = _run_with_sitecustomize(tmp_path, "import copium.autopatch\n", PATCH_STATE_SCRIPT)
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.split() == ["True", "True"]
+    assert not result.stderr
+
+
+def test_autopatch_respects_disable(tmp_path):
+    result = _run_with_sitecustomize(
+        tmp_path, "import copium.autopatch\n", PATCH_STATE_SCRIPT, COPIUM_PATCH_DISABLE="1"
+    )
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.split() == ["False", "True"]
+
+
+def test_autopatch_failure_warns_and_startup_continues(tmp_path):
+    sitecustomize = """\
+import copium.patch
+
+def _refuse(*args, **kwargs):
+    raise RuntimeError("refused")
+
+copium.patch.enable = _refuse
+import copium.autopatch
+"""
+    result = _run_with_sitecustomize(tmp_path, sitecustomize, PATCH_STATE_SCRIPT)
+
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.split() == ["False", "True"]
+    assert "RuntimeWarning" in result.stderr
+    assert "copy.deepcopy() was left unpatched: RuntimeError('refused')" in result.stderr
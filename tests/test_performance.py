@@ -32,6 +32,7 @@ from typing import NamedTuple
 import pytest
 
 import copium
+import copium.extra
 import copium.patch
 
 
@@ -132,6 +133,48 @@ MEMO_CASES = chain(
     scaled("unique_mut", memo_unique_mut, SIZES),
 )
 
+
+# ═══════════════════════════════════════════════════════════
+#  FAN-OUT
+#
+#  One shared list behind every value of a wide dict.  Each
+#  value is a memo hit on the same pointer and each key is a
+#  distinct atomic, so the Python-level memo paths should
+#  stay within a small factor of the native one.
+# ═══════════════════════════════════════════════════════════
+
+
+def fanout_shared_value(n):
+    shared = [1]
+    return {i: shared for i in range(n)}
+
+
+FANOUT_CASES = list(scaled("shared_value", fanout_shared_value, (1000, 10_000, 100_000)))
+
+# ═══════════════════════════════════════════════════════════
+#  MEMO PROTOCOL
+#
+#  A __deepcopy__ driving the memo it is handed from Python,
+#  the way pydantic v1 models do: membership test, setdefault
+#  and get for n distinct keys, so 3n mapping calls per copy.
+# ═══════════════════════════════════════════════════════════
+
+
+class MemoHammer:
+    def __init__(self, n):
+        self.keys = list(range(1 << 32, (1 << 32) + n))
+
+    def __deepcopy__(self, memo):
+        for key in self.keys:
+            if key not in memo:
+                memo.setdefault(key, key)
+            memo.get(key)
+        return self
+
+
+MEMO_PROTOCOL_CASES = list(scaled("memo_protocol", MemoHammer, (1000, 10_000, 33_334)))
+
+
 # ═══════════════════════════════════════════════════════════
 #  CONTAINER TRAVERSAL
 #
@@ -143,9 +186,20 @@ CONTAINER_CASES = chain(
     scaled("list", lambda n: list(range(n)), SIZES),
     scaled("tuple", lambda n: tuple(range(n)), SIZES),
     scaled("dict", lambda n: {i: i for i in range(n)}, SIZES),
+    scaled("dict_str_keys", lambda n: {f"k{i}": i for i in range(n)}, SIZES),
     scaled("set", lambda n: set(range(n)), SIZES),
     scaled("frozenset", lambda n: frozenset(range(n)), SIZES),
     scaled("bytearray", lambda n: bytearray(n), (100, 10_000, 1_000_000)),
+    scaled(
+        "rows",
+        lambda n: [{"id": i, "name": f"u{i}", "score": i / 2, "tags": None} for i in range(n)],
+        SIZES,
+    ),
+    scaled(
+        "columns",
+        lambda n: {"id": list(range(n)), "name": [f"u{i}" for i in range(n)], "tags": [None] * n},
+        SIZES,
+    ),
 )
 
 
@@ -244,6 +298,12 @@ class MutableDataclass:
     mapping: dict = field(default_factory=dict)
 
 
+@dataclass(frozen=True)
+class FrozenKeyDataclass:
+    x: int
+    y: str
+
+
 @dataclass
 class NestedDataclass:
     inner: SimpleDataclass
@@ -267,6 +327,20 @@ class CustomDeepcopyObject:
         return CustomDeepcopyObject(stdlib_copy.deepcopy(self.v, memo))
 
 
+class FragmentDeepcopyObject:
+    def __init__(self, a, b, c, d):
+        self.a, self.b, self.c, self.d = a, b, c, d
+
+    def __deepcopy__(self, memo):
+        fragment = copium.deepcopy_fragment
+        return FragmentDeepcopyObject(
+            fragment(self.a, memo),
+            fragment(self.b, memo),
+            fragment(self.c, memo),
+            fragment(self.d, memo),
+        )
+
+
 GENERIC_CASES = chain(
     scaled(
         "dataclass_simple",
@@ -283,6 +357,11 @@ GENERIC_CASES = chain(
         lambda n: [NestedDataclass(SimpleDataclass(i, f"v{i}"), [i]) for i in range(n)],
         REDUCE_SIZES,
     ),
+    scaled(
+        "dataclass_frozen_keys",
+        lambda n: {FrozenKeyDataclass(i, f"v{i}"): [i] for i in range(n)},
+        REDUCE_SIZES,
+    ),
     scaled(
         "slots",
         lambda n: [SlotsObject(i, f"v{i}", float(i)) for i in range(n)],
@@ -298,6 +377,11 @@ GENERIC_CASES = chain(
         lambda n: [CustomDeepcopyObject([i]) for i in range(n)],
         REDUCE_SIZES,
     ),
+    scaled(
+        "fragment_deepcopy",
+        lambda n: [FragmentDeepcopyObject([i], {"k": i}, (i, [i]), f"v{i}") for i in range(n)],
+        REDUCE_SIZES,
+    ),
 )
 
 
@@ -530,6 +614,12 @@ SAMPLE_CASES = [
     Case("orm_graph_10u3s", make_orm_graph()),
 ]
 
+JSON_CASES = [
+    case
+    for case in SAMPLE_CASES
+    if case.name in {"json_api_response", "openapi_schema", "tabular_1000", "image_1024x1024"}
+]
+
 
 # ═══════════════════════════════════════════════════════════
 #  BENCHMARKS
@@ -542,6 +632,24 @@ def memo(case: Case, _python, benchmark):
     benchmark(copium.deepcopy, case.obj)
 
 
+@PYTHON_VERSION
+@generate_params(FANOUT_CASES)
+def fanout(case: Case, _python, benchmark):
+    benchmark(copium.deepcopy, case.obj)
+
+
+@PYTHON_VERSION
+@generate_params(FANOUT_CASES)
+def dict_memo_fanout(case: Case, _python, benchmark):
+    benchmark(lambda obj: copium.deepcopy(obj, {}), case.obj)
+
+
+@PYTHON_VERSION
+@generate_params(MEMO_PROTOCOL_CASES)
+def memo_protocol(case: Case, _python, benchmark):
+    benchmark(copium.deepcopy, case.obj)
+
+
 @PYTHON_VERSION
 @generate_params(CONTAINER_CASES)
 def container(case: Case, _python, benchmark):
@@ -585,6 +693,18 @@ def dict_memo_sample_data(case: Case, _python, benchmark):
     benchmark(copium.deepcopy, case.obj)
 
 
+@PYTHON_VERSION
+@generate_params(SAMPLE_CASES)
+def assume_tree_sample_data(case: Case, _python, benchmark):
+    benchmark(copium.deepcopy, case.obj, assume_tree=True)
+
+
+@PYTHON_VERSION
+@generate_params(JSON_CASES)
+def json_sample_data(case: Case, _python, benchmark):
+    benchmark(copium.extra.deepcopy_json, case.obj)
+
+
 @PYTHON_VERSION
 @generate_params(SAMPLE_CASES)
 def stdlib_sample_data(case: Case, _python, benchmark):
@@ -2,6 +2,7 @@
 Test that on top of errors equivalent to stdlib, copium also chains descriptive __cause__.
 """
 
+import copy
 import copyreg
 
 import pytest
@@ -57,7 +58,7 @@ class SlotStateNoItems:
         ),
         pytest.param(
             DictStateNotMapping(),
-            "dict state from DictStateNotMapping.__reduce__ must be a dict or mapping, got list",
+            "dict state from DictStateNotMapping.__reduce__ must be a mapping or an iterable of pairs, got list",
             id="dict-state-not-mapping",
         ),
         pytest.param(
@@ -78,3 +79,103 @@ def test_reduce_chained_type_error(obj, cause_message):
         f"expected TypeError cause, got {type(cause).__name__}: {cause}"
     )
     assert str(cause) == str(cause_message)
+
+
+class HalfInitialized:
+    """Published before __init__ finished: reducing it touches a missing attribute."""
+
+    def __init__(self, registry):
+        registry.append(self)
+        raise RuntimeError("interrupted")
+
+    def __reduce_ex__(self, protocol):
+        return type(self), (self.value,)
+
+    def __repr__(self):
+        return f"<HalfInitialized {'x' * 300}>"
+
+
+class Holder:
+    def __init__(self, payload):
+        self.payload = payload
+
+
+class UnreprableFailure:
+    def __reduce_ex__(self, protocol):
+        raise ValueError("cannot reduce")
+
+    def __repr__(self):
+        raise RuntimeError("repr is broken too")
+
+
+class ExplicitCause:
+    def __reduce_ex__(self, protocol):
+        raise ValueError("cannot reduce") from KeyError("original")
+
+
+class NoReduceProtocol:
+    __reduce_ex__ = None
+    __reduce__ = None
+
+
+def _half_initialized():
+    registry = []
+    with pytest.raises(RuntimeError):
+        HalfInitialized(registry)
+    return registry[0]
+
+
+def test_nested_failure_names_innermost_object():
+    half = _half_initialized()
+    graph = {"holders": [Holder({"half": half})]}
+
+    with pytest.raises(AttributeError) as exc_info:
+        copium.deepcopy(graph)
+
+    cause = exc_info.value.__cause__
+    assert isinstance(cause, TypeError)
+    assert str(cause) == f"failed to deep-copy HalfInitialized object: {repr(half)[:200]}"
+
+
+def test_nested_failure_without_repr():
+    with pytest.raises(ValueError, match="cannot reduce") as exc_info:
+        copium.deepcopy([Holder(UnreprableFailure())])
+
+    assert str(exc_info.value.__cause__) == "failed to deep-copy UnreprableFailure object"
+
+
+def test_nested_failure_keeps_explicit_cause():
+    with pytest.raises(ValueError, match="cannot reduce") as exc_info:
+        copium.deepcopy([Holder(ExplicitCause())])
+
+    assert isinstance(exc_info.value.__cause__, KeyError)
+
+
+def test_nested_uncopyable_raises_copy_error():
+    graph = {"holders": [Holder({"items": (1, NoReduceProtocol())})]}
+
+    with pytest.raises(copy.Error) as exc_info:
+        copium.deepcopy(graph)
+
+    error = exc_info.value
+    assert isinstance(error, copium.CopyError)
+    assert issubclass(copium.CopyError, copy.Error)
+    assert copium.CopyError is not copium.Error
+    assert str(error) == f"un(deep)copyable object of type {NoReduceProtocol}"
+    assert error.obj_type == f"{__name__}.NoReduceProtocol"
+    assert error.path is None
+    assert error.original is None
+    assert error.__cause__ is None
+
+
+def test_shallow_uncopyable_raises_copy_error():
+    with pytest.raises(copium.CopyError, match=r"^un\(shallow\)copyable object") as exc_info:
+        copium.copy(NoReduceProtocol())
+
+    assert exc_info.value.obj_type == f"{__name__}.NoReduceProtocol"
+
+
+def test_copy_error_attributes_default_to_none():
+    error = copium.CopyError("message")
+
+    assert (error.obj_type, error.path, error.original) == (None, None, None)
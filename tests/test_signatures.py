@@ -4,6 +4,7 @@
 
 from __future__ import annotations
 
+import importlib
 import inspect
 import types
 import typing
@@ -16,6 +17,12 @@ import copium
 
 PACKAGE_ROOT = Path(copium.__file__).parent
 PACKAGE_STUB = PACKAGE_ROOT / "__init__.pyi"
+MODULE_STUBS = {
+    "copium": PACKAGE_STUB,
+    "copium._debug": PACKAGE_ROOT / "_debug.pyi",
+    "copium.extra": PACKAGE_ROOT / "extra.pyi",
+    "copium.patch": PACKAGE_ROOT / "patch.pyi",
+}
 
 
 def _exec_stub_module(path: Path) -> types.ModuleType:
@@ -124,3 +131,95 @@ def test_stub_signatures_bind_to_runtime_signatures(subtests):
                     f"  args={len(args)} kwargs={list(kwargs.keys())}\n"
                     f"  error: {e}"
                 )
+
+
+def _parameters(signature: inspect.Signature) -> list[tuple[str, Any, Any]]:
+    return [(p.name, p.kind, p.default) for p in signature.parameters.values()]
+
+
+@pytest.mark.parametrize("module_name", sorted(MODULE_STUBS))
+def test_stub_parameters_match_runtime_signatures(module_name: str) -> None:
+    """
+    Names, kinds and defaults declared in the stub are exactly what the runtime
+    reports through __text_signature__, so IDEs and inspect.signature agree.
+    """
+    module = importlib.import_module(module_name)
+    stub_module = _exec_stub_module(MODULE_STUBS[module_name])
+
+    mismatched: dict[str, tuple[str, str]] = {}
+    for name, stub_function in sorted(_collect_stub_functions(stub_module).items()):
+        if stub_function.__module__ != stub_module.__name__:
+            continue  # imported helper or @overload placeholder
+        stub_signature = inspect.signature(stub_function)
+        runtime_object = getattr(module, name, None)
+        if runtime_object is None:
+            mismatched[name] = (str(stub_signature), "<missing>")
+            continue
+        runtime_signature = _drop_clinic_internals(inspect.signature(runtime_object))
+        if _parameters(stub_signature) != _parameters(runtime_signature):
+            mismatched[name] = (str(stub_signature), str(runtime_signature))
+
+    assert mismatched == {}
+
+
+PUBLIC_MODULES = ("copium", "copium.extra", "copium.patch", "copium.config")
+
+
+def _public_callables() -> list[tuple[str, Any]]:
+    import copy as stdlib_copy
+
+    found = []
+    for module_name in PUBLIC_MODULES:
+        module = importlib.import_module(module_name)
+        names = getattr(module, "__all__", None) or [
+            name for name in vars(module) if not name.startswith("_")
+        ]
+        for name in names:
+            obj = getattr(module, name)
+            if isinstance(obj, types.ModuleType) or not callable(obj):
+                continue
+            if getattr(stdlib_copy, name, None) is obj:
+                continue  # re-exported from stdlib, e.g. copium.Error
+            found.append((f"{module_name}.{name}", obj))
+    return found
+
+
+def _native_types() -> list[type]:
+    captured = []
+
+    class Capture:
+        def __deepcopy__(self, memo):
+            captured.append(memo)
+            return self
+
+    copium.deepcopy([[1], Capture()])  # the list is memoized first, so keepalive exists
+    memo = captured[0]
+    return [type(memo), type(memo[id(memo)])]
+
+
+def test_public_callables_have_docstrings_and_module() -> None:
+    problems = []
+    for qualified_name, obj in _public_callables():
+        if not (obj.__doc__ or "").strip():
+            problems.append(f"{qualified_name}: no docstring")
+        module_name = getattr(obj, "__module__", None)
+        if module_name not in PUBLIC_MODULES:
+            problems.append(f"{qualified_name}: __module__ is {module_name!r}")
+        elif getattr(importlib.import_module(module_name), obj.__name__, None) is not obj:
+            problems.append(f"{qualified_name}: not found as {module_name}.{obj.__name__}")
+
+    assert problems == []
+
+
+def test_native_type_methods_have_docstrings() -> None:
+    problems = []
+    for cls in _native_types():
+        if not (cls.__doc__ or "").strip():
+            problems.append(f"{cls.__qualname__}: no docstring")
+        for name, attribute in vars(cls).items():
+            if isinstance(attribute, types.MethodDescriptorType) and not (
+                attribute.__doc__ or ""
+            ).strip():
+                problems.append(f"{cls.__qualname__}.{name}: no docstring")
+
+    assert problems == []
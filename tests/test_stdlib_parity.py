@@ -0,0 +1,529 @@
+# SPDX-FileCopyrightText: 2025-present Arseny Boykov (Bobronium) <hi@bobronium.me>
+#
+# SPDX-License-Identifier: MIT
+
+"""
+Cases from Lib/test/test_copy.py, run through copy and copium side by side.
+
+Each case builds one original and copies it with both modules: they must return
+equivalent copies, or raise the same exception type with the same message.
+copium may raise a subclass (copium.CopyError is a copy.Error). Where copium
+deliberately words an error differently, the case is listed in
+DIFFERENT_MESSAGES with the reason, and only the exception type is compared.
+"""
+
+import collections
+import contextlib
+import copy as stdlib_copy
+import copyreg
+import dataclasses
+import functools
+import itertools
+import os
+import sqlite3
+import sys
+import types
+from collections.abc import Callable
+from typing import Any
+from typing import Literal
+
+import pytest
+from indifference import assert_equivalent_transformations
+
+import copium
+
+Operation = Literal["copy", "deepcopy"]
+
+REDUCE_VALIDATION = "copium validates __reduce__ results up front, like pickle"
+
+DIFFERENT_MESSAGES = {
+    "reduce-1-tuple": REDUCE_VALIDATION,
+    "reduce-6-tuple": REDUCE_VALIDATION,
+    "reduce-int": REDUCE_VALIDATION,
+    "reduce-bytes": REDUCE_VALIDATION,
+    "reduce-args-int": REDUCE_VALIDATION,
+    "dictitems-not-pairs": "copium counts the values it got, as unpacking does since 3.14",
+}
+
+
+# ── Vendored from test_copy ─────────────────────────────────
+
+
+class Vanilla:
+    def __init__(self, foo: Any) -> None:
+        self.foo = foo
+
+    def __eq__(self, other: object) -> bool:
+        return type(other) is type(self) and self.foo == other.foo
+
+
+class WithCopy(Vanilla):
+    def __copy__(self) -> "WithCopy":
+        return WithCopy(self.foo)
+
+
+class WithDeepcopy(Vanilla):
+    def __deepcopy__(self, memo: Any) -> "WithDeepcopy":
+        return WithDeepcopy(stdlib_copy.deepcopy(self.foo, memo))
+
+
+class WithGetinitargs(Vanilla):
+    def __getinitargs__(self) -> tuple[Any]:
+        return (self.foo,)
+
+
+class WithGetnewargs(int):
+    def __new__(cls, foo: Any) -> "WithGetnewargs":
+        self = int.__new__(cls)
+        self.foo = foo
+        return self
+
+    def __getnewargs__(self) -> tuple[Any]:
+        return (self.foo,)
+
+
+class WithGetnewargsEx(int):
+    def __new__(cls, *, foo: Any) -> "WithGetnewargsEx":
+        self = int.__new__(cls)
+        self.foo = foo
+        return self
+
+    def __getnewargs_ex__(self) -> tuple[tuple[()], dict[str, Any]]:
+        return (), {"foo": self.foo}
+
+
+class WithGetstate(Vanilla):
+    def __getstate__(self) -> dict[str, Any]:
+        return {"foo": self.foo}
+
+
+class WithSetstate(Vanilla):
+    def __setstate__(self, state: dict[str, Any]) -> None:
+        self.foo = state["foo"]
+
+
+class WithGetstateSetstate(Vanilla):
+    def __getstate__(self) -> Any:
+        return self.foo
+
+    def __setstate__(self, state: Any) -> None:
+        self.foo = state
+
+
+class WithSlots:
+    __slots__ = ("foo", "bar")
+
+    def __init__(self) -> None:
+        self.foo = [1]
+
+
+class SlotsNode:
+    __slots__ = ("parent", "children")
+
+    def __init__(self, parent: "SlotsNode | None" = None) -> None:
+        self.parent = parent
+        self.children: list[SlotsNode] = []
+        if parent is not None:
+            parent.children.append(self)
+
+
+class MixedNode(SlotsNode):
+    """Slots from the base, a __dict__ from here."""
+
+
+def slots_tree(node_type: type[SlotsNode]) -> SlotsNode:
+    root = node_type()
+    for _ in range(3):
+        child = node_type(root)
+        node_type(child)
+    if node_type is MixedNode:
+        root.label = [root.children[0]]
+    return root
+
+
+class ReducesEx:
+    def __reduce_ex__(self, proto: int) -> str:
+        return ""
+
+
+class Reduces:
+    def __reduce__(self) -> str:
+        return ""
+
+
+class Uncopyable:
+    def __getattribute__(self, name: str) -> Any:
+        if name.startswith("__reduce"):
+            raise AttributeError(name)
+        return object.__getattribute__(self, name)
+
+
+class Registered:
+    def __new__(cls, foo: Any) -> "Registered":
+        self = object.__new__(cls)
+        self.foo = foo
+        return self
+
+
+copyreg.pickle(Registered, lambda obj: (Registered, (obj.foo,)))
+
+
+class Meta(type):
+    pass
+
+
+class WithMeta(metaclass=Meta):
+    pass
+
+
+class ListWithItems(list):
+    def __reduce__(self) -> Any:
+        return ListWithItems, (), self.__dict__, iter(self)
+
+
+class DictWithItems(dict):
+    def __reduce__(self) -> Any:
+        return DictWithItems, (), self.__dict__, None, iter(self.items())
+
+
+class DictTrackingKeys(dict):
+    def __init__(self, d: Any = None) -> None:
+        if not d:
+            d = {}
+        self._keys = list(d.keys())
+        super().__init__(d)
+
+    def __setitem__(self, key: Any, item: Any) -> None:
+        super().__setitem__(key, item)
+        if key not in self._keys:
+            self._keys.append(key)
+
+
+class ListSubclass(list):
+    pass
+
+
+class TupleSubclass(tuple):
+    pass
+
+
+class TaggedList(list):
+    pass
+
+
+class TaggedSet(set):
+    pass
+
+
+class TaggedFrozenset(frozenset):
+    pass
+
+
+class LabelledList(list):
+    def __init__(self, items: Any = (), label: str = "") -> None:
+        super().__init__(items)
+        self.label = label
+
+
+class PrefixedFrozenset(frozenset):
+    def __new__(cls, items: Any = (), prefix: str = "") -> "PrefixedFrozenset":
+        return super().__new__(cls, [prefix + str(item) for item in items])
+
+
+def tagged(container: Any) -> Any:
+    container.tag = ["shared"]
+    container.alias = container.tag
+    return container
+
+
+def reflexive_list_subclass() -> TaggedList:
+    x = TaggedList([[1]])
+    x.append(x)
+    x.owner = x
+    return x
+
+
+def reflexive_set_subclass() -> TaggedSet:
+    x = TaggedSet({1, (2, 3)})
+    x.owner = x
+    return x
+
+
+class StateCompares:
+    def __reduce__(self) -> Any:
+        return StateCompares, (), self.__dict__
+
+    def __eq__(self, other: object) -> bool:
+        return type(other) is type(self) and self.__dict__ == other.__dict__
+
+
+class Reflexive:
+    def __reduce__(self) -> Any:
+        return Reflexive, (), self.__dict__
+
+
+def reflexive_list() -> list[Any]:
+    x: list[Any] = []
+    x.append(x)
+    return x
+
+
+def reflexive_dict() -> dict[str, Any]:
+    x: dict[str, Any] = {}
+    x["foo"] = x
+    return x
+
+
+def reflexive_instance() -> Reflexive:
+    x = Reflexive()
+    x.foo = x
+    return x
+
+
+def tracked_dict() -> DictTrackingKeys:
+    return DictTrackingKeys({"foo": 0})
+
+
+# ── Protocol misuse ─────────────────────────────────────────
+
+
+class ReducesTo:
+    def __init__(self, result: Any) -> None:
+        self.result = result
+
+    def __reduce_ex__(self, proto: int) -> Any:
+        return self.result
+
+
+class DeepcopyIsNone(Vanilla):
+    __deepcopy__ = None
+
+
+class CopyIsNone(Vanilla):
+    __copy__ = None
+
+
+class DispatchNone(Vanilla):
+    pass
+
+
+class DispatchNotCallable(Vanilla):
+    pass
+
+
+copyreg.dispatch_table[DispatchNone] = None
+copyreg.dispatch_table[DispatchNotCallable] = 42
+
+
+class SetstateRaises(Vanilla):
+    def __setstate__(self, state: Any) -> None:
+        raise ValueError("refusing state")
+
+
+class ReduceRaises:
+    def __reduce_ex__(self, proto: int) -> Any:
+        raise RuntimeError("cannot reduce")
+
+
+class NoAppend:
+    def __reduce__(self) -> Any:
+        return NoAppend, (), None, iter([1])
+
+
+class DictItemsNotPairs(dict):
+    def __reduce__(self) -> Any:
+        return DictItemsNotPairs, (), None, None, iter([(1, 2, 3)])
+
+
+class Plugin:
+    """Gets its __reduce_ex__ assigned per instance, as a partial."""
+
+    def __init__(self, payload: Any) -> None:
+        self.payload = payload
+
+
+def plugin_reduce(plugin: Plugin, proto: int, as_list: bool) -> Any:
+    result = (Plugin, ([plugin.payload, proto],))
+    return list(result) if as_list else result
+
+
+def plugin(*, as_list: bool) -> Plugin:
+    instance = Plugin([42])
+    instance.__reduce_ex__ = functools.partial(plugin_reduce, instance, as_list=as_list)
+    return instance
+
+
+def collect(*args: Any) -> tuple[Any, ...]:
+    return args
+
+
+class Collector:
+    def collect(self, *args: Any) -> tuple[Any, ...]:
+        return args
+
+
+# ── Dataclasses ─────────────────────────────────────────────
+
+
+def dataclass_variant(**params: bool) -> type:
+    """A dataclass whose fields have no defaults, so __init__ can't be called bare."""
+
+    @dataclasses.dataclass(**params)
+    class Record:
+        name: str
+        items: list[Any]
+        shared: list[Any]
+
+    return Record
+
+
+def dataclass_record(cls: type) -> Any:
+    shared = [0]
+    return cls(name="x", items=[[1], shared], shared=shared)
+
+
+DATACLASS_PARAMS = [
+    dict(zip(("kw_only", "slots", "frozen"), flags))
+    for flags in itertools.product((False, True), repeat=3)
+]
+
+
+def dataclass_cases() -> list[Any]:
+    cases = []
+    for params in DATACLASS_PARAMS:
+        cls = dataclass_variant(**params)
+        name = "-".join(["dataclass", *(k.replace("_", "-") for k, v in params.items() if v)])
+        cases += case(name, functools.partial(dataclass_record, cls))
+    return cases
+
+
+# ── Mapping-like objects from C and the stdlib ──────────────
+
+
+def sqlite_row() -> sqlite3.Row:
+    with contextlib.closing(sqlite3.connect(":memory:")) as connection:
+        connection.row_factory = sqlite3.Row
+        return connection.execute("select 1 as id, 'x' as name").fetchone()
+
+
+def case(
+    name: str, make: Callable[[], Any], *operations: Operation, marks: Any = ()
+) -> list[Any]:
+    return [
+        pytest.param(operation, make, id=f"{operation}-{name}", marks=marks)
+        for operation in operations or ("copy", "deepcopy")
+    ]
+
+
+CASES = [
+    *case("atomic", lambda: (1, 2.5, "text", b"raw", None, ..., NotImplemented, len, Vanilla)),
+    *case("list", lambda: [1, [2], {"three": 3}]),
+    *case("tuple", lambda: (1, [2], (3,))),
+    *case("dict", lambda: {"a": [1], "b": {"c": 2}}),
+    *case("set", lambda: {1, 2, 3}),
+    *case("frozenset", lambda: frozenset({1, 2, 3})),
+    *case("bytearray", lambda: bytearray(b"raw")),
+    *case("inst-vanilla", lambda: Vanilla([42])),
+    *case("inst-copy", lambda: WithCopy([42]), "copy"),
+    *case("inst-deepcopy", lambda: WithDeepcopy([42]), "deepcopy"),
+    *case("inst-getinitargs", lambda: WithGetinitargs([42])),
+    *case("inst-getnewargs", lambda: WithGetnewargs(42)),
+    *case("inst-getnewargs-ex", lambda: WithGetnewargsEx(foo=42)),
+    *case("inst-getstate", lambda: WithGetstate([42])),
+    *case("inst-setstate", lambda: WithSetstate([42])),
+    *case("inst-getstate-setstate", lambda: WithGetstateSetstate([42])),
+    *case("inst-getstate-setstate-falsy", lambda: WithGetstateSetstate(0.0)),
+    *case("slots", WithSlots),
+    *case("slots-tree", lambda: slots_tree(SlotsNode), "deepcopy"),
+    *case("slots-tree-mixed", lambda: slots_tree(MixedNode), "deepcopy"),
+    *case("reduce-ex-string", ReducesEx),
+    *case("reduce-string", Reduces),
+    *case("cant", Uncopyable),
+    *case("registry", lambda: Registered([42])),
+    *case("metaclass", lambda: WithMeta),
+    *case("reduce-4tuple", lambda: ListWithItems([[1], 2])),
+    *case("reduce-5tuple", lambda: DictWithItems(a=[1])),
+    *case("dict-subclass", tracked_dict),
+    *case("list-subclass", lambda: ListSubclass([[1], 2])),
+    *case("tuple-subclass", lambda: TupleSubclass(([1], 2))),
+    *case("list-subclass-attrs", lambda: tagged(TaggedList([[1], 2]))),
+    *case("list-subclass-reflexive", reflexive_list_subclass, "deepcopy"),
+    *case("list-subclass-init", lambda: LabelledList([[1]], label="x")),
+    *case("set-subclass-attrs", lambda: tagged(TaggedSet({1, (2, 3)}))),
+    *case("set-subclass-reflexive", reflexive_set_subclass, "deepcopy"),
+    *case("frozenset-subclass", lambda: TaggedFrozenset({1, (2, 3)})),
+    *case("frozenset-subclass-attrs", lambda: tagged(TaggedFrozenset({1, (2, 3)}))),
+    *case("frozenset-subclass-new", lambda: PrefixedFrozenset([1, 2], prefix="x")),
+    *case("reconstruct-state", lambda: StateCompares()),
+    *case("reflexive-list", reflexive_list, "deepcopy"),
+    *case("reflexive-dict", reflexive_dict, "deepcopy"),
+    *case("reflexive-instance", reflexive_instance),
+    *case("deepcopy-none", lambda: DeepcopyIsNone([42]), "deepcopy"),
+    *case("copy-none", lambda: CopyIsNone([42]), "copy"),
+    *case("dispatch-none", lambda: DispatchNone([42])),
+    *case("dispatch-not-callable", lambda: DispatchNotCallable([42])),
+    *case("setstate-raises", lambda: SetstateRaises([42])),
+    *case("reduce-raises", ReduceRaises),
+    *case("listitems-without-append", NoAppend),
+    *case("dictitems-not-pairs", DictItemsNotPairs),
+    *case("reduce-1-tuple", lambda: ReducesTo((Vanilla,))),
+    *case("reduce-6-tuple", lambda: ReducesTo((Vanilla, (1,), None, None, None, None))),
+    *case("reduce-int", lambda: ReducesTo(42)),
+    *case("reduce-bytes", lambda: ReducesTo(b"ab")),
+    *case("reduce-args-int", lambda: ReducesTo((Vanilla, 42))),
+    *case("reduce-args-list", lambda: ReducesTo((Vanilla, [[42]]))),
+    *case("reduce-call-function", lambda: ReducesTo((collect, ([1], 2, "three")))),
+    *case("reduce-call-function-many", lambda: ReducesTo((collect, tuple([i] for i in range(9))))),
+    *case("reduce-call-method", lambda: ReducesTo((Collector().collect, ([1], [2])))),
+    *case("reduce-call-partial", lambda: ReducesTo((functools.partial(Vanilla), ([42],)))),
+    *case("reduce-call-tuple-only", lambda: ReducesTo((slice, (1, [2], 3)))),
+    *case("reduce-call-raises", lambda: ReducesTo((int, ([1],)))),
+    *dataclass_cases(),
+    *case("sqlite3-row", sqlite_row),
+    *case("sqlite3-rows-in-result", lambda: {"rows": [sqlite_row(), sqlite_row()]}, "deepcopy"),
+    *case("os-environ", lambda: os.environ),
+    *case("mappingproxy", lambda: types.MappingProxyType({"a": [1]})),
+    *case("chainmap", lambda: collections.ChainMap({"a": [1]}, {"b": [2]})),
+    *case("reduce-ex-instance-partial", lambda: plugin(as_list=False)),
+    *case(
+        "reduce-ex-instance-partial-list",
+        lambda: plugin(as_list=True),
+        marks=pytest.mark.xfail(strict=True, reason=REDUCE_VALIDATION),
+    ),
+    *case(
+        "reduce-list",
+        lambda: ReducesTo([Vanilla, ([42],)]),
+        marks=pytest.mark.xfail(strict=True, reason=REDUCE_VALIDATION),
+    ),
+]
+
+
+@pytest.mark.parametrize("operation,make", CASES)
+def test_parity_with_stdlib(request: pytest.FixtureRequest, operation: Operation, make) -> None:
+    original = make()
+    baseline_copy = getattr(stdlib_copy, operation)
+    candidate_copy = getattr(copium, operation)
+
+    try:
+        baseline = baseline_copy(original)
+    except Exception as baseline_error:
+        with pytest.raises(type(baseline_error)) as candidate_error:
+            candidate_copy(original)
+        name = request.node.callspec.id.split("-", 1)[1]
+        if name not in DIFFERENT_MESSAGES:
+            assert str(candidate_error.value) == str(baseline_error)
+        return
+
+    candidate = candidate_copy(original)
+    assert_equivalent_transformations(original, baseline, candidate)
+
+
+@pytest.mark.skipif(sys.version_info < (3, 13), reason="copy.replace() is new in 3.13")
+@pytest.mark.parametrize("make", [lambda: Vanilla([1]), lambda: [1]], ids=["instance", "list"])
+def test_replace_errors_match_stdlib(make) -> None:
+    original = make()
+    with pytest.raises(TypeError) as baseline_error:
+        stdlib_copy.replace(original, foo=2)
+    with pytest.raises(TypeError) as candidate_error:
+        copium.replace(original, foo=2)
+    assert str(candidate_error.value) == str(baseline_error.value)